@@ -0,0 +1,125 @@
+//! Compares decoding a list of short ASCII strings, which borrows, with
+//! decoding into owned `String`s, and measures decoding the keys and values
+//! of an ASCII-heavy owned compound.
+//!
+//! Run with `cargo bench --bench decode`. The allocation counts per list are
+//! printed before the timings.
+
+use std::{
+    alloc::{GlobalAlloc, Layout, System},
+    hint::black_box,
+    sync::atomic::{AtomicUsize, Ordering},
+};
+
+use criterion::{Criterion, criterion_group, criterion_main};
+use na_nbt::{OwnedCompound, OwnedList, OwnedValue, read_borrowed};
+use zerocopy::byteorder::BigEndian as BE;
+
+struct CountingAlloc;
+
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for CountingAlloc {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        unsafe { System.alloc(layout) }
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        unsafe { System.dealloc(ptr, layout) }
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        unsafe { System.realloc(ptr, layout, new_size) }
+    }
+}
+
+#[global_allocator]
+static GLOBAL: CountingAlloc = CountingAlloc;
+
+/// Block ids, like the palette of a chunk section.
+fn create_document() -> Vec<u8> {
+    let mut list = OwnedList::<BE>::default();
+    for i in 0..1000 {
+        list.push(format!("minecraft:block_{i}").as_str());
+    }
+    OwnedValue::List(list).write_to_vec::<BE>().unwrap()
+}
+
+/// Named entries with string values, like a block entity or an item stack.
+fn create_compound() -> OwnedCompound<BE> {
+    let mut compound = OwnedCompound::<BE>::default();
+    for i in 0..1000 {
+        compound.insert(&format!("key_{i}"), format!("minecraft:value_{i}").as_str());
+    }
+    compound
+}
+
+fn count_allocations(f: impl FnOnce()) -> usize {
+    let before = ALLOCATIONS.load(Ordering::Relaxed);
+    f();
+    ALLOCATIONS.load(Ordering::Relaxed) - before
+}
+
+fn bench_decode(c: &mut Criterion) {
+    let data = create_document();
+    let doc = read_borrowed::<BE>(&data).unwrap();
+    let root = doc.root();
+    let list = root.as_list().unwrap();
+
+    println!(
+        "allocations per 1000 strings: decode = {}, decode().into_owned() = {}",
+        count_allocations(|| {
+            for string in list.iter() {
+                black_box(string.as_string().unwrap().decode().len());
+            }
+        }),
+        count_allocations(|| {
+            for string in list.iter() {
+                black_box(string.as_string().unwrap().decode().into_owned());
+            }
+        }),
+    );
+
+    let mut group = c.benchmark_group("decode_1000_ascii_strings");
+    group.bench_function("decode", |b| {
+        b.iter(|| {
+            for string in black_box(&list).iter() {
+                black_box(string.as_string().unwrap().decode().len());
+            }
+        })
+    });
+    group.bench_function("decode_into_owned", |b| {
+        b.iter(|| {
+            for string in black_box(&list).iter() {
+                black_box(string.as_string().unwrap().decode().into_owned());
+            }
+        })
+    });
+    group.finish();
+}
+
+fn bench_decode_compound(c: &mut Criterion) {
+    let compound = create_compound();
+
+    println!(
+        "allocations per 1000 entries: decode = {}",
+        count_allocations(|| {
+            for (key, value) in compound.iter() {
+                black_box(key.decode().len() + value.as_string().unwrap().decode().len());
+            }
+        }),
+    );
+
+    c.bench_function("decode_owned_compound_1000_ascii_entries", |b| {
+        b.iter(|| {
+            for (key, value) in black_box(&compound).iter() {
+                black_box(key.decode().len() + value.as_string().unwrap().decode().len());
+            }
+        })
+    });
+}
+
+criterion_group!(benches, bench_decode, bench_decode_compound);
+criterion_main!(benches);
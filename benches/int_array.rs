@@ -0,0 +1,54 @@
+//! Compares `to_vec_i32` on a large int array with a naive loop pushing one
+//! decoded element at a time.
+//!
+//! Run with `cargo bench --bench int_array`.
+
+use std::hint::black_box;
+
+use criterion::{Criterion, criterion_group, criterion_main};
+use na_nbt::{ByteOrder, OwnedValue, read_owned};
+use zerocopy::byteorder::{BigEndian as BE, I32, LittleEndian as LE};
+
+const LEN: usize = 1_000_000;
+
+/// A big-endian document holding an int array of a million elements.
+fn create_document() -> Vec<u8> {
+    let values: Vec<I32<BE>> = (0..LEN as i32)
+        .map(|i| I32::new(i.wrapping_mul(7919)))
+        .collect();
+    OwnedValue::<BE>::from(values).write_to_vec::<BE>().unwrap()
+}
+
+fn naive<O: ByteOrder>(values: &[I32<O>]) -> Vec<i32> {
+    let mut out = Vec::new();
+    for value in values {
+        out.push(value.get());
+    }
+    out
+}
+
+fn bench_store<O: ByteOrder>(c: &mut Criterion, name: &str, data: &[u8]) {
+    let value = read_owned::<BE, O>(data).unwrap();
+    let OwnedValue::IntArray(array) = &value else {
+        unreachable!();
+    };
+    assert_eq!(array.to_vec_i32(), naive(array));
+
+    let mut group = c.benchmark_group(format!("int_array_1m_{name}"));
+    group.bench_function("to_vec_i32", |b| {
+        b.iter(|| black_box(black_box(array).to_vec_i32()))
+    });
+    group.bench_function("naive_loop", |b| {
+        b.iter(|| black_box(naive(black_box(array))))
+    });
+    group.finish();
+}
+
+fn bench_int_array(c: &mut Criterion) {
+    let data = create_document();
+    bench_store::<BE>(c, "big_endian", &data);
+    bench_store::<LE>(c, "little_endian", &data);
+}
+
+criterion_group!(benches, bench_int_array);
+criterion_main!(benches);
@@ -0,0 +1,56 @@
+//! Compares reading 2 of 10,000 keys through `read_shared_lazy` with the
+//! eager `read_shared`, which indexes every entry before the first lookup.
+//!
+//! Run with `cargo bench --bench lazy_compound`.
+
+use std::hint::black_box;
+
+use bytes::Bytes;
+use criterion::{Criterion, criterion_group, criterion_main};
+use na_nbt::{OwnedCompound, OwnedList, OwnedValue, read_shared, read_shared_lazy};
+use zerocopy::byteorder::BigEndian as BE;
+
+const KEYS: [&str; 2] = ["entity_100", "entity_5000"];
+
+/// A large compound of small entity-like compounds.
+fn create_document() -> Bytes {
+    let mut root = OwnedCompound::<BE>::default();
+    for i in 0..10_000 {
+        let mut pos = OwnedList::<BE>::default();
+        pos.push(i as f64);
+        pos.push(64.0f64);
+        pos.push(-(i as f64));
+        let mut entity = OwnedCompound::<BE>::default();
+        entity.insert("id", "minecraft:zombie");
+        entity.insert("Health", 20.0f32);
+        entity.insert("Pos", pos);
+        root.insert(&format!("entity_{i}"), entity);
+    }
+    Bytes::from(OwnedValue::Compound(root).write_to_vec::<BE>().unwrap())
+}
+
+fn bench_lazy_compound(c: &mut Criterion) {
+    let document = create_document();
+
+    let mut group = c.benchmark_group("get_2_of_10000_keys");
+    group.bench_function("read_shared", |b| {
+        b.iter(|| {
+            let root = read_shared::<BE>(black_box(document.clone())).unwrap();
+            for key in KEYS {
+                black_box(root.get(key).unwrap());
+            }
+        })
+    });
+    group.bench_function("read_shared_lazy", |b| {
+        b.iter(|| {
+            let mut root = read_shared_lazy::<BE>(black_box(document.clone())).unwrap();
+            for key in KEYS {
+                black_box(root.get(key).unwrap().unwrap());
+            }
+        })
+    });
+    group.finish();
+}
+
+criterion_group!(benches, bench_lazy_compound);
+criterion_main!(benches);
@@ -0,0 +1,159 @@
+//! Compares parsing many small documents with the free functions against a
+//! reused [`Parser`].
+//!
+//! Run with `cargo bench --bench parser`. The allocation counts per batch are
+//! printed before the timings.
+
+use std::{
+    alloc::{GlobalAlloc, Layout, System},
+    hint::black_box,
+    sync::atomic::{AtomicUsize, Ordering},
+};
+
+use criterion::{Criterion, criterion_group, criterion_main};
+use na_nbt::{
+    OwnedCompound, OwnedList, OwnedValue, Parser, read_owned, read_owned_from_reader,
+    read_owned_strict,
+};
+use zerocopy::byteorder::BigEndian as BE;
+
+struct CountingAlloc;
+
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for CountingAlloc {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        unsafe { System.alloc(layout) }
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        unsafe { System.dealloc(ptr, layout) }
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        unsafe { System.realloc(ptr, layout, new_size) }
+    }
+}
+
+#[global_allocator]
+static GLOBAL: CountingAlloc = CountingAlloc;
+
+/// Small packets, like the entity updates of a network protocol.
+fn create_documents() -> Vec<Vec<u8>> {
+    (0..1000)
+        .map(|i| {
+            let mut pos = OwnedList::<BE>::default();
+            pos.push(i as f64);
+            pos.push(64.0f64);
+            pos.push(-(i as f64));
+            let mut root = OwnedCompound::<BE>::default();
+            root.insert("id", i);
+            root.insert("name", "minecraft:zombie");
+            root.insert("Pos", pos);
+            OwnedValue::Compound(root).write_to_vec::<BE>().unwrap()
+        })
+        .collect()
+}
+
+fn count_allocations<R>(f: impl FnOnce() -> R) -> usize {
+    let before = ALLOCATIONS.load(Ordering::Relaxed);
+    black_box(f());
+    ALLOCATIONS.load(Ordering::Relaxed) - before
+}
+
+fn bench_parser(c: &mut Criterion) {
+    let documents = create_documents();
+    let mut parser = Parser::new();
+
+    println!(
+        "allocations per 1000 documents: read_owned = {}, Parser::read_owned = {}, read_owned_from_reader = {}, Parser::read_owned_from_reader = {}, read_owned_strict = {}, Parser::read_owned_strict = {}",
+        count_allocations(|| {
+            for data in &documents {
+                black_box(read_owned::<BE, BE>(data).unwrap());
+            }
+        }),
+        count_allocations(|| {
+            for data in &documents {
+                black_box(parser.read_owned::<BE, BE>(data).unwrap());
+            }
+        }),
+        count_allocations(|| {
+            for data in &documents {
+                black_box(read_owned_from_reader::<BE, BE>(data.as_slice()).unwrap());
+            }
+        }),
+        count_allocations(|| {
+            for data in &documents {
+                black_box(
+                    parser
+                        .read_owned_from_reader::<BE, BE>(data.as_slice())
+                        .unwrap(),
+                );
+            }
+        }),
+        count_allocations(|| {
+            for data in &documents {
+                black_box(read_owned_strict::<BE, BE>(data).unwrap());
+            }
+        }),
+        count_allocations(|| {
+            for data in &documents {
+                black_box(parser.read_owned_strict::<BE, BE>(data).unwrap());
+            }
+        }),
+    );
+
+    let mut group = c.benchmark_group("parse_1000_documents");
+    group.bench_function("read_owned", |b| {
+        b.iter(|| {
+            for data in &documents {
+                black_box(read_owned::<BE, BE>(black_box(data)).unwrap());
+            }
+        })
+    });
+    group.bench_function("parser_read_owned", |b| {
+        b.iter(|| {
+            for data in &documents {
+                black_box(parser.read_owned::<BE, BE>(black_box(data)).unwrap());
+            }
+        })
+    });
+    group.bench_function("read_owned_from_reader", |b| {
+        b.iter(|| {
+            for data in &documents {
+                black_box(read_owned_from_reader::<BE, BE>(black_box(data.as_slice())).unwrap());
+            }
+        })
+    });
+    group.bench_function("parser_read_owned_from_reader", |b| {
+        b.iter(|| {
+            for data in &documents {
+                black_box(
+                    parser
+                        .read_owned_from_reader::<BE, BE>(black_box(data.as_slice()))
+                        .unwrap(),
+                );
+            }
+        })
+    });
+    group.bench_function("read_owned_strict", |b| {
+        b.iter(|| {
+            for data in &documents {
+                black_box(read_owned_strict::<BE, BE>(black_box(data)).unwrap());
+            }
+        })
+    });
+    group.bench_function("parser_read_owned_strict", |b| {
+        b.iter(|| {
+            for data in &documents {
+                black_box(parser.read_owned_strict::<BE, BE>(black_box(data)).unwrap());
+            }
+        })
+    });
+    group.finish();
+}
+
+criterion_group!(benches, bench_parser);
+criterion_main!(benches);
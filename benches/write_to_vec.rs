@@ -0,0 +1,95 @@
+//! Compares `write_to_vec`, which sizes its buffer up front, with writing the
+//! same value into a growing `Vec` through `write_to_writer`.
+//!
+//! Run with `cargo bench --bench write_to_vec`. The allocation counts per
+//! write are printed before the timings.
+
+use std::{
+    alloc::{GlobalAlloc, Layout, System},
+    hint::black_box,
+    sync::atomic::{AtomicUsize, Ordering},
+};
+
+use criterion::{Criterion, criterion_group, criterion_main};
+use na_nbt::{OwnedCompound, OwnedList, OwnedValue};
+use zerocopy::byteorder::{BigEndian as BE, I64, LittleEndian as LE};
+
+struct CountingAlloc;
+
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for CountingAlloc {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        unsafe { System.alloc(layout) }
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        unsafe { System.dealloc(ptr, layout) }
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        unsafe { System.realloc(ptr, layout, new_size) }
+    }
+}
+
+#[global_allocator]
+static GLOBAL: CountingAlloc = CountingAlloc;
+
+fn create_large_compound() -> OwnedValue<BE> {
+    let mut root = OwnedCompound::<BE>::default();
+    for i in 0..10_000 {
+        let mut entry = OwnedCompound::<BE>::default();
+        entry.insert("id", format!("minecraft:item_{i}").as_str());
+        entry.insert("count", (i % 64) as i8);
+        entry.insert("damage", i as i32);
+        let mut lore = OwnedList::<BE>::default();
+        lore.push("first line");
+        lore.push("second line");
+        entry.insert("lore", lore);
+        entry.insert("bits", vec![I64::<BE>::new(i); 8]);
+        root.insert(&format!("slot{i}"), entry);
+    }
+    OwnedValue::Compound(root)
+}
+
+fn count_allocations<R>(f: impl FnOnce() -> R) -> usize {
+    let before = ALLOCATIONS.load(Ordering::Relaxed);
+    black_box(f());
+    ALLOCATIONS.load(Ordering::Relaxed) - before
+}
+
+fn bench_write_to_vec(c: &mut Criterion) {
+    let value = create_large_compound();
+
+    println!(
+        "allocations per write: write_to_vec (same endian) = {}, write_to_vec (swap endian) = {}, growing Vec = {}",
+        count_allocations(|| value.write_to_vec::<BE>().unwrap()),
+        count_allocations(|| value.write_to_vec::<LE>().unwrap()),
+        count_allocations(|| {
+            let mut buf = Vec::new();
+            value.write_to_writer::<BE>(&mut buf).unwrap();
+            buf
+        }),
+    );
+
+    let mut group = c.benchmark_group("write_large_compound");
+    group.bench_function("write_to_vec", |b| {
+        b.iter(|| black_box(&value).write_to_vec::<BE>().unwrap())
+    });
+    group.bench_function("write_to_vec_swap_endian", |b| {
+        b.iter(|| black_box(&value).write_to_vec::<LE>().unwrap())
+    });
+    group.bench_function("growing_vec", |b| {
+        b.iter(|| {
+            let mut buf = Vec::new();
+            black_box(&value).write_to_writer::<BE>(&mut buf).unwrap();
+            buf
+        })
+    });
+    group.finish();
+}
+
+criterion_group!(benches, bench_write_to_vec);
+criterion_main!(benches);
@@ -60,6 +60,26 @@
 //! }
 //! ```
 //!
+//! # Wrapper Types
+//!
+//! When a field should always be a native array, the [`ByteArray`],
+//! [`IntArray`] and [`LongArray`] newtypes say so in the type instead of an
+//! attribute. They serialize as the corresponding array tag, and unlike the
+//! modules above they *require* it on deserialize: a `List<Int>` where an
+//! [`IntArray`] is expected is a [`TagMismatch`](crate::Error::TagMismatch).
+//!
+//! ```ignore
+//! use serde::{Serialize, Deserialize};
+//! use na_nbt::{IntArray, LongArray};
+//!
+//! #[derive(Serialize, Deserialize)]
+//! struct Section {
+//!     biomes: IntArray,         // always IntArray
+//!     block_states: LongArray,  // always LongArray
+//!     palette: Vec<i32>,        // still List<Int>
+//! }
+//! ```
+//!
 //! # Performance
 //!
 //! These modules serialize directly from slice references (`&[T]`) without
@@ -90,9 +110,12 @@
 //! assert_eq!(heightmap, loaded);
 //! ```
 
-use std::slice;
+use std::{
+    ops::{Deref, DerefMut},
+    slice,
+};
 
-use serde::{Deserializer, Serialize, Serializer, de};
+use serde::{Deserialize, Deserializer, Serialize, Serializer, de};
 
 // ============================================================================
 // Internal serialization helpers (zero-copy from slices)
@@ -289,7 +312,7 @@ pub mod int_array {
     where
         S: Serializer,
     {
-        serializer.serialize_newtype_struct("na_nbt:int_array", &IntArrayRef(data))
+        serializer.serialize_newtype_struct(INT_ARRAY_NAME, &IntArrayRef(data))
     }
 
     /// Deserialize `Vec<i32>` from NBT `IntArray` or `List<Int>`.
@@ -346,7 +369,7 @@ pub mod long_array {
     where
         S: Serializer,
     {
-        serializer.serialize_newtype_struct("na_nbt:long_array", &LongArrayRef(data))
+        serializer.serialize_newtype_struct(LONG_ARRAY_NAME, &LongArrayRef(data))
     }
 
     /// Deserialize `Vec<i64>` from NBT `LongArray` or `List<Long>`.
@@ -360,3 +383,153 @@ pub mod long_array {
         deserializer.deserialize_seq(LongArrayVisitor)
     }
 }
+
+// ============================================================================
+// Newtype wrappers
+// ============================================================================
+
+/// Newtype names recognized by the NBT serializer and deserializer.
+pub(crate) const BYTE_ARRAY_NAME: &str = "na_nbt:byte_array";
+pub(crate) const INT_ARRAY_NAME: &str = "na_nbt:int_array";
+pub(crate) const LONG_ARRAY_NAME: &str = "na_nbt:long_array";
+
+macro_rules! array_wrapper {
+    ($(#[$meta:meta])* $name:ident, $element:ty, $module:ident, $tag_name:ident, $visitor:ident) => {
+        $(#[$meta])*
+        #[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+        pub struct $name(pub Vec<$element>);
+
+        impl Deref for $name {
+            type Target = Vec<$element>;
+
+            #[inline]
+            fn deref(&self) -> &Self::Target {
+                &self.0
+            }
+        }
+
+        impl DerefMut for $name {
+            #[inline]
+            fn deref_mut(&mut self) -> &mut Self::Target {
+                &mut self.0
+            }
+        }
+
+        impl From<Vec<$element>> for $name {
+            #[inline]
+            fn from(value: Vec<$element>) -> Self {
+                Self(value)
+            }
+        }
+
+        impl From<$name> for Vec<$element> {
+            #[inline]
+            fn from(value: $name) -> Self {
+                value.0
+            }
+        }
+
+        impl Serialize for $name {
+            fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+            where
+                S: Serializer,
+            {
+                $module::serialize(&self.0, serializer)
+            }
+        }
+
+        impl<'de> Deserialize<'de> for $name {
+            fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+            where
+                D: Deserializer<'de>,
+            {
+                struct NewtypeVisitor;
+
+                impl<'de> de::Visitor<'de> for NewtypeVisitor {
+                    type Value = $name;
+
+                    fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+                        formatter.write_str(concat!("an NBT ", stringify!($name)))
+                    }
+
+                    fn visit_newtype_struct<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+                    where
+                        D: Deserializer<'de>,
+                    {
+                        $module::deserialize(deserializer).map($name)
+                    }
+
+                    // other formats may hand over the elements directly
+                    fn visit_seq<A>(self, seq: A) -> Result<Self::Value, A::Error>
+                    where
+                        A: de::SeqAccess<'de>,
+                    {
+                        $visitor.visit_seq(seq).map($name)
+                    }
+                }
+
+                // the NBT deserializer checks the tag for this name
+                deserializer.deserialize_newtype_struct($tag_name, NewtypeVisitor)
+            }
+        }
+    };
+}
+
+array_wrapper!(
+    /// A `Vec<i8>` that is always an NBT `ByteArray` (tag 7) in serde.
+    ///
+    /// Serializes like [`byte_array`], and deserializing it fails with
+    /// [`TagMismatch`](crate::Error::TagMismatch) if the value has any other
+    /// tag. It derefs to the inner `Vec`.
+    ByteArray,
+    i8,
+    byte_array,
+    BYTE_ARRAY_NAME,
+    ByteArrayVisitor
+);
+
+array_wrapper!(
+    /// A `Vec<i32>` that is always an NBT `IntArray` (tag 11) in serde.
+    ///
+    /// A plain `Vec<i32>` serializes as `List<Int>`; use this type where the
+    /// format calls for an `IntArray`. Deserializing it fails with
+    /// [`TagMismatch`](crate::Error::TagMismatch) if the value has any other
+    /// tag, including `List<Int>`. It derefs to the inner `Vec`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use na_nbt::{IntArray, from_slice_be, to_vec_be};
+    /// use serde::{Deserialize, Serialize};
+    ///
+    /// #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    /// struct Section {
+    ///     biomes: IntArray,
+    /// }
+    ///
+    /// let section = Section { biomes: vec![1, 2, 3].into() };
+    /// let bytes = to_vec_be(&section)?;
+    /// assert_eq!(bytes[3], 0x0b); // the field's tag
+    /// assert_eq!(from_slice_be::<Section>(&bytes)?, section);
+    /// # Ok::<(), na_nbt::Error>(())
+    /// ```
+    IntArray,
+    i32,
+    int_array,
+    INT_ARRAY_NAME,
+    IntArrayVisitor
+);
+
+array_wrapper!(
+    /// A `Vec<i64>` that is always an NBT `LongArray` (tag 12) in serde.
+    ///
+    /// A plain `Vec<i64>` serializes as `List<Long>`; use this type where the
+    /// format calls for a `LongArray`. Deserializing it fails with
+    /// [`TagMismatch`](crate::Error::TagMismatch) if the value has any other
+    /// tag, including `List<Long>`. It derefs to the inner `Vec`.
+    LongArray,
+    i64,
+    long_array,
+    LONG_ARRAY_NAME,
+    LongArrayVisitor
+);
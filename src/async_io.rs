@@ -0,0 +1,103 @@
+//! Reading and writing NBT over tokio's async IO traits.
+//!
+//! NBT has no length prefix at the top level, so [`read_async`] follows the
+//! structure of the document as it arrives, pulling exactly the bytes of one
+//! document from the stream. Anything after it is left unread, which makes it
+//! suitable for sockets carrying several documents back to back.
+//!
+//! This module requires the `tokio` feature.
+
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt};
+
+use crate::{
+    ByteOrder, Error, OwnedValue, Result, ScopedReadableValue, read_owned,
+    walk::{Progress, Walk},
+};
+
+/// Reads one NBT document from an [`AsyncRead`] into an owned, mutable value.
+///
+/// This is the async counterpart of
+/// [`read_owned_from_reader`](crate::read_owned_from_reader). The document is
+/// buffered as it is read and then parsed with [`read_owned`], so the result
+/// is the same as reading the bytes into a `Vec` first. Unlike the sync
+/// reader, data following the document is not an error and stays in the
+/// reader.
+///
+/// # Example
+///
+/// ```
+/// use na_nbt::{OwnedValue, read_async};
+/// use zerocopy::byteorder::BigEndian;
+///
+/// # tokio::runtime::Builder::new_current_thread().build().unwrap().block_on(async {
+/// let mut socket: &[u8] = &[0x0a, 0x00, 0x00, 0x00, 0xff];
+/// let value: OwnedValue<BigEndian> = read_async::<BigEndian, BigEndian>(&mut socket).await?;
+/// assert!(value.is_compound());
+/// assert_eq!(socket, &[0xff]);
+/// # Ok::<(), na_nbt::Error>(())
+/// # }).unwrap();
+/// ```
+///
+/// # Errors
+///
+/// Returns an error if:
+/// - The reader fails ([`Error::IO`])
+/// - The stream ends before the document does ([`Error::EndOfFile`])
+/// - An invalid tag type is encountered ([`Error::InvalidTagType`])
+pub async fn read_async<SOURCE: ByteOrder, STORE: ByteOrder>(
+    reader: &mut (impl AsyncRead + Unpin),
+) -> Result<OwnedValue<STORE>> {
+    let mut buf = Vec::new();
+    let mut stack = Vec::new();
+    let mut walk = Walk::document(&mut stack, 0);
+    loop {
+        match walk.run::<SOURCE>(&buf, &mut ())? {
+            Progress::Done(_) => break,
+            Progress::Stopped => unreachable!("the default hooks fail on an unknown tag"),
+            Progress::Need { end, list_header } => {
+                let n = (end - buf.len()) as u64;
+                read_n(reader, &mut buf, n)
+                    .await
+                    .map_err(|error| match error {
+                        Error::EndOfFile if list_header => Error::truncated_list_header(),
+                        error => error,
+                    })?;
+            }
+        }
+    }
+
+    read_owned::<SOURCE, STORE>(&buf)
+}
+
+/// Writes a value as an NBT document to an [`AsyncWrite`].
+///
+/// The document is encoded with
+/// [`write_to_vec`](ScopedReadableValue::write_to_vec) and then written with a
+/// single `write_all`. The writer is not flushed.
+///
+/// # Errors
+///
+/// Returns [`Error::IO`] if the writer fails.
+pub async fn write_async<'doc, TARGET: ByteOrder>(
+    value: &impl ScopedReadableValue<'doc>,
+    writer: &mut (impl AsyncWrite + Unpin),
+) -> Result<()> {
+    let buf = value.write_to_vec::<TARGET>()?;
+    writer.write_all(&buf).await.map_err(Error::IO)
+}
+
+/// Appends exactly `n` bytes from the reader to `buf`.
+///
+/// The buffer grows with the data actually received, so a corrupt length does
+/// not trigger a huge allocation up front.
+async fn read_n(reader: &mut (impl AsyncRead + Unpin), buf: &mut Vec<u8>, n: u64) -> Result<()> {
+    let read = (&mut *reader)
+        .take(n)
+        .read_to_end(buf)
+        .await
+        .map_err(Error::IO)?;
+    if (read as u64) < n {
+        return Err(Error::EndOfFile);
+    }
+    Ok(())
+}
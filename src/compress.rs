@@ -0,0 +1,254 @@
+//! Compressed NBT files.
+//!
+//! Minecraft stores most NBT files gzip-compressed with a named root
+//! compound, and region chunks zlib-compressed. This module writes and reads
+//! such files in one call, and can also detect and undo the compression
+//! without parsing.
+//!
+//! This module requires the `compression` feature.
+
+use alloc::vec::Vec;
+use std::io::{Read, Write};
+
+use flate2::{
+    read::{MultiGzDecoder, ZlibDecoder},
+    write::{GzEncoder, ZlibEncoder},
+};
+
+use crate::{ByteOrder, Error, OwnedValue, Result, ScopedReadableValue, read_owned};
+
+/// The compression applied to a whole NBT file.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum Compression {
+    /// Raw, uncompressed NBT.
+    #[default]
+    None,
+    /// Gzip, as used by `level.dat` and player data.
+    Gzip,
+    /// Zlib, as used by region file chunks.
+    Zlib,
+}
+
+/// Returns the compression of `data`, judging by its first two bytes.
+///
+/// Gzip starts with `1f 8b`, and zlib with `78` followed by the flags of one
+/// of its compression levels: `01`, `5e`, `9c` or `da`. Anything else is
+/// [`Compression::None`]. The first byte of raw NBT is a tag type, at most
+/// `0c`, so it is never mistaken for either; conversely, a `78` without valid
+/// flags after it is not reported as zlib.
+///
+/// Only the header is looked at, so data detected as compressed may still
+/// fail to inflate.
+///
+/// # Example
+///
+/// ```
+/// use na_nbt::{Compression, detect_compression};
+///
+/// assert_eq!(detect_compression(&[0x1f, 0x8b, 0x08]), Compression::Gzip);
+/// assert_eq!(detect_compression(&[0x78, 0x9c]), Compression::Zlib);
+/// assert_eq!(detect_compression(&[0x0a, 0x00, 0x00, 0x00]), Compression::None);
+/// ```
+pub fn detect_compression(data: &[u8]) -> Compression {
+    match data {
+        [0x1f, 0x8b, ..] => Compression::Gzip,
+        [0x78, 0x01 | 0x5e | 0x9c | 0xda, ..] => Compression::Zlib,
+        _ => Compression::None,
+    }
+}
+
+/// Inflates `data` according to [`detect_compression`], without parsing it.
+///
+/// Uncompressed data is returned as a copy. Concatenated gzip members are
+/// inflated one after another, as by [`read_owned_compressed`].
+///
+/// # Example
+///
+/// ```
+/// use na_nbt::{Compression, OwnedCompound, OwnedValue, decompress_to_vec};
+/// use zerocopy::byteorder::BigEndian;
+///
+/// let value = OwnedValue::Compound(OwnedCompound::<BigEndian>::default());
+/// let file = value.to_file_bytes::<BigEndian>("", Compression::Zlib)?;
+/// assert_eq!(decompress_to_vec(&file)?, value.write_to_vec::<BigEndian>()?);
+/// # Ok::<(), na_nbt::Error>(())
+/// ```
+///
+/// # Errors
+///
+/// Returns [`Error::IO`] if the compressed data is corrupt.
+pub fn decompress_to_vec(data: &[u8]) -> Result<Vec<u8>> {
+    let mut inflated = Vec::new();
+    match detect_compression(data) {
+        Compression::None => return Ok(data.to_vec()),
+        Compression::Gzip => MultiGzDecoder::new(data).read_to_end(&mut inflated),
+        Compression::Zlib => ZlibDecoder::new(data).read_to_end(&mut inflated),
+    }
+    .map_err(Error::IO)?;
+    Ok(inflated)
+}
+
+impl<O: ByteOrder> OwnedValue<O> {
+    /// Encodes this value as a complete NBT file: the root tag with
+    /// `root_name`, then the payload, all compressed with `compression`.
+    ///
+    /// This is what `level.dat` and similar files contain, ready to be saved
+    /// as is. An `End` root has no name, so `root_name` is ignored for it.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use na_nbt::{Compression, OwnedCompound, OwnedValue, peek_root_compressed, read_owned_compressed};
+    /// use zerocopy::byteorder::BigEndian;
+    ///
+    /// let mut data: OwnedCompound<BigEndian> = OwnedCompound::default();
+    /// data.insert("LevelName", "world");
+    /// let value = OwnedValue::Compound(data);
+    ///
+    /// let file = value.to_file_bytes::<BigEndian>("Data", Compression::Gzip)?;
+    /// assert_eq!(peek_root_compressed::<BigEndian>(&file)?.1.as_deref(), Some("Data"));
+    /// let read: OwnedValue<BigEndian> = read_owned_compressed::<BigEndian, BigEndian>(&file)?;
+    /// assert!(na_nbt::values_equal(&value, &read));
+    /// # Ok::<(), na_nbt::Error>(())
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if:
+    /// - `root_name` is longer than 65535 bytes once encoded
+    ///   ([`Error::Message`])
+    /// - Compression fails ([`Error::IO`])
+    pub fn to_file_bytes<TARGET: ByteOrder>(
+        &self,
+        root_name: &str,
+        compression: Compression,
+    ) -> Result<Vec<u8>> {
+        let file = self.write_to_vec_named::<TARGET>(root_name)?;
+        match compression {
+            Compression::None => Ok(file),
+            _ => compress_into(Vec::new(), &file, compression),
+        }
+    }
+}
+
+/// Writes `value` as a complete NBT file to `writer`: the root tag with
+/// `root_name`, then the payload, all compressed with `compression`.
+///
+/// The output is that of [`to_file_bytes`](OwnedValue::to_file_bytes), but
+/// the compressed bytes go straight to the writer, and any value can be
+/// written, borrowed ones included. This is the shape needed to save a
+/// `level.dat` over the one it was read from. Pass `&mut writer` to keep
+/// using the writer afterwards; it is not flushed.
+///
+/// # Example
+///
+/// ```
+/// use na_nbt::{Compression, OwnedCompound, OwnedValue, read_owned_compressed, write_leveldat_to_writer};
+/// use zerocopy::byteorder::BigEndian;
+///
+/// let mut data: OwnedCompound<BigEndian> = OwnedCompound::default();
+/// data.insert("LevelName", "world");
+/// let value = OwnedValue::Compound(data);
+///
+/// let mut file = Vec::new();
+/// write_leveldat_to_writer::<BigEndian>(&mut file, "", &value, Compression::Gzip)?;
+/// let read: OwnedValue<BigEndian> = read_owned_compressed::<BigEndian, BigEndian>(&file)?;
+/// assert!(na_nbt::values_equal(&value, &read));
+/// # Ok::<(), na_nbt::Error>(())
+/// ```
+///
+/// # Errors
+///
+/// Returns an error if:
+/// - `root_name` is longer than 65535 bytes once encoded
+///   ([`Error::Message`])
+/// - The writer or the compression fails ([`Error::IO`])
+pub fn write_leveldat_to_writer<'doc, TARGET: ByteOrder>(
+    writer: impl Write,
+    root_name: &str,
+    value: &impl ScopedReadableValue<'doc>,
+    compression: Compression,
+) -> Result<()> {
+    let file = value.write_to_vec_named::<TARGET>(root_name)?;
+    compress_into(writer, &file, compression).map(|_| ())
+}
+
+/// Writes `data` to `writer` compressed with `compression`, and returns the
+/// writer.
+fn compress_into<W: Write>(mut writer: W, data: &[u8], compression: Compression) -> Result<W> {
+    let level = flate2::Compression::default();
+    match compression {
+        Compression::None => {
+            writer.write_all(data).map_err(Error::IO)?;
+            Ok(writer)
+        }
+        Compression::Gzip => {
+            let mut encoder = GzEncoder::new(writer, level);
+            encoder.write_all(data).map_err(Error::IO)?;
+            encoder.finish().map_err(Error::IO)
+        }
+        Compression::Zlib => {
+            let mut encoder = ZlibEncoder::new(writer, level);
+            encoder.write_all(data).map_err(Error::IO)?;
+            encoder.finish().map_err(Error::IO)
+        }
+    }
+}
+
+/// Parses NBT that may be compressed, like [`read_owned`].
+///
+/// Gzip and zlib data are recognized by their magic bytes, as by
+/// [`detect_compression`], and inflated first; anything else is parsed as
+/// uncompressed NBT. As with [`read_owned`], the
+/// root name is skipped; read it with
+/// [`peek_root_compressed`](crate::peek_root_compressed).
+///
+/// # Errors
+///
+/// Same as [`read_owned`], plus [`Error::IO`] if the compressed data is
+/// corrupt.
+pub fn read_owned_compressed<SOURCE: ByteOrder, STORE: ByteOrder>(
+    data: &[u8],
+) -> Result<OwnedValue<STORE>> {
+    read_auto::<SOURCE, STORE>(data).map(|(value, _)| value)
+}
+
+/// Parses NBT that may be compressed, like [`read_owned_compressed`], and
+/// also returns the compression that was detected.
+///
+/// Passing the compression back to
+/// [`to_file_bytes`](OwnedValue::to_file_bytes) writes the value in the same
+/// container it was read from.
+///
+/// # Example
+///
+/// ```
+/// use na_nbt::{Compression, OwnedCompound, OwnedValue, read_auto};
+/// use zerocopy::byteorder::BigEndian;
+///
+/// let mut data: OwnedCompound<BigEndian> = OwnedCompound::default();
+/// data.insert("version", 1i32);
+/// let file = OwnedValue::Compound(data).to_file_bytes::<BigEndian>("", Compression::Zlib)?;
+///
+/// let (mut value, compression) = read_auto::<BigEndian, BigEndian>(&file)?;
+/// assert_eq!(compression, Compression::Zlib);
+/// value.get_mut("version").unwrap().set_int(2);
+/// let saved = value.to_file_bytes::<BigEndian>("", compression)?;
+/// assert_eq!(read_auto::<BigEndian, BigEndian>(&saved)?.1, Compression::Zlib);
+/// # Ok::<(), na_nbt::Error>(())
+/// ```
+///
+/// # Errors
+///
+/// Same as [`read_owned_compressed`].
+pub fn read_auto<SOURCE: ByteOrder, STORE: ByteOrder>(
+    data: &[u8],
+) -> Result<(OwnedValue<STORE>, Compression)> {
+    let compression = detect_compression(data);
+    let value = if compression == Compression::None {
+        read_owned::<SOURCE, STORE>(data)?
+    } else {
+        read_owned::<SOURCE, STORE>(&decompress_to_vec(data)?)?
+    };
+    Ok((value, compression))
+}
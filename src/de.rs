@@ -104,7 +104,11 @@ use serde::{
 };
 use zerocopy::byteorder;
 
-use crate::{ByteOrder, Error, Result, Tag, cold_path};
+use crate::{
+    ByteOrder, Error, Result, Tag,
+    array::{BYTE_ARRAY_NAME, INT_ARRAY_NAME, LONG_ARRAY_NAME},
+    cold_path, decode_mutf8,
+};
 
 /// NBT deserializer implementing [`serde::Deserializer`].
 ///
@@ -132,9 +136,12 @@ pub struct Deserializer<'de, O: ByteOrder> {
 
 macro_rules! check_bounds {
     ($len:expr, $input:expr) => {
+        check_bounds!($len, $input, Error::EndOfFile)
+    };
+    ($len:expr, $input:expr, $error:expr) => {
         if $len > $input.len() {
             cold_path();
-            return Err(Error::EndOfFile);
+            return Err($error);
         }
     };
 }
@@ -342,7 +349,7 @@ impl<'de, O: ByteOrder> Deserializer<'de, O> {
         check_bounds!(2, self.input);
         let length = byteorder::U16::<O>::from_bytes(unsafe { *self.input.as_ptr().cast() }).get();
         check_bounds!(2 + length as usize, self.input);
-        let value = simd_cesu8::mutf8::decode_lossy(&self.input[2..2 + length as usize]);
+        let value = decode_mutf8(&self.input[2..2 + length as usize]);
         self.input = &self.input[2 + length as usize..];
         Ok(value)
     }
@@ -396,7 +403,7 @@ impl<'de, O: ByteOrder> de::Deserializer<'de> for &mut Deserializer<'de, O> {
             Tag::ByteArray => visitor.visit_borrowed_bytes(self.parse_bytes()?),
             Tag::String => visitor.visit_str(self.parse_str()?.as_ref()),
             Tag::List => {
-                check_bounds!(1 + 4, self.input);
+                check_bounds!(1 + 4, self.input, Error::truncated_list_header());
                 let tag_id = self.input[0];
                 if tag_id > Tag::LongArray as u8 {
                     cold_path();
@@ -661,13 +668,22 @@ impl<'de, O: ByteOrder> de::Deserializer<'de> for &mut Deserializer<'de, O> {
 
     fn deserialize_newtype_struct<V>(
         self,
-        _name: &'static str,
+        name: &'static str,
         visitor: V,
     ) -> std::result::Result<V::Value, Self::Error>
     where
         V: de::Visitor<'de>,
     {
-        visitor.visit_newtype_struct(self)
+        // the array wrappers require their tag rather than accepting a list
+        let expected = match name {
+            BYTE_ARRAY_NAME => Tag::ByteArray,
+            INT_ARRAY_NAME => Tag::IntArray,
+            LONG_ARRAY_NAME => Tag::LongArray,
+            _ => return visitor.visit_newtype_struct(self),
+        };
+        check_tag!(expected, self.current_tag, {
+            visitor.visit_newtype_struct(self)
+        })
     }
 
     fn deserialize_seq<V>(self, visitor: V) -> std::result::Result<V::Value, Self::Error>
@@ -704,7 +720,7 @@ impl<'de, O: ByteOrder> de::Deserializer<'de> for &mut Deserializer<'de, O> {
             }
             Tag::List => {
                 // Standard List format: element_tag (1 byte) + length (4 bytes)
-                check_bounds!(1 + 4, self.input);
+                check_bounds!(1 + 4, self.input, Error::truncated_list_header());
                 let tag_id = self.input[0];
                 if tag_id > Tag::LongArray as u8 {
                     cold_path();
@@ -742,7 +758,7 @@ impl<'de, O: ByteOrder> de::Deserializer<'de> for &mut Deserializer<'de, O> {
     {
         // Tuples are serialized as List<Compound> where each compound has a single unnamed field
         check_tag!(Tag::List, self.current_tag, {
-            check_bounds!(1 + 4, self.input);
+            check_bounds!(1 + 4, self.input, Error::truncated_list_header());
             let tag_id = self.input[0];
             if tag_id != Tag::Compound as u8 {
                 cold_path();
@@ -0,0 +1,404 @@
+//! Readers for the formats of each Minecraft edition.
+//!
+//! [`read_owned`] takes two byte orders, one for the input and one for the
+//! value it builds, and nothing stops them from being mixed up. The functions
+//! here fix both for the common cases:
+//!
+//! | Function | Edition | Format |
+//! |----------|---------|--------|
+//! | [`read_java`] | Java | big-endian, in files and on the network |
+//! | [`read_bedrock`] | Bedrock | little-endian, in files such as `level.dat` |
+//! | [`read_bedrock_network`] | Bedrock | little-endian with varints, on the network |
+//! | [`read_bedrock_leveldat`] | Bedrock | little-endian after an 8-byte header, in `level.dat` |
+
+use alloc::{format, vec::Vec};
+
+use zerocopy::byteorder;
+
+use crate::{
+    BigEndian, Error, LittleEndian, OwnedValue, Result, ScopedReadableValue, Tag, cold_path,
+    read_owned,
+};
+
+/// Reads a Java Edition document: big-endian, stored big-endian.
+///
+/// This is [`read_owned::<BigEndian, BigEndian>`](read_owned).
+///
+/// # Example
+///
+/// ```
+/// use na_nbt::read_java;
+///
+/// let data = [0x0a, 0x00, 0x00, 0x03, 0x00, 0x01, b'x', 0x00, 0x00, 0x00, 0x2a, 0x00];
+/// let value = read_java(&data)?;
+/// assert_eq!(value.get("x").unwrap().as_int(), Some(42));
+/// # Ok::<(), na_nbt::Error>(())
+/// ```
+///
+/// # Errors
+///
+/// See [`read_owned`].
+#[inline]
+pub fn read_java(source: &[u8]) -> Result<OwnedValue<BigEndian>> {
+    read_owned::<BigEndian, BigEndian>(source)
+}
+
+/// Reads a Bedrock Edition document as stored on disk: little-endian, stored
+/// little-endian.
+///
+/// This is [`read_owned::<LittleEndian, LittleEndian>`](read_owned). Bedrock's
+/// `level.dat` starts with an 8-byte header; read it with
+/// [`read_bedrock_leveldat`].
+///
+/// # Example
+///
+/// ```
+/// use na_nbt::read_bedrock;
+///
+/// let data = [0x0a, 0x00, 0x00, 0x03, 0x01, 0x00, b'x', 0x2a, 0x00, 0x00, 0x00, 0x00];
+/// let value = read_bedrock(&data)?;
+/// assert_eq!(value.get("x").unwrap().as_int(), Some(42));
+/// # Ok::<(), na_nbt::Error>(())
+/// ```
+///
+/// # Errors
+///
+/// See [`read_owned`].
+#[inline]
+pub fn read_bedrock(source: &[u8]) -> Result<OwnedValue<LittleEndian>> {
+    read_owned::<LittleEndian, LittleEndian>(source)
+}
+
+/// Reads a Bedrock Edition `level.dat`: an 8-byte header, then a
+/// little-endian document, stored little-endian.
+///
+/// The header holds two little-endian `u32`s: the storage version of the
+/// world, which is returned with the value, and the length of the document
+/// that follows, which must match the bytes left.
+///
+/// # Example
+///
+/// ```
+/// use na_nbt::read_bedrock_leveldat;
+///
+/// let data = [
+///     0x0a, 0x00, 0x00, 0x00, // version 10
+///     0x0c, 0x00, 0x00, 0x00, // 12 bytes of NBT
+///     0x0a, 0x00, 0x00, 0x03, 0x01, 0x00, b'x', 0x2a, 0x00, 0x00, 0x00, 0x00,
+/// ];
+/// let (version, value) = read_bedrock_leveldat(&data)?;
+/// assert_eq!(version, 10);
+/// assert_eq!(value.get("x").unwrap().as_int(), Some(42));
+/// # Ok::<(), na_nbt::Error>(())
+/// ```
+///
+/// # Errors
+///
+/// Same as [`read_owned`], plus:
+/// - The header is cut short, or declares more bytes than follow it
+///   ([`Error::EndOfFile`])
+/// - More bytes follow the header than it declares
+///   ([`Error::TrailingData`])
+pub fn read_bedrock_leveldat(source: &[u8]) -> Result<(u32, OwnedValue<LittleEndian>)> {
+    let Some((header, document)) = source.split_first_chunk::<8>() else {
+        cold_path();
+        return Err(Error::EndOfFile);
+    };
+    let version = u32::from_le_bytes(header[..4].try_into().unwrap());
+    let len = u32::from_le_bytes(header[4..].try_into().unwrap()) as usize;
+    if len > document.len() {
+        cold_path();
+        return Err(Error::EndOfFile);
+    }
+    if len < document.len() {
+        cold_path();
+        return Err(Error::TrailingData(document.len() - len));
+    }
+    let value = read_owned::<LittleEndian, LittleEndian>(document)?;
+    Ok((version, value))
+}
+
+/// Writes a Bedrock Edition `level.dat`: the 8-byte header with `version`
+/// and the length of the document, then `value` as a little-endian
+/// document.
+///
+/// Whatever byte order `value` is stored in, the output can be read back
+/// with [`read_bedrock_leveldat`].
+///
+/// # Example
+///
+/// ```
+/// use na_nbt::{OwnedCompound, OwnedValue, read_bedrock_leveldat, write_bedrock_leveldat};
+/// use zerocopy::byteorder::LittleEndian;
+///
+/// let mut data: OwnedCompound<LittleEndian> = OwnedCompound::default();
+/// data.insert("LevelName", "world");
+/// let file = write_bedrock_leveldat(10, &OwnedValue::Compound(data))?;
+///
+/// let (version, value) = read_bedrock_leveldat(&file)?;
+/// assert_eq!(version, 10);
+/// assert_eq!(value.get("LevelName").unwrap().as_string().unwrap().decode(), "world");
+/// # Ok::<(), na_nbt::Error>(())
+/// ```
+///
+/// # Errors
+///
+/// Returns an error if writing the value fails, or if the document is too
+/// long for the length field ([`Error::Message`]).
+pub fn write_bedrock_leveldat<'doc>(
+    version: u32,
+    value: &impl ScopedReadableValue<'doc>,
+) -> Result<Vec<u8>> {
+    let document = value.write_to_vec::<LittleEndian>()?;
+    let Ok(len) = u32::try_from(document.len()) else {
+        cold_path();
+        return Err(Error::Message(format!(
+            "document of {} bytes is too long for level.dat",
+            document.len()
+        )));
+    };
+    let mut out = Vec::with_capacity(8 + document.len());
+    out.extend_from_slice(&version.to_le_bytes());
+    out.extend_from_slice(&len.to_le_bytes());
+    out.extend_from_slice(&document);
+    Ok(out)
+}
+
+/// Reads a Bedrock Edition document in its network format, stored
+/// little-endian.
+///
+/// The network format is the little-endian format with variable-length
+/// numbers, as in [`VarInt`](crate::VarInt):
+///
+/// - `Int` is a zigzag-encoded varint, and `Long` a zigzag-encoded varlong.
+/// - String and name lengths are unsigned varints.
+/// - Array and list lengths are zigzag-encoded varints, as are the elements
+///   of int and long arrays.
+///
+/// The input is rewritten into the little-endian format and then read with
+/// [`read_owned`], so the result is the same as for [`read_bedrock`].
+///
+/// # Example
+///
+/// ```
+/// use na_nbt::read_bedrock_network;
+///
+/// // Compound { x: Int 42 }, 42 being zigzag-encoded as 84
+/// let data = [0x0a, 0x00, 0x03, 0x01, b'x', 0x54, 0x00];
+/// let value = read_bedrock_network(&data)?;
+/// assert_eq!(value.get("x").unwrap().as_int(), Some(42));
+/// # Ok::<(), na_nbt::Error>(())
+/// ```
+///
+/// # Errors
+///
+/// Returns an error if:
+/// - The data is truncated ([`Error::EndOfFile`])
+/// - An invalid tag type is encountered ([`Error::InvalidTagType`])
+/// - A varint is too long, a length is negative or a string is longer than
+///   the little-endian format allows ([`Error::Message`])
+/// - Extra data remains after parsing ([`Error::TrailingData`])
+///
+/// Invalid tags and malformed numbers are wrapped in [`Error::At`] with the
+/// offset of the offending byte in `source`.
+pub fn read_bedrock_network(source: &[u8]) -> Result<OwnedValue<LittleEndian>> {
+    let bytes = network_to_little_endian(source)?;
+    read_owned::<LittleEndian, LittleEndian>(&bytes)
+}
+
+/// Rewrites a document in the Bedrock network format into the little-endian
+/// format, checking tags and bounds on the way.
+fn network_to_little_endian(source: &[u8]) -> Result<Vec<u8>> {
+    enum Frame {
+        Compound,
+        List { tag_id: u8, remaining: usize },
+    }
+
+    let mut reader = Reader {
+        data: source,
+        pos: 0,
+    };
+    let mut out = Vec::with_capacity(source.len() + source.len() / 2);
+
+    let mut tag_id = reader.tag()?;
+    out.push(tag_id);
+    if tag_id != Tag::End as u8 {
+        reader.string(&mut out)?;
+    }
+
+    let mut stack = Vec::new();
+    loop {
+        let at = reader.pos;
+        match tag_id {
+            0 => {}
+            1 => out.push(reader.byte()?),
+            2 => out.extend_from_slice(reader.bytes(2)?),
+            3 => out.extend_from_slice(
+                &byteorder::I32::<LittleEndian>::new(reader.var_i32()?).to_bytes(),
+            ),
+            4 => out.extend_from_slice(
+                &byteorder::I64::<LittleEndian>::new(reader.var_i64()?).to_bytes(),
+            ),
+            5 => out.extend_from_slice(reader.bytes(4)?),
+            6 => out.extend_from_slice(reader.bytes(8)?),
+            7 => {
+                let len = reader.len(&mut out)?;
+                out.extend_from_slice(reader.bytes(len)?);
+            }
+            8 => reader.string(&mut out)?,
+            9 => {
+                let element_type = reader.tag()?;
+                out.push(element_type);
+                let count = reader.len(&mut out)?;
+                if element_type == Tag::End as u8 && count > 0 {
+                    cold_path();
+                    return Err(Error::InvalidTagType(element_type).at(at));
+                }
+                stack.push(Frame::List {
+                    tag_id: element_type,
+                    remaining: count,
+                });
+            }
+            10 => stack.push(Frame::Compound),
+            11 => {
+                for _ in 0..reader.len(&mut out)? {
+                    let value = reader.var_i32()?;
+                    out.extend_from_slice(&byteorder::I32::<LittleEndian>::new(value).to_bytes());
+                }
+            }
+            12 => {
+                for _ in 0..reader.len(&mut out)? {
+                    let value = reader.var_i64()?;
+                    out.extend_from_slice(&byteorder::I64::<LittleEndian>::new(value).to_bytes());
+                }
+            }
+            _ => unreachable!("tags are checked when read"),
+        }
+
+        tag_id = loop {
+            match stack.last_mut() {
+                None => {
+                    if reader.pos < source.len() {
+                        cold_path();
+                        return Err(Error::TrailingData(source.len() - reader.pos));
+                    }
+                    return Ok(out);
+                }
+                Some(Frame::Compound) => {
+                    let next = reader.tag()?;
+                    out.push(next);
+                    if next == Tag::End as u8 {
+                        stack.pop();
+                        continue;
+                    }
+                    reader.string(&mut out)?;
+                    break next;
+                }
+                Some(Frame::List { tag_id, remaining }) => {
+                    if *remaining == 0 {
+                        stack.pop();
+                        continue;
+                    }
+                    *remaining -= 1;
+                    break *tag_id;
+                }
+            }
+        };
+    }
+}
+
+struct Reader<'a> {
+    data: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> Reader<'a> {
+    #[inline]
+    fn bytes(&mut self, n: usize) -> Result<&'a [u8]> {
+        match self.pos.checked_add(n) {
+            Some(end) if end <= self.data.len() => {
+                let bytes = &self.data[self.pos..end];
+                self.pos = end;
+                Ok(bytes)
+            }
+            _ => {
+                cold_path();
+                Err(Error::EndOfFile)
+            }
+        }
+    }
+
+    #[inline]
+    fn byte(&mut self) -> Result<u8> {
+        Ok(self.bytes(1)?[0])
+    }
+
+    /// Reads a tag type, rejecting unknown ones.
+    #[inline]
+    fn tag(&mut self) -> Result<u8> {
+        let at = self.pos;
+        let tag_id = self.byte()?;
+        if tag_id > 12 {
+            cold_path();
+            return Err(Error::InvalidTagType(tag_id).at(at));
+        }
+        Ok(tag_id)
+    }
+
+    /// Reads an unsigned varint of at most `max_bytes` bytes.
+    fn var_u64(&mut self, max_bytes: usize) -> Result<u64> {
+        let at = self.pos;
+        let mut value = 0;
+        for i in 0..max_bytes {
+            let byte = self.byte()?;
+            value |= ((byte & 0x7f) as u64) << (7 * i);
+            if byte & 0x80 == 0 {
+                return Ok(value);
+            }
+        }
+        cold_path();
+        Err(Error::Message("varint is too long".into()).at(at))
+    }
+
+    #[inline]
+    fn var_u32(&mut self) -> Result<u32> {
+        Ok(self.var_u64(5)? as u32)
+    }
+
+    #[inline]
+    fn var_i32(&mut self) -> Result<i32> {
+        let value = self.var_u32()?;
+        Ok((value >> 1) as i32 ^ -((value & 1) as i32))
+    }
+
+    #[inline]
+    fn var_i64(&mut self) -> Result<i64> {
+        let value = self.var_u64(10)?;
+        Ok((value >> 1) as i64 ^ -((value & 1) as i64))
+    }
+
+    /// Reads an array or list length and writes it as a `u32`.
+    fn len(&mut self, out: &mut Vec<u8>) -> Result<usize> {
+        let at = self.pos;
+        let len = self.var_i32()?;
+        if len < 0 {
+            cold_path();
+            return Err(Error::Message(format!("negative length {len}")).at(at));
+        }
+        out.extend_from_slice(&byteorder::U32::<LittleEndian>::new(len as u32).to_bytes());
+        Ok(len as usize)
+    }
+
+    /// Reads a string and writes it with a `u16` length.
+    fn string(&mut self, out: &mut Vec<u8>) -> Result<()> {
+        let at = self.pos;
+        let len = self.var_u32()?;
+        let Ok(len) = u16::try_from(len) else {
+            cold_path();
+            return Err(Error::Message(format!("string of {len} bytes is too long")).at(at));
+        };
+        out.extend_from_slice(&byteorder::U16::<LittleEndian>::new(len).to_bytes());
+        out.extend_from_slice(self.bytes(len as usize)?);
+        Ok(())
+    }
+}
@@ -22,11 +22,12 @@
 //!             println!("Data was truncated");
 //!             Err(Error::EndOfFile)
 //!         }
-//!         Err(Error::InvalidTagType(tag)) => {
-//!             println!("Unknown tag type: {:#04x}", tag);
-//!             Err(Error::InvalidTagType(tag))
+//!         Err(e) => {
+//!             if let Error::InvalidTagType(tag) = e.kind() {
+//!                 println!("Unknown tag type {:#04x} at {:?}", tag, e.offset());
+//!             }
+//!             Err(e)
 //!         }
-//!         Err(e) => Err(e),
 //!     }
 //! }
 //! ```
@@ -49,7 +50,8 @@
 //! }
 //! ```
 
-use std::fmt::{self, Display};
+use alloc::{boxed::Box, format, string::String};
+use core::fmt::{self, Display};
 
 /// Alias for a `Result` with the error type [`Error`].
 ///
@@ -64,7 +66,7 @@ use std::fmt::{self, Display};
 ///     Ok(42)
 /// }
 /// ```
-pub type Result<T> = std::result::Result<T, Error>;
+pub type Result<T> = core::result::Result<T, Error>;
 
 /// This type represents all possible errors that can occur when reading or
 /// writing NBT data.
@@ -75,17 +77,24 @@ pub type Result<T> = std::result::Result<T, Error>;
 ///
 /// **Parsing Errors**
 /// - [`EndOfFile`](Error::EndOfFile) - Data truncated unexpectedly
+/// - [`Truncated`](Error::Truncated) - Data truncated inside a list header
 /// - [`InvalidTagType`](Error::InvalidTagType) - Unknown NBT tag byte
 /// - [`TrailingData`](Error::TrailingData) - Extra bytes after root tag
+/// - [`DuplicateKey`](Error::DuplicateKey) - Repeated compound key (strict reading only)
+/// - [`TooManyNodes`](Error::TooManyNodes) - More values than [`ReadLimits::max_nodes`](crate::ReadLimits::max_nodes)
+/// - [`StringBytesExceeded`](Error::StringBytesExceeded) - More string bytes than [`ReadLimits::max_string_bytes`](crate::ReadLimits::max_string_bytes)
+/// - [`At`](Error::At) - One of the above, with the byte offset it occurred at
 ///
 /// **Type Errors**
 /// - [`TagMismatch`](Error::TagMismatch) - Type mismatch in list or during deserialization
+/// - [`LengthMismatch`](Error::LengthMismatch) - Array of the wrong length for a fixed-size array
 /// - [`InvalidCharacter`](Error::InvalidCharacter) - Invalid Unicode code point
 ///
 /// **Serialization Errors**
 /// - [`KeyMustBeString`](Error::KeyMustBeString) - Non-string map key
 /// - [`ListTooLong`](Error::ListTooLong) - List exceeds i32::MAX elements
 /// - [`ListLengthUnknown`](Error::ListLengthUnknown) - Sequence without known length
+/// - [`BufferTooSmall`](Error::BufferTooSmall) - Output buffer too small
 ///
 /// **I/O Errors**
 /// - [`IO`](Error::IO) - Underlying I/O error
@@ -137,6 +146,9 @@ pub enum Error {
     ///     Err(e) => println!("Could not create file: {}", e),
     /// }
     /// ```
+    ///
+    /// Requires the `std` feature.
+    #[cfg(feature = "std")]
     IO(std::io::Error),
 
     /// The input ended unexpectedly.
@@ -158,6 +170,31 @@ pub enum Error {
     /// ```
     EndOfFile,
 
+    /// The input ended inside a structural header, rather than inside a
+    /// value's data.
+    ///
+    /// `context` names the header. The readers return this with context
+    /// `"list header"` when a list's element type or length is cut off, and
+    /// [`EndOfFile`](Error::EndOfFile) when the data runs out inside the
+    /// elements. Use [`Error::is_end_of_input`] to treat both alike.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use na_nbt::{read_borrowed, Error};
+    /// use zerocopy::byteorder::BigEndian;
+    ///
+    /// // List root whose length is cut off after two bytes
+    /// let truncated = [0x09, 0x00, 0x00, 0x01, 0x00, 0x00];
+    /// let err = read_borrowed::<BigEndian>(&truncated).err().unwrap();
+    /// assert!(matches!(err, Error::Truncated { context: "list header" }));
+    /// assert_eq!(err.to_string(), "unexpected end of input in list header");
+    /// ```
+    Truncated {
+        /// What was being read when the input ended.
+        context: &'static str,
+    },
+
     /// Extra bytes remain after parsing the NBT data.
     ///
     /// NBT documents should be consumed completely. If there are remaining
@@ -197,11 +234,49 @@ pub enum Error {
     ///
     /// // Tag type 0xFF is invalid
     /// let invalid = [0xFF, 0x00, 0x00];
-    /// let result = read_borrowed::<BigEndian>(&invalid);
-    /// assert!(matches!(result, Err(Error::InvalidTagType(0xFF))));
+    /// let err = read_borrowed::<BigEndian>(&invalid).err().unwrap();
+    /// assert!(matches!(err.kind(), Error::InvalidTagType(0xFF)));
     /// ```
     InvalidTagType(u8),
 
+    /// A compound contains the same key more than once.
+    ///
+    /// The regular readers keep duplicate entries as encoded; only
+    /// [`read_owned_strict`](crate::read_owned_strict) rejects them, returning
+    /// the first repeated key.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use na_nbt::{read_owned_strict, Error};
+    /// use zerocopy::byteorder::BigEndian;
+    ///
+    /// // Compound with two Byte entries named "x"
+    /// let data = [
+    ///     0x0a, 0x00, 0x00,
+    ///     0x01, 0x00, 0x01, b'x', 1,
+    ///     0x01, 0x00, 0x01, b'x', 2,
+    ///     0x00,
+    /// ];
+    /// let err = read_owned_strict::<BigEndian, BigEndian>(&data).err().unwrap();
+    /// assert!(matches!(err, Error::DuplicateKey(key) if key == "x"));
+    /// ```
+    DuplicateKey(String),
+
+    /// A document holds more values than allowed.
+    ///
+    /// Returned by the readers that take [`ReadLimits`](crate::ReadLimits),
+    /// with the [`max_nodes`](crate::ReadLimits::max_nodes) limit that was
+    /// exceeded.
+    TooManyNodes(usize),
+
+    /// The strings of a document take more bytes than allowed.
+    ///
+    /// Returned by the readers that take [`ReadLimits`](crate::ReadLimits),
+    /// with the [`max_string_bytes`](crate::ReadLimits::max_string_bytes)
+    /// limit that was exceeded.
+    StringBytesExceeded(usize),
+
     /// A list or array length exceeds the maximum.
     ///
     /// NBT list lengths are stored as signed 32-bit integers, so lists
@@ -215,6 +290,12 @@ pub enum Error {
     /// be serialized directly.
     ListLengthUnknown,
 
+    /// The output buffer is too small for the value.
+    ///
+    /// Returned by [`write_to_slice`](crate::ScopedReadableValue::write_to_slice),
+    /// with the number of bytes the value needs.
+    BufferTooSmall(usize),
+
     /// Map key must be a string type.
     ///
     /// NBT compound tags require string keys. This error is returned when
@@ -257,6 +338,13 @@ pub enum Error {
     /// ```
     TagMismatch(u8, u8),
 
+    /// An array did not have the length required.
+    ///
+    /// Returned when converting an array view into a fixed-size Rust array,
+    /// such as `[i32; 3]`. The first value is the expected length, the second
+    /// is the actual length.
+    LengthMismatch(usize, usize),
+
     /// An invalid Unicode code point was encountered.
     ///
     /// This error occurs when deserializing a `char` from an integer value
@@ -264,6 +352,75 @@ pub enum Error {
     ///
     /// Valid Unicode scalar values are `0x0000..=0xD7FF` and `0xE000..=0x10FFFF`.
     InvalidCharacter(u32),
+
+    /// An error annotated with the byte offset in the input where it was detected.
+    ///
+    /// The readers ([`read_borrowed`](crate::read_borrowed), `read_shared`,
+    /// [`read_owned`](crate::read_owned) and
+    /// [`read_owned_from_reader`](crate::read_owned_from_reader)) wrap
+    /// [`InvalidTagType`](Error::InvalidTagType) in this variant, with `offset`
+    /// pointing at the offending tag byte. Use [`Error::kind`] to look through
+    /// the wrapper and [`Error::offset`] to get the position.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use na_nbt::{read_borrowed, Error};
+    /// use zerocopy::byteorder::BigEndian;
+    ///
+    /// // Compound containing an entry with tag type 0x0d
+    /// let data = [0x0a, 0x00, 0x00, 0x0d, 0x00, 0x00];
+    /// let err = read_borrowed::<BigEndian>(&data).err().unwrap();
+    /// assert!(matches!(err.kind(), Error::InvalidTagType(0x0d)));
+    /// assert_eq!(err.offset(), Some(3));
+    /// assert_eq!(err.to_string(), "invalid NBT tag type: 0x0d at byte offset 3");
+    /// ```
+    At {
+        /// Byte offset from the start of the input.
+        offset: usize,
+        /// The underlying error.
+        kind: Box<Error>,
+    },
+}
+
+impl Error {
+    /// Returns the byte offset at which this error was detected, if known.
+    pub fn offset(&self) -> Option<usize> {
+        match self {
+            Error::At { offset, .. } => Some(*offset),
+            _ => None,
+        }
+    }
+
+    /// Returns `true` if the input ended early, whether as
+    /// [`EndOfFile`](Error::EndOfFile) or [`Truncated`](Error::Truncated),
+    /// looking through any [`At`](Error::At) wrapper.
+    pub fn is_end_of_input(&self) -> bool {
+        matches!(self.kind(), Error::EndOfFile | Error::Truncated { .. })
+    }
+
+    /// Returns the underlying error, looking through any [`At`](Error::At) wrapper.
+    pub fn kind(&self) -> &Error {
+        match self {
+            Error::At { kind, .. } => kind.kind(),
+            _ => self,
+        }
+    }
+
+    #[inline]
+    pub(crate) fn truncated_list_header() -> Self {
+        Error::Truncated {
+            context: "list header",
+        }
+    }
+
+    #[inline]
+    pub(crate) fn at(self, offset: usize) -> Self {
+        Error::At {
+            offset,
+            kind: Box::new(self),
+        }
+    }
 }
 
 #[cfg(feature = "serde")]
@@ -284,25 +441,48 @@ impl Display for Error {
     fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
         match self {
             Error::Message(message) => formatter.write_str(message),
+            #[cfg(feature = "std")]
             Error::IO(error) => formatter.write_str(&error.to_string()),
             Error::EndOfFile => formatter.write_str("unexpected end of input"),
+            Error::Truncated { context } => {
+                formatter.write_str(&format!("unexpected end of input in {context}"))
+            }
             Error::TrailingData(remaining_bytes) => formatter.write_str(&format!(
                 "trailing data after end of input: {remaining_bytes} bytes remaining"
             )),
             Error::InvalidTagType(tag) => {
                 formatter.write_str(&format!("invalid NBT tag type: {tag:#04x}"))
             }
+            Error::DuplicateKey(key) => {
+                formatter.write_str(&format!("duplicate compound key: {key:?}"))
+            }
+            Error::TooManyNodes(max) => {
+                formatter.write_str(&format!("document has more than {max} values"))
+            }
+            Error::StringBytesExceeded(max) => {
+                formatter.write_str(&format!("document strings take more than {max} bytes"))
+            }
             Error::ListTooLong(len) => formatter.write_str(&format!("list length too long: {len}")),
             Error::ListLengthUnknown => formatter.write_str("list length unknown"),
+            Error::BufferTooSmall(needed) => {
+                formatter.write_str(&format!("output buffer too small: {needed} bytes needed"))
+            }
             Error::KeyMustBeString => formatter.write_str("map key must be a string"),
             Error::TagMismatch(expected, actual) => formatter.write_str(&format!(
                 "tag in list mismatch: expected {expected:#04x}, got {actual:#04x}"
             )),
+            Error::LengthMismatch(expected, actual) => formatter.write_str(&format!(
+                "array length mismatch: expected {expected}, got {actual}"
+            )),
             Error::InvalidCharacter(character) => {
                 formatter.write_str(&format!("invalid character: {character:#04x}"))
             }
+            Error::At { offset, kind } => {
+                formatter.write_str(&format!("{kind} at byte offset {offset}"))
+            }
         }
     }
 }
 
+#[cfg(feature = "std")]
 impl std::error::Error for Error {}
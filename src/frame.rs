@@ -0,0 +1,131 @@
+//! Length-prefixed NBT for framed protocols.
+//!
+//! Network protocols often send an NBT value after its length, so that the
+//! receiver can read the frame before parsing it. [`write_framed_value_to_vec`]
+//! writes such a frame in one pass: it reserves the length field, writes the
+//! value after it and then fills the field in.
+
+use alloc::{vec, vec::Vec};
+
+use zerocopy::byteorder;
+
+use crate::{ByteOrder, Error, Result, ScopedReadableValue, cold_path};
+
+/// The encoding of the length field in front of a frame.
+///
+/// Implemented by [`U16`](zerocopy::byteorder::U16) and
+/// [`U32`](zerocopy::byteorder::U32) in either byte order, for fixed-width
+/// fields, and by [`VarInt`].
+pub trait LengthPrefix {
+    /// The most bytes the field can take.
+    const MAX_LEN: usize;
+
+    /// Encodes `len` at the start of `out`, which is [`MAX_LEN`](Self::MAX_LEN)
+    /// bytes long, and returns the number of bytes used, or `None` if `len`
+    /// cannot be represented.
+    fn encode(len: usize, out: &mut [u8]) -> Option<usize>;
+}
+
+impl<O: ByteOrder> LengthPrefix for byteorder::U16<O> {
+    const MAX_LEN: usize = 2;
+
+    #[inline]
+    fn encode(len: usize, out: &mut [u8]) -> Option<usize> {
+        let len = u16::try_from(len).ok()?;
+        out[..2].copy_from_slice(&byteorder::U16::<O>::new(len).to_bytes());
+        Some(2)
+    }
+}
+
+impl<O: ByteOrder> LengthPrefix for byteorder::U32<O> {
+    const MAX_LEN: usize = 4;
+
+    #[inline]
+    fn encode(len: usize, out: &mut [u8]) -> Option<usize> {
+        let len = u32::try_from(len).ok()?;
+        out[..4].copy_from_slice(&byteorder::U32::<O>::new(len).to_bytes());
+        Some(4)
+    }
+}
+
+/// A variable-length field: seven bits per byte, least significant group
+/// first, with the high bit set on every byte but the last.
+///
+/// This is the `VarInt` of Java Edition and the unsigned varint of Bedrock
+/// Edition. Lengths up to `u32::MAX` are supported, taking at most five bytes.
+pub struct VarInt;
+
+impl LengthPrefix for VarInt {
+    const MAX_LEN: usize = 5;
+
+    fn encode(len: usize, out: &mut [u8]) -> Option<usize> {
+        let mut len = u32::try_from(len).ok()?;
+        let mut i = 0;
+        loop {
+            let byte = (len & 0x7f) as u8;
+            len >>= 7;
+            if len == 0 {
+                out[i] = byte;
+                return Some(i + 1);
+            }
+            out[i] = byte | 0x80;
+            i += 1;
+        }
+    }
+}
+
+/// Writes `value` as an NBT document preceded by its length, encoded as `L`.
+///
+/// The length counts the bytes of the document alone, not of the field. The
+/// document is written as by
+/// [`write_to_vec`](ScopedReadableValue::write_to_vec), in the `TARGET` byte
+/// order, which need not match the byte order of the length field.
+///
+/// # Example
+///
+/// ```
+/// use na_nbt::{OwnedCompound, OwnedValue, VarInt, read_owned, write_framed_value_to_vec};
+/// use zerocopy::byteorder::{BigEndian, LittleEndian, U32};
+///
+/// let mut compound: OwnedCompound<BigEndian> = OwnedCompound::default();
+/// compound.insert("id", "minecraft:chest");
+/// let value = OwnedValue::Compound(compound);
+///
+/// let frame = write_framed_value_to_vec::<LittleEndian, U32<LittleEndian>>(&value)?;
+/// let len = u32::from_le_bytes(frame[..4].try_into().unwrap()) as usize;
+/// assert_eq!(len, frame.len() - 4);
+///
+/// let frame = write_framed_value_to_vec::<BigEndian, VarInt>(&value)?;
+/// assert_eq!(frame[0] as usize, frame.len() - 1);
+/// let read: OwnedValue<BigEndian> = read_owned::<BigEndian, BigEndian>(&frame[1..])?;
+/// # Ok::<(), na_nbt::Error>(())
+/// ```
+///
+/// # Errors
+///
+/// Returns an error if writing the value fails, or if its length does not fit
+/// in `L` ([`Error::Message`]).
+pub fn write_framed_value_to_vec<'doc, TARGET: ByteOrder, L: LengthPrefix>(
+    value: &impl ScopedReadableValue<'doc>,
+) -> Result<Vec<u8>> {
+    let mut frame = vec![0; L::MAX_LEN];
+    #[cfg(feature = "std")]
+    value.write_to_writer::<TARGET>(&mut frame)?;
+    #[cfg(not(feature = "std"))]
+    frame.extend_from_slice(&value.write_to_vec::<TARGET>()?);
+
+    let body_len = frame.len() - L::MAX_LEN;
+    let Some(prefix_len) = L::encode(body_len, &mut frame[..L::MAX_LEN]) else {
+        cold_path();
+        return Err(Error::Message(
+            "value is too long for the length prefix".into(),
+        ));
+    };
+
+    // a prefix shorter than the space reserved moves the body back
+    if prefix_len < L::MAX_LEN {
+        frame.copy_within(L::MAX_LEN.., prefix_len);
+        frame.truncate(prefix_len + body_len);
+    }
+    Ok(frame)
+}
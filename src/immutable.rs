@@ -43,25 +43,41 @@
 //! ```
 
 #[cfg(feature = "shared")]
-use std::sync::Arc;
-use std::{any::TypeId, io::Write, ptr};
+use alloc::sync::Arc;
+use alloc::{vec, vec::Vec};
+use core::{any::TypeId, ptr};
+#[cfg(feature = "std")]
+use std::io::Write;
 
 #[cfg(feature = "shared")]
 use bytes::Bytes;
-use zerocopy::{IntoBytes, byteorder};
+#[cfg(feature = "std")]
+use zerocopy::IntoBytes;
+use zerocopy::byteorder;
 
-use crate::{ByteOrder, Error, Result, Tag, cold_path};
+#[cfg(feature = "std")]
+use crate::Error;
+use crate::{ByteOrder, Result, Tag, cold_path, value_trait::write_to_vec_named};
 
+#[cfg(feature = "std")]
+mod compound_index;
+#[cfg(feature = "shared")]
+mod lazy;
 mod mark;
+#[cfg(feature = "shared")]
+mod preserving;
 mod read;
 mod trait_impl;
 mod util;
 mod value;
 mod write;
 
+#[cfg(feature = "std")]
+pub use compound_index::CompoundIndex;
 pub use value::{
-    ReadonlyArray, ReadonlyCompound, ReadonlyCompoundIter, ReadonlyList, ReadonlyListIter,
-    ReadonlyString, ReadonlyValue,
+    ReadonlyArray, ReadonlyCompound, ReadonlyCompoundIter, ReadonlyCompoundIterOf,
+    ReadonlyCompoundIterStr, ReadonlyList, ReadonlyListIter, ReadonlyString, ReadonlyTag,
+    ReadonlyValue,
 };
 
 /// A zero-copy NBT value that borrows from a byte slice.
@@ -86,7 +102,7 @@ pub use value::{
 ///
 /// - `O` - The byte order of the NBT data ([`BigEndian`](zerocopy::byteorder::BigEndian)
 ///   for Java Edition, [`LittleEndian`](zerocopy::byteorder::LittleEndian) for Bedrock)
-pub type BorrowedValue<'s, O> = value::ReadonlyValue<'s, O, ()>;
+pub type BorrowedValue<'s, O> = value::ReadonlyValue<'s, O, value::Borrowed>;
 
 /// Parses NBT from a byte slice with zero-copy borrowing.
 ///
@@ -122,8 +138,17 @@ pub type BorrowedValue<'s, O> = value::ReadonlyValue<'s, O, ()>;
 ///
 /// Returns an error if:
 /// - The data is truncated ([`Error::EndOfFile`])
-/// - An invalid tag type is encountered ([`Error::InvalidTagType`])
+/// - An invalid tag type is encountered ([`Error::InvalidTagType`], wrapped in
+///   [`Error::At`] with the offset of the offending byte)
 /// - Extra data remains after parsing ([`Error::TrailingData`])
+///
+/// # Duplicate Keys
+///
+/// Duplicate compound keys are not an error. The document is read in place,
+/// so every entry stays visible to iteration and writing, while lookups such
+/// as `get` scan from the start and return the first occurrence. This differs
+/// from [`read_owned`](crate::read_owned), which keeps only the last entry;
+/// use [`read_owned_strict`](crate::read_owned_strict) to reject such input.
 pub fn read_borrowed<'s, O: ByteOrder>(source: &'s [u8]) -> Result<BorrowedDocument<'s, O>> {
     unsafe {
         read::read_unsafe::<O, _>(source.as_ptr(), source.len(), |mark| BorrowedDocument {
@@ -134,6 +159,59 @@ pub fn read_borrowed<'s, O: ByteOrder>(source: &'s [u8]) -> Result<BorrowedDocum
     }
 }
 
+/// Parses a borrowed document whose byte order is unknown.
+///
+/// The data is read as big-endian first and, if that fails, as
+/// little-endian. The returned [`AutoDocument`] says which one succeeded.
+/// Since [`read_borrowed`] rejects trailing bytes, a read that stops short of
+/// the end of the data counts as a failure.
+///
+/// This is a best-effort heuristic for inspecting byte-swapped or unlabeled
+/// files. Some data parses under both byte orders, most simply a root whose
+/// name is empty and that holds nothing, and is then reported as big-endian
+/// whatever it was written as. When the byte order is known, use
+/// [`read_borrowed`] with it.
+///
+/// # Example
+///
+/// ```
+/// use na_nbt::{AutoDocument, read_borrowed_auto};
+///
+/// // Compound { "a": Int(1) }, written little-endian
+/// let data = [
+///     0x0a, 0x00, 0x00,
+///     0x03, 0x01, 0x00, b'a', 0x01, 0x00, 0x00, 0x00,
+///     0x00,
+/// ];
+/// match read_borrowed_auto(&data)? {
+///     AutoDocument::LittleEndian(doc) => assert!(doc.root().is_compound()),
+///     AutoDocument::BigEndian(_) => unreachable!(),
+/// }
+/// # Ok::<(), na_nbt::Error>(())
+/// ```
+///
+/// # Errors
+///
+/// Returns the error of the big-endian read if the data parses under
+/// neither byte order.
+pub fn read_borrowed_auto(source: &[u8]) -> Result<AutoDocument<'_>> {
+    let big_endian = match read_borrowed::<byteorder::BigEndian>(source) {
+        Ok(doc) => return Ok(AutoDocument::BigEndian(doc)),
+        Err(e) => e,
+    };
+    match read_borrowed::<byteorder::LittleEndian>(source) {
+        Ok(doc) => Ok(AutoDocument::LittleEndian(doc)),
+        Err(_) => Err(big_endian),
+    }
+}
+
+/// A document parsed by [`read_borrowed_auto`], tagged with the byte order
+/// it parsed under.
+pub enum AutoDocument<'s> {
+    BigEndian(BorrowedDocument<'s, byteorder::BigEndian>),
+    LittleEndian(BorrowedDocument<'s, byteorder::LittleEndian>),
+}
+
 /// A parsed NBT document that borrows from a byte slice.
 ///
 /// This type is returned by [`read_borrowed`] and provides access to the
@@ -149,10 +227,6 @@ pub struct BorrowedDocument<'s, O: ByteOrder> {
     _marker: core::marker::PhantomData<(&'s (), O)>,
 }
 
-impl value::Never for () {
-    unsafe fn never() -> Self {}
-}
-
 impl<'s, O: ByteOrder> BorrowedDocument<'s, O> {
     /// Returns the root value of the NBT document.
     ///
@@ -188,10 +262,68 @@ impl<'s, O: ByteOrder> BorrowedDocument<'s, O> {
                 root_tag,
                 self.source.add(3 + name_len as usize),
                 self.mark.as_ptr(),
-                (),
+                value::Borrowed::new(self.source),
             )
         }
     }
+
+    /// Returns the name of the root value, or `None` if the root is `End`,
+    /// which has no name.
+    ///
+    /// Most documents have an empty root name, but some files name their
+    /// root; keep the name to write such a file back unchanged.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use na_nbt::read_borrowed;
+    /// use zerocopy::byteorder::BigEndian;
+    ///
+    /// let data = [0x0a, 0x00, 0x04, b'D', b'a', b't', b'a', 0x00];
+    /// let doc = read_borrowed::<BigEndian>(&data).unwrap();
+    /// assert_eq!(doc.root_name().unwrap().decode(), "Data");
+    ///
+    /// let doc = read_borrowed::<BigEndian>(&[0x00]).unwrap();
+    /// assert!(doc.root_name().is_none());
+    /// ```
+    pub fn root_name<'doc>(&'doc self) -> Option<ReadonlyString<'doc, value::Borrowed>> {
+        let root_tag: Tag = unsafe { *self.source.cast() };
+        if root_tag == Tag::End {
+            cold_path();
+            return None;
+        }
+
+        let name_len = byteorder::U16::<O>::from_bytes(unsafe { *self.source.add(1).cast() }).get();
+        let name = unsafe { core::slice::from_raw_parts(self.source.add(3), name_len as usize) };
+        Some(ReadonlyString::new(name, value::Borrowed::new(self.source)))
+    }
+
+    /// Writes the document back, root name included.
+    ///
+    /// Unlike `root().write_to_vec()`, which writes an empty root name, this
+    /// keeps the name found by [`root_name`](Self::root_name). Otherwise the
+    /// output is that of [`write_to_vec`](crate::ScopedReadableValue::write_to_vec),
+    /// so an empty list is written with the `End` element tag whatever tag it
+    /// was read with; apart from that, a read followed by a write in the same
+    /// byte order gives back the same bytes.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use na_nbt::read_borrowed;
+    /// use zerocopy::byteorder::{BigEndian, LittleEndian};
+    ///
+    /// let data = [0x0a, 0x00, 0x04, b'D', b'a', b't', b'a', 0x00];
+    /// let doc = read_borrowed::<BigEndian>(&data)?;
+    /// assert_eq!(doc.write_to_vec::<BigEndian>()?, data);
+    /// assert_eq!(doc.write_to_vec::<LittleEndian>()?[1..3], [0x04, 0x00]);
+    /// # Ok::<(), na_nbt::Error>(())
+    /// ```
+    pub fn write_to_vec<TARGET: ByteOrder>(&self) -> Result<Vec<u8>> {
+        let name = self.root_name();
+        let name = name.as_ref().map_or(&[][..], |name| name.raw_bytes());
+        write_to_vec_named::<TARGET>(&self.root(), name)
+    }
 }
 
 unsafe impl<'s, O: ByteOrder> Send for BorrowedDocument<'s, O> {}
@@ -274,6 +406,11 @@ mod shared {
     /// While parsing is still zero-copy, accessing the shared value has slightly
     /// more overhead than borrowed values due to `Arc` reference counting.
     /// Use [`read_borrowed`] when the borrowed lifetime is acceptable.
+    ///
+    /// # Duplicate Keys
+    ///
+    /// Handled as in [`read_borrowed`]: every entry is kept and lookups return
+    /// the first occurrence.
     pub fn read_shared<O: ByteOrder>(source: Bytes) -> Result<SharedValue<O>> {
         Ok(unsafe {
             read::read_unsafe::<O, _>(source.as_ptr(), source.len(), |mark| {
@@ -301,6 +438,71 @@ mod shared {
         }
     }
 
+    impl value::Source for Arc<SharedDocument> {
+        #[inline]
+        fn source(&self) -> *const u8 {
+            // a document read from a source has at least its root tag
+            if self.source.is_empty() {
+                return ptr::null();
+            }
+            self.source.as_ptr()
+        }
+    }
+
+    impl<O: ByteOrder> SharedValue<O> {
+        /// Extracts the value at `path` as a standalone [`SharedValue`].
+        ///
+        /// `path` is a sequence of compound keys leading from `self` to the
+        /// child. The child's entry is sliced out of the source buffer with
+        /// [`Bytes::slice`], so no NBT data is copied; the entry is re-indexed
+        /// as a document of its own, whose root name is the last key. The new
+        /// value holds a reference to the original buffer, not to this document.
+        ///
+        /// An empty `path` returns `self` unchanged.
+        ///
+        /// Returns `None` if any key is missing or a step is not a compound.
+        ///
+        /// # Example
+        ///
+        /// ```
+        /// use na_nbt::{OwnedCompound, OwnedValue, read_shared};
+        /// use bytes::Bytes;
+        /// use zerocopy::byteorder::BigEndian;
+        ///
+        /// let mut player = OwnedCompound::<BigEndian>::default();
+        /// player.insert("name", "Steve");
+        /// let mut root = OwnedCompound::<BigEndian>::default();
+        /// root.insert("player", player);
+        /// let bytes = OwnedValue::Compound(root).write_to_vec::<BigEndian>().unwrap();
+        ///
+        /// let root = read_shared::<BigEndian>(Bytes::from(bytes)).unwrap();
+        /// let player = root.into_shared_child(&["player"]).unwrap();
+        /// let name = player.get("name").unwrap();
+        /// assert_eq!(name.as_string().unwrap().decode(), "Steve");
+        /// ```
+        pub fn into_shared_child(self, path: &[&str]) -> Option<SharedValue<O>> {
+            let Some((last, parents)) = path.split_last() else {
+                return Some(self);
+            };
+
+            let mut value = self;
+            for key in parents {
+                value = value.get(*key)?;
+            }
+
+            let SharedValue::Compound(compound) = value else {
+                cold_path();
+                return None;
+            };
+
+            let entry = compound.get_entry_bytes(last)?;
+            let source = &compound.doc.source;
+            let start = unsafe { entry.as_ptr().byte_offset_from_unsigned(source.as_ptr()) };
+            // the entry was validated when the parent was read
+            read_shared::<O>(source.slice(start..start + entry.len())).ok()
+        }
+    }
+
     impl SharedDocument {
         /// Returns the root value of the document.
         #[inline]
@@ -328,9 +530,119 @@ mod shared {
     }
 }
 
+#[cfg(feature = "shared")]
+pub use lazy::{LazyCompound, read_shared_lazy};
+#[cfg(feature = "shared")]
+pub use preserving::{PreservingDocument, read_shared_preserving};
 #[cfg(feature = "shared")]
 pub use shared::{SharedDocument, SharedValue, read_shared};
 
+// MmapDocument and read_mmap require the "mmap" feature (memmap2 crate)
+#[cfg(feature = "mmap")]
+mod mmap {
+    use std::{fs::File, path::Path};
+
+    use memmap2::Mmap;
+
+    use super::*;
+
+    /// A parsed NBT document backed by a memory-mapped file.
+    ///
+    /// This type is returned by [`read_mmap`]. It works like
+    /// [`BorrowedDocument`], but owns the mapping it borrows from, so it can
+    /// be stored and moved freely. The file is never copied into memory;
+    /// pages are loaded by the OS as values are accessed.
+    pub struct MmapDocument<O: ByteOrder> {
+        // declared before `mmap` so it is dropped first
+        doc: BorrowedDocument<'static, O>,
+        mmap: Mmap,
+    }
+
+    impl<O: ByteOrder> MmapDocument<O> {
+        /// Returns the root value of the NBT document.
+        ///
+        /// The returned value borrows from this document and cannot outlive it.
+        #[inline]
+        pub fn root(&self) -> BorrowedValue<'_, O> {
+            self.doc.root()
+        }
+
+        /// Returns the name of the root value, or `None` if the root is `End`.
+        ///
+        /// See [`BorrowedDocument::root_name`].
+        #[inline]
+        pub fn root_name(&self) -> Option<ReadonlyString<'_, value::Borrowed>> {
+            self.doc.root_name()
+        }
+
+        /// Writes the document back, root name included.
+        ///
+        /// See [`BorrowedDocument::write_to_vec`]. Use
+        /// [`as_bytes`](Self::as_bytes) for the file exactly as it is.
+        #[inline]
+        pub fn write_to_vec<TARGET: ByteOrder>(&self) -> Result<Vec<u8>> {
+            self.doc.write_to_vec::<TARGET>()
+        }
+
+        /// Returns the mapped bytes of the file.
+        #[inline]
+        pub fn as_bytes(&self) -> &[u8] {
+            &self.mmap
+        }
+    }
+
+    /// Memory-maps a file and parses it with zero-copy borrowing.
+    ///
+    /// This is [`read_borrowed`] for files: instead of reading the file into a
+    /// buffer, it is mapped into memory and the returned [`MmapDocument`] keeps
+    /// the mapping alive for as long as values borrow from it. This suits
+    /// scanning many large files, where copying each one would dominate.
+    ///
+    /// This function requires the `mmap` feature.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use na_nbt::read_mmap;
+    /// use zerocopy::byteorder::BigEndian;
+    ///
+    /// let path = std::env::temp_dir().join("na_nbt_read_mmap_doc.nbt");
+    /// std::fs::write(&path, [0x0a, 0x00, 0x00, 0x00])?;
+    ///
+    /// // SAFETY: nothing else modifies the file while it is mapped.
+    /// let doc = unsafe { read_mmap::<BigEndian>(&path)? };
+    /// assert!(doc.root().is_compound());
+    /// # drop(doc);
+    /// # std::fs::remove_file(&path)?;
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    ///
+    /// # Safety
+    ///
+    /// The file must not be modified, truncated or deleted-and-reused while the
+    /// document is alive, by this or any other process. The parsed structure
+    /// and every value read from it assume the mapped bytes never change; see
+    /// [`memmap2::Mmap::map`].
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::IO`] if the file cannot be opened or mapped, and
+    /// otherwise the same errors as [`read_borrowed`].
+    pub unsafe fn read_mmap<O: ByteOrder>(path: impl AsRef<Path>) -> Result<MmapDocument<O>> {
+        let file = File::open(path).map_err(Error::IO)?;
+        let mmap = unsafe { Mmap::map(&file) }.map_err(Error::IO)?;
+        // the mapping does not move when `mmap` does, so the document can
+        // keep pointing into it for as long as both live
+        let source: &'static [u8] =
+            unsafe { core::slice::from_raw_parts(mmap.as_ptr(), mmap.len()) };
+        let doc = read_borrowed::<O>(source)?;
+        Ok(MmapDocument { doc, mmap })
+    }
+}
+
+#[cfg(feature = "mmap")]
+pub use mmap::{MmapDocument, read_mmap};
+
 pub(crate) fn write_value_to_vec<'s, D: value::Document, SOURCE: ByteOrder, TARGET: ByteOrder>(
     value: &value::ReadonlyValue<'s, SOURCE, D>,
 ) -> Result<Vec<u8>> {
@@ -434,11 +746,11 @@ pub(crate) fn write_value_to_vec<'s, D: value::Document, SOURCE: ByteOrder, TARG
                 let buf_ptr = buf.as_mut_ptr();
                 ptr::write(buf_ptr.cast(), [Tag::List as u8, 0u8, 0u8]);
                 ptr::copy_nonoverlapping(payload.as_ptr(), buf_ptr.add(3), payload_len);
-                if TypeId::of::<SOURCE>() != TypeId::of::<TARGET>() {
-                    let size_written =
-                        write::write_list_fallback::<SOURCE, TARGET>(buf_ptr.add(1 + 2))?;
-                    debug_assert!(size_written == payload_len);
-                }
+                // walked even when the byte order is kept, to write empty
+                // lists as End
+                let size_written =
+                    write::write_list_fallback::<SOURCE, TARGET>(buf_ptr.add(1 + 2))?;
+                debug_assert!(size_written == payload_len);
                 buf.set_len(3 + payload_len);
                 Ok(buf)
             }
@@ -449,18 +761,18 @@ pub(crate) fn write_value_to_vec<'s, D: value::Document, SOURCE: ByteOrder, TARG
                 let buf_ptr = buf.as_mut_ptr();
                 ptr::write(buf_ptr.cast(), [Tag::Compound as u8, 0u8, 0u8]);
                 ptr::copy_nonoverlapping(payload.as_ptr(), buf_ptr.add(3), payload_len);
-                if TypeId::of::<SOURCE>() != TypeId::of::<TARGET>() {
-                    let size_written =
-                        write::write_compound_fallback::<SOURCE, TARGET>(buf_ptr.add(1 + 2))?;
-                    debug_assert!(size_written == payload_len);
-                }
+                // walked even when the byte order is kept, to write empty
+                // lists as End
+                let size_written =
+                    write::write_compound_fallback::<SOURCE, TARGET>(buf_ptr.add(1 + 2))?;
+                debug_assert!(size_written == payload_len);
                 buf.set_len(3 + payload_len);
                 Ok(buf)
             }
             value::ReadonlyValue::IntArray(value) => {
                 let payload = value.data.as_ptr().cast::<u8>();
                 let len = value.data.len();
-                let len_bytes = std::mem::size_of_val(value.data);
+                let len_bytes = core::mem::size_of_val(value.data);
                 let mut buf = Vec::<u8>::with_capacity(3 + 4 + len_bytes);
                 let mut buf_ptr = buf.as_mut_ptr();
                 // head
@@ -489,7 +801,7 @@ pub(crate) fn write_value_to_vec<'s, D: value::Document, SOURCE: ByteOrder, TARG
             value::ReadonlyValue::LongArray(value) => {
                 let payload = value.data.as_ptr().cast::<u8>();
                 let len = value.data.len();
-                let len_bytes = std::mem::size_of_val(value.data);
+                let len_bytes = core::mem::size_of_val(value.data);
                 let mut buf = Vec::<u8>::with_capacity(3 + 4 + len_bytes);
                 let mut buf_ptr = buf.as_mut_ptr();
                 // head
@@ -519,6 +831,7 @@ pub(crate) fn write_value_to_vec<'s, D: value::Document, SOURCE: ByteOrder, TARG
     }
 }
 
+#[cfg(feature = "std")]
 pub(crate) fn write_value_to_writer<
     's,
     D: value::Document,
@@ -606,31 +919,27 @@ pub(crate) fn write_value_to_writer<
                 writer
                     .write_all(&[Tag::List as u8, 0u8, 0u8])
                     .map_err(Error::IO)?;
-                if TypeId::of::<SOURCE>() == TypeId::of::<TARGET>() {
-                    writer.write_all(value.data.as_bytes()).map_err(Error::IO)
-                } else {
-                    let size_written = write::write_list_to_writer_fallback::<SOURCE, TARGET>(
-                        value.data.as_ptr(),
-                        &mut writer,
-                    )?;
-                    debug_assert!(size_written == value.data.len());
-                    Ok(())
-                }
+                // walked even when the byte order is kept, to write empty
+                // lists as End
+                let size_written = write::write_list_to_writer_fallback::<SOURCE, TARGET>(
+                    value.data.as_ptr(),
+                    &mut writer,
+                )?;
+                debug_assert!(size_written == value.data.len());
+                Ok(())
             }
             value::ReadonlyValue::Compound(value) => {
                 writer
                     .write_all(&[Tag::Compound as u8, 0u8, 0u8])
                     .map_err(Error::IO)?;
-                if TypeId::of::<SOURCE>() == TypeId::of::<TARGET>() {
-                    writer.write_all(value.data.as_bytes()).map_err(Error::IO)
-                } else {
-                    let size_written = write::write_compound_to_writer_fallback::<SOURCE, TARGET>(
-                        value.data.as_ptr(),
-                        &mut writer,
-                    )?;
-                    debug_assert!(size_written == value.data.len());
-                    Ok(())
-                }
+                // walked even when the byte order is kept, to write empty
+                // lists as End
+                let size_written = write::write_compound_to_writer_fallback::<SOURCE, TARGET>(
+                    value.data.as_ptr(),
+                    &mut writer,
+                )?;
+                debug_assert!(size_written == value.data.len());
+                Ok(())
             }
             value::ReadonlyValue::IntArray(value) => {
                 let mut buf_head = [0u8; 1 + 2 + 4];
@@ -0,0 +1,73 @@
+use std::collections::HashMap;
+
+use crate::{ByteOrder, ReadonlyCompound, ReadonlyValue, immutable::value::Document};
+
+/// A hash index over the entries of a [`ReadonlyCompound`].
+///
+/// This type is returned by [`ReadonlyCompound::index`]. Building it scans
+/// the compound once; after that, [`get`](CompoundIndex::get) finds a key
+/// without scanning, where [`ReadonlyCompound::get`] walks the entries in
+/// order on every call. It pays off when one compound is looked up many times.
+///
+/// The index borrows the same data as the compound, so it cannot outlive it.
+pub struct CompoundIndex<'doc, O: ByteOrder, D: Document> {
+    entries: HashMap<&'doc [u8], ReadonlyValue<'doc, O, D>>,
+}
+
+impl<'doc, O: ByteOrder, D: Document> ReadonlyCompound<'doc, O, D> {
+    /// Builds a hash index of this compound's entries for repeated lookups.
+    ///
+    /// A repeated key resolves to its first occurrence, as with
+    /// [`get`](Self::get).
+    ///
+    /// This method requires the `std` feature.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use na_nbt::{OwnedCompound, OwnedValue, read_borrowed};
+    /// use zerocopy::byteorder::BigEndian;
+    ///
+    /// let mut compound: OwnedCompound<BigEndian> = OwnedCompound::default();
+    /// for i in 0..100 {
+    ///     compound.insert(&format!("key{i}"), i);
+    /// }
+    /// let bytes = OwnedValue::Compound(compound).write_to_vec::<BigEndian>()?;
+    ///
+    /// let doc = read_borrowed::<BigEndian>(&bytes)?;
+    /// let root = doc.root();
+    /// let index = root.as_compound().unwrap().index();
+    /// assert_eq!(index.len(), 100);
+    /// assert_eq!(index.get("key42").unwrap().as_int(), Some(42));
+    /// assert!(index.get("missing").is_none());
+    /// # Ok::<(), na_nbt::Error>(())
+    /// ```
+    pub fn index(&self) -> CompoundIndex<'doc, O, D> {
+        let mut entries = HashMap::new();
+        for (key, value) in self.iter() {
+            entries.entry(key.data).or_insert(value);
+        }
+        CompoundIndex { entries }
+    }
+}
+
+impl<'doc, O: ByteOrder, D: Document> CompoundIndex<'doc, O, D> {
+    /// Returns the value associated with the given key, or `None` if not found.
+    #[inline]
+    pub fn get(&self, key: &str) -> Option<ReadonlyValue<'doc, O, D>> {
+        let name = simd_cesu8::mutf8::encode(key);
+        self.entries.get(&*name).cloned()
+    }
+
+    /// Returns the number of distinct keys.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Returns `true` if the compound has no entries.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+}
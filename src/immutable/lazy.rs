@@ -0,0 +1,192 @@
+use alloc::vec::Vec;
+use core::{marker::PhantomData, ops::Range};
+
+use bytes::Bytes;
+use zerocopy::byteorder;
+
+use crate::{ByteOrder, Error, Result, SharedValue, Tag, cold_path, read_shared, walk::Walk};
+
+/// A compound read from shared bytes that only indexes the entries it needs.
+///
+/// This type is returned by [`read_shared_lazy`]. Where [`read_shared`]
+/// indexes the whole document up front, a `LazyCompound` starts with nothing
+/// but the root header. Each [`get`](LazyCompound::get) scans the root's
+/// entries only as far as the requested key, remembering the offsets of every
+/// entry it passes, so later lookups of those keys skip the scan. The entry
+/// that is found is indexed on its own and returned as a [`SharedValue`].
+///
+/// This pays off when a few keys are read from a large compound. When most
+/// of the document is used, [`read_shared`] is faster, since every `get`
+/// here re-indexes the value it returns.
+///
+/// Since the data is only checked as far as it is scanned, malformed input is
+/// reported by `get`, not by [`read_shared_lazy`].
+pub struct LazyCompound<O: ByteOrder> {
+    source: Bytes,
+    entries: Vec<Entry>,
+    /// Offset of the first entry not scanned yet, or `None` once the root's
+    /// `End` tag has been reached.
+    cursor: Option<usize>,
+    _marker: PhantomData<O>,
+}
+
+struct Entry {
+    start: usize,
+    key: Range<usize>,
+    end: usize,
+}
+
+/// Reads the root header of a compound from a `Bytes` buffer, leaving its
+/// entries to be scanned on demand.
+///
+/// See [`LazyCompound`] for how lookups work.
+///
+/// # Example
+///
+/// ```
+/// use bytes::Bytes;
+/// use na_nbt::{OwnedCompound, OwnedValue, read_shared_lazy};
+/// use zerocopy::byteorder::BigEndian;
+///
+/// let mut root = OwnedCompound::<BigEndian>::default();
+/// root.insert("DataVersion", 3465);
+/// root.insert("Status", "minecraft:full");
+/// let bytes = OwnedValue::Compound(root).write_to_vec::<BigEndian>()?;
+///
+/// let mut root = read_shared_lazy::<BigEndian>(Bytes::from(bytes))?;
+/// let version = root.get("DataVersion")?.unwrap();
+/// assert_eq!(version.as_int(), Some(3465));
+/// assert!(root.get("Missing")?.is_none());
+/// # Ok::<(), na_nbt::Error>(())
+/// ```
+///
+/// # Errors
+///
+/// Returns an error if:
+/// - The header is truncated ([`Error::EndOfFile`])
+/// - The root is not a compound ([`Error::TagMismatch`])
+pub fn read_shared_lazy<O: ByteOrder>(source: Bytes) -> Result<LazyCompound<O>> {
+    let Some(&root_tag) = source.first() else {
+        cold_path();
+        return Err(Error::EndOfFile);
+    };
+    if root_tag != Tag::Compound as u8 {
+        cold_path();
+        return Err(Error::TagMismatch(Tag::Compound as u8, root_tag));
+    }
+    let name_len = byteorder::U16::<O>::from_bytes(read_array(&source, 1)?).get() as usize;
+    let cursor = advance(&source, 3, name_len)?;
+
+    Ok(LazyCompound {
+        source,
+        entries: Vec::new(),
+        cursor: Some(cursor),
+        _marker: PhantomData,
+    })
+}
+
+impl<O: ByteOrder> LazyCompound<O> {
+    /// Returns the value of the first entry with the given key, or `None` if
+    /// there is none.
+    ///
+    /// Keys already passed by an earlier lookup are found without scanning;
+    /// otherwise scanning resumes where the last lookup stopped. A missing key
+    /// scans the rest of the compound once, after which all lookups are
+    /// served from the cached offsets.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the scanned entries, or the returned value, are
+    /// malformed. See [`read_shared`] for the possible errors.
+    pub fn get(&mut self, key: &str) -> Result<Option<SharedValue<O>>> {
+        let name = simd_cesu8::mutf8::encode(key);
+        let matches = |entry: &Entry| self.source[entry.key.clone()] == *name;
+        let entry = match self.entries.iter().position(matches) {
+            Some(index) => &self.entries[index],
+            None => loop {
+                if !self.scan_entry()? {
+                    return Ok(None);
+                }
+                let entry = self.entries.last().unwrap();
+                if self.source[entry.key.clone()] == *name {
+                    break entry;
+                }
+            },
+        };
+        read_shared::<O>(self.source.slice(entry.start..entry.end)).map(Some)
+    }
+
+    /// Returns the number of entries scanned so far.
+    #[inline]
+    pub fn scanned_len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Returns the underlying buffer.
+    #[inline]
+    pub fn source(&self) -> &Bytes {
+        &self.source
+    }
+
+    /// Scans the next entry of the root and caches it, returning `false` at
+    /// the end of the compound.
+    fn scan_entry(&mut self) -> Result<bool> {
+        let Some(start) = self.cursor else {
+            return Ok(false);
+        };
+        let data = &self.source[..];
+
+        let Some(&tag_id) = data.get(start) else {
+            cold_path();
+            return Err(Error::EndOfFile);
+        };
+        if tag_id == 0 {
+            self.cursor = None;
+            let end = start + 1;
+            if end < data.len() {
+                cold_path();
+                return Err(Error::TrailingData(data.len() - end));
+            }
+            return Ok(false);
+        }
+        if tag_id > 12 {
+            cold_path();
+            return Err(Error::InvalidTagType(tag_id).at(start));
+        }
+
+        let name_len = byteorder::U16::<O>::from_bytes(read_array(data, start + 1)?).get() as usize;
+        let key = start + 3..advance(data, start + 3, name_len)?;
+        let end = skip_value::<O>(data, key.end, tag_id)?;
+
+        self.cursor = Some(end);
+        self.entries.push(Entry { start, key, end });
+        Ok(true)
+    }
+}
+
+/// Returns `pos + n`, or an error if that is past the end of `data`.
+#[inline]
+pub(super) fn advance(data: &[u8], pos: usize, n: usize) -> Result<usize> {
+    match pos.checked_add(n) {
+        Some(end) if end <= data.len() => Ok(end),
+        _ => {
+            cold_path();
+            Err(Error::EndOfFile)
+        }
+    }
+}
+
+#[inline]
+pub(super) fn read_array<const N: usize>(data: &[u8], pos: usize) -> Result<[u8; N]> {
+    let end = advance(data, pos, N)?;
+    Ok(data[pos..end].try_into().unwrap())
+}
+
+/// Returns the offset just past the payload of type `tag_id` at `pos`,
+/// checking bounds and tag types but building no index.
+pub(super) fn skip_value<O: ByteOrder>(data: &[u8], pos: usize, tag_id: u8) -> Result<usize> {
+    match Walk::payload(&mut Vec::new(), pos, tag_id).run_to_end::<O>(data, &mut ())? {
+        Some(end) => Ok(end),
+        None => unreachable!("the default hooks fail on an unknown tag"),
+    }
+}
@@ -0,0 +1,376 @@
+use alloc::{format, vec::Vec};
+use core::ops::Range;
+
+use bytes::Bytes;
+use zerocopy::byteorder;
+
+use super::lazy::{advance, read_array, skip_value};
+use crate::{
+    ByteOrder, Error, MutableCompound, MutableValue, OwnedValue, Result, SharedValue, Tag,
+    cold_path, read_shared,
+};
+
+/// A document read from shared bytes that writes untouched values back
+/// byte for byte.
+///
+/// This type is returned by [`read_shared_preserving`]. Where
+/// [`read_owned`](crate::read_owned) decodes the whole document and writes
+/// it out again from the decoded form, a `PreservingDocument` keeps the
+/// source and only records changes. On [`write_to_vec`](Self::write_to_vec),
+/// every entry that was not changed is copied verbatim from the source, so
+/// encodings that a round trip would normalize, such as repeated keys or the
+/// element type of an empty list, survive outside the changed values.
+///
+/// Changing an entry splits the compounds on its path into their entries;
+/// the siblings of those compounds stay untouched. Only compounds can be
+/// traversed, so entries inside lists cannot be changed one by one.
+pub struct PreservingDocument<O: ByteOrder> {
+    source: Bytes,
+    root: Node<O>,
+}
+
+enum Node<O: ByteOrder> {
+    /// An entry as read; `source[range]` holds its tag, name and payload.
+    Original(Range<usize>),
+    /// A compound split into its entries.
+    Compound(Vec<Entry<O>>),
+    /// A value set by the caller.
+    Replaced(OwnedValue<O>),
+}
+
+struct Entry<O: ByteOrder> {
+    key: Vec<u8>,
+    node: Node<O>,
+}
+
+/// The compound that holds the last key of a path.
+enum Parent<'a, 'p, O: ByteOrder> {
+    Entries(&'a mut Vec<Entry<O>>),
+    /// A replaced value, with the keys left to follow inside it.
+    Owned(&'a mut OwnedValue<O>, &'p [&'p str]),
+}
+
+/// Reads a compound from a `Bytes` buffer for editing, keeping the source to
+/// copy untouched values from.
+///
+/// The whole document is checked up front, but nothing is decoded until an
+/// entry is changed. See [`PreservingDocument`] for what is preserved.
+///
+/// # Example
+///
+/// ```
+/// use bytes::Bytes;
+/// use na_nbt::{OwnedCompound, OwnedValue, read_shared_preserving};
+/// use zerocopy::byteorder::BigEndian;
+///
+/// let mut player = OwnedCompound::<BigEndian>::default();
+/// player.insert("Score", 10);
+/// player.insert("Name", "Steve");
+/// let mut root = OwnedCompound::<BigEndian>::default();
+/// root.insert("Player", player);
+/// root.insert("DataVersion", 3465);
+/// let bytes = OwnedValue::Compound(root).write_to_vec::<BigEndian>()?;
+///
+/// let mut doc = read_shared_preserving::<BigEndian>(Bytes::from(bytes.clone()))?;
+/// doc.set(&["Player", "Score"], 11)?;
+/// let written = doc.write_to_vec()?;
+///
+/// let score = doc.get(&["Player", "Score"])?.unwrap();
+/// assert_eq!(score.as_int(), Some(11));
+/// assert_eq!(written.len(), bytes.len());
+/// # Ok::<(), na_nbt::Error>(())
+/// ```
+///
+/// # Errors
+///
+/// Returns an error if:
+/// - The data is truncated ([`Error::EndOfFile`])
+/// - The root is not a compound ([`Error::TagMismatch`])
+/// - An invalid tag type is encountered ([`Error::InvalidTagType`])
+/// - Extra data remains after the root ([`Error::TrailingData`])
+pub fn read_shared_preserving<O: ByteOrder>(source: Bytes) -> Result<PreservingDocument<O>> {
+    let Some(&root_tag) = source.first() else {
+        cold_path();
+        return Err(Error::EndOfFile);
+    };
+    if root_tag != Tag::Compound as u8 {
+        cold_path();
+        return Err(Error::TagMismatch(Tag::Compound as u8, root_tag));
+    }
+    let end = skip_value::<O>(&source, header_end::<O>(&source, 0)?, root_tag)?;
+    if end < source.len() {
+        cold_path();
+        return Err(Error::TrailingData(source.len() - end));
+    }
+
+    Ok(PreservingDocument {
+        root: Node::Original(0..end),
+        source,
+    })
+}
+
+impl<O: ByteOrder> PreservingDocument<O> {
+    /// Returns the value at `path`, with its changes, or `None` if there is
+    /// no such entry.
+    ///
+    /// An empty path returns the root. A repeated key resolves to its first
+    /// occurrence, as with [`ReadonlyCompound::get`](crate::ReadonlyCompound::get).
+    ///
+    /// An untouched value is read from the source; a changed one is encoded
+    /// first. Either way the value is indexed on each call, as by
+    /// [`read_shared`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a replaced value cannot be encoded.
+    pub fn get(&self, path: &[&str]) -> Result<Option<SharedValue<O>>> {
+        let mut node = &self.root;
+        let mut rest = path;
+        while let (Node::Compound(entries), Some((key, tail))) = (node, rest.split_first()) {
+            let Some(entry) = find(entries, key) else {
+                return Ok(None);
+            };
+            node = &entry.node;
+            rest = tail;
+        }
+
+        let value = match node {
+            // the document was validated when it was read
+            Node::Original(range) => read_shared::<O>(self.source.slice(range.clone()))?,
+            _ => {
+                let mut out = Vec::new();
+                self.write_entry(&mut out, &[], node)?;
+                read_shared::<O>(Bytes::from(out))?
+            }
+        };
+        Ok(value.into_shared_child(rest))
+    }
+
+    /// Sets the entry at `path` to `value`, adding it at the end of its
+    /// compound if there is none.
+    ///
+    /// A repeated key sets its first occurrence; later ones are left as they
+    /// are.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if:
+    /// - The path is empty, a parent entry is missing or the key is longer
+    ///   than 65535 bytes ([`Error::Message`])
+    /// - A parent entry is not a compound ([`Error::TagMismatch`])
+    /// - `value` is [`OwnedValue::End`] ([`Error::InvalidTagType`])
+    pub fn set(&mut self, path: &[&str], value: impl Into<OwnedValue<O>>) -> Result<()> {
+        let value = value.into();
+        if let OwnedValue::End = value {
+            cold_path();
+            return Err(Error::InvalidTagType(Tag::End as u8));
+        }
+        let Some((last, parents)) = path.split_last() else {
+            cold_path();
+            return Err(Error::Message("path is empty".into()));
+        };
+        let key = simd_cesu8::mutf8::encode(last);
+        if key.len() > u16::MAX as usize {
+            cold_path();
+            return Err(Error::Message(format!(
+                "key of {} bytes is too long",
+                key.len()
+            )));
+        }
+
+        match self.parent(parents)? {
+            Some(Parent::Entries(entries)) => {
+                match entries.iter_mut().find(|entry| *entry.key == *key) {
+                    Some(entry) => entry.node = Node::Replaced(value),
+                    None => entries.push(Entry {
+                        key: key.into_owned(),
+                        node: Node::Replaced(value),
+                    }),
+                }
+                Ok(())
+            }
+            Some(Parent::Owned(owned, rest)) => {
+                owned_compound(&mut owned.to_mut(), rest, |compound| {
+                    compound.insert(last, value);
+                })?
+                .ok_or_else(|| {
+                    cold_path();
+                    Error::Message(format!("no compound at {parents:?}"))
+                })
+            }
+            None => {
+                cold_path();
+                Err(Error::Message(format!("no compound at {parents:?}")))
+            }
+        }
+    }
+
+    /// Removes the entry at `path`, returning `true` if there was one.
+    ///
+    /// A repeated key removes its first occurrence.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a parent entry is not a compound
+    /// ([`Error::TagMismatch`]).
+    pub fn remove(&mut self, path: &[&str]) -> Result<bool> {
+        let Some((last, parents)) = path.split_last() else {
+            return Ok(false);
+        };
+        match self.parent(parents)? {
+            Some(Parent::Entries(entries)) => {
+                let key = simd_cesu8::mutf8::encode(last);
+                match entries.iter().position(|entry| *entry.key == *key) {
+                    Some(index) => {
+                        entries.remove(index);
+                        Ok(true)
+                    }
+                    None => Ok(false),
+                }
+            }
+            Some(Parent::Owned(owned, rest)) => {
+                Ok(owned_compound(&mut owned.to_mut(), rest, |compound| {
+                    compound.remove(last).is_some()
+                })?
+                .unwrap_or(false))
+            }
+            None => Ok(false),
+        }
+    }
+
+    /// Writes the document, copying untouched entries from the source.
+    ///
+    /// The root name is kept. Without changes, the output equals the source.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a replaced value cannot be encoded.
+    pub fn write_to_vec(&self) -> Result<Vec<u8>> {
+        let name = header_end::<O>(&self.source, 0)?;
+        let mut out = Vec::with_capacity(self.source.len());
+        self.write_entry(&mut out, &self.source[3..name], &self.root)?;
+        Ok(out)
+    }
+
+    /// Returns the underlying buffer.
+    #[inline]
+    pub fn source(&self) -> &Bytes {
+        &self.source
+    }
+
+    /// Follows `parents` from the root, splitting the compounds on the way,
+    /// or returns `None` if an entry is missing.
+    fn parent<'a, 'p>(&'a mut self, parents: &'p [&'p str]) -> Result<Option<Parent<'a, 'p, O>>> {
+        let mut node = &mut self.root;
+        for (i, key) in parents.iter().enumerate() {
+            if let Node::Replaced(owned) = node {
+                return Ok(Some(Parent::Owned(owned, &parents[i..])));
+            }
+            let entries = split::<O>(&self.source, node)?;
+            let key = simd_cesu8::mutf8::encode(key);
+            let Some(entry) = entries.iter_mut().find(|entry| *entry.key == *key) else {
+                return Ok(None);
+            };
+            node = &mut entry.node;
+        }
+        if let Node::Replaced(owned) = node {
+            return Ok(Some(Parent::Owned(owned, &[])));
+        }
+        Ok(Some(Parent::Entries(split::<O>(&self.source, node)?)))
+    }
+
+    fn write_entry(&self, out: &mut Vec<u8>, key: &[u8], node: &Node<O>) -> Result<()> {
+        match node {
+            Node::Original(range) => out.extend_from_slice(&self.source[range.clone()]),
+            Node::Compound(entries) => {
+                write_header::<O>(out, Tag::Compound as u8, key);
+                for entry in entries {
+                    self.write_entry(out, &entry.key, &entry.node)?;
+                }
+                out.push(Tag::End as u8);
+            }
+            Node::Replaced(value) => {
+                // an unnamed document: the tag, an empty name and the payload
+                let bytes = value.write_to_vec::<O>()?;
+                write_header::<O>(out, bytes[0], key);
+                out.extend_from_slice(&bytes[3..]);
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Returns the first entry with the given key.
+fn find<'a, O: ByteOrder>(entries: &'a [Entry<O>], key: &str) -> Option<&'a Entry<O>> {
+    let key = simd_cesu8::mutf8::encode(key);
+    entries.iter().find(|entry| *entry.key == *key)
+}
+
+/// Returns the offset just past the tag and name of the entry at `start`.
+#[inline]
+fn header_end<O: ByteOrder>(data: &[u8], start: usize) -> Result<usize> {
+    let name_len = byteorder::U16::<O>::from_bytes(read_array(data, start + 1)?).get() as usize;
+    advance(data, start + 3, name_len)
+}
+
+#[inline]
+fn write_header<O: ByteOrder>(out: &mut Vec<u8>, tag_id: u8, key: &[u8]) {
+    out.push(tag_id);
+    out.extend_from_slice(&byteorder::U16::<O>::new(key.len() as u16).to_bytes());
+    out.extend_from_slice(key);
+}
+
+/// Splits an original compound into its entries, returning them.
+fn split<'a, O: ByteOrder>(source: &Bytes, node: &'a mut Node<O>) -> Result<&'a mut Vec<Entry<O>>> {
+    if let Node::Original(range) = node {
+        let tag_id = source[range.start];
+        if tag_id != Tag::Compound as u8 {
+            cold_path();
+            return Err(Error::TagMismatch(Tag::Compound as u8, tag_id));
+        }
+
+        let mut entries = Vec::new();
+        let mut pos = header_end::<O>(source, range.start)?;
+        loop {
+            let tag_id = source[pos];
+            if tag_id == Tag::End as u8 {
+                break;
+            }
+            let key = pos + 3..header_end::<O>(source, pos)?;
+            let end = skip_value::<O>(source, key.end, tag_id)?;
+            entries.push(Entry {
+                key: source[key].to_vec(),
+                node: Node::Original(pos..end),
+            });
+            pos = end;
+        }
+        *node = Node::Compound(entries);
+    }
+    match node {
+        Node::Compound(entries) => Ok(entries),
+        _ => unreachable!("replaced values are handled by the caller"),
+    }
+}
+
+/// Calls `f` on the compound at `path` inside `value`, or returns `None` if
+/// an entry on the way is missing and an error if one is not a compound.
+fn owned_compound<O: ByteOrder, R>(
+    value: &mut MutableValue<'_, O>,
+    path: &[&str],
+    f: impl FnOnce(&mut MutableCompound<'_, O>) -> R,
+) -> Result<Option<R>> {
+    let MutableValue::Compound(compound) = value else {
+        cold_path();
+        return Err(Error::TagMismatch(
+            Tag::Compound as u8,
+            value.tag_id() as u8,
+        ));
+    };
+    match path.split_first() {
+        None => Ok(Some(f(compound))),
+        Some((key, rest)) => match compound.get_mut(key) {
+            Some(mut child) => owned_compound(&mut child, rest, f),
+            None => Ok(None),
+        },
+    }
+}
@@ -1,4 +1,5 @@
-use std::hint::{assert_unchecked, unreachable_unchecked};
+use alloc::vec::Vec;
+use core::hint::{assert_unchecked, unreachable_unchecked};
 
 use zerocopy::{ByteOrder, byteorder};
 
@@ -51,6 +52,7 @@ pub unsafe fn read_unsafe<O: ByteOrder, R>(
         };
     }
 
+    let start_pos = current_pos;
     let mut bytes_read: usize = 1;
 
     let mut mark = Vec::with_capacity(len / 32);
@@ -88,7 +90,10 @@ pub unsafe fn read_unsafe<O: ByteOrder, R>(
                 current = mark.len() - 1;
 
                 bytes_read += 1 + 4;
-                check_bounds!(bytes_read, len);
+                if bytes_read > len {
+                    cold_path();
+                    return Err(Error::truncated_list_header());
+                }
 
                 let element_type = *current_pos;
                 let element_count =
@@ -99,6 +104,12 @@ pub unsafe fn read_unsafe<O: ByteOrder, R>(
                 cur.cache.general_parent_offset =
                     ((current - parent) as u64) | ((element_type as u64) << TAG_TYPE_SHIFT);
 
+                if element_type == 0 && element_count > 0 {
+                    cold_path();
+                    return Err(Error::InvalidTagType(element_type)
+                        .at(current_pos.byte_offset_from_unsigned(start_pos)));
+                }
+
                 if element_type <= 6 {
                     let element_size = tag_size(element_type);
                     let total_size = element_count as usize * element_size;
@@ -113,7 +124,8 @@ pub unsafe fn read_unsafe<O: ByteOrder, R>(
                     cur.cache.list_current_length = 0;
                 } else {
                     cold_path();
-                    return Err(Error::InvalidTagType(element_type));
+                    return Err(Error::InvalidTagType(element_type)
+                        .at(current_pos.byte_offset_from_unsigned(start_pos)));
                 }
                 label = Label::ListItemBegin;
             }};
@@ -179,7 +191,7 @@ pub unsafe fn read_unsafe<O: ByteOrder, R>(
             10 => comp_begin!(),
             _ => {
                 cold_path();
-                return Err(Error::InvalidTagType(root_tag));
+                return Err(Error::InvalidTagType(root_tag).at(0));
             }
         }
 
@@ -272,7 +284,9 @@ pub unsafe fn read_unsafe<O: ByteOrder, R>(
                         }
                         _ => {
                             cold_path();
-                            return Err(Error::InvalidTagType(tag_id));
+                            let offset = current_pos.byte_offset_from_unsigned(start_pos)
+                                - (1 + 2 + name_len);
+                            return Err(Error::InvalidTagType(tag_id).at(offset));
                         }
                     }
                 },
@@ -347,7 +361,8 @@ pub unsafe fn read_unsafe<O: ByteOrder, R>(
                         }
                         _ => {
                             cold_path();
-                            return Err(Error::InvalidTagType(element_type));
+                            return Err(Error::InvalidTagType(element_type)
+                                .at(current_pos.byte_offset_from_unsigned(start_pos)));
                         }
                     }
                 },
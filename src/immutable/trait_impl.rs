@@ -1,4 +1,7 @@
-use std::{io::Write, marker::PhantomData};
+use alloc::vec::Vec;
+use core::marker::PhantomData;
+#[cfg(feature = "std")]
+use std::io::Write;
 
 use zerocopy::byteorder;
 
@@ -20,7 +23,7 @@ impl<'doc, D: Document> ReadableString<'doc> for ReadonlyString<'doc, D> {
     }
 
     #[inline]
-    fn decode(&self) -> std::borrow::Cow<'_, str> {
+    fn decode(&self) -> alloc::borrow::Cow<'_, str> {
         self.decode()
     }
 }
@@ -235,6 +238,7 @@ impl<'doc, O: ByteOrder, D: Document> ScopedReadableValue<'doc> for ReadonlyValu
         self.write_to_vec::<TARGET>()
     }
 
+    #[cfg(feature = "std")]
     #[inline]
     fn write_to_writer<TARGET: ByteOrder>(&self, writer: impl Write) -> Result<()> {
         self.write_to_writer::<TARGET>(writer)
@@ -1,12 +1,24 @@
-use std::{borrow::Cow, io::Write, marker::PhantomData, ops::Deref, ptr, slice};
+use alloc::{borrow::Cow, vec::Vec};
+use core::{
+    marker::PhantomData,
+    ops::{Deref, Range},
+    ptr, slice,
+    sync::atomic::{AtomicUsize, Ordering},
+};
+#[cfg(feature = "std")]
+use std::{collections::HashMap, io::Write, string::String};
 
 use zerocopy::byteorder;
 
+#[cfg(feature = "std")]
+use crate::write_value_to_writer;
 use crate::{
-    ByteOrder, EMPTY_COMPOUND, EMPTY_LIST, Result, Tag, cold_path,
+    ByteOrder, EMPTY_COMPOUND, EMPTY_LIST, OwnedValue, Result, Tag, TagType, cold_path,
+    decode_mutf8,
     immutable::{mark::Mark, util::tag_size},
     index::Index,
-    write_value_to_vec, write_value_to_writer,
+    mutable::{array_to_list, list_to_array},
+    write_value_to_vec,
 };
 
 /// Marker trait for document ownership types.
@@ -15,15 +27,62 @@ use crate::{
 /// It is used to abstract over the ownership model of the underlying NBT data.
 ///
 /// The two main implementations are:
-/// - `()` - For borrowed values that reference external data
+/// - [`Borrowed`] - For borrowed values that reference external data
 /// - `Arc<SharedDocument>` - For shared values with `Arc` ownership
-pub trait Document: Send + Sync + Clone + Never + 'static {}
+pub trait Document: Send + Sync + Clone + Never + Source + 'static {}
 
 pub trait Never {
     unsafe fn never() -> Self;
 }
 
-impl<T: Send + Sync + Clone + Never + 'static> Document for T {}
+pub trait Source {
+    /// Returns where the buffer the document was read from starts, or null
+    /// for a value that was not read from one.
+    fn source(&self) -> *const u8;
+}
+
+impl<T: Send + Sync + Clone + Never + Source + 'static> Document for T {}
+
+/// The document of a [`BorrowedValue`](crate::BorrowedValue): the start of
+/// the slice it was read from.
+#[derive(Clone, Copy)]
+pub struct Borrowed(*const u8);
+
+impl Borrowed {
+    #[inline]
+    pub(crate) fn new(source: *const u8) -> Self {
+        Self(source)
+    }
+}
+
+unsafe impl Send for Borrowed {}
+unsafe impl Sync for Borrowed {}
+
+impl Never for Borrowed {
+    unsafe fn never() -> Self {
+        Self(ptr::null())
+    }
+}
+
+impl Source for Borrowed {
+    #[inline]
+    fn source(&self) -> *const u8 {
+        self.0
+    }
+}
+
+/// Returns where `len` bytes at `ptr` lie in the buffer `doc` was read
+/// from, or `None` if there is none.
+#[inline]
+fn range_in<D: Document>(doc: &D, ptr: *const u8, len: usize) -> Option<Range<usize>> {
+    let source = doc.source();
+    if source.is_null() {
+        cold_path();
+        return None;
+    }
+    let start = unsafe { ptr.byte_offset_from_unsigned(source) };
+    Some(start..start + len)
+}
 
 /// A zero-copy, immutable NBT value.
 ///
@@ -40,7 +99,7 @@ impl<T: Send + Sync + Clone + Never + 'static> Document for T {}
 ///
 /// - `'doc`: Lifetime of the underlying byte data
 /// - `O`: Byte order ([`BigEndian`](zerocopy::byteorder::BigEndian) or [`LittleEndian`](zerocopy::byteorder::LittleEndian))
-/// - `D`: Document type managing data ownership (`()` for borrowed, `Arc<...>` for shared)
+/// - `D`: Document type managing data ownership (`Borrowed` for borrowed, `Arc<...>` for shared)
 ///
 /// # Variants
 ///
@@ -131,25 +190,24 @@ impl<'doc, O: ByteOrder, D: Document> ReadonlyValue<'doc, O, D> {
                 ($t:tt, $l:tt) => {{
                     ReadonlyValue::$t(ReadonlyArray {
                         data: slice::from_raw_parts(
-                            data.add(std::mem::size_of::<byteorder::$l<O>>()).cast(),
+                            data.add(core::mem::size_of::<byteorder::$l<O>>()).cast(),
                             byteorder::$l::<O>::from_bytes(*data.cast()).get() as usize,
                         ),
-                        _doc: doc,
+                        doc,
                     })
                 }};
             }
 
             macro_rules! get_composite {
                 ($t:tt, $s:tt) => {
-                    ReadonlyValue::$t($s {
-                        data: slice::from_raw_parts(
+                    ReadonlyValue::$t($s::from_raw(
+                        slice::from_raw_parts(
                             data,
                             (*mark).store.end_pointer.byte_offset_from_unsigned(data),
                         ),
-                        mark: mark.add(1),
+                        mark.add(1),
                         doc,
-                        _marker: PhantomData,
-                    })
+                    ))
                 };
             }
 
@@ -185,12 +243,12 @@ impl<'doc, O: ByteOrder, D: Document> ReadonlyValue<'doc, O, D> {
     pub fn tag_id(&self) -> Tag {
         match self {
             ReadonlyValue::End => Tag::End,
-            ReadonlyValue::Byte(_) => Tag::Byte,
-            ReadonlyValue::Short(_) => Tag::Short,
-            ReadonlyValue::Int(_) => Tag::Int,
-            ReadonlyValue::Long(_) => Tag::Long,
-            ReadonlyValue::Float(_) => Tag::Float,
-            ReadonlyValue::Double(_) => Tag::Double,
+            ReadonlyValue::Byte(..) => Tag::Byte,
+            ReadonlyValue::Short(..) => Tag::Short,
+            ReadonlyValue::Int(..) => Tag::Int,
+            ReadonlyValue::Long(..) => Tag::Long,
+            ReadonlyValue::Float(..) => Tag::Float,
+            ReadonlyValue::Double(..) => Tag::Double,
             ReadonlyValue::ByteArray(_) => Tag::ByteArray,
             ReadonlyValue::String(_) => Tag::String,
             ReadonlyValue::List(_) => Tag::List,
@@ -223,7 +281,7 @@ impl<'doc, O: ByteOrder, D: Document> ReadonlyValue<'doc, O, D> {
 
     #[inline]
     pub fn is_byte(&self) -> bool {
-        matches!(self, ReadonlyValue::Byte(_))
+        matches!(self, ReadonlyValue::Byte(..))
     }
 
     #[inline]
@@ -236,7 +294,7 @@ impl<'doc, O: ByteOrder, D: Document> ReadonlyValue<'doc, O, D> {
 
     #[inline]
     pub fn is_short(&self) -> bool {
-        matches!(self, ReadonlyValue::Short(_))
+        matches!(self, ReadonlyValue::Short(..))
     }
 
     #[inline]
@@ -249,7 +307,7 @@ impl<'doc, O: ByteOrder, D: Document> ReadonlyValue<'doc, O, D> {
 
     #[inline]
     pub fn is_int(&self) -> bool {
-        matches!(self, ReadonlyValue::Int(_))
+        matches!(self, ReadonlyValue::Int(..))
     }
 
     #[inline]
@@ -262,7 +320,7 @@ impl<'doc, O: ByteOrder, D: Document> ReadonlyValue<'doc, O, D> {
 
     #[inline]
     pub fn is_long(&self) -> bool {
-        matches!(self, ReadonlyValue::Long(_))
+        matches!(self, ReadonlyValue::Long(..))
     }
 
     #[inline]
@@ -275,7 +333,7 @@ impl<'doc, O: ByteOrder, D: Document> ReadonlyValue<'doc, O, D> {
 
     #[inline]
     pub fn is_float(&self) -> bool {
-        matches!(self, ReadonlyValue::Float(_))
+        matches!(self, ReadonlyValue::Float(..))
     }
 
     #[inline]
@@ -288,7 +346,7 @@ impl<'doc, O: ByteOrder, D: Document> ReadonlyValue<'doc, O, D> {
 
     #[inline]
     pub fn is_double(&self) -> bool {
-        matches!(self, ReadonlyValue::Double(_))
+        matches!(self, ReadonlyValue::Double(..))
     }
 
     #[inline]
@@ -402,6 +460,88 @@ impl<'doc, O: ByteOrder, D: Document> ReadonlyValue<'doc, O, D> {
         )
     }
 
+    /// Returns the encoded payload of this value, as a slice of the source.
+    ///
+    /// The payload is what follows the tag type and name: the length prefix
+    /// and elements of an array or string, the element type, length and
+    /// elements of a list, or the entries of a compound up to and including
+    /// its `End` tag. Returns `None` for `End` and the numeric variants, which
+    /// are decoded when read and keep no reference to the source.
+    ///
+    /// For a [`SharedValue`](crate::SharedValue), [`Bytes::slice_ref`] turns
+    /// the payload into a buffer of its own without copying.
+    ///
+    /// [`Bytes::slice_ref`]: https://docs.rs/bytes/latest/bytes/struct.Bytes.html#method.slice_ref
+    pub fn raw_payload(&self) -> Option<&'doc [u8]> {
+        unsafe fn with_prefix<T>(data: &[T], prefix: usize) -> &[u8] {
+            // the length prefix sits right before the elements in the source
+            unsafe {
+                slice::from_raw_parts(
+                    data.as_ptr().cast::<u8>().sub(prefix),
+                    prefix + core::mem::size_of_val(data),
+                )
+            }
+        }
+
+        unsafe {
+            match self {
+                ReadonlyValue::ByteArray(value) => Some(with_prefix(value.data, 4)),
+                ReadonlyValue::String(value) => Some(with_prefix(value.data, 2)),
+                ReadonlyValue::IntArray(value) => Some(with_prefix(value.data, 4)),
+                ReadonlyValue::LongArray(value) => Some(with_prefix(value.data, 4)),
+                ReadonlyValue::List(value) => Some(value.data),
+                ReadonlyValue::Compound(value) => Some(value.data),
+                _ => None,
+            }
+        }
+    }
+
+    /// Returns where the payload of this value lies in the source it was
+    /// read from.
+    ///
+    /// The range covers the bytes of [`raw_payload`](Self::raw_payload), so
+    /// it is `None` for `End` and the numeric variants, which keep no
+    /// reference to the source; use
+    /// [`ReadonlyCompound::get_byte_range`] or [`ReadonlyList::get_byte_range`]
+    /// to locate a number. It is also `None` for a value that was not read
+    /// from a source, such as an empty default list.
+    ///
+    /// With [`read_shared`](crate::read_shared), the range slices the value
+    /// back out of the source buffer without copying.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use na_nbt::read_borrowed;
+    /// use zerocopy::byteorder::BigEndian;
+    ///
+    /// let data = [
+    ///     0x0a, 0x00, 0x00, // Compound
+    ///     0x08, 0x00, 0x01, b'n', 0x00, 0x02, b'h', b'i', // String "n" = "hi"
+    ///     0x03, 0x00, 0x01, b'x', 0x00, 0x00, 0x00, 0x07, // Int "x" = 7
+    ///     0x00, // End
+    /// ];
+    /// let doc = read_borrowed::<BigEndian>(&data)?;
+    /// let root = doc.root();
+    /// assert_eq!(root.get("n").unwrap().byte_range(), Some(7..11));
+    /// assert_eq!(root.get("x").unwrap().byte_range(), None);
+    /// assert_eq!(root.byte_range(), Some(3..data.len()));
+    /// # Ok::<(), na_nbt::Error>(())
+    /// ```
+    pub fn byte_range(&self) -> Option<Range<usize>> {
+        let payload = self.raw_payload()?;
+        let doc = match self {
+            ReadonlyValue::ByteArray(value) => &value.doc,
+            ReadonlyValue::String(value) => &value.doc,
+            ReadonlyValue::List(value) => &value.doc,
+            ReadonlyValue::Compound(value) => &value.doc,
+            ReadonlyValue::IntArray(value) => &value.doc,
+            ReadonlyValue::LongArray(value) => &value.doc,
+            _ => return None,
+        };
+        range_in(doc, payload.as_ptr(), payload.len())
+    }
+
     /// Serializes this value to a byte vector.
     ///
     /// The output includes the tag type and empty root name, making it a complete
@@ -455,12 +595,104 @@ impl<'doc, O: ByteOrder, D: Document> ReadonlyValue<'doc, O, D> {
     /// let mut buffer = Cursor::new(Vec::new());
     /// root.write_to_writer::<BigEndian>(&mut buffer).unwrap();
     /// ```
+    #[cfg(feature = "std")]
     #[inline]
     pub fn write_to_writer<TARGET: ByteOrder>(&self, writer: impl Write) -> Result<()> {
         write_value_to_writer::<D, O, TARGET>(self, writer)
     }
 }
 
+impl<'doc, O: ByteOrder, D: Document> ReadonlyValue<'doc, O, D> {
+    /// Copies a list of `Byte` into an owned `ByteArray`.
+    ///
+    /// Returns `None` if `self` is not a list, or is a non-empty list of another type.
+    pub fn list_to_byte_array(&self) -> Option<OwnedValue<O>> {
+        let ReadonlyValue::List(list) = self else {
+            return None;
+        };
+        unsafe { list_to_array::<O, i8>(list.data.as_ptr(), Tag::Byte) }.map(OwnedValue::ByteArray)
+    }
+
+    /// Copies a `ByteArray` into an owned list of `Byte`.
+    ///
+    /// Returns `None` if `self` is not a byte array.
+    pub fn byte_array_to_list(&self) -> Option<OwnedValue<O>> {
+        match self {
+            ReadonlyValue::ByteArray(value) => Some(array_to_list(Tag::Byte, value)),
+            _ => None,
+        }
+    }
+
+    /// Copies a list of `Int` into an owned `IntArray`.
+    ///
+    /// These are the borrowing counterparts of the conversions on
+    /// [`OwnedValue`], for normalizing data read with [`read_borrowed`] or
+    /// [`read_shared`] without parsing it into an owned tree first. An empty
+    /// list converts to an empty array whatever its element type.
+    ///
+    /// Returns `None` if `self` is not a list, or is a non-empty list of another type.
+    ///
+    /// [`read_borrowed`]: crate::read_borrowed
+    /// [`read_shared`]: crate::read_shared
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use na_nbt::read_borrowed;
+    /// use zerocopy::byteorder::BigEndian;
+    ///
+    /// // List<Int>[1, 2]
+    /// let data = [
+    ///     0x09, 0x00, 0x00, 0x03, 0x00, 0x00, 0x00, 0x02,
+    ///     0x00, 0x00, 0x00, 0x01, 0x00, 0x00, 0x00, 0x02,
+    /// ];
+    /// let doc = read_borrowed::<BigEndian>(&data).unwrap();
+    /// let array = doc.root().list_to_int_array().unwrap();
+    /// assert_eq!(array.as_int_array().map(|a| a.len()), Some(2));
+    ///
+    /// // a list of ints has no byte array counterpart
+    /// assert!(doc.root().list_to_byte_array().is_none());
+    /// ```
+    pub fn list_to_int_array(&self) -> Option<OwnedValue<O>> {
+        let ReadonlyValue::List(list) = self else {
+            return None;
+        };
+        unsafe { list_to_array::<O, byteorder::I32<O>>(list.data.as_ptr(), Tag::Int) }
+            .map(OwnedValue::IntArray)
+    }
+
+    /// Copies an `IntArray` into an owned list of `Int`.
+    ///
+    /// Returns `None` if `self` is not an int array.
+    pub fn int_array_to_list(&self) -> Option<OwnedValue<O>> {
+        match self {
+            ReadonlyValue::IntArray(value) => Some(array_to_list(Tag::Int, value)),
+            _ => None,
+        }
+    }
+
+    /// Copies a list of `Long` into an owned `LongArray`.
+    ///
+    /// Returns `None` if `self` is not a list, or is a non-empty list of another type.
+    pub fn list_to_long_array(&self) -> Option<OwnedValue<O>> {
+        let ReadonlyValue::List(list) = self else {
+            return None;
+        };
+        unsafe { list_to_array::<O, byteorder::I64<O>>(list.data.as_ptr(), Tag::Long) }
+            .map(OwnedValue::LongArray)
+    }
+
+    /// Copies a `LongArray` into an owned list of `Long`.
+    ///
+    /// Returns `None` if `self` is not a long array.
+    pub fn long_array_to_list(&self) -> Option<OwnedValue<O>> {
+        match self {
+            ReadonlyValue::LongArray(value) => Some(array_to_list(Tag::Long, value)),
+            _ => None,
+        }
+    }
+}
+
 /// A zero-copy view of an NBT array (byte array, int array, or long array).
 ///
 /// This type provides direct access to array data without copying. It implements
@@ -499,7 +731,7 @@ impl<'doc, O: ByteOrder, D: Document> ReadonlyValue<'doc, O, D> {
 #[derive(Clone)]
 pub struct ReadonlyArray<'doc, T, D: Document> {
     pub(crate) data: &'doc [T],
-    _doc: D,
+    pub(crate) doc: D,
 }
 
 impl<'doc, T, D: Document> Deref for ReadonlyArray<'doc, T, D> {
@@ -522,6 +754,38 @@ impl<'doc, T, D: Document> ReadonlyArray<'doc, T, D> {
     }
 }
 
+impl<'doc, O: ByteOrder, D: Document> ReadonlyArray<'doc, byteorder::I64<O>, D> {
+    /// Unpacks the first `count` values of `bits_per_entry` bits each, in the
+    /// 1.16+ layout where no value spans two longs.
+    ///
+    /// Returns `None` if `bits_per_entry` is not between 1 and 32, or if the
+    /// array is too short to hold `count` values.
+    #[inline]
+    pub fn unpack_bits(&self, bits_per_entry: u8, count: usize) -> Option<Vec<u32>> {
+        crate::unpack_bits(self, bits_per_entry, count)
+    }
+}
+
+impl<D: Document> ReadonlyArray<'_, i8, D> {
+    /// Returns the 4-bit value at index `i`, low nibble first.
+    ///
+    /// See [`nibble::get_nibble`](crate::nibble::get_nibble).
+    #[inline]
+    pub fn get_nibble(&self, i: usize) -> u8 {
+        crate::nibble::get_nibble(self, i)
+    }
+}
+
+crate::view::fixed_array_methods! {
+    [D: Document] ReadonlyArray<'_, i8, D> => i8, |value| value;
+    [O: ByteOrder, D: Document] ReadonlyArray<'_, byteorder::I32<O>, D> => i32, |value: byteorder::I32<O>| value.get();
+    [O: ByteOrder, D: Document] ReadonlyArray<'_, byteorder::I64<O>, D> => i64, |value: byteorder::I64<O>| value.get();
+}
+
+crate::view::native_vec_methods! {
+    [O: ByteOrder, D: Document] ReadonlyArray<'_, byteorder::I32<O>, D>, ReadonlyArray<'_, byteorder::I64<O>, D>;
+}
+
 /// A zero-copy view of an NBT string.
 ///
 /// NBT strings use Modified UTF-8 encoding (MUTF-8), which is similar to CESU-8.
@@ -552,6 +816,11 @@ impl<'doc, T, D: Document> ReadonlyArray<'doc, T, D> {
 pub type ReadonlyString<'doc, D> = ReadonlyArray<'doc, u8, D>;
 
 impl<'doc, D: Document> ReadonlyString<'doc, D> {
+    #[inline]
+    pub(crate) fn new(data: &'doc [u8], doc: D) -> Self {
+        Self { data, doc }
+    }
+
     /// Returns the raw MUTF-8 bytes of the string.
     ///
     /// For most ASCII strings, this is identical to UTF-8. Use [`decode`](Self::decode)
@@ -563,13 +832,14 @@ impl<'doc, D: Document> ReadonlyString<'doc, D> {
 
     /// Decodes the MUTF-8 string to a Rust string.
     ///
-    /// Returns a [`Cow<str>`](std::borrow::Cow) - borrowed if the string is valid UTF-8,
-    /// owned if conversion was needed.
+    /// Returns a [`Cow<str>`](alloc::borrow::Cow) - borrowed if the string is valid UTF-8,
+    /// owned if conversion was needed. Only strings containing NUL or characters
+    /// outside the Basic Multilingual Plane need conversion.
     ///
     /// Invalid sequences are replaced with the Unicode replacement character (U+FFFD).
     #[inline]
     pub fn decode<'a>(&'a self) -> Cow<'a, str> {
-        simd_cesu8::mutf8::decode_lossy(self.data)
+        decode_mutf8(self.data)
     }
 }
 
@@ -649,6 +919,18 @@ impl<'doc, O: ByteOrder, D: Document> IntoIterator for ReadonlyList<'doc, O, D>
 }
 
 impl<'doc, O: ByteOrder, D: Document> ReadonlyList<'doc, O, D> {
+    /// Creates a list view over `data`, from its element tag to its end,
+    /// with `mark` pointing at the marks of its elements.
+    #[inline]
+    pub(crate) fn from_raw(data: &'doc [u8], mark: *const Mark, doc: D) -> Self {
+        Self {
+            data,
+            mark,
+            doc,
+            _marker: PhantomData,
+        }
+    }
+
     /// Returns the tag type of elements in this list.
     ///
     /// All elements in an NBT list have the same type.
@@ -657,6 +939,15 @@ impl<'doc, O: ByteOrder, D: Document> ReadonlyList<'doc, O, D> {
         unsafe { *self.data.as_ptr().cast() }
     }
 
+    /// Returns the tag type of elements in this list.
+    ///
+    /// Same as [`tag_id`](Self::tag_id), under a name that cannot be mistaken
+    /// for the tag of the list itself.
+    #[inline]
+    pub fn element_tag(&self) -> Tag {
+        self.tag_id()
+    }
+
     /// Returns the number of elements in this list.
     #[inline]
     pub fn len(&self) -> usize {
@@ -682,15 +973,15 @@ impl<'doc, O: ByteOrder, D: Document> ReadonlyList<'doc, O, D> {
                     let mut ptr = self.data.as_ptr().add(1 + 4);
                     for _ in 0..index {
                         let len = byteorder::$l::<O>::from_bytes(*ptr.cast()).get();
-                        ptr = ptr.add(std::mem::size_of::<byteorder::$l<O>>() + len as usize);
+                        ptr = ptr.add(core::mem::size_of::<byteorder::$l<O>>() + len as usize);
                     }
                     let len = byteorder::$l::<O>::from_bytes(*ptr.cast()).get();
                     Some(ReadonlyValue::$t(ReadonlyArray {
                         data: slice::from_raw_parts(
-                            ptr.add(std::mem::size_of::<byteorder::$l<O>>()).cast(),
+                            ptr.add(core::mem::size_of::<byteorder::$l<O>>()).cast(),
                             len as usize,
                         ),
-                        _doc: self.doc.clone(),
+                        doc: self.doc.clone(),
                     }))
                 }
             };
@@ -705,44 +996,32 @@ impl<'doc, O: ByteOrder, D: Document> ReadonlyList<'doc, O, D> {
                         ptr = (*mark).store.end_pointer;
                         mark = mark.add((*mark).store.flat_next_mark as usize);
                     }
-                    Some(ReadonlyValue::$t($s {
-                        doc: self.doc.clone(),
-                        data: slice::from_raw_parts(
+                    Some(ReadonlyValue::$t($s::from_raw(
+                        slice::from_raw_parts(
                             ptr,
                             (*mark).store.end_pointer.byte_offset_from_unsigned(ptr),
                         ),
-                        mark: mark.add(1),
-                        _marker: PhantomData,
-                    }))
+                        mark.add(1),
+                        self.doc.clone(),
+                    )))
                 }
             };
         }
 
         match self.tag_id() {
             Tag::End => Some(ReadonlyValue::End),
-            Tag::Byte => Some(ReadonlyValue::Byte(unsafe {
-                *self.data.as_ptr().add(1 + 4 + index).cast()
-            })),
-            Tag::Short => Some(ReadonlyValue::Short(unsafe {
-                byteorder::I16::<O>::from_bytes(*self.data.as_ptr().add(1 + 4 + index * 2).cast())
-                    .get()
-            })),
-            Tag::Int => Some(ReadonlyValue::Int(unsafe {
-                byteorder::I32::<O>::from_bytes(*self.data.as_ptr().add(1 + 4 + index * 4).cast())
-                    .get()
-            })),
-            Tag::Long => Some(ReadonlyValue::Long(unsafe {
-                byteorder::I64::<O>::from_bytes(*self.data.as_ptr().add(1 + 4 + index * 8).cast())
-                    .get()
-            })),
-            Tag::Float => Some(ReadonlyValue::Float(unsafe {
-                byteorder::F32::<O>::from_bytes(*self.data.as_ptr().add(1 + 4 + index * 4).cast())
-                    .get()
-            })),
-            Tag::Double => Some(ReadonlyValue::Double(unsafe {
-                byteorder::F64::<O>::from_bytes(*self.data.as_ptr().add(1 + 4 + index * 8).cast())
-                    .get()
-            })),
+            tag @ (Tag::Byte | Tag::Short | Tag::Int | Tag::Long | Tag::Float | Tag::Double) => {
+                // numbers sit back to back, so the element is found at once
+                Some(unsafe {
+                    let size = tag_size::<O>(tag, ptr::null(), ptr::null()).0;
+                    ReadonlyValue::read(
+                        tag,
+                        self.data.as_ptr().add(1 + 4 + index * size),
+                        ptr::null(),
+                        self.doc.clone(),
+                    )
+                })
+            }
             Tag::ByteArray => get!(ByteArray, U32),
             Tag::String => get!(String, U16),
             Tag::List => get_composite!(List, ReadonlyList),
@@ -752,6 +1031,41 @@ impl<'doc, O: ByteOrder, D: Document> ReadonlyList<'doc, O, D> {
         }
     }
 
+    /// Returns where the payload of the element at `index` lies in the
+    /// source, or `None` if out of bounds.
+    ///
+    /// This is [`ReadonlyValue::byte_range`] of the element, except that it
+    /// also locates a number, whose value keeps no reference to the source.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use na_nbt::read_borrowed;
+    /// use zerocopy::byteorder::BigEndian;
+    ///
+    /// // a list of two shorts, 1 and 2
+    /// let data = [0x09, 0x00, 0x00, 0x02, 0x00, 0x00, 0x00, 0x02, 0x00, 0x01, 0x00, 0x02];
+    /// let doc = read_borrowed::<BigEndian>(&data)?;
+    /// let list = doc.root();
+    /// let list = list.as_list().unwrap();
+    /// assert_eq!(list.get_byte_range(1), Some(10..12));
+    /// # Ok::<(), na_nbt::Error>(())
+    /// ```
+    pub fn get_byte_range(&self, index: usize) -> Option<Range<usize>> {
+        match self.tag_id() {
+            tag @ (Tag::Byte | Tag::Short | Tag::Int | Tag::Long | Tag::Float | Tag::Double) => {
+                if index >= self.len() {
+                    cold_path();
+                    return None;
+                }
+                let size = unsafe { tag_size::<O>(tag, ptr::null(), ptr::null()).0 };
+                let ptr = unsafe { self.data.as_ptr().add(1 + 4 + index * size) };
+                range_in(&self.doc, ptr, size)
+            }
+            _ => self.get(index)?.byte_range(),
+        }
+    }
+
     /// Returns an iterator over the elements of this list.
     #[inline]
     pub fn iter(&self) -> ReadonlyListIter<'doc, O, D> {
@@ -764,6 +1078,50 @@ impl<'doc, O: ByteOrder, D: Document> ReadonlyList<'doc, O, D> {
             _marker: PhantomData,
         }
     }
+
+    /// Builds a map from the string at `key` in each compound element to that
+    /// element.
+    ///
+    /// Elements that are not compounds, or whose `key` is missing or not a
+    /// string, are skipped. If several elements share a key, the first one is
+    /// kept, as a compound lookup keeps the first of repeated keys.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use na_nbt::{OwnedCompound, OwnedList, OwnedValue, read_borrowed};
+    /// use zerocopy::byteorder::BigEndian;
+    ///
+    /// let mut entities: OwnedList<BigEndian> = OwnedList::default();
+    /// for (id, health) in [("minecraft:zombie", 20), ("minecraft:pig", 10)] {
+    ///     let mut entity = OwnedCompound::default();
+    ///     entity.insert("id", id);
+    ///     entity.insert("Health", health);
+    ///     entities.push(entity);
+    /// }
+    /// let bytes = OwnedValue::List(entities).write_to_vec::<BigEndian>()?;
+    ///
+    /// let doc = read_borrowed::<BigEndian>(&bytes)?;
+    /// let root = doc.root();
+    /// let by_id = root.as_list().unwrap().index_by("id");
+    /// assert_eq!(by_id["minecraft:pig"].get("Health").unwrap().as_int(), Some(10));
+    /// # Ok::<(), na_nbt::Error>(())
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn index_by(&self, key: &str) -> HashMap<String, ReadonlyValue<'doc, O, D>> {
+        let mut map = HashMap::new();
+        for element in self.iter() {
+            let Some(name) = element
+                .as_compound()
+                .and_then(|compound| compound.get(key))
+                .and_then(|value| value.as_string().map(|name| name.decode().into_owned()))
+            else {
+                continue;
+            };
+            map.entry(name).or_insert(element);
+        }
+        map
+    }
 }
 
 /// An iterator over the elements of a [`ReadonlyList`].
@@ -866,18 +1224,36 @@ impl<'doc, O: ByteOrder, D: Document> ExactSizeIterator for ReadonlyListIter<'do
 pub struct ReadonlyCompound<'doc, O: ByteOrder, D: Document> {
     pub(crate) data: &'doc [u8],
     pub(crate) mark: *const Mark,
-    doc: D,
+    pub(crate) doc: D,
+    len: LenCache,
     _marker: PhantomData<O>,
 }
 
+/// The number of entries of a compound, counted on the first call to
+/// [`ReadonlyCompound::len`].
+struct LenCache(AtomicUsize);
+
+impl LenCache {
+    const UNKNOWN: usize = usize::MAX;
+}
+
+impl Default for LenCache {
+    #[inline]
+    fn default() -> Self {
+        Self(AtomicUsize::new(Self::UNKNOWN))
+    }
+}
+
+impl Clone for LenCache {
+    #[inline]
+    fn clone(&self) -> Self {
+        Self(AtomicUsize::new(self.0.load(Ordering::Relaxed)))
+    }
+}
+
 impl<'doc, O: ByteOrder, D: Document> Default for ReadonlyCompound<'doc, O, D> {
     fn default() -> Self {
-        Self {
-            data: &EMPTY_COMPOUND,
-            mark: ptr::null(),
-            doc: unsafe { Never::never() },
-            _marker: PhantomData,
-        }
+        Self::from_raw(&EMPTY_COMPOUND, ptr::null(), unsafe { Never::never() })
     }
 }
 
@@ -900,9 +1276,85 @@ impl<'doc, O: ByteOrder, D: Document> IntoIterator for ReadonlyCompound<'doc, O,
 }
 
 impl<'doc, O: ByteOrder, D: Document> ReadonlyCompound<'doc, O, D> {
+    /// Creates a compound view over `data`, from its first entry to its end
+    /// tag, with `mark` pointing at the marks of its entries.
+    #[inline]
+    pub(crate) fn from_raw(data: &'doc [u8], mark: *const Mark, doc: D) -> Self {
+        Self {
+            data,
+            mark,
+            doc,
+            len: LenCache::default(),
+            _marker: PhantomData,
+        }
+    }
+
+    /// Returns the number of entries, repeated keys included.
+    ///
+    /// NBT does not store the count, so the first call walks the entries,
+    /// skipping over each value by its length without decoding it. The result
+    /// is kept, and clones made afterwards start with it, so later calls are
+    /// free. This is much cheaper than `iter().count()`, which builds every
+    /// key and value.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use na_nbt::{OwnedCompound, OwnedValue, read_borrowed};
+    /// use zerocopy::byteorder::BigEndian;
+    ///
+    /// let mut compound: OwnedCompound<BigEndian> = OwnedCompound::default();
+    /// compound.insert("a", 1);
+    /// compound.insert("b", vec![1i8, 2, 3]);
+    /// compound.insert("c", OwnedCompound::default());
+    /// let bytes = OwnedValue::Compound(compound).write_to_vec::<BigEndian>()?;
+    ///
+    /// let doc = read_borrowed::<BigEndian>(&bytes)?;
+    /// let root = doc.root();
+    /// assert_eq!(root.as_compound().unwrap().len(), 3);
+    /// # Ok::<(), na_nbt::Error>(())
+    /// ```
+    pub fn len(&self) -> usize {
+        let cached = self.len.0.load(Ordering::Relaxed);
+        if cached != LenCache::UNKNOWN {
+            return cached;
+        }
+
+        let mut len = 0;
+        unsafe {
+            let mut ptr = self.data.as_ptr();
+            let mut mark = self.mark;
+            loop {
+                let tag_id = *ptr.cast();
+                if tag_id == Tag::End {
+                    break;
+                }
+                let name_len = byteorder::U16::<O>::from_bytes(*ptr.add(1).cast()).get();
+                ptr = ptr.add(1 + 2 + name_len as usize);
+
+                let (data_advance, mark_advance) = tag_size::<O>(tag_id, ptr, mark);
+                ptr = ptr.add(data_advance);
+                mark = mark.add(mark_advance);
+                len += 1;
+            }
+        }
+        self.len.0.store(len, Ordering::Relaxed);
+        len
+    }
+
+    /// Returns `true` if the compound has no entries.
+    ///
+    /// Unlike [`len`](Self::len), this only looks at the first tag.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.data.first() == Some(&(Tag::End as u8))
+    }
+
     /// Returns the value associated with the given key, or `None` if not found.
     ///
     /// Key lookup uses MUTF-8 encoding internally to match NBT string format.
+    /// Entries are scanned in order, so if the key is repeated the first
+    /// occurrence is returned.
     pub fn get(&self, key: &str) -> Option<ReadonlyValue<'doc, O, D>> {
         let name = simd_cesu8::mutf8::encode(key);
         unsafe {
@@ -934,6 +1386,74 @@ impl<'doc, O: ByteOrder, D: Document> ReadonlyCompound<'doc, O, D> {
         }
     }
 
+    /// Returns where the payload of the value for `key` lies in the source,
+    /// or `None` if not found.
+    ///
+    /// This is [`ReadonlyValue::byte_range`] of [`get`](Self::get), except
+    /// that it also locates a number, whose value keeps no reference to the
+    /// source. If the key is repeated, the first occurrence is used.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use na_nbt::read_borrowed;
+    /// use zerocopy::byteorder::BigEndian;
+    ///
+    /// let data = [
+    ///     0x0a, 0x00, 0x00, // Compound
+    ///     0x03, 0x00, 0x01, b'x', 0x00, 0x00, 0x00, 0x07, // Int "x" = 7
+    ///     0x00, // End
+    /// ];
+    /// let doc = read_borrowed::<BigEndian>(&data)?;
+    /// let root = doc.root();
+    /// let root = root.as_compound().unwrap();
+    /// assert_eq!(root.get_byte_range("x"), Some(7..11));
+    /// assert_eq!(&data[root.get_byte_range("x").unwrap()], 7i32.to_be_bytes());
+    /// # Ok::<(), na_nbt::Error>(())
+    /// ```
+    pub fn get_byte_range(&self, key: &str) -> Option<Range<usize>> {
+        let entry = self.get_entry_bytes(key)?;
+        let name_len = unsafe { byteorder::U16::<O>::from_bytes(*entry.as_ptr().add(1).cast()) };
+        let header = 1 + 2 + name_len.get() as usize;
+        range_in(&self.doc, entry[header..].as_ptr(), entry.len() - header)
+    }
+
+    /// Returns the encoded entry for `key`, from its tag byte to the end of its payload.
+    pub(crate) fn get_entry_bytes(&self, key: &str) -> Option<&'doc [u8]> {
+        let name = simd_cesu8::mutf8::encode(key);
+        unsafe {
+            let mut ptr = self.data.as_ptr();
+            let mut mark = self.mark;
+            loop {
+                let start = ptr;
+                let tag_id = *ptr.cast();
+                ptr = ptr.add(1);
+
+                if tag_id == Tag::End {
+                    cold_path();
+                    return None;
+                }
+
+                let name_len = byteorder::U16::<O>::from_bytes(*ptr.cast()).get();
+                ptr = ptr.add(2);
+
+                let name_bytes = core::slice::from_raw_parts(ptr, name_len as usize);
+                ptr = ptr.add(name_len as usize);
+
+                let (data_advance, mark_advance) = tag_size::<O>(tag_id, ptr, mark);
+                ptr = ptr.add(data_advance);
+                mark = mark.add(mark_advance);
+
+                if name == name_bytes {
+                    return Some(slice::from_raw_parts(
+                        start,
+                        ptr.byte_offset_from_unsigned(start),
+                    ));
+                }
+            }
+        }
+    }
+
     /// Returns an iterator over the entries of this compound.
     ///
     /// Each entry is a `(ReadonlyString, ReadonlyValue)` pair.
@@ -946,6 +1466,120 @@ impl<'doc, O: ByteOrder, D: Document> ReadonlyCompound<'doc, O, D> {
             _marker: PhantomData,
         }
     }
+
+    /// Returns an iterator over the entries of this compound, sorted by key.
+    ///
+    /// Keys compare as raw MUTF-8 bytes, which orders ASCII keys as `str`
+    /// would. Repeated keys keep their relative order. The document is not
+    /// changed; the entries are collected and sorted on the side, so this
+    /// allocates once per call.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use na_nbt::{OwnedCompound, OwnedValue, read_borrowed};
+    /// use zerocopy::byteorder::BigEndian;
+    ///
+    /// let mut compound: OwnedCompound<BigEndian> = OwnedCompound::default();
+    /// compound.insert("b", 2);
+    /// compound.insert("c", 3);
+    /// compound.insert("a", 1);
+    /// let bytes = OwnedValue::Compound(compound).write_to_vec::<BigEndian>()?;
+    ///
+    /// let doc = read_borrowed::<BigEndian>(&bytes)?;
+    /// let root = doc.root();
+    /// let keys: Vec<_> = root
+    ///     .as_compound()
+    ///     .unwrap()
+    ///     .iter_sorted()
+    ///     .map(|(key, _)| key.decode().into_owned())
+    ///     .collect();
+    /// assert_eq!(keys, ["a", "b", "c"]);
+    /// # Ok::<(), na_nbt::Error>(())
+    /// ```
+    pub fn iter_sorted(
+        &self,
+    ) -> alloc::vec::IntoIter<(ReadonlyString<'doc, D>, ReadonlyValue<'doc, O, D>)> {
+        let mut entries: Vec<_> = self.iter().collect();
+        entries.sort_by(|(a, _), (b, _)| a.raw_bytes().cmp(b.raw_bytes()));
+        entries.into_iter()
+    }
+
+    /// Returns an iterator over the entries of this compound with their keys
+    /// decoded.
+    ///
+    /// Each entry is a `(Cow<str>, ReadonlyValue)` pair, so keys can be
+    /// matched as `&str`. ASCII keys are borrowed; only keys that need
+    /// conversion, as described for [`decode`](ReadonlyString::decode),
+    /// allocate.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use na_nbt::{OwnedCompound, OwnedValue, read_borrowed};
+    /// use zerocopy::byteorder::BigEndian;
+    ///
+    /// let mut compound: OwnedCompound<BigEndian> = OwnedCompound::default();
+    /// compound.insert("x", 1);
+    /// compound.insert("y", 64);
+    /// let bytes = OwnedValue::Compound(compound).write_to_vec::<BigEndian>()?;
+    ///
+    /// let doc = read_borrowed::<BigEndian>(&bytes)?;
+    /// let root = doc.root();
+    /// for (key, value) in root.as_compound().unwrap().iter_str() {
+    ///     match &*key {
+    ///         "x" => assert_eq!(value.as_int(), Some(1)),
+    ///         "y" => assert_eq!(value.as_int(), Some(64)),
+    ///         _ => unreachable!(),
+    ///     }
+    /// }
+    /// # Ok::<(), na_nbt::Error>(())
+    /// ```
+    #[inline]
+    pub fn iter_str<'a>(&'a self) -> ReadonlyCompoundIterStr<'a, 'doc, O, D> {
+        ReadonlyCompoundIterStr {
+            iter: self.iter(),
+            _marker: PhantomData,
+        }
+    }
+
+    /// Returns an iterator over the entries whose value has the tag `T`,
+    /// with decoded keys.
+    ///
+    /// Entries of any other tag are skipped. `T` is one of the tag types of
+    /// [`TagType`], such as [`TagInt`](crate::TagInt) or
+    /// [`TagString`](crate::TagString); see [`ReadonlyTag`] for what each
+    /// yields.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use na_nbt::{OwnedCompound, OwnedValue, TagInt, TagString, read_borrowed};
+    /// use zerocopy::byteorder::BigEndian;
+    ///
+    /// let mut compound: OwnedCompound<BigEndian> = OwnedCompound::default();
+    /// compound.insert("x", 1);
+    /// compound.insert("name", "Steve");
+    /// compound.insert("y", 64);
+    /// compound.insert("z", 3i64);
+    /// let bytes = OwnedValue::Compound(compound).write_to_vec::<BigEndian>()?;
+    ///
+    /// let doc = read_borrowed::<BigEndian>(&bytes)?;
+    /// let root = doc.root();
+    /// let compound = root.as_compound().unwrap();
+    /// let ints: Vec<_> = compound.iter_of::<TagInt>().collect();
+    /// assert_eq!(ints, [("x".into(), 1), ("y".into(), 64)]);
+    /// let (key, name) = compound.iter_of::<TagString>().next().unwrap();
+    /// assert_eq!((&*key, &*name.decode()), ("name", "Steve"));
+    /// # Ok::<(), na_nbt::Error>(())
+    /// ```
+    #[inline]
+    pub fn iter_of<'a, T: ReadonlyTag>(&'a self) -> ReadonlyCompoundIterOf<'a, 'doc, O, D, T> {
+        ReadonlyCompoundIterOf {
+            iter: self.iter(),
+            _marker: PhantomData,
+        }
+    }
 }
 
 /// An iterator over the entries of a [`ReadonlyCompound`].
@@ -989,7 +1623,7 @@ impl<'doc, O: ByteOrder, D: Document> Iterator for ReadonlyCompoundIter<'doc, O,
             let name_len = byteorder::U16::<O>::from_bytes(*self.data.add(1).cast()).get();
             let name = ReadonlyString {
                 data: slice::from_raw_parts(self.data.add(3), name_len as usize),
-                _doc: self.doc.clone(),
+                doc: self.doc.clone(),
             };
 
             let value = ReadonlyValue::read(
@@ -1009,3 +1643,110 @@ impl<'doc, O: ByteOrder, D: Document> Iterator for ReadonlyCompoundIter<'doc, O,
         }
     }
 }
+
+/// An iterator over the entries of a [`ReadonlyCompound`] with decoded
+/// keys.
+///
+/// This type is returned by [`ReadonlyCompound::iter_str`].
+#[derive(Clone)]
+pub struct ReadonlyCompoundIterStr<'a, 'doc, O: ByteOrder, D: Document> {
+    iter: ReadonlyCompoundIter<'doc, O, D>,
+    _marker: PhantomData<&'a ()>,
+}
+
+impl<'a, 'doc, O: ByteOrder, D: Document> Iterator for ReadonlyCompoundIterStr<'a, 'doc, O, D> {
+    type Item = (Cow<'a, str>, ReadonlyValue<'doc, O, D>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (key, value) = self.iter.next()?;
+        // the compound borrowed for 'a keeps the document alive
+        let key = unsafe { slice::from_raw_parts(key.data.as_ptr(), key.data.len()) };
+        Some((decode_mutf8(key), value))
+    }
+}
+
+/// A [`TagType`] whose values can be read out of a [`ReadonlyValue`].
+///
+/// Numbers are decoded, and the other tags give their view:
+///
+/// | Tag type | Value |
+/// |----------|-------|
+/// | [`TagByte`](crate::TagByte) to [`TagDouble`](crate::TagDouble) | `i8` to `f64` |
+/// | [`TagByteArray`](crate::TagByteArray) | [`ReadonlyArray`] of `i8` |
+/// | [`TagString`](crate::TagString) | [`ReadonlyString`] |
+/// | [`TagList`](crate::TagList) | [`ReadonlyList`] |
+/// | [`TagCompound`](crate::TagCompound) | [`ReadonlyCompound`] |
+/// | [`TagIntArray`](crate::TagIntArray) | [`ReadonlyArray`] of `I32` |
+/// | [`TagLongArray`](crate::TagLongArray) | [`ReadonlyArray`] of `I64` |
+///
+/// This trait is sealed through [`TagType`].
+pub trait ReadonlyTag: TagType {
+    /// What a value of this tag is read as.
+    type Value<'doc, O: ByteOrder, D: Document>;
+
+    #[doc(hidden)]
+    fn from_readonly<'doc, O: ByteOrder, D: Document>(
+        value: ReadonlyValue<'doc, O, D>,
+    ) -> Option<Self::Value<'doc, O, D>>;
+}
+
+macro_rules! impl_readonly_tag {
+    ($($tag:ident => $variant:ident -> $value:ty),*) => {
+        $(
+            impl ReadonlyTag for crate::$tag {
+                type Value<'doc, O: ByteOrder, D: Document> = $value;
+
+                #[inline]
+                fn from_readonly<'doc, O: ByteOrder, D: Document>(
+                    value: ReadonlyValue<'doc, O, D>,
+                ) -> Option<Self::Value<'doc, O, D>> {
+                    match value {
+                        ReadonlyValue::$variant(value) => Some(value),
+                        _ => None,
+                    }
+                }
+            }
+        )*
+    };
+}
+
+impl_readonly_tag!(
+    TagByte => Byte -> i8,
+    TagShort => Short -> i16,
+    TagInt => Int -> i32,
+    TagLong => Long -> i64,
+    TagFloat => Float -> f32,
+    TagDouble => Double -> f64,
+    TagByteArray => ByteArray -> ReadonlyArray<'doc, i8, D>,
+    TagString => String -> ReadonlyString<'doc, D>,
+    TagList => List -> ReadonlyList<'doc, O, D>,
+    TagCompound => Compound -> ReadonlyCompound<'doc, O, D>,
+    TagIntArray => IntArray -> ReadonlyArray<'doc, byteorder::I32<O>, D>,
+    TagLongArray => LongArray -> ReadonlyArray<'doc, byteorder::I64<O>, D>
+);
+
+/// An iterator over the entries of a [`ReadonlyCompound`] whose value has
+/// the tag `T`.
+///
+/// This type is returned by [`ReadonlyCompound::iter_of`]. Each iteration
+/// yields the decoded key and the value, as given by [`ReadonlyTag`].
+#[derive(Clone)]
+pub struct ReadonlyCompoundIterOf<'a, 'doc, O: ByteOrder, D: Document, T: ReadonlyTag> {
+    iter: ReadonlyCompoundIter<'doc, O, D>,
+    _marker: PhantomData<(&'a (), T)>,
+}
+
+impl<'a, 'doc, O: ByteOrder, D: Document, T: ReadonlyTag> Iterator
+    for ReadonlyCompoundIterOf<'a, 'doc, O, D, T>
+{
+    type Item = (Cow<'a, str>, T::Value<'doc, O, D>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.iter.find_map(|(key, value)| {
+            let value = T::from_readonly(value)?;
+            // the compound borrowed for 'a keeps the document alive
+            let key = unsafe { slice::from_raw_parts(key.data.as_ptr(), key.data.len()) };
+            Some((decode_mutf8(key), value))
+        })
+    }
+}
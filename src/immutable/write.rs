@@ -1,8 +1,12 @@
-use std::{hint::unreachable_unchecked, io::Write, ptr, slice};
+use core::{hint::unreachable_unchecked, ptr, slice};
+#[cfg(feature = "std")]
+use std::io::Write;
 
 use zerocopy::byteorder;
 
-use crate::{ByteOrder, Error, Result, Tag, cold_path};
+#[cfg(feature = "std")]
+use crate::Error;
+use crate::{ByteOrder, Result, Tag, cold_path};
 
 macro_rules! change_endian {
     ($value:expr, $type:ident, $from:ident, $to:ident) => {
@@ -101,6 +105,10 @@ pub unsafe fn write_list_fallback<O: ByteOrder, R: ByteOrder>(mut buf: *mut u8)
         let tag_id = *buf.cast();
         buf = buf.add(1);
         let len = byteorder::U32::<O>::from_bytes(*buf.cast()).get();
+        if len == 0 {
+            // empty lists are always written as End, whatever their element type
+            *buf_start = 0;
+        }
         ptr::write(buf.cast(), byteorder::U32::<R>::new(len).to_bytes());
         buf = buf.add(4);
         match tag_id {
@@ -182,6 +190,7 @@ pub unsafe fn write_list_fallback<O: ByteOrder, R: ByteOrder>(mut buf: *mut u8)
     }
 }
 
+#[cfg(feature = "std")]
 pub unsafe fn write_compound_to_writer_fallback<O: ByteOrder, R: ByteOrder>(
     mut buf: *const u8,
     writer: &mut impl Write,
@@ -298,6 +307,7 @@ pub unsafe fn write_compound_to_writer_fallback<O: ByteOrder, R: ByteOrder>(
     }
 }
 
+#[cfg(feature = "std")]
 pub unsafe fn write_list_to_writer_fallback<O: ByteOrder, R: ByteOrder>(
     mut buf: *const u8,
     writer: &mut impl Write,
@@ -306,9 +316,11 @@ pub unsafe fn write_list_to_writer_fallback<O: ByteOrder, R: ByteOrder>(
         let buf_start = buf;
         let tag_id = *buf.cast();
         let mut temp = [0u8; 1 + 4];
-        ptr::write(temp.as_mut_ptr(), tag_id as u8);
         buf = buf.add(1);
         let len = byteorder::U32::<O>::from_bytes(*buf.cast()).get();
+        if len != 0 {
+            ptr::write(temp.as_mut_ptr(), tag_id as u8);
+        }
         ptr::write(
             temp.as_mut_ptr().add(1).cast(),
             byteorder::U32::<R>::new(len).to_bytes(),
@@ -1,4 +1,8 @@
+use alloc::string::String;
+
 mod private {
+    use alloc::string::String;
+
     pub trait Sealed {}
     impl Sealed for usize {}
     impl Sealed for str {}
@@ -0,0 +1,147 @@
+//! Conversion from [`serde_json::Value`] to NBT.
+//!
+//! JSON has far fewer types than NBT, so [`from_json`] has to pick a tag for
+//! each value:
+//!
+//! | JSON | NBT Tag | Notes |
+//! |------|---------|-------|
+//! | `true` / `false` | Byte | `1` / `0` |
+//! | integer | Int | Long if outside the `i32` range |
+//! | float | Double | |
+//! | string | String | |
+//! | array | List | Element type must be the same for every element |
+//! | object | Compound | |
+//! | `null` | - | Rejected |
+//!
+//! An array of numbers becomes the narrowest list that holds all of them:
+//! `List<Int>` if every element fits in an `i32`, `List<Long>` if every element
+//! is an integer fitting in an `i64`, and `List<Double>` otherwise. An empty
+//! array becomes an empty list.
+//!
+//! Array types such as `IntArray` are never produced; convert afterwards with
+//! [`OwnedValue::list_to_int_array`] and friends if needed.
+//!
+//! This module requires the `json` feature.
+
+use serde_json::{Number, Value};
+
+use crate::{ByteOrder, Error, OwnedCompound, OwnedList, OwnedValue, Result, Tag};
+
+/// Builds an [`OwnedValue`] from a JSON value.
+///
+/// See the [module documentation](self) for how JSON types are mapped.
+///
+/// # Example
+///
+/// ```
+/// use na_nbt::{from_json, Tag};
+/// use serde_json::json;
+/// use zerocopy::byteorder::BigEndian;
+///
+/// let value = from_json::<BigEndian>(&json!({
+///     "name": "Steve",
+///     "level": 42,
+///     "seed": 1u64 << 40,
+///     "pos": [1.5, 64, -3],
+/// }))?;
+///
+/// assert_eq!(value.get("level").and_then(|v| v.as_int()), Some(42));
+/// assert_eq!(value.get("seed").and_then(|v| v.as_long()), Some(1 << 40));
+/// let pos = value.get("pos").unwrap();
+/// assert_eq!(pos.as_list().map(|l| l.tag_id()), Some(Tag::Double));
+/// # Ok::<(), na_nbt::Error>(())
+/// ```
+///
+/// # Errors
+///
+/// Returns [`Error::Message`] if the value contains `null`, an integer that
+/// does not fit in an `i64`, or an array whose elements map to different tags.
+pub fn from_json<O: ByteOrder>(value: &Value) -> Result<OwnedValue<O>> {
+    match value {
+        Value::Null => Err(Error::Message("null has no NBT equivalent".to_string())),
+        Value::Bool(value) => Ok(OwnedValue::Byte(*value as i8)),
+        Value::Number(number) => Ok(match number_tag(number)? {
+            Tag::Int => (number_to_i64(number) as i32).into(),
+            Tag::Long => number_to_i64(number).into(),
+            _ => number_to_f64(number).into(),
+        }),
+        Value::String(value) => Ok(value.as_str().into()),
+        Value::Array(values) => array_to_list(values).map(OwnedValue::List),
+        Value::Object(map) => {
+            let mut compound = OwnedCompound::default();
+            for (key, value) in map {
+                compound.insert(key, from_json::<O>(value)?);
+            }
+            Ok(OwnedValue::Compound(compound))
+        }
+    }
+}
+
+fn array_to_list<O: ByteOrder>(values: &[Value]) -> Result<OwnedList<O>> {
+    let mut list = OwnedList::default();
+
+    if values.iter().all(Value::is_number) {
+        // widen to the narrowest tag that every element fits in
+        let mut tag_id = Tag::End;
+        for value in values {
+            let number = value.as_number().unwrap();
+            tag_id = match (tag_id, number_tag(number)?) {
+                (Tag::Double, _) | (_, Tag::Double) => Tag::Double,
+                (Tag::Long, _) | (_, Tag::Long) => Tag::Long,
+                _ => Tag::Int,
+            };
+        }
+        for value in values {
+            let number = value.as_number().unwrap();
+            match tag_id {
+                Tag::Int => list.push(number_to_i64(number) as i32),
+                Tag::Long => list.push(number_to_i64(number)),
+                _ => list.push(number_to_f64(number)),
+            }
+        }
+        return Ok(list);
+    }
+
+    let mut tag_id = None;
+    for value in values {
+        let value = from_json::<O>(value)?;
+        match tag_id {
+            None => tag_id = Some(value.tag_id()),
+            Some(tag_id) if tag_id != value.tag_id() => {
+                return Err(Error::Message(format!(
+                    "heterogeneous JSON array: expected {tag_id:?} elements, found {:?}",
+                    value.tag_id()
+                )));
+            }
+            Some(_) => {}
+        }
+        list.push(value);
+    }
+    Ok(list)
+}
+
+fn number_tag(number: &Number) -> Result<Tag> {
+    if let Some(value) = number.as_i64() {
+        Ok(if i32::try_from(value).is_ok() {
+            Tag::Int
+        } else {
+            Tag::Long
+        })
+    } else if number.is_u64() {
+        Err(Error::Message(format!(
+            "integer {number} does not fit in a Long"
+        )))
+    } else {
+        Ok(Tag::Double)
+    }
+}
+
+#[inline]
+fn number_to_i64(number: &Number) -> i64 {
+    number.as_i64().unwrap()
+}
+
+#[inline]
+fn number_to_f64(number: &Number) -> f64 {
+    number.as_f64().unwrap()
+}
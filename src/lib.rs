@@ -21,13 +21,28 @@
 //!
 //! # Features
 //!
-//! This crate has two optional features, both enabled by default:
+//! These optional features are enabled by default:
 //!
 //! | Feature | Description | Dependencies |
 //! |---------|-------------|--------------|
-//! | `serde` | Serialize/deserialize Rust types to/from NBT | `serde` |
+//! | `std` | `std::io` readers and writers | - |
+//! | `serde` | Serialize/deserialize Rust types to/from NBT (implies `std`) | `serde` |
 //! | `shared` | [`SharedValue`] with Arc ownership | `bytes` |
 //!
+//! Further opt-in features: `json` adds `from_json`, `tokio` adds
+//! `read_async` and `write_async`, `compression` adds `peek_root_compressed`,
+//! `read_owned_compressed`, `read_auto`, `to_file_bytes`,
+//! `write_leveldat_to_writer`, `detect_compression`, `decompress_to_vec` and
+//! `RegionReader`, and `mmap` adds
+//! `read_mmap`. `strict` adds no API: in debug builds it turns every write
+//! skipped for a type mismatch, such as `set_int` on a string or
+//! `OwnedList::set` with the wrong element type, into a panic.
+//!
+//! Without `std` the crate is `no_std` and only needs `alloc`: parsing from
+//! slices, the owned and borrowed values, and `write_to_vec` all keep working,
+//! while the `*_to_writer` / `*_from_reader` functions and [`Error::IO`] are
+//! unavailable.
+//!
 //! To use without optional dependencies:
 //!
 //! ```toml
@@ -166,6 +181,11 @@
 //! }).join().unwrap();
 //! ```
 //!
+//! To read only a few keys of a large root compound, [`read_shared_lazy`]
+//! skips indexing the whole document and scans for each key on demand.
+//! To change a few values and write the rest back byte for byte,
+//! [`read_shared_preserving`] copies every untouched entry from the source.
+//!
 //! # Owned parsing for mutation
 //!
 //! When you need to modify NBT data, use [`read_owned`]. This creates an
@@ -185,6 +205,11 @@
 //! }
 //! ```
 //!
+//! To parse straight from a file or socket, use [`read_owned_from_reader`], or
+//! [`read_owned_from_reader_with_limits`] when the input is untrusted.
+//! [`read_owned_from_buf_reader`] reads one document out of a longer stream
+//! and leaves the reader right after it.
+//!
 //! # Building NBT from scratch
 //!
 //! You can construct NBT values programmatically using the owned types:
@@ -245,6 +270,18 @@
 //! let bedrock_bytes = value.write_to_vec::<LittleEndian>().unwrap();
 //! ```
 //!
+//! [`read_java`], [`read_bedrock`] and [`read_bedrock_network`] fix both byte
+//! orders for each edition; see the [`edition`] module.
+//!
+//! When the byte order of some data is unknown, [`read_borrowed_auto`] tries
+//! both and reports which one parsed. It is a guess, meant for inspecting
+//! files, not for reading data whose format is known.
+//!
+//! # SNBT
+//!
+//! [`to_snbt`] writes any value in the text form used by commands, and
+//! [`from_snbt`] parses it back. See the [`snbt`] module.
+//!
 //! # Trait hierarchy for generic code
 //!
 //! This crate provides a trait hierarchy for writing generic code that works
@@ -440,6 +477,7 @@
 //! | `Vec<i8>` | ByteArray | With `#[serde(with = "na_nbt::byte_array")]` |
 //! | `Vec<i32>` | IntArray | With `#[serde(with = "na_nbt::int_array")]` |
 //! | `Vec<i64>` | LongArray | With `#[serde(with = "na_nbt::long_array")]` |
+//! | [`ByteArray`], [`IntArray`], [`LongArray`] | ByteArray, IntArray, LongArray | Tag required on deserialize |
 //! | `String`, `&str` | String | MUTF-8 encoded |
 //! | `Vec<T>`, `&[T]` | List | Homogeneous elements |
 //! | struct | Compound | Named fields |
@@ -542,25 +580,53 @@
 //!
 //! For more details, see the [`de`] and [`ser`] module documentation.
 
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+
 // Serde support (optional, enabled by default)
 #[cfg(feature = "serde")]
 pub mod array;
+#[cfg(feature = "tokio")]
+pub mod async_io;
+#[cfg(feature = "compression")]
+pub mod compress;
 #[cfg(feature = "serde")]
 pub mod de;
 #[cfg(feature = "serde")]
 pub mod ser;
 
+pub mod edition;
 pub mod error;
+pub mod frame;
 pub mod immutable;
 mod index;
+#[cfg(feature = "json")]
+pub mod json;
 pub mod mutable;
+pub mod nibble;
+pub mod packed;
+mod peek;
+#[cfg(feature = "compression")]
+pub mod region;
+#[cfg(feature = "std")]
+pub mod schema;
+pub mod snbt;
 pub mod tag;
 pub mod util;
 pub mod value_trait;
 mod view;
+mod walk;
 
 #[cfg(feature = "serde")]
-pub use array::{byte_array, int_array, long_array};
+pub use array::{ByteArray, IntArray, LongArray, byte_array, int_array, long_array};
+#[cfg(feature = "tokio")]
+pub use async_io::{read_async, write_async};
+#[cfg(feature = "compression")]
+pub use compress::{
+    Compression, decompress_to_vec, detect_compression, read_auto, read_owned_compressed,
+    write_leveldat_to_writer,
+};
 #[cfg(feature = "serde")]
 pub use de::{
     Deserializer, from_reader, from_reader_be, from_reader_le, from_slice, from_slice_be,
@@ -568,13 +634,29 @@ pub use de::{
 };
 #[cfg(feature = "serde")]
 pub use ser::{Serializer, to_vec, to_vec_be, to_vec_le, to_writer, to_writer_be, to_writer_le};
+#[cfg(feature = "serde")]
+pub use snbt::{from_snbt_str, to_snbt_string};
 
+pub use edition::{
+    read_bedrock, read_bedrock_leveldat, read_bedrock_network, read_java, write_bedrock_leveldat,
+};
 pub use error::*;
+pub use frame::{LengthPrefix, VarInt, write_framed_value_to_vec};
 pub use immutable::*;
+#[cfg(feature = "json")]
+pub use json::from_json;
 pub use mutable::*;
+pub use packed::{pack_bits, unpack_bits};
+pub use peek::peek_root;
+#[cfg(feature = "compression")]
+pub use peek::peek_root_compressed;
+#[cfg(feature = "compression")]
+pub use region::RegionReader;
+pub use snbt::{from_snbt, to_snbt};
 pub use tag::*;
 pub use util::*;
 pub use value_trait::*;
+pub use view::{StringViewOwn, VecViewOwn};
 
 pub use zerocopy::BigEndian;
 pub use zerocopy::LittleEndian;
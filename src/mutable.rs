@@ -14,6 +14,7 @@
 //! ## Container Types
 //!
 //! - [`OwnedCompound`] / [`OwnedList`] - Owned compound and list types
+//! - [`TypedListBuilder`] - Builds an [`OwnedList`] of one primitive type
 //! - [`MutableCompound`] / [`MutableList`] - Mutable views into compounds and lists
 //! - [`ImmutableCompound`] / [`ImmutableList`] / [`ImmutableString`] - Immutable views
 //!
@@ -49,41 +50,61 @@
 //! ```
 
 mod into_owned_value;
+mod into_visit;
 mod iter;
+mod parser;
 mod read;
+mod rewrite;
 mod trait_impl;
 mod trait_impl_mut;
 mod trait_impl_own;
+mod typed_list;
 mod util;
 mod value;
 mod value_mut;
 mod value_own;
 mod write;
 
-use std::{
-    any::TypeId,
-    io::{BufRead, BufReader, Read, Write},
-    ptr,
-};
+use alloc::{string::String, vec, vec::Vec};
+use core::{any::TypeId, marker::PhantomData, ops::Range, ptr};
+#[cfg(feature = "std")]
+use std::io::{BufRead, BufReader, Read, Write};
 
 pub(crate) use into_owned_value::IntoOwnedValue;
+pub use into_visit::IntoVisit;
+pub use parser::Parser;
+pub use rewrite::RewriteAction;
+pub use typed_list::TypedListBuilder;
 pub use value::{ImmutableCompound, ImmutableList, ImmutableString, ImmutableValue};
 pub use value_mut::{MutableCompound, MutableList, MutableValue};
-pub use value_own::{OwnedCompound, OwnedList, OwnedValue};
+pub use value_own::{OwnedCompound, OwnedList, OwnedTag, OwnedValue};
+pub(crate) use value_own::{array_to_list, list_to_array};
 
-use zerocopy::{IntoBytes, byteorder};
+#[cfg(feature = "std")]
+use zerocopy::IntoBytes;
+use zerocopy::byteorder;
 
+#[cfg(feature = "std")]
+use crate::mutable::{
+    read::{PositionReader, read_unsafe_from_reader},
+    write::{
+        write_compound_to_writer, write_compound_to_writer_fallback, write_list_to_writer,
+        write_list_to_writer_fallback,
+    },
+};
 use crate::{
-    ByteOrder, Error, Result, Tag, ValueScoped, cold_path,
+    ByteOrder, Error, ReadableString, Result, ScopedReadableCompound, ScopedReadableValue, Tag,
+    ValueScoped, cold_path, decode_mutf8,
     mutable::{
-        read::{read_unsafe, read_unsafe_fallback, read_unsafe_from_reader},
+        read::{read_unsafe, read_unsafe_fallback},
         trait_impl::Config,
+        util::compound_remove_at,
         write::{
-            write_compound, write_compound_fallback, write_compound_to_writer,
-            write_compound_to_writer_fallback, write_list, write_list_fallback,
-            write_list_to_writer, write_list_to_writer_fallback,
+            compound_size, list_size, write_compound, write_compound_fallback, write_list,
+            write_list_fallback,
         },
     },
+    walk::{Frame, Hooks, Place, Walk},
 };
 
 /// Parses NBT from bytes into an owned, mutable value.
@@ -131,9 +152,125 @@ use crate::{
 ///
 /// Returns an error if:
 /// - The data is truncated ([`Error::EndOfFile`])
-/// - An invalid tag type is encountered ([`Error::InvalidTagType`])
+/// - An invalid tag type is encountered ([`Error::InvalidTagType`], wrapped in
+///   [`Error::At`] with the offset of the offending byte)
 /// - Extra data remains after parsing ([`Error::TrailingData`])
+///
+/// # Duplicate Keys
+///
+/// If a key appears more than once in a compound, the last entry wins, as it
+/// does in the game: the earlier ones are dropped while reading, so lookups,
+/// iteration and writing all see only the last. Use [`read_owned_strict`] to
+/// reject such input instead.
 pub fn read_owned<SOURCE: ByteOrder, STORE: ByteOrder>(source: &[u8]) -> Result<OwnedValue<STORE>> {
+    let mut value = read_owned_as_encoded::<SOURCE, STORE>(source)?;
+    drop_shadowed_keys(value.to_mut(), &mut KeySet::default());
+    Ok(value)
+}
+
+/// Parses NBT like [`read_owned`], keeping every entry of a repeated key.
+pub(crate) fn read_owned_as_encoded<SOURCE: ByteOrder, STORE: ByteOrder>(
+    source: &[u8],
+) -> Result<OwnedValue<STORE>> {
+    let (value, len) = read_owned_prefix::<SOURCE, STORE>(source)?;
+    if len < source.len() {
+        cold_path();
+        return Err(Error::TrailingData(source.len() - len));
+    }
+    Ok(value)
+}
+
+/// Parses NBT like [`read_owned`], also returning the decoded name of the
+/// root.
+///
+/// [`read_owned`] drops the root name, which is almost always empty. Use this
+/// to keep a non-empty one, for instance to write the document back with the
+/// same name through
+/// [`write_to_vec_named`](crate::ScopedReadableValue::write_to_vec_named). An
+/// `End` root has no name and gives an empty string.
+///
+/// # Example
+///
+/// ```
+/// use na_nbt::read_owned_named;
+/// use zerocopy::byteorder::BigEndian;
+///
+/// let data = [0x0a, 0x00, 0x04, b'D', b'a', b't', b'a', 0x00];
+/// let (name, value) = read_owned_named::<BigEndian, BigEndian>(&data)?;
+/// assert_eq!(name, "Data");
+/// assert!(value.is_compound());
+/// # Ok::<(), na_nbt::Error>(())
+/// ```
+///
+/// # Errors
+///
+/// See [`read_owned`].
+pub fn read_owned_named<SOURCE: ByteOrder, STORE: ByteOrder>(
+    source: &[u8],
+) -> Result<(String, OwnedValue<STORE>)> {
+    let value = read_owned::<SOURCE, STORE>(source)?;
+    if source[0] == Tag::End as u8 {
+        return Ok((String::new(), value));
+    }
+    // the header was checked by `read_owned`
+    let name_len = byteorder::U16::<SOURCE>::from_bytes([source[1], source[2]]).get() as usize;
+    let name = decode_mutf8(&source[3..3 + name_len]).into_owned();
+    Ok((name, value))
+}
+
+/// Parses NBT like [`read_owned`], requiring the root to be a compound and
+/// returning that compound directly.
+///
+/// Files written by the game, such as `level.dat` and player data, always
+/// have a compound root, while [`read_owned`] accepts a root of any type.
+/// The root tag is checked before anything else is read.
+///
+/// # Example
+///
+/// ```
+/// use na_nbt::{Error, read_owned_compound_root};
+/// use zerocopy::byteorder::BigEndian;
+///
+/// let data = [0x0a, 0x00, 0x00, 0x01, 0x00, 0x01, b'x', 0x05, 0x00];
+/// let root = read_owned_compound_root::<BigEndian, BigEndian>(&data)?;
+/// assert_eq!(root.get("x").and_then(|x| x.as_byte()), Some(5));
+///
+/// let int_root = [0x03, 0x00, 0x00, 0x00, 0x00, 0x00, 0x01];
+/// assert!(matches!(
+///     read_owned_compound_root::<BigEndian, BigEndian>(&int_root),
+///     Err(Error::TagMismatch(10, 3))
+/// ));
+/// # Ok::<(), Error>(())
+/// ```
+///
+/// # Errors
+///
+/// Returns the same errors as [`read_owned`], plus [`Error::TagMismatch`]
+/// with `Compound` and the root tag if the root is not a compound.
+pub fn read_owned_compound_root<SOURCE: ByteOrder, STORE: ByteOrder>(
+    source: &[u8],
+) -> Result<OwnedCompound<STORE>> {
+    let Some(&root_tag) = source.first() else {
+        cold_path();
+        return Err(Error::EndOfFile);
+    };
+    if root_tag != Tag::Compound as u8 {
+        cold_path();
+        return Err(Error::TagMismatch(Tag::Compound as u8, root_tag));
+    }
+    match read_owned::<SOURCE, STORE>(source)? {
+        OwnedValue::Compound(compound) => Ok(compound),
+        _ => unreachable!("the root tag was checked above"),
+    }
+}
+
+/// Parses the document at the start of `source`, returning it along with the
+/// number of bytes it took. Whatever follows the document is left unread.
+///
+/// Compounds are kept exactly as encoded, repeated keys included.
+fn read_owned_prefix<SOURCE: ByteOrder, STORE: ByteOrder>(
+    source: &[u8],
+) -> Result<(OwnedValue<STORE>, usize)> {
     unsafe {
         macro_rules! check_bounds {
             ($required:expr) => {
@@ -154,7 +291,7 @@ pub fn read_owned<SOURCE: ByteOrder, STORE: ByteOrder>(source: &[u8]) -> Result<
 
         if tag_id == 0 {
             cold_path();
-            return Ok(OwnedValue::End);
+            return Ok((OwnedValue::End, 1));
         }
 
         check_bounds!(1 + 2);
@@ -163,30 +300,355 @@ pub fn read_owned<SOURCE: ByteOrder, STORE: ByteOrder>(source: &[u8]) -> Result<
         check_bounds!(1 + 2 + name_len as usize);
         current_pos = current_pos.add(2 + name_len as usize);
 
+        if tag_id > 12 {
+            cold_path();
+            return Err(Error::InvalidTagType(tag_id).at(0));
+        }
+
         let value = if TypeId::of::<SOURCE>() == TypeId::of::<STORE>() {
-            let result = read_unsafe::<SOURCE>(tag_id, &mut current_pos, end_pos)?;
-            Ok(std::mem::transmute::<OwnedValue<SOURCE>, OwnedValue<STORE>>(result))
+            read_unsafe::<SOURCE>(tag_id, &mut current_pos, end_pos)
+                .map(|result| core::mem::transmute::<OwnedValue<SOURCE>, OwnedValue<STORE>>(result))
         } else {
             read_unsafe_fallback::<SOURCE, STORE>(tag_id, &mut current_pos, end_pos)
-        }?;
-
-        if current_pos < end_pos {
-            cold_path();
-            return Err(Error::TrailingData(
-                end_pos.byte_offset_from_unsigned(current_pos),
-            ));
         }
+        .map_err(|e| match e {
+            // the reader leaves the cursor on the offending tag byte
+            Error::InvalidTagType(_) => {
+                e.at(current_pos.byte_offset_from_unsigned(source.as_ptr()))
+            }
+            e => e,
+        })?;
 
-        Ok(value)
+        Ok((
+            value,
+            current_pos.byte_offset_from_unsigned(source.as_ptr()),
+        ))
+    }
+}
+
+/// Parses every NBT document in `source`, where the documents are stored back
+/// to back with nothing in between.
+///
+/// This is [`iter_owned`] collected into a `Vec`; see there for details.
+///
+/// # Example
+///
+/// ```
+/// use na_nbt::{OwnedValue, read_all_owned};
+/// use zerocopy::byteorder::BigEndian;
+///
+/// // An empty compound followed by an int named "" with value 7
+/// let data = [0x0a, 0x00, 0x00, 0x00, 0x03, 0x00, 0x00, 0x00, 0x00, 0x00, 0x07];
+/// let values: Vec<OwnedValue<BigEndian>> = read_all_owned::<BigEndian, BigEndian>(&data)?;
+/// assert_eq!(values.len(), 2);
+/// assert_eq!(values[1].as_int(), Some(7));
+/// # Ok::<(), na_nbt::Error>(())
+/// ```
+///
+/// # Errors
+///
+/// Returns the first error [`iter_owned`] yields.
+pub fn read_all_owned<SOURCE: ByteOrder, STORE: ByteOrder>(
+    source: &[u8],
+) -> Result<Vec<OwnedValue<STORE>>> {
+    iter_owned::<SOURCE, STORE>(source).collect()
+}
+
+/// Returns an iterator that parses the NBT documents stored back to back in
+/// `source`, one per call to `next`.
+///
+/// Each document is parsed as by [`read_owned`], except that the bytes after
+/// it are not trailing data but the start of the next document. The iterator
+/// ends once `source` is used up, so an empty `source` yields nothing.
+///
+/// If a document fails to parse, that error is yielded and the iterator ends,
+/// as the start of the next document cannot be found. A document cut off by
+/// the end of `source` yields [`Error::EndOfFile`]. Error offsets are counted
+/// from the start of `source`, not of the document.
+pub fn iter_owned<SOURCE: ByteOrder, STORE: ByteOrder>(
+    source: &[u8],
+) -> OwnedDocuments<'_, SOURCE, STORE> {
+    OwnedDocuments {
+        source,
+        pos: 0,
+        _marker: PhantomData,
+    }
+}
+
+/// An iterator over concatenated NBT documents, returned by [`iter_owned`].
+pub struct OwnedDocuments<'a, SOURCE: ByteOrder, STORE: ByteOrder> {
+    source: &'a [u8],
+    pos: usize,
+    _marker: PhantomData<(SOURCE, STORE)>,
+}
+
+impl<SOURCE: ByteOrder, STORE: ByteOrder> Iterator for OwnedDocuments<'_, SOURCE, STORE> {
+    type Item = Result<OwnedValue<STORE>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.pos == self.source.len() {
+            return None;
+        }
+        let start = self.pos;
+        match read_owned_prefix::<SOURCE, STORE>(&self.source[start..]) {
+            Ok((mut value, len)) => {
+                self.pos += len;
+                drop_shadowed_keys(value.to_mut(), &mut KeySet::default());
+                Some(Ok(value))
+            }
+            Err(e) => {
+                cold_path();
+                self.pos = self.source.len();
+                Some(Err(match e {
+                    Error::At { offset, kind } => Error::At {
+                        offset: start + offset,
+                        kind,
+                    },
+                    e => e,
+                }))
+            }
+        }
     }
 }
 
+impl<SOURCE: ByteOrder, STORE: ByteOrder> core::iter::FusedIterator
+    for OwnedDocuments<'_, SOURCE, STORE>
+{
+}
+
+/// Parses NBT from any [`Read`] source into an owned, mutable value.
+///
+/// This is [`read_owned`] for files, sockets and other streams: the input is
+/// parsed incrementally through an internal buffer, so there is no need to
+/// read it into a `Vec` first. Short reads are handled transparently. Pass
+/// `&mut reader` to keep using the reader afterwards.
+///
+/// # Type Parameters
+///
+/// * `SOURCE` - The byte order of the input data
+/// * `STORE` - The byte order for the in-memory representation
+///
+/// # Example
+///
+/// ```
+/// use na_nbt::{read_owned_from_reader, OwnedValue};
+/// use std::io::Cursor;
+/// use zerocopy::byteorder::BigEndian;
+///
+/// let mut cursor = Cursor::new(vec![0x0a, 0x00, 0x00, 0x00]);
+/// let value: OwnedValue<BigEndian> = read_owned_from_reader::<BigEndian, BigEndian>(&mut cursor)?;
+/// assert!(value.is_compound());
+/// # Ok::<(), na_nbt::Error>(())
+/// ```
+///
+/// # Errors
+///
+/// Returns an error if:
+/// - The reader fails or the stream ends early ([`Error::IO`])
+/// - An invalid tag type is encountered ([`Error::InvalidTagType`], wrapped in
+///   [`Error::At`] with the offset of the offending byte)
+/// - Extra data remains after parsing ([`Error::TrailingData`])
+#[cfg(feature = "std")]
 pub fn read_owned_from_reader<SOURCE: ByteOrder, STORE: ByteOrder>(
     reader: impl Read,
 ) -> Result<OwnedValue<STORE>> {
-    unsafe {
-        let mut reader = BufReader::new(reader);
+    read_owned_from_reader_with_limits::<SOURCE, STORE>(reader, ReadLimits::default())
+}
+
+/// Limits applied while reading untrusted input, with
+/// [`read_owned_from_reader_with_limits`] or [`read_owned_with_limits`].
+///
+/// Every length in an NBT document is declared up front, so a few corrupt or
+/// hostile bytes can claim a multi-gigabyte array. Readers never reserve
+/// memory for data that has not arrived yet, but a stream that keeps
+/// supplying it can still grow the tree without bound; `max_bytes` caps that.
+///
+/// Even input of a bounded size can take many times that size in memory:
+/// every list and compound is a separate allocation, so a document made of
+/// tiny nested values costs far more to build than to send. `max_nodes`
+/// bounds that, whatever the nesting.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ReadLimits {
+    /// The most bytes of input to consume, header included.
+    ///
+    /// A declared length that would run past it fails with
+    /// [`Error::ListTooLong`] before any memory is reserved for it. Defaults
+    /// to `usize::MAX`, i.e. no limit.
+    pub max_bytes: usize,
+    /// The most values the document may hold, the root, compound entries and
+    /// list elements included, as counted by
+    /// [`node_count`](crate::ScopedReadableValue::node_count).
+    ///
+    /// Going over it fails with [`Error::TooManyNodes`]. Defaults to
+    /// [`ReadLimits::DEFAULT_MAX_NODES`].
+    pub max_nodes: usize,
+    /// The most bytes all string values together may take.
+    ///
+    /// One string holds at most 65535 bytes, but nothing bounds how many
+    /// there are. Going over it fails with [`Error::StringBytesExceeded`].
+    /// Compound keys are not counted.
+    /// Defaults to `usize::MAX`, i.e. no limit.
+    pub max_string_bytes: usize,
+}
+
+impl ReadLimits {
+    /// The default for [`max_nodes`](Self::max_nodes): 16 777 216, far more
+    /// than any world file holds.
+    pub const DEFAULT_MAX_NODES: usize = 1 << 24;
+
+    /// No limit at all, for the readers that check input they trust.
+    pub(crate) const UNLIMITED: Self = Self {
+        max_bytes: usize::MAX,
+        max_nodes: usize::MAX,
+        max_string_bytes: usize::MAX,
+    };
+}
+
+impl Default for ReadLimits {
+    fn default() -> Self {
+        Self {
+            max_bytes: usize::MAX,
+            max_nodes: Self::DEFAULT_MAX_NODES,
+            max_string_bytes: usize::MAX,
+        }
+    }
+}
+
+/// Parses NBT like [`read_owned`], refusing documents that hold more values
+/// than [`ReadLimits::max_nodes`] or more string bytes than
+/// [`ReadLimits::max_string_bytes`].
+///
+/// The document is walked once to count its values and strings before
+/// anything is built, so an oversized one is rejected without allocating for
+/// it. The walk also
+/// validates list headers as [`read_owned_strict`] does. `max_bytes` is not
+/// consulted, since the input is already in memory.
+///
+/// # Example
+///
+/// ```
+/// use na_nbt::{Error, ReadLimits, read_owned_with_limits};
+/// use zerocopy::byteorder::BigEndian;
+///
+/// // a list of 1000 empty compounds
+/// let mut data = vec![0x09, 0x00, 0x00, 0x0a, 0x00, 0x00, 0x03, 0xe8];
+/// data.extend([0x00; 1000]);
+/// let limits = ReadLimits { max_nodes: 100, ..ReadLimits::default() };
+/// let result = read_owned_with_limits::<BigEndian, BigEndian>(&data, limits);
+/// assert!(matches!(result, Err(Error::TooManyNodes(100))));
+/// ```
+///
+/// # Errors
+///
+/// Returns the same errors as [`read_owned`], plus:
+/// - A list claims more elements than the input can hold
+///   ([`Error::ListTooLong`])
+/// - The strings take more than `max_string_bytes`
+///   ([`Error::StringBytesExceeded`])
+/// - The document holds more than `max_nodes` values
+///   ([`Error::TooManyNodes`])
+pub fn read_owned_with_limits<SOURCE: ByteOrder, STORE: ByteOrder>(
+    source: &[u8],
+    limits: ReadLimits,
+) -> Result<OwnedValue<STORE>> {
+    check_list_headers::<SOURCE>(source, &mut Vec::new(), &limits)?;
+    read_owned::<SOURCE, STORE>(source)
+}
+
+/// Parses NBT like [`read_owned_from_reader`], refusing to read past the
+/// given [`ReadLimits`].
+///
+/// # Example
+///
+/// ```
+/// use na_nbt::{Error, ReadLimits, read_owned_from_reader_with_limits};
+/// use zerocopy::byteorder::BigEndian;
+///
+/// // a byte array claiming 2 GiB of data
+/// let data = [0x07, 0x00, 0x00, 0x80, 0x00, 0x00, 0x00];
+/// let limits = ReadLimits { max_bytes: 1 << 20, ..ReadLimits::default() };
+/// let result = read_owned_from_reader_with_limits::<BigEndian, BigEndian>(&data[..], limits);
+/// assert!(matches!(result, Err(Error::ListTooLong(0x8000_0000))));
+/// ```
+///
+/// # Errors
+///
+/// Returns the same errors as [`read_owned_from_reader`], plus:
+/// - A declared length would run past `max_bytes` ([`Error::ListTooLong`])
+/// - The strings take more than `max_string_bytes`
+///   ([`Error::StringBytesExceeded`])
+/// - The document holds more than `max_nodes` values
+///   ([`Error::TooManyNodes`])
+/// - The document itself runs past `max_bytes` ([`Error::IO`] with
+///   [`ErrorKind::InvalidData`](std::io::ErrorKind::InvalidData))
+#[cfg(feature = "std")]
+pub fn read_owned_from_reader_with_limits<SOURCE: ByteOrder, STORE: ByteOrder>(
+    reader: impl Read,
+    limits: ReadLimits,
+) -> Result<OwnedValue<STORE>> {
+    let mut reader = PositionReader::with_limits(BufReader::new(reader), limits);
+    let value = read_document_from_reader::<SOURCE, STORE>(&mut reader)?;
+
+    let remaining = reader.fill_buf().map_err(Error::IO)?.len();
+    if remaining > 0 {
+        cold_path();
+        return Err(Error::TrailingData(remaining));
+    }
+
+    Ok(value)
+}
+
+/// Parses one NBT document from the front of a [`BufRead`] source, leaving
+/// the reader just past it.
+///
+/// Unlike [`read_owned_from_reader`], this takes the caller's buffered reader
+/// instead of wrapping it in its own buffer, and consumes exactly the bytes
+/// of the document: whatever follows stays in the reader for the next call.
+/// It is the synchronous counterpart of `read_async` (with the `tokio` feature),
+/// for documents embedded in a larger file or stream. The number of bytes
+/// consumed is returned with the value; with a [`Cursor`](std::io::Cursor)
+/// it is also the cursor's new position.
+///
+/// # Example
+///
+/// ```
+/// use na_nbt::read_owned_from_buf_reader;
+/// use std::io::Cursor;
+/// use zerocopy::byteorder::BigEndian;
+///
+/// // two documents back to back
+/// let mut cursor = Cursor::new([0x0a, 0x00, 0x00, 0x00, 0x01, 0x00, 0x00, 0x05]);
+/// let (value, len) = read_owned_from_buf_reader::<BigEndian, BigEndian>(&mut cursor)?;
+/// assert!(value.is_compound());
+/// assert_eq!(len, 4);
+/// assert_eq!(cursor.position(), 4);
+///
+/// let (value, _) = read_owned_from_buf_reader::<BigEndian, BigEndian>(&mut cursor)?;
+/// assert_eq!(value.as_byte(), Some(5));
+/// # Ok::<(), na_nbt::Error>(())
+/// ```
+///
+/// # Errors
+///
+/// Same as [`read_owned_from_reader`], except that data after the document is
+/// not an error. On an error the reader has consumed an unspecified part of
+/// the document.
+#[cfg(feature = "std")]
+pub fn read_owned_from_buf_reader<SOURCE: ByteOrder, STORE: ByteOrder>(
+    reader: &mut impl BufRead,
+) -> Result<(OwnedValue<STORE>, usize)> {
+    let mut reader = PositionReader::with_limits(reader, ReadLimits::default());
+    let value = read_document_from_reader::<SOURCE, STORE>(&mut reader)?;
+    Ok((value, reader.position()))
+}
 
+/// Reads the root tag, its name and its payload, stopping right after the
+/// document. A repeated key keeps its last entry, as with [`read_owned`].
+#[cfg(feature = "std")]
+fn read_document_from_reader<SOURCE: ByteOrder, STORE: ByteOrder>(
+    reader: &mut PositionReader<impl BufRead>,
+) -> Result<OwnedValue<STORE>> {
+    unsafe {
         let mut tag_id = [0u8];
         reader.read_exact(&mut tag_id).map_err(Error::IO)?;
         let tag_id = tag_id[0];
@@ -207,23 +669,348 @@ pub fn read_owned_from_reader<SOURCE: ByteOrder, STORE: ByteOrder>(
                     cold_path();
                     return Err(Error::EndOfFile);
                 }
-                let read = std::cmp::min(buf_len, name_len - skipped);
+                let read = core::cmp::min(buf_len, name_len - skipped);
                 reader.consume(read);
                 skipped += read;
             }
         }
 
-        let value = read_unsafe_from_reader::<SOURCE, STORE>(tag_id, &mut reader)?;
+        if tag_id > 12 {
+            cold_path();
+            return Err(Error::InvalidTagType(tag_id).at(0));
+        }
 
-        {
-            let remaining = reader.fill_buf().map_err(Error::IO)?.len();
-            if remaining > 0 {
+        reader.add_nodes(1)?;
+        let mut value = read_unsafe_from_reader::<SOURCE, STORE>(tag_id, reader)?;
+        drop_shadowed_keys(value.to_mut(), &mut KeySet::default());
+        Ok(value)
+    }
+}
+
+/// Parses NBT like [`read_owned`], but rejects compounds with duplicate keys
+/// and list headers that cannot be right.
+///
+/// [`read_owned`] keeps only the last entry of a repeated key, while a
+/// borrowed reader sees every entry and looks up the first. When the input is
+/// untrusted this can let two consumers disagree about a value, so this
+/// function fails with
+/// [`Error::DuplicateKey`] instead. Keys are compared by their encoded bytes,
+/// and every compound in the tree is checked.
+///
+/// Every list header is also validated before anything is parsed: the
+/// element tag must be a known tag, and the element count must fit in the
+/// remaining input given the smallest encoding of one element. A count that
+/// cannot fit fails with [`Error::ListTooLong`] rather than surfacing later as
+/// [`Error::EndOfFile`].
+///
+/// # Example
+///
+/// ```
+/// use na_nbt::{read_owned_strict, OwnedValue};
+/// use zerocopy::byteorder::BigEndian;
+///
+/// let data = [0x0a, 0x00, 0x00, 0x01, 0x00, 0x01, b'x', 0x05, 0x00];
+/// let value: OwnedValue<BigEndian> = read_owned_strict::<BigEndian, BigEndian>(&data)?;
+/// assert_eq!(value.get("x").and_then(|x| x.as_byte()), Some(5));
+/// # Ok::<(), na_nbt::Error>(())
+/// ```
+///
+/// # Errors
+///
+/// Returns the same errors as [`read_owned`], plus:
+/// - A list claims more elements than the input can hold
+///   ([`Error::ListTooLong`])
+/// - A compound repeats a key ([`Error::DuplicateKey`] with the first
+///   repeated key found)
+pub fn read_owned_strict<SOURCE: ByteOrder, STORE: ByteOrder>(
+    source: &[u8],
+) -> Result<OwnedValue<STORE>> {
+    check_list_headers::<SOURCE>(source, &mut Vec::new(), &ReadLimits::UNLIMITED)?;
+    let value = read_owned_as_encoded::<SOURCE, STORE>(source)?;
+    check_duplicate_keys(&value, &mut KeySet::default())?;
+    Ok(value)
+}
+
+/// Parses NBT like [`read_owned`], but stops at a tag type it does not know
+/// instead of failing, returning what was read up to there.
+///
+/// This is meant for data written by a newer format with tags this crate
+/// does not know yet. The length of such a tag's payload is unknown, so
+/// nothing after it can be read: the document is cut off at that tag and
+/// every container still open around it is closed where it stands. The
+/// compound holding the unknown entry keeps the entries before it, and so
+/// does each compound around it; a list around it keeps the elements up to
+/// and including the one that was cut short. A list whose element type is
+/// unknown is read as empty. The returned flag is `true` if anything was cut
+/// off, and `false` if the whole input was read.
+///
+/// The input is first read exactly as by [`read_owned`], and only walked
+/// again looking for an unknown tag if that fails, so valid input costs no
+/// more to read than with [`read_owned`].
+///
+/// # Example
+///
+/// ```
+/// use na_nbt::{OwnedValue, read_owned_lenient};
+/// use zerocopy::byteorder::BigEndian;
+///
+/// let data = [
+///     0x0a, 0x00, 0x00,
+///     0x01, 0x00, 0x01, b'a', 0x05, // Byte "a" = 5
+///     0x63, 0x00, 0x01, b'b', 0xde, 0xad, // tag 99, unknown
+///     0x00,
+/// ];
+/// let (value, truncated): (OwnedValue<BigEndian>, bool) =
+///     read_owned_lenient::<BigEndian, BigEndian>(&data)?;
+/// assert!(truncated);
+/// assert_eq!(value.get("a").and_then(|a| a.as_byte()), Some(5));
+/// assert!(value.get("b").is_none());
+/// # Ok::<(), na_nbt::Error>(())
+/// ```
+///
+/// # Errors
+///
+/// Returns the same errors as [`read_owned`] for anything before the first
+/// unknown tag, and [`Error::InvalidTagType`] if the root itself has an
+/// unknown tag type, since then there is nothing to return.
+pub fn read_owned_lenient<SOURCE: ByteOrder, STORE: ByteOrder>(
+    source: &[u8],
+) -> Result<(OwnedValue<STORE>, bool)> {
+    match read_owned::<SOURCE, STORE>(source) {
+        Ok(value) => Ok((value, false)),
+        Err(e) => {
+            cold_path();
+            // the reader may report a bounds error before it looks at the
+            // tag, so any failure is checked for an unknown tag before it
+            match cut_at_unknown_tag::<SOURCE>(source) {
+                Ok(Some(cut)) => Ok((read_owned::<SOURCE, STORE>(&cut)?, true)),
+                Ok(None) | Err(_) => Err(e),
+            }
+        }
+    }
+}
+
+/// Validates list headers and applies [`ReadLimits`] during a [`Walk`].
+struct HeaderChecks<'l> {
+    limits: &'l ReadLimits,
+    nodes: usize,
+    string_bytes: usize,
+}
+
+impl Hooks for HeaderChecks<'_> {
+    #[inline]
+    fn values(&mut self, count: usize) -> Result<()> {
+        self.nodes = self.nodes.saturating_add(count);
+        if self.nodes > self.limits.max_nodes {
+            cold_path();
+            return Err(Error::TooManyNodes(self.limits.max_nodes));
+        }
+        Ok(())
+    }
+
+    #[inline]
+    fn string(&mut self, len: usize) -> Result<()> {
+        self.string_bytes = self.string_bytes.saturating_add(len);
+        if self.string_bytes > self.limits.max_string_bytes {
+            cold_path();
+            return Err(Error::StringBytesExceeded(self.limits.max_string_bytes));
+        }
+        Ok(())
+    }
+
+    #[inline]
+    fn list(&mut self, tag_id: u8, len: usize, rest: usize) -> Result<()> {
+        /// The fewest bytes one payload of each tag can take.
+        const MIN_SIZE: [usize; 13] = [0, 1, 2, 4, 8, 4, 8, 4, 2, 5, 1, 4, 4];
+
+        if len.saturating_mul(MIN_SIZE[tag_id as usize]) > rest {
+            cold_path();
+            return Err(Error::ListTooLong(len));
+        }
+        Ok(())
+    }
+}
+
+/// Walks the encoded document and validates every list header, without
+/// building anything, failing once the values or string bytes seen go past
+/// `limits`.
+///
+/// `stack` is scratch space, passed in so that it can be reused.
+fn check_list_headers<O: ByteOrder>(
+    source: &[u8],
+    stack: &mut Vec<Frame>,
+    limits: &ReadLimits,
+) -> Result<()> {
+    let mut checks = HeaderChecks {
+        limits,
+        nodes: 0,
+        string_bytes: 0,
+    };
+    Walk::document(stack, 0).run_to_end::<O>(source, &mut checks)?;
+    Ok(())
+}
+
+/// Cuts the document off at the first unknown tag type during a [`Walk`].
+struct Cut<'s, O: ByteOrder> {
+    source: &'s [u8],
+    out: Option<Vec<u8>>,
+    _marker: PhantomData<O>,
+}
+
+impl<O: ByteOrder> Hooks for Cut<'_, O> {
+    fn unknown_tag(&mut self, tag_id: u8, pos: usize, place: Place, stack: &[Frame]) -> Result<()> {
+        // an unknown list element type leaves an empty list of `End`
+        let tail: &[u8] = match place {
+            Place::Root => {
                 cold_path();
-                return Err(Error::TrailingData(remaining));
+                return Err(Error::InvalidTagType(tag_id).at(pos));
+            }
+            Place::Entry => &[],
+            Place::Element => &[0; 5],
+        };
+
+        let mut out = Vec::with_capacity(pos + tail.len() + stack.len());
+        out.extend_from_slice(&self.source[..pos]);
+        out.extend_from_slice(tail);
+        for frame in stack.iter().rev() {
+            match *frame {
+                Frame::Compound => out.push(Tag::End as u8),
+                Frame::List {
+                    header,
+                    len,
+                    remaining,
+                    ..
+                } => out[header + 1..header + 5]
+                    .copy_from_slice(&byteorder::U32::<O>::new(len - remaining).to_bytes()),
             }
         }
+        self.out = Some(out);
+        Ok(())
+    }
+}
 
-        Ok(value)
+/// Walks the encoded document up to the first unknown tag type and returns a
+/// copy cut off there, with every open container closed, or `None` if there
+/// is no unknown tag. Fails if the input is broken before such a tag.
+fn cut_at_unknown_tag<O: ByteOrder>(source: &[u8]) -> Result<Option<Vec<u8>>> {
+    let mut cut = Cut::<O> {
+        source,
+        out: None,
+        _marker: PhantomData,
+    };
+    Walk::document(&mut Vec::new(), 0).run_to_end::<O>(source, &mut cut)?;
+    Ok(cut.out)
+}
+
+/// Scratch space for [`check_duplicate_keys`], kept by [`Parser`] between
+/// calls.
+#[derive(Default)]
+pub(crate) struct KeySet {
+    /// The keys of the compound being checked, back to back.
+    bytes: Vec<u8>,
+    /// Where each key sits in `bytes`, and its index in the compound.
+    keys: Vec<(Range<usize>, usize)>,
+}
+
+impl KeySet {
+    /// Collects the keys of `compound` and sorts them, so that equal keys end
+    /// up next to each other, in their order in the compound.
+    fn sort<'doc>(&mut self, compound: &impl ScopedReadableCompound<'doc>) {
+        self.bytes.clear();
+        self.keys.clear();
+        for (index, (key, _)) in compound.iter_scoped().enumerate() {
+            let start = self.bytes.len();
+            self.bytes.extend_from_slice(key.raw_bytes());
+            self.keys.push((start..self.bytes.len(), index));
+        }
+
+        let bytes = &self.bytes;
+        self.keys.sort_unstable_by(|(a, i), (b, j)| {
+            bytes[a.clone()].cmp(&bytes[b.clone()]).then(i.cmp(j))
+        });
+    }
+
+    /// Returns pairs of neighbouring sorted keys that are equal.
+    fn repeats(&self) -> impl Iterator<Item = &[(Range<usize>, usize)]> {
+        self.keys
+            .windows(2)
+            .filter(|pair| self.bytes[pair[0].0.clone()] == self.bytes[pair[1].0.clone()])
+    }
+
+    /// Returns the index of the first entry of `compound` whose key repeats
+    /// an earlier one.
+    fn first_repeat<'doc>(
+        &mut self,
+        compound: &impl ScopedReadableCompound<'doc>,
+    ) -> Option<usize> {
+        self.sort(compound);
+        self.repeats().map(|pair| pair[1].1).min()
+    }
+
+    /// Returns the indices of the entries of `compound` whose key comes again
+    /// later, in descending order.
+    fn shadowed<'doc>(&mut self, compound: &impl ScopedReadableCompound<'doc>) -> Vec<usize> {
+        self.sort(compound);
+        let mut shadowed: Vec<usize> = self.repeats().map(|pair| pair[0].1).collect();
+        shadowed.sort_unstable_by(|a, b| b.cmp(a));
+        shadowed
+    }
+
+    pub(crate) fn clear(&mut self) {
+        self.bytes.clear();
+        self.keys.clear();
+    }
+}
+
+/// Fails with [`Error::DuplicateKey`] on the first repeated key in a
+/// depth-first walk of `value`.
+///
+/// `keys` is scratch space, passed in so that it can be reused.
+fn check_duplicate_keys<'doc>(
+    value: &impl ScopedReadableValue<'doc>,
+    keys: &mut KeySet,
+) -> Result<()> {
+    value.visit_scoped(|value| match value {
+        ValueScoped::List(list) => list
+            .into_iter()
+            .try_for_each(|item| check_duplicate_keys(&item, keys)),
+        ValueScoped::Compound(compound) => {
+            let repeat = keys.first_repeat(&compound);
+            for (index, (key, item)) in compound.into_iter().enumerate() {
+                if repeat == Some(index) {
+                    cold_path();
+                    return Err(Error::DuplicateKey(key.decode().into_owned()));
+                }
+                check_duplicate_keys(&item, keys)?;
+            }
+            Ok(())
+        }
+        _ => Ok(()),
+    })
+}
+
+/// Removes every compound entry whose key comes again later in the same
+/// compound, in a depth-first walk of `value`, so that the last entry wins.
+///
+/// `keys` is scratch space, passed in so that it can be reused.
+fn drop_shadowed_keys<O: ByteOrder>(value: MutableValue<'_, O>, keys: &mut KeySet) {
+    match value {
+        MutableValue::List(list) => {
+            if matches!(list.tag_id(), Tag::List | Tag::Compound) {
+                list.into_iter()
+                    .for_each(|item| drop_shadowed_keys(item, keys));
+            }
+        }
+        MutableValue::Compound(mut compound) => {
+            for index in keys.shadowed(&compound) {
+                cold_path();
+                compound_remove_at::<O>(&mut compound.data, index);
+            }
+            compound
+                .into_iter()
+                .for_each(|(_, item)| drop_shadowed_keys(item, keys));
+        }
+        _ => {}
     }
 }
 
@@ -324,8 +1111,10 @@ pub(crate) fn write_owned_to_vec<'a, SOURCE: ByteOrder, TARGET: ByteOrder>(
                 Ok(buf)
             }
             ValueScoped::List(value) => {
+                // size the buffer exactly so the tree is written with a single allocation
                 let payload = value.data;
-                let mut buf = Vec::<u8>::with_capacity(1 + 2 + 4 + 128);
+                let len = list_size::<SOURCE>(payload);
+                let mut buf = Vec::<u8>::with_capacity(1 + 2 + len);
                 let buf_ptr = buf.as_mut_ptr();
                 ptr::write(buf_ptr.cast(), [Tag::List as u8, 0u8, 0u8]);
                 buf.set_len(1 + 2);
@@ -337,8 +1126,10 @@ pub(crate) fn write_owned_to_vec<'a, SOURCE: ByteOrder, TARGET: ByteOrder>(
                 Ok(buf)
             }
             ValueScoped::Compound(value) => {
+                // size the buffer exactly so the tree is written with a single allocation
                 let payload = value.data;
-                let mut buf = Vec::<u8>::with_capacity(1 + 2 + 4 + 128);
+                let len = compound_size::<SOURCE>(payload);
+                let mut buf = Vec::<u8>::with_capacity(1 + 2 + len);
                 let buf_ptr = buf.as_mut_ptr();
                 ptr::write(buf_ptr.cast(), [Tag::Compound as u8, 0u8, 0u8]);
                 buf.set_len(1 + 2);
@@ -371,13 +1162,13 @@ pub(crate) fn write_owned_to_vec<'a, SOURCE: ByteOrder, TARGET: ByteOrder>(
                         buf_ptr = buf_ptr.add(4);
                     }
                 }
-                buf.set_len(1 + 2 + 4 + len);
+                buf.set_len(1 + 2 + 4 + 4 * len);
                 Ok(buf)
             }
             ValueScoped::LongArray(value) => {
                 let payload = value.as_ptr().cast::<u8>();
                 let len = value.len();
-                let mut buf = Vec::<u8>::with_capacity(1 + 2 + 8 + 8 * len);
+                let mut buf = Vec::<u8>::with_capacity(1 + 2 + 4 + 8 * len);
                 let mut buf_ptr = buf.as_mut_ptr();
                 ptr::write(buf_ptr.cast(), [Tag::LongArray as u8, 0u8, 0u8]);
                 ptr::write(
@@ -396,13 +1187,14 @@ pub(crate) fn write_owned_to_vec<'a, SOURCE: ByteOrder, TARGET: ByteOrder>(
                         buf_ptr = buf_ptr.add(8);
                     }
                 }
-                buf.set_len(1 + 2 + 4 + len);
+                buf.set_len(1 + 2 + 4 + 8 * len);
                 Ok(buf)
             }
         }
     }
 }
 
+#[cfg(feature = "std")]
 pub(crate) fn write_owned_to_writer<'a, SOURCE: ByteOrder, TARGET: ByteOrder>(
     value: ValueScoped<'a, Config<SOURCE>>,
     mut writer: impl Write,
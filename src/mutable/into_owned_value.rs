@@ -1,3 +1,4 @@
+use alloc::{string::String, vec::Vec};
 use zerocopy::byteorder;
 
 use crate::{
@@ -0,0 +1,72 @@
+use zerocopy::byteorder;
+
+use crate::{ByteOrder, OwnedCompound, OwnedList, OwnedValue, StringViewOwn, VecViewOwn};
+
+/// An [`OwnedValue`] taken apart, as passed to
+/// [`into_visit`](OwnedValue::into_visit).
+///
+/// This is the consuming counterpart of [`ValueScoped`](crate::ValueScoped):
+/// numbers are decoded to native values, and everything else is handed over
+/// by value, so it can be moved into another structure without a copy.
+pub enum IntoVisit<O: ByteOrder> {
+    End,
+    Byte(i8),
+    Short(i16),
+    Int(i32),
+    Long(i64),
+    Float(f32),
+    Double(f64),
+    ByteArray(VecViewOwn<i8>),
+    String(StringViewOwn),
+    List(OwnedList<O>),
+    Compound(OwnedCompound<O>),
+    IntArray(VecViewOwn<byteorder::I32<O>>),
+    LongArray(VecViewOwn<byteorder::I64<O>>),
+}
+
+impl<O: ByteOrder> OwnedValue<O> {
+    /// Consumes the value and passes its contents to `match_fn`.
+    ///
+    /// Where [`visit`](crate::ReadableValue::visit) lends the value out, this
+    /// gives up ownership of it: a list or compound matched here is the one
+    /// that was in the value, not a copy.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use na_nbt::{IntoVisit, OwnedCompound, OwnedList, OwnedValue};
+    /// use zerocopy::byteorder::BigEndian;
+    ///
+    /// let mut inner: OwnedList<BigEndian> = OwnedList::default();
+    /// inner.push("a");
+    /// let mut compound: OwnedCompound<BigEndian> = OwnedCompound::default();
+    /// compound.insert("items", inner);
+    ///
+    /// let items = OwnedValue::Compound(compound).into_visit(|value| match value {
+    ///     IntoVisit::Compound(mut compound) => compound.remove("items"),
+    ///     _ => None,
+    /// });
+    /// let items = items.unwrap().into_visit(|value| match value {
+    ///     IntoVisit::List(list) => Some(list),
+    ///     _ => None,
+    /// });
+    /// assert_eq!(items.unwrap().len(), 1);
+    /// ```
+    pub fn into_visit<R>(self, match_fn: impl FnOnce(IntoVisit<O>) -> R) -> R {
+        match self {
+            OwnedValue::End => match_fn(IntoVisit::End),
+            OwnedValue::Byte(value) => match_fn(IntoVisit::Byte(value)),
+            OwnedValue::Short(value) => match_fn(IntoVisit::Short(value.get())),
+            OwnedValue::Int(value) => match_fn(IntoVisit::Int(value.get())),
+            OwnedValue::Long(value) => match_fn(IntoVisit::Long(value.get())),
+            OwnedValue::Float(value) => match_fn(IntoVisit::Float(value.get())),
+            OwnedValue::Double(value) => match_fn(IntoVisit::Double(value.get())),
+            OwnedValue::ByteArray(value) => match_fn(IntoVisit::ByteArray(value)),
+            OwnedValue::String(value) => match_fn(IntoVisit::String(value)),
+            OwnedValue::List(value) => match_fn(IntoVisit::List(value)),
+            OwnedValue::Compound(value) => match_fn(IntoVisit::Compound(value)),
+            OwnedValue::IntArray(value) => match_fn(IntoVisit::IntArray(value)),
+            OwnedValue::LongArray(value) => match_fn(IntoVisit::LongArray(value)),
+        }
+    }
+}
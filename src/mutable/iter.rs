@@ -1,10 +1,14 @@
-use std::{hint::unreachable_unchecked, marker::PhantomData, ptr, slice};
+use alloc::{
+    borrow::Cow,
+    string::{String, ToString},
+};
+use core::{hint::unreachable_unchecked, marker::PhantomData, ptr, slice};
 
 use zerocopy::byteorder;
 
 use crate::{
     ByteOrder, EMPTY_COMPOUND, ImmutableString, ImmutableValue, MutableValue, OwnedCompound,
-    OwnedList, OwnedValue, Tag, cold_path,
+    OwnedList, OwnedValue, Tag, cold_path, decode_mutf8,
     mutable::util::tag_size,
     view::{StringViewOwn, VecViewOwn},
 };
@@ -173,6 +177,39 @@ impl<'s, O: ByteOrder> Iterator for MutableCompoundIter<'s, O> {
     }
 }
 
+/// An iterator over the entries of a compound with decoded keys, returned by
+/// `iter_str`.
+#[derive(Clone)]
+pub struct ImmutableCompoundIterStr<'s, O: ByteOrder> {
+    pub(crate) iter: ImmutableCompoundIter<'s, O>,
+}
+
+impl<'s, O: ByteOrder> Iterator for ImmutableCompoundIterStr<'s, O> {
+    type Item = (Cow<'s, str>, ImmutableValue<'s, O>);
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        let (key, value) = self.iter.next()?;
+        Some((decode_mutf8(key.data), value))
+    }
+}
+
+/// An iterator over the entries of a compound with decoded keys and mutable
+/// values, returned by `iter_mut_str`.
+pub struct MutableCompoundIterStr<'s, O: ByteOrder> {
+    pub(crate) iter: MutableCompoundIter<'s, O>,
+}
+
+impl<'s, O: ByteOrder> Iterator for MutableCompoundIterStr<'s, O> {
+    type Item = (Cow<'s, str>, MutableValue<'s, O>);
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        let (key, value) = self.iter.next()?;
+        Some((decode_mutf8(key.data), value))
+    }
+}
+
 pub struct OwnedListIter<O: ByteOrder> {
     pub(crate) tag_id: Tag,
     pub(crate) remaining: u32,
@@ -288,9 +325,7 @@ impl<O: ByteOrder> Iterator for OwnedCompoundIter<O> {
             let name_len = byteorder::U16::<O>::from_bytes(*self.ptr.cast()).get();
             self.ptr = self.ptr.add(2);
 
-            let name =
-                simd_cesu8::mutf8::decode_lossy(slice::from_raw_parts(self.ptr, name_len as usize))
-                    .to_string();
+            let name = decode_mutf8(slice::from_raw_parts(self.ptr, name_len as usize)).to_string();
             self.ptr = self.ptr.add(name_len as usize);
 
             let value = OwnedValue::read(tag_id, self.ptr);
@@ -0,0 +1,164 @@
+use alloc::vec::Vec;
+#[cfg(feature = "std")]
+use std::io::{self, Read};
+
+use crate::{
+    ByteOrder, OwnedValue, ReadLimits, Result,
+    mutable::{KeySet, check_duplicate_keys, check_list_headers, read_owned_as_encoded},
+    read_owned,
+    walk::Frame,
+};
+#[cfg(feature = "std")]
+use crate::{Error, cold_path};
+
+/// A reusable parser for reading many documents in a row.
+///
+/// The free functions such as [`read_owned`] start from nothing on every
+/// call. A `Parser` keeps its scratch space between calls instead: the input
+/// buffer of [`read_owned_from_reader`](Self::read_owned_from_reader), the
+/// traversal stack that validates list headers and the key set that
+/// [`read_owned_strict`](Self::read_owned_strict) checks compounds with. In a
+/// hot loop over many documents this saves an allocation or more per call.
+/// The output is the same as that of the free functions.
+///
+/// Plain [`read_owned`](Self::read_owned) builds the value as it goes and has
+/// no scratch space to keep, so it costs the same as the free function.
+///
+/// The retained capacity grows to fit the largest document seen; call
+/// [`clear`](Self::clear) to drop the retained contents and
+/// [`reset`](Self::reset) to release the capacity too.
+///
+/// # Example
+///
+/// ```
+/// use na_nbt::{OwnedValue, Parser};
+/// use zerocopy::byteorder::BigEndian;
+///
+/// let documents: [&[u8]; 2] = [&[0x0a, 0x00, 0x00, 0x00], &[0x01, 0x00, 0x00, 0x05]];
+///
+/// let mut parser = Parser::new();
+/// for data in documents {
+///     let value: OwnedValue<BigEndian> = parser.read_owned_strict::<BigEndian, BigEndian>(data)?;
+///     # let _ = value;
+/// }
+/// # Ok::<(), na_nbt::Error>(())
+/// ```
+#[derive(Default)]
+pub struct Parser {
+    #[cfg(feature = "std")]
+    buf: Vec<u8>,
+    stack: Vec<Frame>,
+    keys: KeySet,
+    #[cfg(feature = "std")]
+    limits: ReadLimits,
+}
+
+impl Parser {
+    /// Creates a parser with no retained capacity.
+    #[inline]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates a parser that applies `limits` when reading from a stream.
+    #[cfg(feature = "std")]
+    #[inline]
+    pub fn with_limits(limits: ReadLimits) -> Self {
+        Self {
+            limits,
+            ..Self::default()
+        }
+    }
+
+    /// Parses a document like [`read_owned`].
+    ///
+    /// Parsing from a slice builds the value directly and needs no scratch
+    /// space, so this is exactly the free function; it is here so that code
+    /// holding a parser can use it for every kind of input.
+    #[inline]
+    pub fn read_owned<SOURCE: ByteOrder, STORE: ByteOrder>(
+        &mut self,
+        data: &[u8],
+    ) -> Result<OwnedValue<STORE>> {
+        read_owned::<SOURCE, STORE>(data)
+    }
+
+    /// Parses a document like [`read_owned_strict`](crate::read_owned_strict),
+    /// reusing the stack used to validate list headers and the set used to
+    /// find repeated keys.
+    ///
+    /// # Errors
+    ///
+    /// Same as [`read_owned_strict`](crate::read_owned_strict).
+    pub fn read_owned_strict<SOURCE: ByteOrder, STORE: ByteOrder>(
+        &mut self,
+        data: &[u8],
+    ) -> Result<OwnedValue<STORE>> {
+        check_list_headers::<SOURCE>(data, &mut self.stack, &ReadLimits::UNLIMITED)?;
+        let value = read_owned_as_encoded::<SOURCE, STORE>(data)?;
+        check_duplicate_keys(&value, &mut self.keys)?;
+        Ok(value)
+    }
+
+    /// Reads `reader` to its end into the parser's buffer and parses the
+    /// result like [`read_owned`].
+    ///
+    /// Unlike [`read_owned_from_reader`](crate::read_owned_from_reader), the
+    /// whole stream is consumed, so it suits a file or a length-delimited
+    /// message rather than a socket carrying several documents. The buffer is
+    /// kept for the next call.
+    ///
+    /// # Errors
+    ///
+    /// Same as [`read_owned`], plus:
+    /// - The reader fails ([`Error::IO`])
+    /// - The stream is longer than [`ReadLimits::max_bytes`] ([`Error::IO`]
+    ///   with [`ErrorKind::InvalidData`](std::io::ErrorKind::InvalidData))
+    /// - A list claims more elements than the input can hold
+    ///   ([`Error::ListTooLong`])
+    /// - The strings take more than [`ReadLimits::max_string_bytes`]
+    ///   ([`Error::StringBytesExceeded`])
+    /// - The document holds more than [`ReadLimits::max_nodes`] values
+    ///   ([`Error::TooManyNodes`])
+    #[cfg(feature = "std")]
+    pub fn read_owned_from_reader<SOURCE: ByteOrder, STORE: ByteOrder>(
+        &mut self,
+        reader: impl Read,
+    ) -> Result<OwnedValue<STORE>> {
+        self.buf.clear();
+        // one byte past the limit tells an exact fit from an overrun
+        let max_bytes = (self.limits.max_bytes as u64).saturating_add(1);
+        reader
+            .take(max_bytes)
+            .read_to_end(&mut self.buf)
+            .map_err(Error::IO)?;
+        if self.buf.len() > self.limits.max_bytes {
+            cold_path();
+            return Err(Error::IO(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "input exceeds ReadLimits::max_bytes",
+            )));
+        }
+        check_list_headers::<SOURCE>(&self.buf, &mut self.stack, &self.limits)?;
+        read_owned::<SOURCE, STORE>(&self.buf)
+    }
+
+    /// Drops the retained contents, such as the bytes last read from a
+    /// stream, keeping the capacity for the next call.
+    pub fn clear(&mut self) {
+        #[cfg(feature = "std")]
+        self.buf.clear();
+        self.stack.clear();
+        self.keys.clear();
+    }
+
+    /// Releases all retained capacity.
+    pub fn reset(&mut self) {
+        #[cfg(feature = "std")]
+        {
+            self.buf = Vec::new();
+        }
+        self.stack = Vec::new();
+        self.keys = KeySet::default();
+    }
+}
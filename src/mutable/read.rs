@@ -1,16 +1,132 @@
-use std::{
-    any::TypeId, hint::assert_unchecked, io::BufRead, marker::PhantomData, mem::ManuallyDrop, ptr,
-    slice,
-};
+use alloc::vec::Vec;
+#[cfg(feature = "std")]
+use core::any::TypeId;
+use core::{hint::assert_unchecked, marker::PhantomData, mem::ManuallyDrop, ptr, slice};
+#[cfg(feature = "std")]
+use std::io::{self, BufRead, Read};
 
 use zerocopy::byteorder;
 
+#[cfg(feature = "std")]
+use crate::ReadLimits;
 use crate::{
     ByteOrder, Error, OwnedCompound, OwnedList, OwnedValue, Result, Tag, cold_path,
     mutable::util::{SIZE_DYN, tag_size},
     view::{StringViewOwn, VecViewOwn},
 };
 
+/// A [`BufRead`] adapter that counts the bytes consumed so far, so that
+/// errors can report where in the stream they occurred.
+///
+/// It also enforces [`ReadLimits`]: declared lengths are checked against the
+/// bytes left before anything is reserved for them, and values and string
+/// bytes are counted as they are read.
+#[cfg(feature = "std")]
+pub struct PositionReader<R> {
+    inner: R,
+    position: usize,
+    max_bytes: usize,
+    max_nodes: usize,
+    nodes: usize,
+    max_string_bytes: usize,
+    string_bytes: usize,
+}
+
+#[cfg(feature = "std")]
+impl<R> PositionReader<R> {
+    #[inline]
+    pub fn with_limits(inner: R, limits: ReadLimits) -> Self {
+        Self {
+            inner,
+            position: 0,
+            max_bytes: limits.max_bytes,
+            max_nodes: limits.max_nodes,
+            nodes: 0,
+            max_string_bytes: limits.max_string_bytes,
+            string_bytes: 0,
+        }
+    }
+
+    #[inline]
+    pub fn position(&self) -> usize {
+        self.position
+    }
+
+    /// Fails with [`Error::ListTooLong`] if `bytes` more bytes would go past
+    /// the limit, `len` being the declared length they were computed from.
+    #[inline]
+    pub fn check_len(&self, len: usize, bytes: usize) -> Result<()> {
+        if bytes > self.max_bytes.saturating_sub(self.position) {
+            cold_path();
+            return Err(Error::ListTooLong(len));
+        }
+        Ok(())
+    }
+
+    /// Counts `count` more values, failing with [`Error::TooManyNodes`] once
+    /// there are more than the limit allows.
+    #[inline]
+    pub fn add_nodes(&mut self, count: usize) -> Result<()> {
+        self.nodes = self.nodes.saturating_add(count);
+        if self.nodes > self.max_nodes {
+            cold_path();
+            return Err(Error::TooManyNodes(self.max_nodes));
+        }
+        Ok(())
+    }
+
+    /// Counts a string of `len` bytes, failing with
+    /// [`Error::StringBytesExceeded`] once the strings read so far take more
+    /// than the limit allows.
+    #[inline]
+    pub fn add_string_bytes(&mut self, len: usize) -> Result<()> {
+        self.string_bytes = self.string_bytes.saturating_add(len);
+        if self.string_bytes > self.max_string_bytes {
+            cold_path();
+            return Err(Error::StringBytesExceeded(self.max_string_bytes));
+        }
+        Ok(())
+    }
+}
+
+#[cfg(feature = "std")]
+impl<R: Read> Read for PositionReader<R> {
+    #[inline]
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let read = self.inner.read(buf)?;
+        self.position += read;
+        Ok(read)
+    }
+
+    #[inline]
+    fn read_exact(&mut self, buf: &mut [u8]) -> io::Result<()> {
+        if buf.len() > self.max_bytes.saturating_sub(self.position) {
+            cold_path();
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "input exceeds ReadLimits::max_bytes",
+            ));
+        }
+        self.inner.read_exact(buf)?;
+        self.position += buf.len();
+        Ok(())
+    }
+}
+
+#[cfg(feature = "std")]
+impl<R: BufRead> BufRead for PositionReader<R> {
+    #[inline]
+    fn fill_buf(&mut self) -> io::Result<&[u8]> {
+        self.inner.fill_buf()
+    }
+
+    #[inline]
+    fn consume(&mut self, amt: usize) {
+        self.inner.consume(amt);
+        self.position += amt;
+    }
+}
+
 struct ListBuildGuard<O: ByteOrder> {
     data: ManuallyDrop<Vec<u8>>,
     tag_id: u8,
@@ -39,7 +155,7 @@ impl<O: ByteOrder> ListBuildGuard<O> {
     unsafe fn finalize(mut self) -> Vec<u8> {
         unsafe {
             let data = ManuallyDrop::take(&mut self.data);
-            std::mem::forget(self);
+            core::mem::forget(self);
             data
         }
     }
@@ -140,7 +256,7 @@ impl<O: ByteOrder> CompoundBuildGuard<O> {
     unsafe fn finalize(mut self) -> Vec<u8> {
         unsafe {
             let data = ManuallyDrop::take(&mut self.data);
-            std::mem::forget(self);
+            core::mem::forget(self);
             data
         }
     }
@@ -307,7 +423,11 @@ unsafe fn read_compound<O: ByteOrder>(
                         *current_pos = current_pos.add(arr_len * 8);
                         VecViewOwn::from(value).write(write_ptr);
                     }
-                    _ => return Err(Error::InvalidTagType(tag_id)),
+                    _ => {
+                        // leave the cursor on the offending tag byte
+                        *current_pos = current_pos.sub(1 + 2 + name_len);
+                        return Err(Error::InvalidTagType(tag_id));
+                    }
                 }
                 guard.set_len(len + raw_len + SIZE_DYN);
                 start = *current_pos;
@@ -330,11 +450,26 @@ unsafe fn read_list<O: ByteOrder>(
     }
 
     unsafe {
-        check_bounds!(1 + 4);
+        if (*current_pos as usize) + 1 + 4 > end_pos as usize {
+            cold_path();
+            return Err(Error::truncated_list_header());
+        }
         let tag_id = **current_pos;
         *current_pos = current_pos.add(1);
         let len = byteorder::U32::<O>::from_bytes(*current_pos.cast()).get() as usize;
         *current_pos = current_pos.add(4);
+        if tag_id == 0 && len > 0 {
+            cold_path();
+            // leave the cursor on the offending tag byte
+            *current_pos = current_pos.sub(1 + 4);
+            return Err(Error::InvalidTagType(tag_id));
+        }
+        if tag_id > 12 {
+            cold_path();
+            // leave the cursor on the offending tag byte
+            *current_pos = current_pos.sub(1 + 4);
+            return Err(Error::InvalidTagType(tag_id));
+        }
         if tag_id <= 6 {
             let size = tag_size(Tag::from_u8_unchecked(tag_id));
             check_bounds!(len * size);
@@ -345,7 +480,15 @@ unsafe fn read_list<O: ByteOrder>(
                 _marker: PhantomData,
             }))
         } else {
-            check_bounds!(len); // one non-primitive tag is at least 1 byte (empty Tag::Compound)
+            // every element takes at least this many bytes of input, so a count
+            // that cannot fit is rejected before the elements are reserved
+            let min_size = match tag_id {
+                7 | 11 | 12 => 4, // empty array
+                8 => 2,           // empty string
+                9 => 1 + 4,       // empty list
+                _ => 1,           // empty compound
+            };
+            check_bounds!(len * min_size);
             let mut list_data = Vec::with_capacity(1 + 4 + len * SIZE_DYN);
             ptr::copy_nonoverlapping((*current_pos).sub(1 + 4), list_data.as_mut_ptr(), 1 + 4);
             let mut guard: ListBuildGuard<O> = ListBuildGuard::new(list_data, tag_id);
@@ -424,7 +567,7 @@ unsafe fn read_list<O: ByteOrder>(
                         guard.set_len(guard.len() + SIZE_DYN);
                     }
                 }
-                _ => return Err(Error::InvalidTagType(tag_id)),
+                _ => unreachable!("element tags are checked above"),
             }
             Ok(OwnedValue::List(OwnedList {
                 data: guard.finalize().into(),
@@ -725,7 +868,11 @@ unsafe fn read_compound_fallback<O: ByteOrder, R: ByteOrder>(
                     VecViewOwn::from(value).write(write_ptr);
                     guard.set_len(old_len + header_len + SIZE_DYN);
                 }
-                _ => return Err(Error::InvalidTagType(tag_id)),
+                _ => {
+                    // leave the cursor on the offending tag byte
+                    *current_pos = current_pos.sub(header_len);
+                    return Err(Error::InvalidTagType(tag_id));
+                }
             }
         }
     }
@@ -745,11 +892,20 @@ unsafe fn read_list_fallback<O: ByteOrder, R: ByteOrder>(
     }
 
     unsafe {
-        check_bounds!(1 + 4);
+        if (*current_pos as usize) + 1 + 4 > end_pos as usize {
+            cold_path();
+            return Err(Error::truncated_list_header());
+        }
         let tag_id = **current_pos;
         *current_pos = current_pos.add(1);
         let len = byteorder::U32::<O>::from_bytes(*current_pos.cast()).get() as usize;
         *current_pos = current_pos.add(4);
+        if tag_id == 0 && len > 0 {
+            cold_path();
+            // leave the cursor on the offending tag byte
+            *current_pos = current_pos.sub(1 + 4);
+            return Err(Error::InvalidTagType(tag_id));
+        }
 
         macro_rules! case {
             ($size:expr, $type:ident) => {{
@@ -982,7 +1138,11 @@ unsafe fn read_list_fallback<O: ByteOrder, R: ByteOrder>(
                     _marker: PhantomData,
                 }))
             }
-            _ => Err(Error::InvalidTagType(tag_id)),
+            _ => {
+                // leave the cursor on the offending tag byte
+                *current_pos = current_pos.sub(1 + 4);
+                Err(Error::InvalidTagType(tag_id))
+            }
         }
     }
 }
@@ -1082,7 +1242,7 @@ pub unsafe fn read_unsafe_fallback<O: ByteOrder, R: ByteOrder>(
                 }
                 *current_pos = current_pos.add(len * 4);
                 Ok(OwnedValue::IntArray(
-                    std::mem::transmute::<Vec<[u8; 4]>, Vec<byteorder::I32<R>>>(value).into(),
+                    core::mem::transmute::<Vec<[u8; 4]>, Vec<byteorder::I32<R>>>(value).into(),
                 ))
             }
             12 => {
@@ -1097,7 +1257,7 @@ pub unsafe fn read_unsafe_fallback<O: ByteOrder, R: ByteOrder>(
                 }
                 *current_pos = current_pos.add(len * 8);
                 Ok(OwnedValue::LongArray(
-                    std::mem::transmute::<Vec<[u8; 8]>, Vec<byteorder::I64<R>>>(value).into(),
+                    core::mem::transmute::<Vec<[u8; 8]>, Vec<byteorder::I64<R>>>(value).into(),
                 ))
             }
             _ => Err(Error::InvalidTagType(tag_id)),
@@ -1105,8 +1265,50 @@ pub unsafe fn read_unsafe_fallback<O: ByteOrder, R: ByteOrder>(
     }
 }
 
+/// The most bytes reserved at once while reading from a stream.
+#[cfg(feature = "std")]
+const READ_CHUNK: usize = 64 * 1024;
+
+/// Reads `len` elements into `value`.
+///
+/// The declared length comes from the input and cannot be trusted, so memory
+/// is reserved in chunks as the data actually arrives rather than all up
+/// front.
+///
+/// # Safety
+///
+/// `T` must be a plain byte type (`u8`, `i8` or a `zerocopy` byteorder type)
+/// for which every bit pattern is valid.
+#[cfg(feature = "std")]
+unsafe fn read_elements<T>(
+    reader: &mut PositionReader<impl BufRead>,
+    value: &mut Vec<T>,
+    len: usize,
+) -> Result<()> {
+    unsafe {
+        let size = core::mem::size_of::<T>();
+        reader.check_len(len, len * size)?;
+        let mut remaining = len;
+        while remaining > 0 {
+            let chunk = remaining.min(READ_CHUNK / size);
+            value.reserve(chunk);
+            let start = value.len();
+            reader
+                .read_exact(slice::from_raw_parts_mut(
+                    value.as_mut_ptr().add(start).cast(),
+                    chunk * size,
+                ))
+                .map_err(Error::IO)?;
+            value.set_len(start + chunk);
+            remaining -= chunk;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(feature = "std")]
 unsafe fn read_compound_from_reader<O: ByteOrder, R: ByteOrder>(
-    reader: &mut impl BufRead,
+    reader: &mut PositionReader<impl BufRead>,
 ) -> Result<OwnedValue<R>> {
     unsafe {
         let mut compound_data = Vec::with_capacity(128);
@@ -1128,6 +1330,7 @@ unsafe fn read_compound_from_reader<O: ByteOrder, R: ByteOrder>(
             let mut name_len = [0u8; 2];
             reader.read_exact(&mut name_len).map_err(Error::IO)?;
             let name_len = byteorder::U16::<O>::from_bytes(name_len).get() as usize;
+            reader.add_nodes(1)?;
 
             let header_len = 1 + 2 + name_len;
             let old_len = compound_data.len();
@@ -1198,11 +1401,8 @@ unsafe fn read_compound_from_reader<O: ByteOrder, R: ByteOrder>(
                     reader.read_exact(&mut len).map_err(Error::IO)?;
                     let len = byteorder::U32::<O>::from_bytes(len).get() as usize;
 
-                    let mut value = Vec::<u8>::with_capacity(len);
-                    reader
-                        .read_exact(slice::from_raw_parts_mut(value.as_mut_ptr(), len))
-                        .map_err(Error::IO)?;
-                    value.set_len(len);
+                    let mut value = Vec::<u8>::new();
+                    read_elements(reader, &mut value, len)?;
 
                     VecViewOwn::from(value).write(write_ptr);
                     compound_data.set_len(old_len + header_len + SIZE_DYN);
@@ -1223,12 +1423,10 @@ unsafe fn read_compound_from_reader<O: ByteOrder, R: ByteOrder>(
                     let mut len = [0u8; 2];
                     reader.read_exact(&mut len).map_err(Error::IO)?;
                     let len = byteorder::U16::<O>::from_bytes(len).get() as usize;
+                    reader.add_string_bytes(len)?;
 
-                    let mut value = Vec::<u8>::with_capacity(len);
-                    reader
-                        .read_exact(slice::from_raw_parts_mut(value.as_mut_ptr(), len))
-                        .map_err(Error::IO)?;
-                    value.set_len(len);
+                    let mut value = Vec::<u8>::new();
+                    read_elements(reader, &mut value, len)?;
 
                     StringViewOwn::from(value).write(write_ptr);
                     compound_data.set_len(old_len + header_len + SIZE_DYN);
@@ -1281,14 +1479,8 @@ unsafe fn read_compound_from_reader<O: ByteOrder, R: ByteOrder>(
                     let mut len = [0u8; 4];
                     reader.read_exact(&mut len).map_err(Error::IO)?;
                     let len = byteorder::U32::<O>::from_bytes(len).get() as usize;
-                    let mut value = Vec::<byteorder::I32<R>>::with_capacity(len);
-                    reader
-                        .read_exact(slice::from_raw_parts_mut(
-                            value.as_mut_ptr().cast(),
-                            len * 4,
-                        ))
-                        .map_err(Error::IO)?;
-                    value.set_len(len);
+                    let mut value = Vec::<byteorder::I32<R>>::new();
+                    read_elements(reader, &mut value, len)?;
                     if TypeId::of::<R>() != TypeId::of::<O>() {
                         let s =
                             slice::from_raw_parts_mut(value.as_mut_ptr().cast::<[u8; 4]>(), len);
@@ -1315,14 +1507,8 @@ unsafe fn read_compound_from_reader<O: ByteOrder, R: ByteOrder>(
                     let mut len = [0u8; 4];
                     reader.read_exact(&mut len).map_err(Error::IO)?;
                     let len = byteorder::U32::<O>::from_bytes(len).get() as usize;
-                    let mut value = Vec::<byteorder::I64<R>>::with_capacity(len);
-                    reader
-                        .read_exact(slice::from_raw_parts_mut(
-                            value.as_mut_ptr().cast(),
-                            len * 8,
-                        ))
-                        .map_err(Error::IO)?;
-                    value.set_len(len);
+                    let mut value = Vec::<byteorder::I64<R>>::new();
+                    read_elements(reader, &mut value, len)?;
                     if TypeId::of::<R>() != TypeId::of::<O>() {
                         let s =
                             slice::from_raw_parts_mut(value.as_mut_ptr().cast::<[u8; 8]>(), len);
@@ -1333,63 +1519,67 @@ unsafe fn read_compound_from_reader<O: ByteOrder, R: ByteOrder>(
                     VecViewOwn::from(value).write(write_ptr);
                     compound_data.set_len(old_len + header_len + SIZE_DYN);
                 }
-                _ => return Err(Error::InvalidTagType(tag_id)),
+                _ => return Err(Error::InvalidTagType(tag_id).at(reader.position() - (1 + 2))),
             }
         }
     }
 }
 
+#[cfg(feature = "std")]
 unsafe fn read_list_from_reader<O: ByteOrder, R: ByteOrder>(
-    reader: &mut impl BufRead,
+    reader: &mut PositionReader<impl BufRead>,
 ) -> Result<OwnedValue<R>> {
     unsafe {
-        let mut tag_id = [0u8];
-        reader.read_exact(&mut tag_id).map_err(Error::IO)?;
-        let tag_id = tag_id[0];
-
-        let mut len = [0u8; 4];
-        reader.read_exact(&mut len).map_err(Error::IO)?;
-        let len = byteorder::U32::<O>::from_bytes(len).get() as usize;
+        let mut header = [0u8; 1 + 4];
+        reader.read_exact(&mut header).map_err(|error| {
+            if error.kind() == io::ErrorKind::UnexpectedEof {
+                Error::truncated_list_header()
+            } else {
+                Error::IO(error)
+            }
+        })?;
+        let tag_id = header[0];
+        let len = byteorder::U32::<O>::from_bytes(header[1..].try_into().unwrap()).get() as usize;
+        if tag_id == 0 && len > 0 {
+            cold_path();
+            return Err(Error::InvalidTagType(tag_id).at(reader.position() - (1 + 4)));
+        }
 
         macro_rules! case {
             ($size:expr, $type:ident) => {{
-                let mut list_data = Vec::with_capacity(1 + 4 + len * $size);
-                let write_ptr = list_data.as_mut_ptr();
-                ptr::write(write_ptr, tag_id);
-                ptr::write(
-                    write_ptr.add(1).cast(),
-                    byteorder::U32::<R>::new(len as u32).to_bytes(),
-                );
-                reader
-                    .read_exact(slice::from_raw_parts_mut(write_ptr.add(1 + 4), len * $size))
-                    .map_err(Error::IO)?;
+                let mut list_data = Vec::with_capacity(1 + 4);
+                list_data.push(tag_id);
+                list_data.extend_from_slice(&byteorder::U32::<R>::new(len as u32).to_bytes());
+                read_elements(reader, &mut list_data, len * $size)?;
+                reader.add_nodes(len)?;
                 if TypeId::of::<R>() != TypeId::of::<O>() {
-                    let s =
-                        slice::from_raw_parts_mut(write_ptr.add(1 + 4).cast::<[u8; $size]>(), len);
+                    let s = slice::from_raw_parts_mut(
+                        list_data.as_mut_ptr().add(1 + 4).cast::<[u8; $size]>(),
+                        len,
+                    );
                     for element in s {
                         *element = change_endian!(*element, $type, O, R).to_bytes();
                     }
                 }
-                list_data.set_len(1 + 4 + len * $size);
                 Ok(OwnedValue::List(OwnedList {
                     data: list_data.into(),
                     _marker: PhantomData,
                 }))
             }};
-            ($parse:block) => {{
-                let mut list_data = Vec::with_capacity(1 + 4 + len * SIZE_DYN);
-                let write_ptr = list_data.as_mut_ptr();
-                ptr::write(write_ptr, tag_id);
-                ptr::write(
-                    write_ptr.add(1).cast(),
-                    byteorder::U32::<R>::new(len as u32).to_bytes(),
-                );
-                let mut write_ptr = list_data.as_mut_ptr().add(1 + 4);
+            ($min_size:expr, $parse:block) => {{
+                // every element takes at least `$min_size` bytes of input, so a
+                // count beyond the limit is rejected before anything is reserved
+                reader.check_len(len, len * $min_size)?;
+                let mut list_data =
+                    Vec::with_capacity(1 + 4 + len.min(READ_CHUNK / SIZE_DYN) * SIZE_DYN);
+                list_data.push(tag_id);
+                list_data.extend_from_slice(&byteorder::U32::<R>::new(len as u32).to_bytes());
                 for _ in 0..len {
-                    $parse.write(write_ptr);
-                    write_ptr = write_ptr.add(SIZE_DYN);
+                    reader.add_nodes(1)?;
+                    list_data.reserve(SIZE_DYN);
+                    $parse.write(list_data.as_mut_ptr().add(list_data.len()));
+                    list_data.set_len(list_data.len() + SIZE_DYN);
                 }
-                list_data.set_len(1 + 4 + len * SIZE_DYN);
                 Ok(OwnedValue::List(OwnedList {
                     data: list_data.into(),
                     _marker: PhantomData,
@@ -1412,17 +1602,10 @@ unsafe fn read_list_from_reader<O: ByteOrder, R: ByteOrder>(
                 }))
             }
             1 => {
-                let mut list_data = Vec::with_capacity(1 + 4 + len);
-                let write_ptr = list_data.as_mut_ptr();
-                ptr::write(write_ptr, tag_id);
-                ptr::write(
-                    write_ptr.add(1).cast(),
-                    byteorder::U32::<R>::new(len as u32).to_bytes(),
-                );
-                reader
-                    .read_exact(slice::from_raw_parts_mut(write_ptr.add(1 + 4), len))
-                    .map_err(Error::IO)?;
-                list_data.set_len(1 + 4 + len);
+                let mut list_data = Vec::with_capacity(1 + 4);
+                list_data.push(tag_id);
+                list_data.extend_from_slice(&byteorder::U32::<R>::new(len as u32).to_bytes());
+                read_elements(reader, &mut list_data, len)?;
                 Ok(OwnedValue::List(OwnedList {
                     data: list_data.into(),
                     _marker: PhantomData,
@@ -1438,50 +1621,39 @@ unsafe fn read_list_from_reader<O: ByteOrder, R: ByteOrder>(
                 case!(8, U64)
             }
             7 => {
-                case!({
+                case!(4, {
                     let mut len = [0u8; 4];
                     reader.read_exact(&mut len).map_err(Error::IO)?;
                     let len = byteorder::U32::<O>::from_bytes(len).get() as usize;
-                    let mut value = Vec::<i8>::with_capacity(len);
-                    reader
-                        .read_exact(slice::from_raw_parts_mut(value.as_mut_ptr().cast(), len))
-                        .map_err(Error::IO)?;
-                    value.set_len(len);
+                    let mut value = Vec::<i8>::new();
+                    read_elements(reader, &mut value, len)?;
                     VecViewOwn::from(value)
                 })
             }
             8 => {
-                case!({
+                case!(2, {
                     let mut len = [0u8; 2];
                     reader.read_exact(&mut len).map_err(Error::IO)?;
                     let len = byteorder::U16::<O>::from_bytes(len).get() as usize;
-                    let mut value = Vec::<u8>::with_capacity(len);
-                    reader
-                        .read_exact(slice::from_raw_parts_mut(value.as_mut_ptr(), len))
-                        .map_err(Error::IO)?;
-                    value.set_len(len);
+                    reader.add_string_bytes(len)?;
+                    let mut value = Vec::<u8>::new();
+                    read_elements(reader, &mut value, len)?;
                     StringViewOwn::from(value)
                 })
             }
             9 => {
-                case!({ read_list_from_reader::<O, R>(reader)? })
+                case!(5, { read_list_from_reader::<O, R>(reader)? })
             }
             10 => {
-                case!({ read_compound_from_reader::<O, R>(reader)? })
+                case!(1, { read_compound_from_reader::<O, R>(reader)? })
             }
             11 => {
-                case!({
+                case!(4, {
                     let mut len = [0u8; 4];
                     reader.read_exact(&mut len).map_err(Error::IO)?;
                     let len = byteorder::U32::<O>::from_bytes(len).get() as usize;
-                    let mut value = Vec::<byteorder::I32<R>>::with_capacity(len);
-                    reader
-                        .read_exact(slice::from_raw_parts_mut(
-                            value.as_mut_ptr().cast(),
-                            len * 4,
-                        ))
-                        .map_err(Error::IO)?;
-                    value.set_len(len);
+                    let mut value = Vec::<byteorder::I32<R>>::new();
+                    read_elements(reader, &mut value, len)?;
                     if TypeId::of::<R>() != TypeId::of::<O>() {
                         let s =
                             slice::from_raw_parts_mut(value.as_mut_ptr().cast::<[u8; 4]>(), len);
@@ -1493,18 +1665,12 @@ unsafe fn read_list_from_reader<O: ByteOrder, R: ByteOrder>(
                 })
             }
             12 => {
-                case!({
+                case!(4, {
                     let mut len = [0u8; 4];
                     reader.read_exact(&mut len).map_err(Error::IO)?;
                     let len = byteorder::U32::<O>::from_bytes(len).get() as usize;
-                    let mut value = Vec::<byteorder::I64<R>>::with_capacity(len);
-                    reader
-                        .read_exact(slice::from_raw_parts_mut(
-                            value.as_mut_ptr().cast(),
-                            len * 8,
-                        ))
-                        .map_err(Error::IO)?;
-                    value.set_len(len);
+                    let mut value = Vec::<byteorder::I64<R>>::new();
+                    read_elements(reader, &mut value, len)?;
                     if TypeId::of::<R>() != TypeId::of::<O>() {
                         let s =
                             slice::from_raw_parts_mut(value.as_mut_ptr().cast::<[u8; 8]>(), len);
@@ -1515,14 +1681,15 @@ unsafe fn read_list_from_reader<O: ByteOrder, R: ByteOrder>(
                     VecViewOwn::from(value)
                 })
             }
-            _ => Err(Error::InvalidTagType(tag_id)),
+            _ => Err(Error::InvalidTagType(tag_id).at(reader.position() - (1 + 4))),
         }
     }
 }
 
+#[cfg(feature = "std")]
 pub unsafe fn read_unsafe_from_reader<O: ByteOrder, R: ByteOrder>(
     tag_id: u8,
-    reader: &mut impl BufRead,
+    reader: &mut PositionReader<impl BufRead>,
 ) -> Result<OwnedValue<R>> {
     unsafe {
         assert_unchecked(tag_id != 0);
@@ -1571,17 +1738,15 @@ pub unsafe fn read_unsafe_from_reader<O: ByteOrder, R: ByteOrder>(
                 let mut len = [0u8; 4];
                 reader.read_exact(&mut len).map_err(Error::IO)?;
                 let len = byteorder::U32::<O>::from_bytes(len).get() as usize;
-                let mut value = Vec::<i8>::with_capacity(len);
-                reader
-                    .read_exact(slice::from_raw_parts_mut(value.as_mut_ptr().cast(), len))
-                    .map_err(Error::IO)?;
-                value.set_len(len);
+                let mut value = Vec::<i8>::new();
+                read_elements(reader, &mut value, len)?;
                 Ok(OwnedValue::ByteArray(VecViewOwn::from(value)))
             }
             8 => {
                 let mut len = [0u8; 2];
                 reader.read_exact(&mut len).map_err(Error::IO)?;
                 let len = byteorder::U16::<O>::from_bytes(len).get() as usize;
+                reader.add_string_bytes(len)?;
                 let mut value = Vec::with_capacity(len);
                 reader
                     .read_exact(slice::from_raw_parts_mut(value.as_mut_ptr(), len))
@@ -1595,14 +1760,8 @@ pub unsafe fn read_unsafe_from_reader<O: ByteOrder, R: ByteOrder>(
                 let mut len = [0u8; 4];
                 reader.read_exact(&mut len).map_err(Error::IO)?;
                 let len = byteorder::U32::<O>::from_bytes(len).get() as usize;
-                let mut value = Vec::<byteorder::I32<R>>::with_capacity(len);
-                reader
-                    .read_exact(slice::from_raw_parts_mut(
-                        value.as_mut_ptr().cast(),
-                        len * 4,
-                    ))
-                    .map_err(Error::IO)?;
-                value.set_len(len);
+                let mut value = Vec::<byteorder::I32<R>>::new();
+                read_elements(reader, &mut value, len)?;
                 if TypeId::of::<R>() != TypeId::of::<O>() {
                     let s = slice::from_raw_parts_mut(value.as_mut_ptr().cast::<[u8; 4]>(), len);
                     for element in s {
@@ -1615,14 +1774,8 @@ pub unsafe fn read_unsafe_from_reader<O: ByteOrder, R: ByteOrder>(
                 let mut len = [0u8; 4];
                 reader.read_exact(&mut len).map_err(Error::IO)?;
                 let len = byteorder::U32::<O>::from_bytes(len).get() as usize;
-                let mut value = Vec::<byteorder::I64<R>>::with_capacity(len);
-                reader
-                    .read_exact(slice::from_raw_parts_mut(
-                        value.as_mut_ptr().cast(),
-                        len * 8,
-                    ))
-                    .map_err(Error::IO)?;
-                value.set_len(len);
+                let mut value = Vec::<byteorder::I64<R>>::new();
+                read_elements(reader, &mut value, len)?;
                 if TypeId::of::<R>() != TypeId::of::<O>() {
                     let s = slice::from_raw_parts_mut(value.as_mut_ptr().cast::<[u8; 8]>(), len);
                     for element in s {
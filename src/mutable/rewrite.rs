@@ -0,0 +1,114 @@
+use alloc::{string::String, vec::Vec};
+
+use crate::{ByteOrder, MutableValue, OwnedValue};
+
+/// What [`OwnedValue::rewrite`] does with the node it just visited.
+pub enum RewriteAction<O: ByteOrder> {
+    /// Leaves the node in place, with any changes made through the
+    /// [`MutableValue`], and goes on to visit its children.
+    Keep,
+    /// Puts this value in place of the node. The replacement is not visited.
+    Replace(OwnedValue<O>),
+    /// Removes the node from the compound or list holding it.
+    Remove,
+}
+
+impl<O: ByteOrder> OwnedValue<O> {
+    /// Visits every node of the tree once, letting `f` keep, replace or
+    /// remove each one.
+    ///
+    /// Nodes are visited parent first, with `self` visited before anything
+    /// else. The children of a node are only visited if `f` keeps it, so a
+    /// replacement is never visited and a removed subtree is skipped.
+    /// Compound entries are visited in storage order and list elements by
+    /// index; removing one does not cause a sibling to be skipped, and a
+    /// replaced entry keeps its place.
+    ///
+    /// Removing the root leaves [`OwnedValue::End`] in its place.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a list element is replaced by a value of a different tag
+    /// type, unless it is the only element of its list.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use na_nbt::{OwnedCompound, OwnedList, OwnedValue, RewriteAction};
+    /// use zerocopy::byteorder::BigEndian;
+    ///
+    /// let mut list: OwnedList<BigEndian> = OwnedList::default();
+    /// for value in [1i32, -2, 3, -4] {
+    ///     list.push(value);
+    /// }
+    /// let mut root: OwnedCompound<BigEndian> = OwnedCompound::default();
+    /// root.insert("values", list);
+    /// let mut root = OwnedValue::Compound(root);
+    ///
+    /// // drop the negative numbers
+    /// root.rewrite(|value| match value.as_int() {
+    ///     Some(n) if n < 0 => RewriteAction::Remove,
+    ///     _ => RewriteAction::Keep,
+    /// });
+    /// assert_eq!(root.get("values").unwrap().as_list().unwrap().len(), 2);
+    /// ```
+    pub fn rewrite<F>(&mut self, mut f: F)
+    where
+        F: FnMut(&mut MutableValue<'_, O>) -> RewriteAction<O>,
+    {
+        let mut root = self.to_mut();
+        match f(&mut root) {
+            RewriteAction::Keep => rewrite_children(&mut root, &mut f),
+            RewriteAction::Replace(value) => *self = value,
+            RewriteAction::Remove => *self = OwnedValue::End,
+        }
+    }
+}
+
+fn rewrite_children<O: ByteOrder, F>(value: &mut MutableValue<'_, O>, f: &mut F)
+where
+    F: FnMut(&mut MutableValue<'_, O>) -> RewriteAction<O>,
+{
+    match value {
+        MutableValue::Compound(compound) => {
+            let keys: Vec<String> = compound
+                .iter()
+                .map(|(key, _)| key.decode().into_owned())
+                .collect();
+            for key in keys {
+                let Some(mut child) = compound.get_mut(&key) else {
+                    continue;
+                };
+                match f(&mut child) {
+                    RewriteAction::Keep => rewrite_children(&mut child, f),
+                    RewriteAction::Replace(value) => {
+                        compound.replace(&key, value);
+                    }
+                    RewriteAction::Remove => {
+                        compound.remove(&key);
+                    }
+                }
+            }
+        }
+        MutableValue::List(list) => {
+            let mut index = 0;
+            while let Some(mut child) = list.get_mut(index) {
+                match f(&mut child) {
+                    RewriteAction::Keep => {
+                        rewrite_children(&mut child, f);
+                        index += 1;
+                    }
+                    RewriteAction::Replace(value) => {
+                        list.remove(index);
+                        list.insert(index, value);
+                        index += 1;
+                    }
+                    RewriteAction::Remove => {
+                        list.remove(index);
+                    }
+                }
+            }
+        }
+        _ => {}
+    }
+}
@@ -1,4 +1,7 @@
-use std::{io::Write, marker::PhantomData};
+use alloc::vec::Vec;
+use core::marker::PhantomData;
+#[cfg(feature = "std")]
+use std::io::Write;
 
 use zerocopy::byteorder;
 
@@ -17,7 +20,7 @@ impl<'doc> ReadableString<'doc> for ImmutableString<'doc> {
     }
 
     #[inline]
-    fn decode(&self) -> std::borrow::Cow<'_, str> {
+    fn decode(&self) -> alloc::borrow::Cow<'_, str> {
         self.decode()
     }
 }
@@ -232,6 +235,7 @@ impl<'doc, O: ByteOrder> ScopedReadableValue<'doc> for ImmutableValue<'doc, O> {
         self.write_to_vec::<TARGET>()
     }
 
+    #[cfg(feature = "std")]
     #[inline]
     fn write_to_writer<TARGET: ByteOrder>(&self, writer: impl Write) -> Result<()> {
         self.write_to_writer::<TARGET>(writer)
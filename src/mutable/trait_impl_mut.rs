@@ -1,4 +1,7 @@
-use std::{io::Write, marker::PhantomData};
+use alloc::vec::Vec;
+use core::marker::PhantomData;
+#[cfg(feature = "std")]
+use std::io::Write;
 
 use zerocopy::byteorder;
 
@@ -225,6 +228,7 @@ impl<'doc, O: ByteOrder> ScopedReadableValue<'doc> for MutableValue<'doc, O> {
         self.write_to_vec::<TARGET>()
     }
 
+    #[cfg(feature = "std")]
     #[inline]
     fn write_to_writer<TARGET: ByteOrder>(&self, writer: impl Write) -> Result<()> {
         self.write_to_writer::<TARGET>(writer)
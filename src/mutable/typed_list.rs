@@ -0,0 +1,132 @@
+use alloc::vec::Vec;
+use core::marker::PhantomData;
+
+use crate::{ByteOrder, OwnedList, OwnedValue, Primitive};
+
+/// Builds an [`OwnedList`] whose element type is fixed by `T`.
+///
+/// [`OwnedList::push`] accepts any value and panics when its tag does not
+/// match the elements already in the list. Here `push` takes exactly `T`, so
+/// a value of the wrong type is a compile error instead:
+///
+/// ```compile_fail
+/// use na_nbt::TypedListBuilder;
+/// use zerocopy::byteorder::BigEndian;
+///
+/// let mut ints = TypedListBuilder::<BigEndian, i32>::new();
+/// ints.push(1i64);
+/// ```
+///
+/// The element type is set from the start, so even an empty list reports
+/// [`T::TAG`](Primitive::TAG) as its [`element_tag`](OwnedList::element_tag).
+/// Like any empty list, it is still written with an `End` element type.
+///
+/// # Example
+///
+/// ```
+/// use na_nbt::{OwnedValue, Tag, TypedListBuilder};
+/// use zerocopy::byteorder::BigEndian;
+///
+/// let mut ints = TypedListBuilder::<BigEndian, i32>::new();
+/// ints.push(1);
+/// ints.extend([2, 3]);
+/// let list = ints.build();
+/// assert_eq!(list.element_tag(), Tag::Int);
+/// assert_eq!(list.get(2).unwrap().as_int(), Some(3));
+///
+/// let empty: OwnedValue<BigEndian> = TypedListBuilder::<BigEndian, f32>::new().into();
+/// assert_eq!(empty.as_list().unwrap().element_tag(), Tag::Float);
+/// ```
+pub struct TypedListBuilder<O: ByteOrder, T: Primitive> {
+    list: OwnedList<O>,
+    _marker: PhantomData<T>,
+}
+
+impl<O: ByteOrder, T: Primitive> TypedListBuilder<O, T> {
+    /// Creates an empty builder.
+    #[inline]
+    pub fn new() -> Self {
+        Self::with_capacity(0)
+    }
+
+    /// Creates an empty builder with room for `capacity` elements.
+    pub fn with_capacity(capacity: usize) -> Self {
+        let mut data = Vec::with_capacity(1 + 4 + capacity * core::mem::size_of::<T>());
+        data.extend_from_slice(&[T::TAG as u8, 0, 0, 0, 0]);
+        Self {
+            list: OwnedList {
+                data: data.into(),
+                _marker: PhantomData,
+            },
+            _marker: PhantomData,
+        }
+    }
+
+    /// Appends an element.
+    #[inline]
+    pub fn push(&mut self, value: T) {
+        // the element tag was set to `T::TAG` on creation
+        unsafe { value.list_push_unchecked(&mut self.list) };
+    }
+
+    /// Returns the number of elements pushed so far.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.list.len()
+    }
+
+    /// Returns `true` if no element has been pushed.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.list.is_empty()
+    }
+
+    /// Releases unused capacity in the list being built.
+    #[inline]
+    pub fn shrink_to_fit(&mut self) {
+        self.list.shrink_to_fit();
+    }
+
+    /// Returns the finished list.
+    #[inline]
+    pub fn build(self) -> OwnedList<O> {
+        self.list
+    }
+}
+
+impl<O: ByteOrder, T: Primitive> Default for TypedListBuilder<O, T> {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<O: ByteOrder, T: Primitive> Extend<T> for TypedListBuilder<O, T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for value in iter {
+            self.push(value);
+        }
+    }
+}
+
+impl<O: ByteOrder, T: Primitive> FromIterator<T> for TypedListBuilder<O, T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut builder = Self::new();
+        builder.extend(iter);
+        builder
+    }
+}
+
+impl<O: ByteOrder, T: Primitive> From<TypedListBuilder<O, T>> for OwnedList<O> {
+    #[inline]
+    fn from(builder: TypedListBuilder<O, T>) -> Self {
+        builder.build()
+    }
+}
+
+impl<O: ByteOrder, T: Primitive> From<TypedListBuilder<O, T>> for OwnedValue<O> {
+    #[inline]
+    fn from(builder: TypedListBuilder<O, T>) -> Self {
+        OwnedValue::List(builder.build())
+    }
+}
@@ -1,4 +1,4 @@
-use std::{hint::assert_unchecked, marker::PhantomData, ptr, slice};
+use core::{hint::assert_unchecked, marker::PhantomData, ptr, slice};
 
 use zerocopy::byteorder;
 
@@ -7,10 +7,11 @@ use crate::{
     mutable::iter::{
         ImmutableCompoundIter, ImmutableListIter, MutableCompoundIter, MutableListIter,
     },
+    type_mismatch,
     view::{StringViewOwn, VecViewMut, VecViewOwn},
 };
 
-pub const SIZE_USIZE: usize = std::mem::size_of::<usize>();
+pub const SIZE_USIZE: usize = core::mem::size_of::<usize>();
 pub const SIZE_DYN: usize = SIZE_USIZE * 3;
 
 #[inline]
@@ -577,6 +578,28 @@ pub fn list_remove<O: ByteOrder>(data: &mut VecViewMut<'_, u8>, index: usize) ->
     }
 }
 
+pub fn list_set<O: ByteOrder>(
+    data: &mut VecViewMut<'_, u8>,
+    index: usize,
+    value: OwnedValue<O>,
+) -> Option<OwnedValue<O>> {
+    let tag_id = list_tag_id(data.as_ptr());
+    if index >= list_len::<O>(data.as_ptr()) {
+        cold_path();
+        return None;
+    }
+    if value.tag_id() != tag_id {
+        type_mismatch(tag_id, value.tag_id());
+        return None;
+    }
+    unsafe {
+        let ptr = data.as_mut_ptr().add(1 + 4 + index * tag_size(tag_id));
+        let old_value = OwnedValue::<O>::read(tag_id, ptr);
+        value.write(ptr);
+        Some(old_value)
+    }
+}
+
 pub fn compound_get<'s, O: ByteOrder>(data: *const u8, key: &str) -> Option<ImmutableValue<'s, O>> {
     let name = simd_cesu8::mutf8::encode(key);
 
@@ -812,9 +835,26 @@ pub fn compound_remove<O: ByteOrder>(
     key: &str,
 ) -> Option<OwnedValue<O>> {
     let name = simd_cesu8::mutf8::encode(key);
+    compound_remove_where(data, |_, name_bytes| name == name_bytes)
+}
 
+/// Removes the entry at `index`, counting from the first entry.
+pub fn compound_remove_at<O: ByteOrder>(
+    data: &mut VecViewMut<'_, u8>,
+    index: usize,
+) -> Option<OwnedValue<O>> {
+    compound_remove_where(data, |current, _| current == index)
+}
+
+/// Removes the first entry for which `matches` returns `true`, given its
+/// index and encoded key.
+fn compound_remove_where<O: ByteOrder>(
+    data: &mut VecViewMut<'_, u8>,
+    mut matches: impl FnMut(usize, &[u8]) -> bool,
+) -> Option<OwnedValue<O>> {
     unsafe {
         let mut ptr = data.as_mut_ptr();
+        let mut index = 0;
         loop {
             let tag_id = *ptr.cast();
             ptr = ptr.add(1);
@@ -830,7 +870,7 @@ pub fn compound_remove<O: ByteOrder>(
             let name_bytes = slice::from_raw_parts(ptr, name_len as usize);
             ptr = ptr.add(name_len as usize);
 
-            if name == name_bytes {
+            if matches(index, name_bytes) {
                 let tag_size = tag_size(tag_id);
                 let pos_bytes = ptr.byte_offset_from_unsigned(data.as_mut_ptr());
                 let value = OwnedValue::<O>::read(tag_id, ptr);
@@ -845,6 +885,63 @@ pub fn compound_remove<O: ByteOrder>(
             }
 
             ptr = ptr.add(tag_size(tag_id));
+            index += 1;
+        }
+    }
+}
+
+pub fn compound_replace<O: ByteOrder>(
+    data: &mut VecViewMut<'_, u8>,
+    key: &str,
+    value: OwnedValue<O>,
+) -> Option<OwnedValue<O>> {
+    if value.is_end() {
+        cold_path();
+        panic!("cannot insert TAG_END");
+    }
+    let name = simd_cesu8::mutf8::encode(key);
+
+    unsafe {
+        let mut ptr = data.as_mut_ptr();
+        loop {
+            let old_tag_id = *ptr.cast();
+            let tag_pos = ptr.byte_offset_from_unsigned(data.as_mut_ptr());
+            ptr = ptr.add(1);
+
+            if old_tag_id == Tag::End {
+                cold_path();
+                return compound_insert_value(data, key, value);
+            }
+
+            let name_len = byteorder::U16::<O>::from_bytes(*ptr.cast()).get();
+            ptr = ptr.add(2);
+
+            let name_bytes = slice::from_raw_parts(ptr, name_len as usize);
+            ptr = ptr.add(name_len as usize);
+
+            if name == name_bytes {
+                let old_size = tag_size(old_tag_id);
+                let new_tag_id = value.tag_id();
+                let new_size = tag_size(new_tag_id);
+                let pos_bytes = ptr.byte_offset_from_unsigned(data.as_mut_ptr());
+                let old_value = OwnedValue::<O>::read(old_tag_id, ptr);
+
+                // resize the payload slot, keeping the entry where it is
+                let len_bytes = data.len();
+                let tail = len_bytes - pos_bytes - old_size;
+                if new_size > old_size {
+                    data.reserve(new_size - old_size);
+                }
+                let start = data.as_mut_ptr().add(pos_bytes);
+                ptr::copy(start.add(old_size), start.add(new_size), tail);
+                data.set_len(len_bytes - old_size + new_size);
+
+                *data.as_mut_ptr().add(tag_pos) = new_tag_id as u8;
+                value.write(data.as_mut_ptr().add(pos_bytes));
+                return Some(old_value);
+            }
+
+            ptr = ptr.add(tag_size(old_tag_id));
         }
     }
 }
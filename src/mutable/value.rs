@@ -1,18 +1,23 @@
-use std::{borrow::Cow, io::Write, marker::PhantomData, ptr, slice};
+use alloc::{borrow::Cow, vec::Vec};
+use core::{marker::PhantomData, ptr, slice};
+#[cfg(feature = "std")]
+use std::io::Write;
 
 use zerocopy::byteorder;
 
+#[cfg(feature = "std")]
+use crate::write_owned_to_writer;
 use crate::{
-    ByteOrder, EMPTY_COMPOUND, EMPTY_LIST, Result, ScopedReadableValue as _, Tag,
+    ByteOrder, EMPTY_COMPOUND, EMPTY_LIST, Result, ScopedReadableValue as _, Tag, decode_mutf8,
     index::Index,
     mutable::{
-        iter::{ImmutableCompoundIter, ImmutableListIter},
+        iter::{ImmutableCompoundIter, ImmutableCompoundIterStr, ImmutableListIter},
         util::{
             SIZE_USIZE, compound_get, compound_iter, list_get, list_is_empty, list_iter, list_len,
             list_tag_id,
         },
     },
-    write_owned_to_vec, write_owned_to_writer,
+    write_owned_to_vec,
 };
 
 /// A zero-copy, immutable NBT value (Pointer-based).
@@ -325,6 +330,7 @@ impl<'s, O: ByteOrder> ImmutableValue<'s, O> {
         self.visit_scoped(|value| write_owned_to_vec::<O, TARGET>(value))
     }
 
+    #[cfg(feature = "std")]
     #[inline]
     pub fn write_to_writer<TARGET: ByteOrder>(&self, writer: impl Write) -> Result<()> {
         self.visit_scoped(|value| write_owned_to_writer::<O, TARGET>(value, writer))
@@ -344,7 +350,7 @@ impl<'s> ImmutableString<'s> {
 
     #[inline]
     pub fn decode<'a>(&'a self) -> Cow<'a, str> {
-        simd_cesu8::mutf8::decode_lossy(self.data)
+        decode_mutf8(self.data)
     }
 }
 
@@ -387,6 +393,12 @@ impl<'s, O: ByteOrder> ImmutableList<'s, O> {
         list_tag_id(self.data)
     }
 
+    /// Same as [`tag_id`](Self::tag_id): the tag of the elements, not of the list.
+    #[inline]
+    pub fn element_tag(&self) -> Tag {
+        self.tag_id()
+    }
+
     #[inline]
     pub fn len(&self) -> usize {
         list_len::<O>(self.data)
@@ -447,4 +459,11 @@ impl<'s, O: ByteOrder> ImmutableCompound<'s, O> {
     pub fn iter(&self) -> ImmutableCompoundIter<'s, O> {
         compound_iter(self.data)
     }
+
+    /// Returns an iterator over the entries with their keys decoded, so that
+    /// keys can be matched as `&str`. ASCII keys are borrowed.
+    #[inline]
+    pub fn iter_str(&self) -> ImmutableCompoundIterStr<'s, O> {
+        ImmutableCompoundIterStr { iter: self.iter() }
+    }
 }
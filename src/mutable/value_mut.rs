@@ -1,21 +1,30 @@
-use std::{io::Write, marker::PhantomData};
+use alloc::vec::Vec;
+use core::marker::PhantomData;
+#[cfg(feature = "std")]
+use std::io::Write;
 
 use zerocopy::byteorder;
 
+#[cfg(feature = "std")]
+use crate::write_owned_to_writer;
 use crate::{
     ByteOrder, ImmutableCompound, ImmutableList, ImmutableString, ImmutableValue, IntoOwnedValue,
     OwnedValue, Result, ScopedReadableValue as _, Tag,
     index::Index,
     mutable::{
-        iter::{ImmutableCompoundIter, ImmutableListIter, MutableCompoundIter, MutableListIter},
+        iter::{
+            ImmutableCompoundIter, ImmutableCompoundIterStr, ImmutableListIter,
+            MutableCompoundIter, MutableCompoundIterStr, MutableListIter,
+        },
         util::{
             SIZE_USIZE, compound_get, compound_get_mut, compound_iter, compound_iter_mut,
-            compound_remove, list_get, list_get_mut, list_is_empty, list_iter, list_iter_mut,
-            list_len, list_pop, list_remove, list_tag_id,
+            compound_remove, compound_replace, list_get, list_get_mut, list_is_empty, list_iter,
+            list_iter_mut, list_len, list_pop, list_remove, list_set, list_tag_id,
         },
     },
+    type_mismatch,
     view::{StringViewMut, VecViewMut},
-    write_owned_to_vec, write_owned_to_writer,
+    write_owned_to_vec,
 };
 
 /// A mutable view into an NBT value.
@@ -337,6 +346,7 @@ impl<'s, O: ByteOrder> MutableValue<'s, O> {
         self.visit_scoped(|value| write_owned_to_vec::<O, TARGET>(value))
     }
 
+    #[cfg(feature = "std")]
     #[inline]
     pub fn write_to_writer<TARGET: ByteOrder>(&self, writer: impl Write) -> Result<()> {
         self.visit_scoped(|value| write_owned_to_writer::<O, TARGET>(value, writer))
@@ -362,7 +372,7 @@ impl<'s, O: ByteOrder> MutableValue<'s, O> {
                 **value = data;
                 true
             }
-            _ => false,
+            _ => type_mismatch(Tag::Byte, self.tag_id()),
         }
     }
 
@@ -373,7 +383,21 @@ impl<'s, O: ByteOrder> MutableValue<'s, O> {
                 **value = f(**value);
                 true
             }
-            _ => false,
+            _ => type_mismatch(Tag::Byte, self.tag_id()),
+        }
+    }
+
+    #[inline]
+    pub fn try_update_byte(&mut self, f: impl FnOnce(i8) -> Option<i8>) -> bool {
+        match self {
+            MutableValue::Byte(value) => match f(**value) {
+                Some(data) => {
+                    **value = data;
+                    true
+                }
+                None => false,
+            },
+            _ => type_mismatch(Tag::Byte, self.tag_id()),
         }
     }
 
@@ -395,7 +419,7 @@ impl<'s, O: ByteOrder> MutableValue<'s, O> {
                 value.set(data);
                 true
             }
-            _ => false,
+            _ => type_mismatch(Tag::Short, self.tag_id()),
         }
     }
 
@@ -406,7 +430,21 @@ impl<'s, O: ByteOrder> MutableValue<'s, O> {
                 value.set(f(value.get()));
                 true
             }
-            _ => false,
+            _ => type_mismatch(Tag::Short, self.tag_id()),
+        }
+    }
+
+    #[inline]
+    pub fn try_update_short(&mut self, f: impl FnOnce(i16) -> Option<i16>) -> bool {
+        match self {
+            MutableValue::Short(value) => match f(value.get()) {
+                Some(data) => {
+                    value.set(data);
+                    true
+                }
+                None => false,
+            },
+            _ => type_mismatch(Tag::Short, self.tag_id()),
         }
     }
 
@@ -428,7 +466,7 @@ impl<'s, O: ByteOrder> MutableValue<'s, O> {
                 value.set(data);
                 true
             }
-            _ => false,
+            _ => type_mismatch(Tag::Int, self.tag_id()),
         }
     }
 
@@ -439,7 +477,21 @@ impl<'s, O: ByteOrder> MutableValue<'s, O> {
                 value.set(f(value.get()));
                 true
             }
-            _ => false,
+            _ => type_mismatch(Tag::Int, self.tag_id()),
+        }
+    }
+
+    #[inline]
+    pub fn try_update_int(&mut self, f: impl FnOnce(i32) -> Option<i32>) -> bool {
+        match self {
+            MutableValue::Int(value) => match f(value.get()) {
+                Some(data) => {
+                    value.set(data);
+                    true
+                }
+                None => false,
+            },
+            _ => type_mismatch(Tag::Int, self.tag_id()),
         }
     }
 
@@ -461,7 +513,7 @@ impl<'s, O: ByteOrder> MutableValue<'s, O> {
                 value.set(data);
                 true
             }
-            _ => false,
+            _ => type_mismatch(Tag::Long, self.tag_id()),
         }
     }
 
@@ -472,7 +524,21 @@ impl<'s, O: ByteOrder> MutableValue<'s, O> {
                 value.set(f(value.get()));
                 true
             }
-            _ => false,
+            _ => type_mismatch(Tag::Long, self.tag_id()),
+        }
+    }
+
+    #[inline]
+    pub fn try_update_long(&mut self, f: impl FnOnce(i64) -> Option<i64>) -> bool {
+        match self {
+            MutableValue::Long(value) => match f(value.get()) {
+                Some(data) => {
+                    value.set(data);
+                    true
+                }
+                None => false,
+            },
+            _ => type_mismatch(Tag::Long, self.tag_id()),
         }
     }
 
@@ -494,7 +560,7 @@ impl<'s, O: ByteOrder> MutableValue<'s, O> {
                 value.set(data);
                 true
             }
-            _ => false,
+            _ => type_mismatch(Tag::Float, self.tag_id()),
         }
     }
 
@@ -505,7 +571,21 @@ impl<'s, O: ByteOrder> MutableValue<'s, O> {
                 value.set(f(value.get()));
                 true
             }
-            _ => false,
+            _ => type_mismatch(Tag::Float, self.tag_id()),
+        }
+    }
+
+    #[inline]
+    pub fn try_update_float(&mut self, f: impl FnOnce(f32) -> Option<f32>) -> bool {
+        match self {
+            MutableValue::Float(value) => match f(value.get()) {
+                Some(data) => {
+                    value.set(data);
+                    true
+                }
+                None => false,
+            },
+            _ => type_mismatch(Tag::Float, self.tag_id()),
         }
     }
 
@@ -527,7 +607,7 @@ impl<'s, O: ByteOrder> MutableValue<'s, O> {
                 value.set(data);
                 true
             }
-            _ => false,
+            _ => type_mismatch(Tag::Double, self.tag_id()),
         }
     }
 
@@ -538,7 +618,21 @@ impl<'s, O: ByteOrder> MutableValue<'s, O> {
                 value.set(f(value.get()));
                 true
             }
-            _ => false,
+            _ => type_mismatch(Tag::Double, self.tag_id()),
+        }
+    }
+
+    #[inline]
+    pub fn try_update_double(&mut self, f: impl FnOnce(f64) -> Option<f64>) -> bool {
+        match self {
+            MutableValue::Double(value) => match f(value.get()) {
+                Some(data) => {
+                    value.set(data);
+                    true
+                }
+                None => false,
+            },
+            _ => type_mismatch(Tag::Double, self.tag_id()),
         }
     }
 
@@ -653,6 +747,12 @@ impl<'s, O: ByteOrder> MutableList<'s, O> {
         list_tag_id(self.data.as_ptr())
     }
 
+    /// Same as [`tag_id`](Self::tag_id): the tag of the elements, not of the list.
+    #[inline]
+    pub fn element_tag(&self) -> Tag {
+        self.tag_id()
+    }
+
     #[inline]
     pub fn len(&self) -> usize {
         list_len::<O>(self.data.as_ptr())
@@ -730,6 +830,16 @@ impl<'s, O: ByteOrder> MutableList<'s, O> {
     pub fn remove(&mut self, index: usize) -> OwnedValue<O> {
         list_remove(&mut self.data, index)
     }
+
+    /// Replaces the element at `index`, returning the old one.
+    ///
+    /// Returns `None` and leaves the list untouched if `index` is out of
+    /// bounds or `value` is not of the list's element type; unlike
+    /// [`insert`](Self::insert), a mismatched tag is rejected rather than a
+    /// panic.
+    pub fn set<V: Into<OwnedValue<O>>>(&mut self, index: usize, value: V) -> Option<OwnedValue<O>> {
+        list_set(&mut self.data, index, value.into())
+    }
 }
 
 pub struct MutableCompound<'s, O: ByteOrder> {
@@ -765,6 +875,16 @@ impl<'s, O: ByteOrder> MutableCompound<'s, O> {
     {
         compound_iter(self.data.as_ptr())
     }
+
+    /// Returns an iterator over the entries with their keys decoded, so that
+    /// keys can be matched as `&str`. ASCII keys are borrowed.
+    #[inline]
+    pub fn iter_str<'a>(&'a self) -> ImmutableCompoundIterStr<'a, O>
+    where
+        's: 'a,
+    {
+        ImmutableCompoundIterStr { iter: self.iter() }
+    }
 }
 
 impl<'s, O: ByteOrder> MutableCompound<'s, O> {
@@ -782,6 +902,18 @@ impl<'s, O: ByteOrder> MutableCompound<'s, O> {
     {
         compound_iter_mut(self.data.as_mut_ptr())
     }
+
+    /// Returns an iterator over the entries with their keys decoded and
+    /// mutable views of their values. ASCII keys are borrowed.
+    #[inline]
+    pub fn iter_mut_str<'a>(&'a mut self) -> MutableCompoundIterStr<'a, O>
+    where
+        's: 'a,
+    {
+        MutableCompoundIterStr {
+            iter: self.iter_mut(),
+        }
+    }
 }
 
 impl<'s, O: ByteOrder> MutableCompound<'s, O> {
@@ -792,4 +924,22 @@ impl<'s, O: ByteOrder> MutableCompound<'s, O> {
     pub fn remove(&mut self, key: &str) -> Option<OwnedValue<O>> {
         compound_remove(&mut self.data, key)
     }
+
+    /// Replaces the value under `key`, returning the old one.
+    ///
+    /// Unlike [`insert`](Self::insert), which moves an existing key to the
+    /// end, the entry keeps its place among the others. The new value may be
+    /// of any tag type. If `key` is absent, the entry is appended and `None`
+    /// is returned.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `value` is `End`.
+    pub fn replace<V: Into<OwnedValue<O>>>(
+        &mut self,
+        key: &str,
+        value: V,
+    ) -> Option<OwnedValue<O>> {
+        compound_replace(&mut self.data, key, value.into())
+    }
 }
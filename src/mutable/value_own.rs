@@ -1,24 +1,39 @@
-use std::{hint::unreachable_unchecked, io::Write, marker::PhantomData, mem::ManuallyDrop, ptr};
+use alloc::{string::String, vec, vec::Vec};
+use core::{
+    cmp::Ordering,
+    hint::unreachable_unchecked,
+    marker::PhantomData,
+    mem::ManuallyDrop,
+    ops::{Bound, RangeBounds},
+    ptr, slice,
+};
+#[cfg(feature = "std")]
+use std::io::Write;
 
-use zerocopy::byteorder;
+use zerocopy::{Immutable, IntoBytes, byteorder};
 
+#[cfg(feature = "std")]
+use crate::write_owned_to_writer;
 use crate::{
-    ByteOrder, ImmutableCompound, ImmutableList, ImmutableString, ImmutableValue, IntoOwnedValue,
-    MutableCompound, MutableList, MutableValue, Result, ScopedReadableValue as _, Tag, cold_path,
+    ByteOrder, Error, ImmutableCompound, ImmutableList, ImmutableString, ImmutableValue,
+    IntoOwnedValue, MutableCompound, MutableList, MutableValue, Primitive, Result,
+    ScopedReadableValue as _, Tag, TagType, cold_path,
     index::Index,
     mutable::{
         iter::{
-            ImmutableCompoundIter, ImmutableListIter, MutableCompoundIter, MutableListIter,
-            OwnedCompoundIter, OwnedListIter,
+            ImmutableCompoundIter, ImmutableCompoundIterStr, ImmutableListIter,
+            MutableCompoundIter, MutableCompoundIterStr, MutableListIter, OwnedCompoundIter,
+            OwnedListIter,
         },
         util::{
             compound_get, compound_get_mut, compound_iter, compound_iter_mut, compound_remove,
-            list_get, list_get_mut, list_is_empty, list_iter, list_iter_mut, list_len, list_pop,
-            list_remove, list_tag_id, tag_size,
+            compound_replace, list_get, list_get_mut, list_is_empty, list_iter, list_iter_mut,
+            list_len, list_pop, list_remove, list_set, list_tag_id, tag_size,
         },
     },
+    type_mismatch, values_equal,
     view::{StringViewMut, StringViewOwn, VecViewMut, VecViewOwn},
-    write_owned_to_vec, write_owned_to_writer,
+    write_owned_to_vec,
 };
 
 impl<T> VecViewOwn<T> {
@@ -164,6 +179,73 @@ pub enum OwnedValue<O: ByteOrder> {
     LongArray(VecViewOwn<byteorder::I64<O>>),
 }
 
+/// A [`TagType`] whose values can be moved out of an [`OwnedValue`].
+///
+/// Numbers are decoded to their native type, and the other tags give the
+/// payload of their [`OwnedValue`] variant, such as [`StringViewOwn`] for
+/// [`TagString`](crate::TagString) or [`OwnedCompound`] for
+/// [`TagCompound`](crate::TagCompound).
+///
+/// This trait is sealed through [`TagType`].
+pub trait OwnedTag: TagType {
+    /// What a value of this tag is moved out as.
+    type Owned<O: ByteOrder>;
+
+    #[doc(hidden)]
+    fn from_owned<O: ByteOrder>(value: OwnedValue<O>) -> Option<Self::Owned<O>>;
+}
+
+macro_rules! impl_owned_tag {
+    (numbers: $($tag:ident => $variant:ident -> $owned:ty),*) => {
+        $(
+            impl OwnedTag for crate::$tag {
+                type Owned<O: ByteOrder> = $owned;
+
+                #[inline]
+                fn from_owned<O: ByteOrder>(value: OwnedValue<O>) -> Option<Self::Owned<O>> {
+                    match value {
+                        OwnedValue::$variant(value) => Some(value.into()),
+                        _ => None,
+                    }
+                }
+            }
+        )*
+    };
+    (payloads: $($tag:ident => $variant:ident -> $owned:ty),*) => {
+        $(
+            impl OwnedTag for crate::$tag {
+                type Owned<O: ByteOrder> = $owned;
+
+                #[inline]
+                fn from_owned<O: ByteOrder>(value: OwnedValue<O>) -> Option<Self::Owned<O>> {
+                    match value {
+                        OwnedValue::$variant(value) => Some(value),
+                        _ => None,
+                    }
+                }
+            }
+        )*
+    };
+}
+
+impl_owned_tag!(numbers:
+    TagByte => Byte -> i8,
+    TagShort => Short -> i16,
+    TagInt => Int -> i32,
+    TagLong => Long -> i64,
+    TagFloat => Float -> f32,
+    TagDouble => Double -> f64
+);
+
+impl_owned_tag!(payloads:
+    TagByteArray => ByteArray -> VecViewOwn<i8>,
+    TagString => String -> StringViewOwn,
+    TagList => List -> OwnedList<O>,
+    TagCompound => Compound -> OwnedCompound<O>,
+    TagIntArray => IntArray -> VecViewOwn<byteorder::I32<O>>,
+    TagLongArray => LongArray -> VecViewOwn<byteorder::I64<O>>
+);
+
 impl<O: ByteOrder> From<()> for OwnedValue<O> {
     fn from(_: ()) -> Self {
         OwnedValue::End
@@ -314,6 +396,52 @@ impl<O: ByteOrder> From<Vec<byteorder::I64<O>>> for OwnedValue<O> {
     }
 }
 
+/// The default value is an empty compound.
+///
+/// A compound is what almost every NBT document has at its root, so it is the
+/// most useful starting point for a builder or for generic code that needs
+/// some value. [`OwnedValue::End`] would be the "nothing" value, but it cannot
+/// be written as a document on its own in most formats, nor hold anything.
+impl<O: ByteOrder> Default for OwnedValue<O> {
+    #[inline]
+    fn default() -> Self {
+        Self::empty_compound()
+    }
+}
+
+impl<O: ByteOrder> OwnedValue<O> {
+    /// Creates an empty compound.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use na_nbt::OwnedValue;
+    /// use zerocopy::byteorder::BigEndian;
+    ///
+    /// let value = OwnedValue::<BigEndian>::empty_compound();
+    /// assert!(value.is_compound());
+    /// assert!(value.get("anything").is_none());
+    /// ```
+    #[inline]
+    pub fn empty_compound() -> Self {
+        OwnedValue::Compound(OwnedCompound::default())
+    }
+
+    /// Creates an empty list, with [`Tag::End`] as its element type.
+    ///
+    /// The element type is fixed by the first element pushed.
+    #[inline]
+    pub fn empty_list() -> Self {
+        OwnedValue::List(OwnedList::default())
+    }
+
+    /// Creates an empty string.
+    #[inline]
+    pub fn empty_string() -> Self {
+        OwnedValue::String("".into())
+    }
+}
+
 impl<O: ByteOrder> OwnedValue<O> {
     pub(crate) unsafe fn write(self, dst: *mut u8) {
         unsafe {
@@ -583,12 +711,126 @@ impl<O: ByteOrder> OwnedValue<O> {
         self.visit_scoped(|value| write_owned_to_vec::<O, TARGET>(value))
     }
 
+    #[cfg(feature = "std")]
     #[inline]
     pub fn write_to_writer<TARGET: ByteOrder>(&self, writer: impl Write) -> Result<()> {
         self.visit_scoped(|value| write_owned_to_writer::<O, TARGET>(value, writer))
     }
 }
 
+impl<O: ByteOrder> OwnedValue<O> {
+    /// Returns an estimate of the memory this value occupies, in bytes.
+    ///
+    /// This is the size of the `OwnedValue` itself plus the capacity of every
+    /// buffer it owns, recursively: array and string buffers, and the buffers
+    /// of lists and compounds, which hold their elements and keys inline. It
+    /// is based on current capacities, so it can exceed what the data needs,
+    /// and it does not include allocator overhead.
+    ///
+    /// This differs from [`serialized_len`](crate::ScopedReadableValue::serialized_len),
+    /// which is the size of the encoded document.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use na_nbt::{OwnedList, OwnedValue};
+    /// use zerocopy::byteorder::BigEndian;
+    ///
+    /// let mut list: OwnedList<BigEndian> = OwnedList::default();
+    /// let before = list.memory_size();
+    /// for i in 0..100 {
+    ///     list.push(i as i64);
+    /// }
+    /// assert!(list.memory_size() >= before + 100 * 8);
+    ///
+    /// let value = OwnedValue::List(list);
+    /// assert!(value.memory_size() > core::mem::size_of::<OwnedValue<BigEndian>>());
+    /// ```
+    pub fn memory_size(&self) -> usize {
+        let heap = match self {
+            OwnedValue::ByteArray(value) => value.capacity(),
+            OwnedValue::String(value) => value.capacity(),
+            OwnedValue::List(value) => value.memory_size() - size_of::<OwnedList<O>>(),
+            OwnedValue::Compound(value) => value.memory_size() - size_of::<OwnedCompound<O>>(),
+            OwnedValue::IntArray(value) => value.capacity() * 4,
+            OwnedValue::LongArray(value) => value.capacity() * 8,
+            _ => 0,
+        };
+        size_of::<Self>() + heap
+    }
+}
+
+impl<O: ByteOrder> OwnedValue<O> {
+    /// Releases unused capacity in every buffer of this value, recursively.
+    ///
+    /// Lists and compounds keep their capacity when elements are removed, and
+    /// so do strings and arrays. This calls `shrink_to_fit` on every buffer in
+    /// the tree, which is worth doing before keeping a value around for long.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use na_nbt::{OwnedCompound, OwnedValue};
+    /// use zerocopy::byteorder::BigEndian;
+    ///
+    /// let mut compound: OwnedCompound<BigEndian> = OwnedCompound::default();
+    /// for i in 0..100 {
+    ///     compound.insert(&format!("key{i}"), i);
+    /// }
+    /// for i in 1..100 {
+    ///     compound.remove(&format!("key{i}"));
+    /// }
+    /// let mut value = OwnedValue::Compound(compound);
+    /// let before = value.memory_size();
+    /// value.compact();
+    /// assert!(value.memory_size() < before);
+    /// ```
+    pub fn compact(&mut self) {
+        match self {
+            OwnedValue::ByteArray(value) => value.shrink_to_fit(),
+            OwnedValue::String(value) => value.shrink_to_fit(),
+            OwnedValue::List(value) => value.compact(),
+            OwnedValue::Compound(value) => value.compact(),
+            OwnedValue::IntArray(value) => value.shrink_to_fit(),
+            OwnedValue::LongArray(value) => value.shrink_to_fit(),
+            _ => {}
+        }
+    }
+}
+
+/// Compacts the payload of type `tag_id` stored inline at `ptr`.
+unsafe fn compact_inline<O: ByteOrder>(tag_id: Tag, ptr: *mut u8) {
+    unsafe {
+        match tag_id {
+            Tag::ByteArray => (*ptr.cast::<VecViewOwn<i8>>()).shrink_to_fit(),
+            Tag::String => (*ptr.cast::<StringViewOwn>()).shrink_to_fit(),
+            Tag::List => (*ptr.cast::<OwnedList<O>>()).compact(),
+            Tag::Compound => (*ptr.cast::<OwnedCompound<O>>()).compact(),
+            Tag::IntArray => (*ptr.cast::<VecViewOwn<byteorder::I32<O>>>()).shrink_to_fit(),
+            Tag::LongArray => (*ptr.cast::<VecViewOwn<byteorder::I64<O>>>()).shrink_to_fit(),
+            _ => {}
+        }
+    }
+}
+
+/// Returns the heap memory owned by the payload of type `tag_id` stored
+/// inline at `ptr`.
+unsafe fn heap_size<O: ByteOrder>(tag_id: Tag, ptr: *const u8) -> usize {
+    unsafe {
+        match tag_id {
+            Tag::ByteArray => (*ptr.cast::<VecViewOwn<i8>>()).capacity(),
+            Tag::String => (*ptr.cast::<StringViewOwn>()).capacity(),
+            Tag::List => (*ptr.cast::<OwnedList<O>>()).memory_size() - size_of::<OwnedList<O>>(),
+            Tag::Compound => {
+                (*ptr.cast::<OwnedCompound<O>>()).memory_size() - size_of::<OwnedCompound<O>>()
+            }
+            Tag::IntArray => (*ptr.cast::<VecViewOwn<byteorder::I32<O>>>()).capacity() * 4,
+            Tag::LongArray => (*ptr.cast::<VecViewOwn<byteorder::I64<O>>>()).capacity() * 8,
+            _ => 0,
+        }
+    }
+}
+
 impl<O: ByteOrder> OwnedValue<O> {
     #[inline]
     pub fn as_byte_mut(&mut self) -> Option<&mut i8> {
@@ -605,7 +847,7 @@ impl<O: ByteOrder> OwnedValue<O> {
                 *value = data;
                 true
             }
-            _ => false,
+            _ => type_mismatch(Tag::Byte, self.tag_id()),
         }
     }
 
@@ -616,7 +858,21 @@ impl<O: ByteOrder> OwnedValue<O> {
                 *value = f(*value);
                 true
             }
-            _ => false,
+            _ => type_mismatch(Tag::Byte, self.tag_id()),
+        }
+    }
+
+    #[inline]
+    pub fn try_update_byte(&mut self, f: impl FnOnce(i8) -> Option<i8>) -> bool {
+        match self {
+            OwnedValue::Byte(value) => match f(*value) {
+                Some(data) => {
+                    *value = data;
+                    true
+                }
+                None => false,
+            },
+            _ => type_mismatch(Tag::Byte, self.tag_id()),
         }
     }
 
@@ -635,7 +891,7 @@ impl<O: ByteOrder> OwnedValue<O> {
                 value.set(data);
                 true
             }
-            _ => false,
+            _ => type_mismatch(Tag::Short, self.tag_id()),
         }
     }
 
@@ -646,7 +902,21 @@ impl<O: ByteOrder> OwnedValue<O> {
                 value.set(f(value.get()));
                 true
             }
-            _ => false,
+            _ => type_mismatch(Tag::Short, self.tag_id()),
+        }
+    }
+
+    #[inline]
+    pub fn try_update_short(&mut self, f: impl FnOnce(i16) -> Option<i16>) -> bool {
+        match self {
+            OwnedValue::Short(value) => match f(value.get()) {
+                Some(data) => {
+                    value.set(data);
+                    true
+                }
+                None => false,
+            },
+            _ => type_mismatch(Tag::Short, self.tag_id()),
         }
     }
 
@@ -665,7 +935,7 @@ impl<O: ByteOrder> OwnedValue<O> {
                 value.set(data);
                 true
             }
-            _ => false,
+            _ => type_mismatch(Tag::Int, self.tag_id()),
         }
     }
 
@@ -676,7 +946,21 @@ impl<O: ByteOrder> OwnedValue<O> {
                 value.set(f(value.get()));
                 true
             }
-            _ => false,
+            _ => type_mismatch(Tag::Int, self.tag_id()),
+        }
+    }
+
+    #[inline]
+    pub fn try_update_int(&mut self, f: impl FnOnce(i32) -> Option<i32>) -> bool {
+        match self {
+            OwnedValue::Int(value) => match f(value.get()) {
+                Some(data) => {
+                    value.set(data);
+                    true
+                }
+                None => false,
+            },
+            _ => type_mismatch(Tag::Int, self.tag_id()),
         }
     }
 
@@ -695,7 +979,7 @@ impl<O: ByteOrder> OwnedValue<O> {
                 value.set(data);
                 true
             }
-            _ => false,
+            _ => type_mismatch(Tag::Long, self.tag_id()),
         }
     }
 
@@ -706,7 +990,21 @@ impl<O: ByteOrder> OwnedValue<O> {
                 value.set(f(value.get()));
                 true
             }
-            _ => false,
+            _ => type_mismatch(Tag::Long, self.tag_id()),
+        }
+    }
+
+    #[inline]
+    pub fn try_update_long(&mut self, f: impl FnOnce(i64) -> Option<i64>) -> bool {
+        match self {
+            OwnedValue::Long(value) => match f(value.get()) {
+                Some(data) => {
+                    value.set(data);
+                    true
+                }
+                None => false,
+            },
+            _ => type_mismatch(Tag::Long, self.tag_id()),
         }
     }
 
@@ -725,7 +1023,7 @@ impl<O: ByteOrder> OwnedValue<O> {
                 value.set(data);
                 true
             }
-            _ => false,
+            _ => type_mismatch(Tag::Float, self.tag_id()),
         }
     }
 
@@ -736,7 +1034,21 @@ impl<O: ByteOrder> OwnedValue<O> {
                 value.set(f(value.get()));
                 true
             }
-            _ => false,
+            _ => type_mismatch(Tag::Float, self.tag_id()),
+        }
+    }
+
+    #[inline]
+    pub fn try_update_float(&mut self, f: impl FnOnce(f32) -> Option<f32>) -> bool {
+        match self {
+            OwnedValue::Float(value) => match f(value.get()) {
+                Some(data) => {
+                    value.set(data);
+                    true
+                }
+                None => false,
+            },
+            _ => type_mismatch(Tag::Float, self.tag_id()),
         }
     }
 
@@ -755,7 +1067,7 @@ impl<O: ByteOrder> OwnedValue<O> {
                 value.set(data);
                 true
             }
-            _ => false,
+            _ => type_mismatch(Tag::Double, self.tag_id()),
         }
     }
 
@@ -766,7 +1078,21 @@ impl<O: ByteOrder> OwnedValue<O> {
                 value.set(f(value.get()));
                 true
             }
-            _ => false,
+            _ => type_mismatch(Tag::Double, self.tag_id()),
+        }
+    }
+
+    #[inline]
+    pub fn try_update_double(&mut self, f: impl FnOnce(f64) -> Option<f64>) -> bool {
+        match self {
+            OwnedValue::Double(value) => match f(value.get()) {
+                Some(data) => {
+                    value.set(data);
+                    true
+                }
+                None => false,
+            },
+            _ => type_mismatch(Tag::Double, self.tag_id()),
         }
     }
 
@@ -858,6 +1184,497 @@ impl<O: ByteOrder> OwnedValue<O> {
             },
         )
     }
+
+    /// Returns a [`MutableValue`] view of this value.
+    pub fn to_mut<'a>(&'a mut self) -> MutableValue<'a, O> {
+        unsafe {
+            match self {
+                OwnedValue::End => MutableValue::End,
+                OwnedValue::Byte(value) => MutableValue::Byte(value),
+                OwnedValue::Short(value) => MutableValue::Short(value),
+                OwnedValue::Int(value) => MutableValue::Int(value),
+                OwnedValue::Long(value) => MutableValue::Long(value),
+                OwnedValue::Float(value) => MutableValue::Float(value),
+                OwnedValue::Double(value) => MutableValue::Double(value),
+                OwnedValue::ByteArray(value) => MutableValue::ByteArray(VecViewMut::new(
+                    &mut value.ptr,
+                    &mut value.len,
+                    &mut value.cap,
+                )),
+                OwnedValue::String(value) => MutableValue::String(StringViewMut::new(
+                    &mut value.ptr,
+                    &mut value.len,
+                    &mut value.cap,
+                )),
+                OwnedValue::List(value) => MutableValue::List(MutableList {
+                    data: VecViewMut::new(
+                        &mut value.data.ptr,
+                        &mut value.data.len,
+                        &mut value.data.cap,
+                    ),
+                    _marker: PhantomData,
+                }),
+                OwnedValue::Compound(value) => MutableValue::Compound(MutableCompound {
+                    data: VecViewMut::new(
+                        &mut value.data.ptr,
+                        &mut value.data.len,
+                        &mut value.data.cap,
+                    ),
+                    _marker: PhantomData,
+                }),
+                OwnedValue::IntArray(value) => MutableValue::IntArray(VecViewMut::new(
+                    &mut value.ptr,
+                    &mut value.len,
+                    &mut value.cap,
+                )),
+                OwnedValue::LongArray(value) => MutableValue::LongArray(VecViewMut::new(
+                    &mut value.ptr,
+                    &mut value.len,
+                    &mut value.cap,
+                )),
+            }
+        }
+    }
+
+    /// Calls `f` on every leaf of the tree, depth first.
+    ///
+    /// Leaves are the numeric scalars, strings and arrays; lists and compounds
+    /// are walked into but never passed to `f`, so the structure of the tree is
+    /// left intact. Entries are visited in storage order.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use na_nbt::{MutableValue, OwnedCompound, OwnedList, OwnedValue};
+    /// use zerocopy::byteorder::BigEndian;
+    ///
+    /// let mut list = OwnedList::<BigEndian>::default();
+    /// list.push(1i32);
+    /// list.push(2i32);
+    /// let mut root = OwnedCompound::<BigEndian>::default();
+    /// root.insert("values", list);
+    /// root.insert("name", "steve");
+    /// let mut root = OwnedValue::Compound(root);
+    ///
+    /// root.transform_scalars(|value| match value {
+    ///     MutableValue::Int(value) => value.set(-value.get()),
+    ///     MutableValue::String(mut value) => {
+    ///         let upper = value.decode().to_uppercase();
+    ///         value.clear();
+    ///         value.push_str(&upper);
+    ///     }
+    ///     _ => {}
+    /// });
+    ///
+    /// assert_eq!(root.get("values").and_then(|v| v.get(1)).and_then(|v| v.as_int()), Some(-2));
+    /// assert_eq!(root.get("name").unwrap().as_string().unwrap().decode(), "STEVE");
+    /// ```
+    pub fn transform_scalars(&mut self, mut f: impl FnMut(MutableValue<'_, O>)) {
+        transform_scalars(self.to_mut(), &mut f);
+    }
+}
+
+fn transform_scalars<O: ByteOrder>(
+    value: MutableValue<'_, O>,
+    f: &mut impl FnMut(MutableValue<'_, O>),
+) {
+    match value {
+        MutableValue::End => {}
+        MutableValue::List(mut list) => {
+            for item in list.iter_mut() {
+                transform_scalars(item, f);
+            }
+        }
+        MutableValue::Compound(mut compound) => {
+            for (_, item) in compound.iter_mut() {
+                transform_scalars(item, f);
+            }
+        }
+        value => f(value),
+    }
+}
+
+impl<O: ByteOrder> OwnedValue<O> {
+    /// Converts a list of `Byte` into a `ByteArray`.
+    ///
+    /// Returns `None` if `self` is not a list, or is a non-empty list of another type.
+    pub fn list_to_byte_array(self) -> Option<OwnedValue<O>> {
+        let OwnedValue::List(list) = self else {
+            return None;
+        };
+        unsafe { list_to_array::<O, i8>(list.data.as_ptr(), Tag::Byte) }.map(OwnedValue::ByteArray)
+    }
+
+    /// Converts a `ByteArray` into a list of `Byte`.
+    ///
+    /// Returns `None` if `self` is not a byte array.
+    pub fn byte_array_to_list(self) -> Option<OwnedValue<O>> {
+        match self {
+            OwnedValue::ByteArray(value) => Some(array_to_list(Tag::Byte, &value)),
+            _ => None,
+        }
+    }
+
+    /// Converts a list of `Int` into an `IntArray`.
+    ///
+    /// The element bytes are carried over unchanged. An empty list converts to
+    /// an empty array whatever its element type.
+    ///
+    /// Returns `None` if `self` is not a list, or is a non-empty list of another type.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use na_nbt::{OwnedList, OwnedValue};
+    /// use zerocopy::byteorder::BigEndian;
+    ///
+    /// let array: OwnedValue<BigEndian> = OwnedValue::IntArray(vec![1i32.into(), 2.into()].into());
+    /// let list = array.int_array_to_list().unwrap();
+    /// assert!(list.is_list());
+    /// let array = list.list_to_int_array().unwrap();
+    /// assert_eq!(array.as_int_array().map(|a| a.len()), Some(2));
+    ///
+    /// // a list of strings has no array counterpart
+    /// let mut strings = OwnedList::<BigEndian>::default();
+    /// strings.push("a");
+    /// assert!(OwnedValue::List(strings).list_to_int_array().is_none());
+    /// ```
+    pub fn list_to_int_array(self) -> Option<OwnedValue<O>> {
+        let OwnedValue::List(list) = self else {
+            return None;
+        };
+        unsafe { list_to_array::<O, byteorder::I32<O>>(list.data.as_ptr(), Tag::Int) }
+            .map(OwnedValue::IntArray)
+    }
+
+    /// Converts an `IntArray` into a list of `Int`.
+    ///
+    /// Returns `None` if `self` is not an int array.
+    pub fn int_array_to_list(self) -> Option<OwnedValue<O>> {
+        match self {
+            OwnedValue::IntArray(value) => Some(array_to_list(Tag::Int, &value)),
+            _ => None,
+        }
+    }
+
+    /// Converts a list of `Long` into a `LongArray`.
+    ///
+    /// Returns `None` if `self` is not a list, or is a non-empty list of another type.
+    pub fn list_to_long_array(self) -> Option<OwnedValue<O>> {
+        let OwnedValue::List(list) = self else {
+            return None;
+        };
+        unsafe { list_to_array::<O, byteorder::I64<O>>(list.data.as_ptr(), Tag::Long) }
+            .map(OwnedValue::LongArray)
+    }
+
+    /// Converts a `LongArray` into a list of `Long`.
+    ///
+    /// Returns `None` if `self` is not a long array.
+    pub fn long_array_to_list(self) -> Option<OwnedValue<O>> {
+        match self {
+            OwnedValue::LongArray(value) => Some(array_to_list(Tag::Long, &value)),
+            _ => None,
+        }
+    }
+}
+
+impl<O: ByteOrder> OwnedValue<O> {
+    /// Converts a numeric value in place to another numeric tag.
+    ///
+    /// Integers narrow with saturation, so an `Int` holding 300 becomes a
+    /// `Byte` holding 127. Floats convert to integers with `as` semantics:
+    /// truncated towards zero, saturating at the bounds, with NaN becoming 0.
+    /// A finite `Double` beyond the range of `Float` saturates to
+    /// `±f32::MAX`. Integers converted to floats round to the nearest
+    /// representable value.
+    ///
+    /// Returns `false` and leaves the value unchanged if either `self` or
+    /// `tag` is not one of `Byte`, `Short`, `Int`, `Long`, `Float` or `Double`.
+    /// Coercing to the current tag is a no-op that returns `true`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use na_nbt::{OwnedValue, Tag};
+    /// use zerocopy::byteorder::BigEndian;
+    ///
+    /// let mut value = OwnedValue::<BigEndian>::from(300i32);
+    /// assert!(value.coerce_to(Tag::Byte));
+    /// assert_eq!(value.as_byte(), Some(127));
+    ///
+    /// assert!(value.coerce_to(Tag::Double));
+    /// assert_eq!(value.as_double(), Some(127.0));
+    ///
+    /// // strings are not coerced
+    /// assert!(!value.coerce_to(Tag::String));
+    /// ```
+    pub fn coerce_to(&mut self, tag: Tag) -> bool {
+        let number = if let Some(value) = self.as_i64_widened() {
+            Number::Int(value)
+        } else if let Some(value) = self.as_f64_widened() {
+            Number::Float(value)
+        } else {
+            return false;
+        };
+        *self = match tag {
+            Tag::Byte => OwnedValue::Byte(number.to_int(i8::MIN as i64, i8::MAX as i64) as i8),
+            Tag::Short => OwnedValue::from(number.to_int(i16::MIN as i64, i16::MAX as i64) as i16),
+            Tag::Int => OwnedValue::from(number.to_int(i32::MIN as i64, i32::MAX as i64) as i32),
+            Tag::Long => OwnedValue::from(number.to_int(i64::MIN, i64::MAX)),
+            Tag::Float => OwnedValue::from(number.to_float()),
+            Tag::Double => OwnedValue::from(number.to_f64()),
+            _ => return false,
+        };
+        true
+    }
+}
+
+impl<O: ByteOrder> OwnedValue<O> {
+    /// Creates a list of three `Double`, the layout of an entity's `Pos` and
+    /// `Motion`.
+    ///
+    /// The inverse of [`as_vec3_f64`](crate::ScopedReadableValue::as_vec3_f64).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use na_nbt::{OwnedValue, ScopedReadableValue};
+    /// use zerocopy::byteorder::BigEndian;
+    ///
+    /// let pos = OwnedValue::<BigEndian>::from_vec3(0.5, 64.0, -12.5);
+    /// assert_eq!(pos.as_vec3_f64(), Some([0.5, 64.0, -12.5]));
+    /// ```
+    pub fn from_vec3(x: f64, y: f64, z: f64) -> Self {
+        let mut list = OwnedList::default();
+        list.push(x);
+        list.push(y);
+        list.push(z);
+        OwnedValue::List(list)
+    }
+
+    /// Creates a list of two `Float`, the layout of an entity's `Rotation`.
+    ///
+    /// The inverse of [`as_vec2_f32`](crate::ScopedReadableValue::as_vec2_f32).
+    pub fn from_vec2(x: f32, y: f32) -> Self {
+        let mut list = OwnedList::default();
+        list.push(x);
+        list.push(y);
+        OwnedValue::List(list)
+    }
+
+    /// Creates an `IntArray` from native integers.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use na_nbt::OwnedValue;
+    /// use zerocopy::byteorder::BigEndian;
+    ///
+    /// let array = OwnedValue::<BigEndian>::int_array_from(&[1, 2, 3]);
+    /// assert_eq!(array.as_int_array().map(|a| a.len()), Some(3));
+    /// ```
+    pub fn int_array_from(values: &[i32]) -> Self {
+        OwnedValue::IntArray(VecViewOwn::from_i32_slice(values))
+    }
+
+    /// Creates a `LongArray` from native integers.
+    pub fn long_array_from(values: &[i64]) -> Self {
+        OwnedValue::LongArray(VecViewOwn::from_i64_slice(values))
+    }
+
+    /// Creates an `IntArray` of four holding `uuid`, most significant part
+    /// first, the layout Java Edition has used since 1.16.
+    ///
+    /// The inverse of [`as_uuid`](crate::ScopedReadableValue::as_uuid).
+    pub fn from_uuid_int_array(uuid: u128) -> Self {
+        let ints: Vec<byteorder::I32<O>> = [96, 64, 32, 0]
+            .into_iter()
+            .map(|shift| byteorder::I32::new((uuid >> shift) as u32 as i32))
+            .collect();
+        OwnedValue::IntArray(ints.into())
+    }
+
+    /// Creates a `LongArray` of two holding `uuid`, most significant part
+    /// first.
+    ///
+    /// The inverse of [`as_uuid`](crate::ScopedReadableValue::as_uuid).
+    pub fn from_uuid_long_array(uuid: u128) -> Self {
+        let longs: Vec<byteorder::I64<O>> = [64, 0]
+            .into_iter()
+            .map(|shift| byteorder::I64::new((uuid >> shift) as u64 as i64))
+            .collect();
+        OwnedValue::LongArray(longs.into())
+    }
+}
+
+#[derive(Clone, Copy)]
+enum Number {
+    Int(i64),
+    Float(f64),
+}
+
+impl Number {
+    #[inline]
+    fn to_int(self, min: i64, max: i64) -> i64 {
+        match self {
+            Number::Int(value) => value.clamp(min, max),
+            // `as` saturates and maps NaN to 0
+            Number::Float(value) => (value as i64).clamp(min, max),
+        }
+    }
+
+    #[inline]
+    fn to_f64(self) -> f64 {
+        match self {
+            Number::Int(value) => value as f64,
+            Number::Float(value) => value,
+        }
+    }
+
+    #[inline]
+    fn to_float(self) -> f32 {
+        match self {
+            Number::Int(value) => value as f32,
+            Number::Float(value) if value.is_finite() => {
+                value.clamp(f32::MIN as f64, f32::MAX as f64) as f32
+            }
+            Number::Float(value) => value as f32,
+        }
+    }
+}
+
+/// What an element is sorted by in [`OwnedList::sort_by_key_field`].
+enum SortKey {
+    Number(Number),
+    String(String),
+    Other,
+}
+
+impl SortKey {
+    fn of<O: ByteOrder>(field: Option<ImmutableValue<'_, O>>) -> Self {
+        match field {
+            Some(ImmutableValue::Byte(value)) => SortKey::Number(Number::Int(value as i64)),
+            Some(ImmutableValue::Short(value)) => SortKey::Number(Number::Int(value as i64)),
+            Some(ImmutableValue::Int(value)) => SortKey::Number(Number::Int(value as i64)),
+            Some(ImmutableValue::Long(value)) => SortKey::Number(Number::Int(value)),
+            Some(ImmutableValue::Float(value)) => SortKey::Number(Number::Float(value as f64)),
+            Some(ImmutableValue::Double(value)) => SortKey::Number(Number::Float(value)),
+            Some(ImmutableValue::String(value)) => SortKey::String(value.decode().into_owned()),
+            _ => SortKey::Other,
+        }
+    }
+
+    fn cmp(&self, other: &Self) -> Ordering {
+        match (self, other) {
+            (SortKey::Number(Number::Int(a)), SortKey::Number(Number::Int(b))) => a.cmp(b),
+            (SortKey::Number(Number::Float(a)), SortKey::Number(Number::Float(b))) => {
+                a.total_cmp(b)
+            }
+            (SortKey::Number(Number::Int(a)), SortKey::Number(Number::Float(b))) => {
+                cmp_int_float(*a, *b)
+            }
+            (SortKey::Number(Number::Float(a)), SortKey::Number(Number::Int(b))) => {
+                cmp_int_float(*b, *a).reverse()
+            }
+            (SortKey::String(a), SortKey::String(b)) => a.cmp(b),
+            (a, b) => a.rank().cmp(&b.rank()),
+        }
+    }
+
+    fn rank(&self) -> u8 {
+        match self {
+            SortKey::Number(_) => 0,
+            SortKey::String(_) => 1,
+            SortKey::Other => 2,
+        }
+    }
+}
+
+/// Compares an integer with a float exactly, without rounding either.
+///
+/// NaN sorts by its sign, below or above every number, as with
+/// [`f64::total_cmp`]. An integer sorts before a float of the same value, so
+/// that `0` comes before both `-0.0` and `0.0`.
+fn cmp_int_float(int: i64, float: f64) -> Ordering {
+    // 2^63, the first float past `i64::MAX`
+    const LIMIT: f64 = 9_223_372_036_854_775_808.0;
+    if float.is_nan() {
+        return if float.is_sign_negative() {
+            Ordering::Greater
+        } else {
+            Ordering::Less
+        };
+    }
+    if float >= LIMIT {
+        return Ordering::Less;
+    }
+    if float < -LIMIT {
+        return Ordering::Greater;
+    }
+    // in range, so this is exact; `float` lies strictly within one of it
+    let whole = float as i64;
+    match int.cmp(&whole) {
+        Ordering::Equal if whole as f64 > float => Ordering::Greater,
+        Ordering::Equal => Ordering::Less,
+        ordering => ordering,
+    }
+}
+
+/// Resolves `range` against a list of length `len` into `start..end`,
+/// without checking that it is in bounds.
+fn resolve_range(range: &impl RangeBounds<usize>, len: usize) -> (usize, usize) {
+    let start = match range.start_bound() {
+        Bound::Included(&start) => start,
+        Bound::Excluded(&start) => start + 1,
+        Bound::Unbounded => 0,
+    };
+    let end = match range.end_bound() {
+        Bound::Included(&end) => end + 1,
+        Bound::Excluded(&end) => end,
+        Bound::Unbounded => len,
+    };
+    (start, end)
+}
+
+/// Copies the elements of the list whose bytes start at `data` into an
+/// array, if they are `tag_id`; an empty list converts whatever its element
+/// type.
+///
+/// # Safety
+///
+/// `data` must point to a valid list payload in `O` order, and `T` must be
+/// the unaligned `O` element type of `tag_id`.
+pub(crate) unsafe fn list_to_array<O: ByteOrder, T: Copy>(
+    data: *const u8,
+    tag_id: Tag,
+) -> Option<VecViewOwn<T>> {
+    let len = list_len::<O>(data);
+    if len == 0 {
+        return Some(Vec::new().into());
+    }
+    if list_tag_id(data) != tag_id {
+        return None;
+    }
+    // primitive list elements are stored back to back in `O` order, which is
+    // exactly the layout of the array's unaligned element type
+    let elements = unsafe { slice::from_raw_parts(data.add(1 + 4).cast::<T>(), len) };
+    Some(elements.into())
+}
+
+pub(crate) fn array_to_list<O: ByteOrder, T: IntoBytes + Immutable>(
+    tag_id: Tag,
+    elements: &[T],
+) -> OwnedValue<O> {
+    let bytes = elements.as_bytes();
+    let mut data = Vec::with_capacity(1 + 4 + bytes.len());
+    data.push(tag_id as u8);
+    data.extend_from_slice(&byteorder::U32::<O>::new(elements.len() as u32).to_bytes());
+    data.extend_from_slice(bytes);
+    OwnedValue::List(OwnedList {
+        data: data.into(),
+        _marker: PhantomData,
+    })
 }
 
 /// An owned NBT list.
@@ -906,6 +1723,27 @@ impl<O: ByteOrder> OwnedValue<O> {
 ///     // list.remove(0);
 /// }
 /// ```
+///
+/// # Draining
+///
+/// Consuming the list yields each element as an [`OwnedValue`], moving it out
+/// without a copy. Every element carries the list's element type.
+///
+/// ```
+/// use na_nbt::{OwnedList, OwnedValue, Tag};
+/// use zerocopy::byteorder::BigEndian;
+///
+/// let mut list: OwnedList<BigEndian> = OwnedList::default();
+/// list.push("a");
+/// list.push("b");
+///
+/// let mut rebuilt: OwnedList<BigEndian> = OwnedList::default();
+/// for value in list {
+///     assert_eq!(value.tag_id(), Tag::String);
+///     rebuilt.push(value);
+/// }
+/// assert_eq!(rebuilt.len(), 2);
+/// ```
 #[repr(transparent)]
 pub struct OwnedList<O: ByteOrder> {
     pub(crate) data: VecViewOwn<u8>,
@@ -1019,6 +1857,12 @@ impl<O: ByteOrder> OwnedList<O> {
         list_tag_id(self.data.as_ptr())
     }
 
+    /// Same as [`tag_id`](Self::tag_id): the tag of the elements, not of the list.
+    #[inline]
+    pub fn element_tag(&self) -> Tag {
+        self.tag_id()
+    }
+
     #[inline]
     pub fn len(&self) -> usize {
         list_len::<O>(self.data.as_ptr())
@@ -1029,6 +1873,52 @@ impl<O: ByteOrder> OwnedList<O> {
         list_is_empty::<O>(self.data.as_ptr())
     }
 
+    /// Returns an estimate of the memory this list occupies, in bytes.
+    ///
+    /// See [`OwnedValue::memory_size`].
+    pub fn memory_size(&self) -> usize {
+        let mut size = size_of::<Self>() + self.data.capacity();
+        let tag_id = self.tag_id();
+        if tag_id.is_primitive() {
+            return size;
+        }
+        unsafe {
+            let mut ptr = self.data.as_ptr().add(1 + 4);
+            for _ in 0..self.len() {
+                size += heap_size::<O>(tag_id, ptr);
+                ptr = ptr.add(tag_size(tag_id));
+            }
+        }
+        size
+    }
+
+    /// Releases unused capacity in the list's buffer.
+    ///
+    /// Elements keep their own capacity; use [`compact`](Self::compact) to
+    /// shrink them too.
+    #[inline]
+    pub fn shrink_to_fit(&mut self) {
+        self.data.shrink_to_fit();
+    }
+
+    /// Releases unused capacity in the list and in every element, recursively.
+    ///
+    /// See [`OwnedValue::compact`].
+    pub fn compact(&mut self) {
+        self.shrink_to_fit();
+        let tag_id = self.tag_id();
+        if tag_id.is_primitive() {
+            return;
+        }
+        unsafe {
+            let mut ptr = self.data.as_mut_ptr().add(1 + 4);
+            for _ in 0..self.len() {
+                compact_inline::<O>(tag_id, ptr);
+                ptr = ptr.add(tag_size(tag_id));
+            }
+        }
+    }
+
     pub fn get<'a>(&'a self, index: usize) -> Option<ImmutableValue<'a, O>> {
         list_get(self.data.as_ptr(), index)
     }
@@ -1102,6 +1992,521 @@ impl<O: ByteOrder> OwnedList<O> {
             unsafe { VecViewMut::new(&mut self.data.ptr, &mut self.data.len, &mut self.data.cap) };
         list_remove(&mut data, index)
     }
+
+    /// Replaces the element at `index`, returning the old one.
+    ///
+    /// Returns `None` and leaves the list untouched if `index` is out of
+    /// bounds or `value` is not of the list's element type; unlike
+    /// [`insert`](Self::insert), a mismatched tag is rejected rather than a
+    /// panic.
+    pub fn set<V: Into<OwnedValue<O>>>(&mut self, index: usize, value: V) -> Option<OwnedValue<O>> {
+        let mut data =
+            unsafe { VecViewMut::new(&mut self.data.ptr, &mut self.data.len, &mut self.data.cap) };
+        list_set(&mut data, index, value.into())
+    }
+
+    /// Removes and returns the element at `index` if the list holds tag `T`.
+    ///
+    /// Returns `None` and leaves the list untouched if the element type is
+    /// not `T` or `index` is out of bounds. See [`OwnedTag`] for what each
+    /// tag type is returned as.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use na_nbt::{OwnedList, TagInt, TagLong};
+    /// use zerocopy::byteorder::BigEndian;
+    ///
+    /// let mut list: OwnedList<BigEndian> = OwnedList::default();
+    /// list.push(1i32);
+    /// list.push(2i32);
+    ///
+    /// assert_eq!(list.take_::<TagLong>(0), None);
+    /// assert_eq!(list.take_::<TagInt>(0), Some(1));
+    /// assert_eq!(list.len(), 1);
+    /// ```
+    pub fn take_<T: OwnedTag>(&mut self, index: usize) -> Option<T::Owned<O>> {
+        if self.tag_id() != T::TAG || index >= self.len() {
+            cold_path();
+            return None;
+        }
+        T::from_owned(self.remove(index))
+    }
+
+    /// Removes and returns the first element for which `pred` returns `true`.
+    ///
+    /// The remaining elements keep their order. Returns `None` and leaves the
+    /// list untouched if no element matches.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use na_nbt::{OwnedCompound, OwnedList};
+    /// use zerocopy::byteorder::BigEndian;
+    ///
+    /// let mut items: OwnedList<BigEndian> = OwnedList::default();
+    /// for id in ["minecraft:stone", "minecraft:dirt", "minecraft:stone"] {
+    ///     let mut item = OwnedCompound::default();
+    ///     item.insert("id", id);
+    ///     items.push(item);
+    /// }
+    ///
+    /// let removed = items.remove_first_where(|item| {
+    ///     item.get("id").and_then(|id| id.as_string().map(|id| id.decode() == "minecraft:stone"))
+    ///         == Some(true)
+    /// });
+    /// assert!(removed.is_some());
+    /// assert_eq!(items.len(), 2);
+    /// let first = items.get(0).unwrap();
+    /// assert_eq!(first.get("id").unwrap().as_string().unwrap().decode(), "minecraft:dirt");
+    /// ```
+    pub fn remove_first_where<F>(&mut self, pred: F) -> Option<OwnedValue<O>>
+    where
+        F: FnMut(ImmutableValue<'_, O>) -> bool,
+    {
+        let index = self.iter().position(pred)?;
+        Some(self.remove(index))
+    }
+
+    /// Removes consecutive repeated elements, keeping the first of each run.
+    ///
+    /// Elements are compared with [`values_equal`](crate::values_equal), so
+    /// compounds match regardless of key order. Lists of numbers are compared
+    /// in place as raw bytes, which gives the same result without decoding.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use na_nbt::OwnedList;
+    /// use zerocopy::byteorder::BigEndian;
+    ///
+    /// let mut list: OwnedList<BigEndian> = OwnedList::default();
+    /// for value in [1i32, 1, 2, 2, 2, 1] {
+    ///     list.push(value);
+    /// }
+    /// list.dedup();
+    /// assert_eq!(list.iter().map(|v| v.as_int().unwrap()).collect::<Vec<_>>(), [1, 2, 1]);
+    /// ```
+    pub fn dedup(&mut self) {
+        let tag_id = self.tag_id();
+        if !tag_id.is_primitive() {
+            self.dedup_by(|a, b| values_equal(&a, &b));
+            return;
+        }
+
+        let len = self.len();
+        if len < 2 {
+            return;
+        }
+        let size = unsafe { tag_size(tag_id) };
+        let mut kept = 1;
+        unsafe {
+            let elements = self.data.as_mut_ptr().add(1 + 4);
+            for index in 1..len {
+                let current = elements.add(index * size);
+                let last = elements.add((kept - 1) * size);
+                if slice::from_raw_parts(current, size) != slice::from_raw_parts(last, size) {
+                    ptr::copy(current, elements.add(kept * size), size);
+                    kept += 1;
+                }
+            }
+            self.data.set_len(1 + 4 + kept * size);
+            ptr::write(
+                self.data.as_mut_ptr().add(1).cast(),
+                byteorder::U32::<O>::new(kept as u32),
+            );
+        }
+    }
+
+    /// Removes consecutive elements for which `same` returns `true`, keeping
+    /// the first of each run.
+    ///
+    /// As with [`Vec::dedup_by`], `same` receives the element under test
+    /// followed by the last element kept before it.
+    pub fn dedup_by<F>(&mut self, mut same: F)
+    where
+        F: FnMut(ImmutableValue<'_, O>, ImmutableValue<'_, O>) -> bool,
+    {
+        let len = self.len();
+        if len < 2 {
+            return;
+        }
+
+        let mut keep = Vec::with_capacity(len);
+        keep.push(true);
+        let mut last = 0;
+        for index in 1..len {
+            let duplicate = same(self.get(index).unwrap(), self.get(last).unwrap());
+            if !duplicate {
+                last = index;
+            }
+            keep.push(!duplicate);
+        }
+        if !keep.contains(&false) {
+            return;
+        }
+
+        let tag_id = self.tag_id();
+        let list = core::mem::take(self);
+        unsafe { self.data.as_mut_ptr().write(tag_id as u8) };
+        for (value, keep) in list.into_iter().zip(keep) {
+            if keep {
+                unsafe { self.push_unchecked(value) };
+            }
+        }
+    }
+
+    /// Sorts a list of compounds by the value each holds under `key`.
+    ///
+    /// Numbers compare by their exact value, whatever their tag type, with an
+    /// integer before a float of the same value, and strings compare
+    /// lexically. Elements whose field is a number come first, then
+    /// those whose field is a string, then those where the field is missing
+    /// or of another type. The sort is stable, so elements that compare
+    /// equal, including all of those in the last group, keep their order.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use na_nbt::{OwnedCompound, OwnedList};
+    /// use zerocopy::byteorder::BigEndian;
+    ///
+    /// let mut list: OwnedList<BigEndian> = OwnedList::default();
+    /// for (id, slot) in [("stone", 3i8), ("dirt", 1), ("sand", 2)] {
+    ///     let mut item = OwnedCompound::default();
+    ///     item.insert("id", id);
+    ///     item.insert("Slot", slot);
+    ///     list.push(item);
+    /// }
+    ///
+    /// list.sort_by_key_field("Slot")?;
+    /// assert_eq!(list.get(0).unwrap().get("id").unwrap().as_string().unwrap().decode(), "dirt");
+    ///
+    /// list.sort_by_key_field("id")?;
+    /// assert_eq!(list.get(2).unwrap().get("Slot").unwrap().as_byte(), Some(3));
+    /// # Ok::<(), na_nbt::Error>(())
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::TagMismatch`] with `Compound` and the element type if
+    /// the list is not empty and does not hold compounds; the list is not
+    /// changed.
+    pub fn sort_by_key_field(&mut self, key: &str) -> Result<()> {
+        let tag_id = self.tag_id();
+        if self.is_empty() {
+            return Ok(());
+        }
+        if tag_id != Tag::Compound {
+            cold_path();
+            return Err(Error::TagMismatch(Tag::Compound as u8, tag_id as u8));
+        }
+
+        let keys: Vec<SortKey> = self.iter().map(|item| SortKey::of(item.get(key))).collect();
+        let mut order: Vec<usize> = (0..keys.len()).collect();
+        order.sort_by(|&a, &b| keys[a].cmp(&keys[b]));
+
+        let mut values: Vec<Option<OwnedValue<O>>> =
+            core::mem::take(self).into_iter().map(Some).collect();
+        unsafe { self.data.as_mut_ptr().write(tag_id as u8) };
+        for i in order {
+            unsafe { self.push_unchecked(values[i].take().unwrap()) };
+        }
+        Ok(())
+    }
+
+    /// Removes the elements in `range` and returns them as owned values,
+    /// without cloning them.
+    ///
+    /// Unlike [`Vec::drain`], the elements are removed as soon as this is
+    /// called, whether or not the iterator is consumed; any left in it are
+    /// dropped with it. If every element is drained, the element type is
+    /// reset to `End`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use na_nbt::{OwnedList, Tag};
+    /// use zerocopy::byteorder::BigEndian;
+    ///
+    /// let mut list: OwnedList<BigEndian> = OwnedList::default();
+    /// for value in ["a", "b", "c", "d"] {
+    ///     list.push(value);
+    /// }
+    ///
+    /// let batch: Vec<_> = list.drain(..2).collect();
+    /// assert_eq!(batch.len(), 2);
+    /// assert_eq!(list.len(), 2);
+    ///
+    /// list.drain(..);
+    /// assert_eq!(list.element_tag(), Tag::End);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if the start of the range is after its end, or if its end is
+    /// past the end of the list.
+    pub fn drain<R: RangeBounds<usize>>(&mut self, range: R) -> OwnedListIter<O> {
+        let len = self.len();
+        let (start, end) = resolve_range(&range, len);
+        if start > end || end > len {
+            cold_path();
+            panic!("drain range {start}..{end} is out of bounds for a list of length {len}");
+        }
+        self.take_range(start, end).into_iter()
+    }
+
+    /// Replaces the elements in `range` with those of `replacement`,
+    /// returning the removed elements.
+    ///
+    /// The elements after the range are moved once, however many are
+    /// inserted, so this is the way to insert a batch: an empty range inserts
+    /// without removing anything. As with [`drain`](Self::drain), the removed
+    /// elements are gone whether or not the iterator is consumed.
+    ///
+    /// If the elements left outside the range are all replaced, the list
+    /// takes the element type of the replacement, or `End` if that is empty.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use na_nbt::{Error, OwnedList};
+    /// use zerocopy::byteorder::BigEndian;
+    ///
+    /// let mut list: OwnedList<BigEndian> = OwnedList::default();
+    /// for value in [1i32, 2, 3, 4] {
+    ///     list.push(value);
+    /// }
+    ///
+    /// let removed: Vec<_> = list.splice(1..3, [20i32, 21, 22])?.collect();
+    /// assert_eq!(removed.len(), 2);
+    /// assert_eq!(list.len(), 5);
+    /// assert_eq!(list.get(3).unwrap().as_int(), Some(22));
+    ///
+    /// // nothing is inserted if any element has the wrong type
+    /// assert!(matches!(list.splice(5..5, ["five"]), Err(Error::TagMismatch(3, 8))));
+    /// assert_eq!(list.len(), 5);
+    /// # Ok::<(), Error>(())
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::TagMismatch`] with the expected element type and that
+    /// of the first element of `replacement` that does not have it; the list
+    /// is not changed. The expected type is the list's own if any element is
+    /// kept, and otherwise that of the first replacement element.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the start of the range is after its end, or if its end is
+    /// past the end of the list.
+    pub fn splice<R, I>(&mut self, range: R, replacement: I) -> Result<OwnedListIter<O>>
+    where
+        R: RangeBounds<usize>,
+        I: IntoIterator,
+        I::Item: Into<OwnedValue<O>>,
+    {
+        let len = self.len();
+        let (start, end) = resolve_range(&range, len);
+        if start > end || end > len {
+            cold_path();
+            panic!("splice range {start}..{end} is out of bounds for a list of length {len}");
+        }
+
+        let mut expected = (len - (end - start) > 0).then(|| self.tag_id());
+        let mut inserted = OwnedList::<O>::default();
+        for value in replacement {
+            let value = value.into();
+            let tag_id = value.tag_id();
+            match expected {
+                Some(expected) if expected != tag_id => {
+                    cold_path();
+                    return Err(Error::TagMismatch(expected as u8, tag_id as u8));
+                }
+                Some(_) => {}
+                None => expected = Some(tag_id),
+            }
+            inserted.push(value);
+        }
+
+        let removed = self.take_range(start, end);
+        let mut tail = self.take_range(start, self.len());
+        // neither append can fail: the element types were checked above
+        self.append(&mut inserted)?;
+        self.append(&mut tail)?;
+        Ok(removed.into_iter())
+    }
+
+    /// Moves the elements in `start..end` into a new list of the same element
+    /// type, resetting this one's to `End` if every element is taken.
+    fn take_range(&mut self, start: usize, end: usize) -> OwnedList<O> {
+        let len = self.len();
+        let tag_id = self.tag_id();
+        let size = unsafe { tag_size(tag_id) };
+        let count = end - start;
+        let mut drained = Vec::with_capacity(1 + 4 + count * size);
+        drained.push(tag_id as u8);
+        drained.extend_from_slice(&byteorder::U32::<O>::new(count as u32).to_bytes());
+        // the drained elements are moved bit for bit: ownership of their heap
+        // data passes to the new list
+        unsafe {
+            let elements = self.data.as_mut_ptr().add(1 + 4);
+            drained.extend_from_slice(slice::from_raw_parts(
+                elements.add(start * size),
+                count * size,
+            ));
+            ptr::copy(
+                elements.add(end * size),
+                elements.add(start * size),
+                (len - end) * size,
+            );
+            self.data.set_len(1 + 4 + (len - count) * size);
+            ptr::write(
+                self.data.as_mut_ptr().add(1).cast(),
+                byteorder::U32::<O>::new((len - count) as u32),
+            );
+            if count > 0 && count == len {
+                self.data.as_mut_ptr().write(Tag::End as u8);
+            }
+        }
+
+        OwnedList {
+            data: drained.into(),
+            _marker: PhantomData,
+        }
+    }
+
+    /// Removes the elements in `range` and returns them as `T` if the list
+    /// holds `T`.
+    ///
+    /// Returns `None` and leaves the list untouched if the element type is
+    /// not `T`. Otherwise this is [`drain`](Self::drain), decoded.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use na_nbt::OwnedList;
+    /// use zerocopy::byteorder::BigEndian;
+    ///
+    /// let mut list: OwnedList<BigEndian> = OwnedList::default();
+    /// for value in [1i32, 2, 3, 4] {
+    ///     list.push(value);
+    /// }
+    ///
+    /// assert_eq!(list.drain_::<i64>(..), None);
+    /// assert_eq!(list.drain_::<i32>(1..3), Some(vec![2, 3]));
+    /// assert_eq!(list.len(), 2);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if the list holds `T` and the range is out of bounds, as with
+    /// [`drain`](Self::drain).
+    pub fn drain_<T: Primitive>(&mut self, range: impl RangeBounds<usize>) -> Option<Vec<T>> {
+        if self.tag_id() != T::TAG {
+            cold_path();
+            return None;
+        }
+        Some(
+            self.drain(range)
+                .map(|value| T::from_value(&value).unwrap())
+                .collect(),
+        )
+    }
+
+    /// Splits the list in two at `at`, returning the elements from `at` on.
+    ///
+    /// The returned list has the same element type. As with
+    /// [`drain`](Self::drain), this list's element type is reset to `End` if
+    /// every element is moved out.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use na_nbt::OwnedList;
+    /// use zerocopy::byteorder::BigEndian;
+    ///
+    /// let mut list: OwnedList<BigEndian> = OwnedList::default();
+    /// for value in [1i32, 2, 3] {
+    ///     list.push(value);
+    /// }
+    /// let tail = list.split_off(1);
+    /// assert_eq!(list.len(), 1);
+    /// assert_eq!(tail.get(0).unwrap().as_int(), Some(2));
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if `at` is greater than the length of the list.
+    pub fn split_off(&mut self, at: usize) -> OwnedList<O> {
+        let len = self.len();
+        if at > len {
+            cold_path();
+            panic!("split index {at} is out of bounds for a list of length {len}");
+        }
+        self.take_range(at, len)
+    }
+
+    /// Moves all the elements of `other` to the end of this list, leaving
+    /// `other` empty.
+    ///
+    /// An empty list takes the element type of `other`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use na_nbt::{Error, OwnedList, Tag};
+    /// use zerocopy::byteorder::BigEndian;
+    ///
+    /// let mut list: OwnedList<BigEndian> = OwnedList::default();
+    /// let mut ints: OwnedList<BigEndian> = OwnedList::default();
+    /// ints.push(1i32);
+    /// list.append(&mut ints)?;
+    /// assert_eq!(list.element_tag(), Tag::Int);
+    /// assert!(ints.is_empty());
+    ///
+    /// let mut strings: OwnedList<BigEndian> = OwnedList::default();
+    /// strings.push("a");
+    /// assert!(matches!(list.append(&mut strings), Err(Error::TagMismatch(3, 8))));
+    /// assert_eq!(strings.len(), 1);
+    /// # Ok::<(), Error>(())
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::TagMismatch`] with this list's element type and that
+    /// of `other` if both lists have elements of different types; neither
+    /// list is changed.
+    pub fn append(&mut self, other: &mut OwnedList<O>) -> Result<()> {
+        let count = other.len();
+        if count == 0 {
+            return Ok(());
+        }
+        let tag_id = other.tag_id();
+        let len = self.len();
+        if len == 0 {
+            unsafe { self.data.as_mut_ptr().write(tag_id as u8) };
+        } else if self.tag_id() != tag_id {
+            cold_path();
+            return Err(Error::TagMismatch(self.tag_id() as u8, tag_id as u8));
+        }
+
+        // the elements are moved bit for bit, and `other` forgets them
+        self.data.extend_from_slice(&other.data[1 + 4..]);
+        unsafe {
+            ptr::write(
+                self.data.as_mut_ptr().add(1).cast(),
+                byteorder::U32::<O>::new((len + count) as u32),
+            );
+            other.data.set_len(1 + 4);
+            ptr::write(
+                other.data.as_mut_ptr().add(1).cast(),
+                byteorder::U32::<O>::new(0),
+            );
+            other.data.as_mut_ptr().write(Tag::End as u8);
+        }
+        Ok(())
+    }
 }
 
 /// An owned NBT compound (key-value map).
@@ -1149,6 +2554,52 @@ impl<O: ByteOrder> OwnedList<O> {
 ///     println!("{}: {:?}", key.decode(), value.as_int());
 /// }
 /// ```
+///
+/// # Draining
+///
+/// Consuming the compound yields `(String, OwnedValue)` pairs in storage
+/// order, moving each value out without a copy.
+///
+/// ```
+/// use na_nbt::OwnedCompound;
+/// use zerocopy::byteorder::BigEndian;
+///
+/// let mut compound: OwnedCompound<BigEndian> = OwnedCompound::default();
+/// compound.insert("a", 1i32);
+/// compound.insert("b", "two");
+///
+/// let mut rebuilt: OwnedCompound<BigEndian> = OwnedCompound::default();
+/// for (key, value) in compound {
+///     rebuilt.insert(&key, value);
+/// }
+/// assert_eq!(rebuilt.get("a").unwrap().as_int(), Some(1));
+/// ```
+///
+/// # Order
+///
+/// A compound is not a hash map: its entries are kept one after another in
+/// their encoded form, and iteration and writing always follow that order.
+/// A parsed compound keeps the order of its source, and entries added later
+/// go at the end, so building a compound with [`insert`](Self::insert) gives
+/// insertion order. Inserting a key that is already present also moves it to
+/// the end; [`replace`](Self::replace) keeps it in place, and removing an
+/// entry leaves the others in order.
+///
+/// Reading a document and writing it back in the same byte order therefore
+/// gives the same bytes, as long as it has no empty list with an element type
+/// other than `End` (see [`write_to_vec`](crate::ScopedReadableValue::write_to_vec)).
+///
+/// ```
+/// use na_nbt::{OwnedCompound, OwnedValue, ReadableString};
+/// use zerocopy::byteorder::BigEndian;
+///
+/// let mut compound: OwnedCompound<BigEndian> = OwnedCompound::default();
+/// for key in ["zeta", "alpha", "mid"] {
+///     compound.insert(key, 0i8);
+/// }
+/// let keys: Vec<_> = compound.iter().map(|(key, _)| key.decode().into_owned()).collect();
+/// assert_eq!(keys, ["zeta", "alpha", "mid"]);
+/// ```
 #[repr(transparent)]
 pub struct OwnedCompound<O: ByteOrder> {
     pub(crate) data: VecViewOwn<u8>,
@@ -1248,10 +2699,67 @@ impl<O: ByteOrder> OwnedCompound<O> {
         compound_get(self.data.as_ptr(), key)
     }
 
+    /// Returns an estimate of the memory this compound occupies, in bytes.
+    ///
+    /// See [`OwnedValue::memory_size`].
+    pub fn memory_size(&self) -> usize {
+        let mut size = size_of::<Self>() + self.data.capacity();
+        unsafe {
+            let mut ptr = self.data.as_ptr();
+            loop {
+                let tag_id = *ptr.cast::<Tag>();
+                if tag_id == Tag::End {
+                    return size;
+                }
+                let name_len = byteorder::U16::<O>::from_bytes(*ptr.add(1).cast()).get();
+                ptr = ptr.add(1 + 2 + name_len as usize);
+                size += heap_size::<O>(tag_id, ptr);
+                ptr = ptr.add(tag_size(tag_id));
+            }
+        }
+    }
+
+    /// Releases unused capacity in the compound's buffer.
+    ///
+    /// Values keep their own capacity; use [`compact`](Self::compact) to
+    /// shrink them too.
+    #[inline]
+    pub fn shrink_to_fit(&mut self) {
+        self.data.shrink_to_fit();
+    }
+
+    /// Releases unused capacity in the compound and in every value,
+    /// recursively.
+    ///
+    /// See [`OwnedValue::compact`].
+    pub fn compact(&mut self) {
+        self.shrink_to_fit();
+        unsafe {
+            let mut ptr = self.data.as_mut_ptr();
+            loop {
+                let tag_id = *ptr.cast::<Tag>();
+                if tag_id == Tag::End {
+                    return;
+                }
+                let name_len = byteorder::U16::<O>::from_bytes(*ptr.add(1).cast()).get();
+                ptr = ptr.add(1 + 2 + name_len as usize);
+                compact_inline::<O>(tag_id, ptr);
+                ptr = ptr.add(tag_size(tag_id));
+            }
+        }
+    }
+
     #[inline]
     pub fn iter<'a>(&'a self) -> ImmutableCompoundIter<'a, O> {
         compound_iter(self.data.as_ptr())
     }
+
+    /// Returns an iterator over the entries with their keys decoded, so that
+    /// keys can be matched as `&str`. ASCII keys are borrowed.
+    #[inline]
+    pub fn iter_str<'a>(&'a self) -> ImmutableCompoundIterStr<'a, O> {
+        ImmutableCompoundIterStr { iter: self.iter() }
+    }
 }
 
 impl<O: ByteOrder> OwnedCompound<O> {
@@ -1259,10 +2767,64 @@ impl<O: ByteOrder> OwnedCompound<O> {
         compound_get_mut(self.data.as_mut_ptr(), key)
     }
 
+    /// Returns mutable views of the values under several keys at once.
+    ///
+    /// Returns `None` if any key is missing or if any two keys are equal, as
+    /// two views of one value would alias. Like [`get_mut`](Self::get_mut),
+    /// each key resolves to its first occurrence.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use na_nbt::OwnedCompound;
+    /// use zerocopy::byteorder::BigEndian;
+    ///
+    /// let mut compound: OwnedCompound<BigEndian> = OwnedCompound::default();
+    /// compound.insert("health", 20i32);
+    /// compound.insert("food", 5i32);
+    ///
+    /// let [mut health, mut food] = compound.get_disjoint_mut(["health", "food"]).unwrap();
+    /// health.try_update_int(|health| Some(health - 1));
+    /// food.try_update_int(|food| Some(food + 1));
+    /// assert_eq!(compound.get("health").unwrap().as_int(), Some(19));
+    /// assert_eq!(compound.get("food").unwrap().as_int(), Some(6));
+    ///
+    /// assert!(compound.get_disjoint_mut(["food", "food"]).is_none());
+    /// assert!(compound.get_disjoint_mut(["food", "missing"]).is_none());
+    /// ```
+    pub fn get_disjoint_mut<'a, const N: usize>(
+        &'a mut self,
+        keys: [&str; N],
+    ) -> Option<[MutableValue<'a, O>; N]> {
+        for (i, key) in keys.iter().enumerate() {
+            if keys[..i].contains(key) {
+                cold_path();
+                return None;
+            }
+        }
+        // distinct keys resolve to distinct entries, so the views never alias
+        let data = self.data.as_mut_ptr();
+        let values = keys.map(|key| compound_get_mut(data, key));
+        if values.iter().any(Option::is_none) {
+            cold_path();
+            return None;
+        }
+        Some(values.map(Option::unwrap))
+    }
+
     #[inline]
     pub fn iter_mut<'a>(&'a mut self) -> MutableCompoundIter<'a, O> {
         compound_iter_mut(self.data.as_mut_ptr())
     }
+
+    /// Returns an iterator over the entries with their keys decoded and
+    /// mutable views of their values. ASCII keys are borrowed.
+    #[inline]
+    pub fn iter_mut_str<'a>(&'a mut self) -> MutableCompoundIterStr<'a, O> {
+        MutableCompoundIterStr {
+            iter: self.iter_mut(),
+        }
+    }
 }
 
 impl<O: ByteOrder> OwnedCompound<O> {
@@ -1277,4 +2839,86 @@ impl<O: ByteOrder> OwnedCompound<O> {
             unsafe { VecViewMut::new(&mut self.data.ptr, &mut self.data.len, &mut self.data.cap) };
         compound_remove(&mut data, key)
     }
+
+    /// Moves all entries of `other` into `self`, leaving `other` empty.
+    ///
+    /// Each entry is [`insert`](Self::insert)ed in turn, so a key present in
+    /// both compounds takes the value from `other` and moves to the end.
+    /// Nested compounds are replaced whole, not merged.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use na_nbt::OwnedCompound;
+    /// use zerocopy::byteorder::BigEndian;
+    ///
+    /// let mut a: OwnedCompound<BigEndian> = OwnedCompound::default();
+    /// a.insert("x", 1i32);
+    /// a.insert("y", 2i32);
+    /// let mut b: OwnedCompound<BigEndian> = OwnedCompound::default();
+    /// b.insert("x", 10i32);
+    /// b.insert("z", 3i32);
+    ///
+    /// a.append(&mut b);
+    /// assert_eq!(a.get("x").unwrap().as_int(), Some(10));
+    /// assert_eq!(a.iter().count(), 3);
+    /// assert_eq!(b.iter().count(), 0);
+    /// ```
+    pub fn append(&mut self, other: &mut OwnedCompound<O>) {
+        for (key, value) in core::mem::take(other) {
+            self.insert(&key, value);
+        }
+    }
+
+    /// Replaces the value under `key`, returning the old one.
+    ///
+    /// Unlike [`insert`](Self::insert), which moves an existing key to the
+    /// end, the entry keeps its place among the others. The new value may be
+    /// of any tag type. If `key` is absent, the entry is appended and `None`
+    /// is returned.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `value` is `End`.
+    pub fn replace<V: Into<OwnedValue<O>>>(
+        &mut self,
+        key: &str,
+        value: V,
+    ) -> Option<OwnedValue<O>> {
+        let mut data =
+            unsafe { VecViewMut::new(&mut self.data.ptr, &mut self.data.len, &mut self.data.cap) };
+        compound_replace(&mut data, key, value.into())
+    }
+
+    /// Removes and returns the value under `key` if it holds tag `T`.
+    ///
+    /// If the key is missing or holds a different tag type, returns `None`
+    /// and leaves the entry in place. As with
+    /// [`get_or`](crate::ScopedReadableCompound::get_or), no numeric widening
+    /// is performed. See [`OwnedTag`] for what each tag type is returned as.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use na_nbt::{OwnedCompound, TagInt, TagString};
+    /// use zerocopy::byteorder::BigEndian;
+    ///
+    /// let mut compound: OwnedCompound<BigEndian> = OwnedCompound::default();
+    /// compound.insert("level", 42i32);
+    /// compound.insert("name", "Steve");
+    ///
+    /// assert_eq!(compound.take_::<TagInt>("name"), None);
+    /// assert!(compound.get("name").is_some());
+    /// assert_eq!(compound.take_::<TagInt>("level"), Some(42));
+    /// assert!(compound.get("level").is_none());
+    /// let name = compound.take_::<TagString>("name").unwrap();
+    /// assert_eq!(name.decode(), "Steve");
+    /// ```
+    pub fn take_<T: OwnedTag>(&mut self, key: &str) -> Option<T::Owned<O>> {
+        if self.get(key)?.tag_id() != T::TAG {
+            cold_path();
+            return None;
+        }
+        self.remove(key).and_then(T::from_owned)
+    }
 }
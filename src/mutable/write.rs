@@ -1,10 +1,16 @@
-use std::{hint::unreachable_unchecked, io::Write, ptr, slice};
+use alloc::vec::Vec;
+use core::{hint::unreachable_unchecked, ptr, slice};
+#[cfg(feature = "std")]
+use std::io::Write;
 
 use zerocopy::byteorder;
 
+#[cfg(feature = "std")]
+use crate::Error;
 use crate::{
-    ByteOrder, Error, Result, Tag, cold_path,
+    ByteOrder, Result, Tag, cold_path,
     mutable::util::{SIZE_DYN, SIZE_USIZE, list_len, list_tag_id, tag_size},
+    util::EMPTY_LIST,
 };
 
 macro_rules! change_endian {
@@ -13,11 +19,74 @@ macro_rules! change_endian {
     };
 }
 
-pub unsafe fn write_compound<O: ByteOrder>(mut data: *const u8, out: &mut Vec<u8>) -> Result<()> {
+/// Number of bytes [`write_compound`] appends for the compound at `data`.
+pub unsafe fn compound_size<O: ByteOrder>(mut data: *const u8) -> usize {
     unsafe {
         let mut start = data;
+        let mut size = 0;
+
+        loop {
+            let tag_id = Tag::from_u8_unchecked(*data);
+            data = data.add(1);
+
+            if tag_id == Tag::End {
+                return size + data.byte_offset_from_unsigned(start);
+            }
 
-        out.reserve(128);
+            let name_len = byteorder::U16::<O>::from_bytes(*data.cast()).get();
+            data = data.add(2 + name_len as usize);
+
+            if tag_id.is_primitive() {
+                data = data.add(tag_size(tag_id));
+            } else {
+                size += data.byte_offset_from_unsigned(start) + dyn_size::<O>(tag_id, data);
+                data = data.add(SIZE_DYN);
+                start = data;
+            }
+        }
+    }
+}
+
+/// Number of bytes [`write_list`] appends for the list at `data`.
+pub unsafe fn list_size<O: ByteOrder>(mut data: *const u8) -> usize {
+    unsafe {
+        let tag_id = list_tag_id(data);
+        let len = list_len::<O>(data);
+        if tag_id.is_primitive() {
+            return 1 + 4 + tag_size(tag_id) * len;
+        }
+        data = data.add(1 + 4);
+        let mut size = 1 + 4;
+        for _ in 0..len {
+            size += dyn_size::<O>(tag_id, data);
+            data = data.add(SIZE_DYN);
+        }
+        size
+    }
+}
+
+#[inline]
+unsafe fn dyn_size<O: ByteOrder>(tag_id: Tag, data: *const u8) -> usize {
+    unsafe {
+        match tag_id {
+            Tag::ByteArray => 4 + usize::from_ne_bytes(*data.add(SIZE_USIZE).cast()),
+            Tag::String => 2 + usize::from_ne_bytes(*data.add(SIZE_USIZE).cast()),
+            Tag::IntArray => 4 + 4 * usize::from_ne_bytes(*data.add(SIZE_USIZE).cast()),
+            Tag::LongArray => 4 + 8 * usize::from_ne_bytes(*data.add(SIZE_USIZE).cast()),
+            Tag::List => list_size::<O>(ptr::with_exposed_provenance(usize::from_ne_bytes(
+                *data.cast(),
+            ))),
+            Tag::Compound => compound_size::<O>(ptr::with_exposed_provenance(
+                usize::from_ne_bytes(*data.cast()),
+            )),
+            _ => unreachable_unchecked(),
+        }
+    }
+}
+
+pub unsafe fn write_compound<O: ByteOrder>(mut data: *const u8, out: &mut Vec<u8>) -> Result<()> {
+    unsafe {
+        let mut start = data;
 
         loop {
             let tag_id = Tag::from_u8_unchecked(*data);
@@ -163,6 +232,11 @@ pub unsafe fn write_list<O: ByteOrder>(mut data: *const u8, out: &mut Vec<u8>) -
     unsafe {
         let tag_id = list_tag_id(data);
         let len = list_len::<O>(data);
+        if len == 0 {
+            // empty lists are always written as End, whatever their element type
+            out.extend_from_slice(&EMPTY_LIST);
+            return Ok(());
+        }
         if tag_id.is_primitive() {
             out.extend_from_slice(slice::from_raw_parts(data, 1 + 4 + tag_size(tag_id) * len));
         } else {
@@ -262,8 +336,6 @@ pub unsafe fn write_compound_fallback<O: ByteOrder, R: ByteOrder>(
     mut data: *const u8,
     out: &mut Vec<u8>,
 ) -> Result<()> {
-    out.reserve(128);
-
     unsafe {
         loop {
             let start = data;
@@ -495,6 +567,10 @@ pub unsafe fn write_list_fallback<O: ByteOrder, R: ByteOrder>(
     unsafe {
         let tag_id = list_tag_id(data);
         let len = list_len::<O>(data);
+        if len == 0 {
+            out.extend_from_slice(&EMPTY_LIST);
+            return Ok(());
+        }
 
         macro_rules! write_head {
             () => {{
@@ -688,6 +764,7 @@ pub unsafe fn write_list_fallback<O: ByteOrder, R: ByteOrder>(
     }
 }
 
+#[cfg(feature = "std")]
 pub unsafe fn write_compound_to_writer<O: ByteOrder>(
     mut data: *const u8,
     writer: &mut impl Write,
@@ -804,6 +881,7 @@ pub unsafe fn write_compound_to_writer<O: ByteOrder>(
     }
 }
 
+#[cfg(feature = "std")]
 pub unsafe fn write_list_to_writer<O: ByteOrder>(
     mut data: *const u8,
     writer: &mut impl Write,
@@ -811,6 +889,9 @@ pub unsafe fn write_list_to_writer<O: ByteOrder>(
     unsafe {
         let tag_id = list_tag_id(data);
         let len = list_len::<O>(data);
+        if len == 0 {
+            return writer.write_all(&EMPTY_LIST).map_err(Error::IO);
+        }
         if tag_id.is_primitive() {
             writer
                 .write_all(slice::from_raw_parts(data, 1 + 4 + tag_size(tag_id) * len))
@@ -894,6 +975,7 @@ pub unsafe fn write_list_to_writer<O: ByteOrder>(
     }
 }
 
+#[cfg(feature = "std")]
 pub unsafe fn write_compound_to_writer_fallback<O: ByteOrder, R: ByteOrder>(
     mut data: *const u8,
     writer: &mut impl Write,
@@ -1017,6 +1099,7 @@ pub unsafe fn write_compound_to_writer_fallback<O: ByteOrder, R: ByteOrder>(
     }
 }
 
+#[cfg(feature = "std")]
 pub unsafe fn write_list_to_writer_fallback<O: ByteOrder, R: ByteOrder>(
     mut data: *const u8,
     writer: &mut impl Write,
@@ -1024,6 +1107,9 @@ pub unsafe fn write_list_to_writer_fallback<O: ByteOrder, R: ByteOrder>(
     unsafe {
         let tag_id = list_tag_id(data);
         let len = list_len::<O>(data);
+        if len == 0 {
+            return writer.write_all(&EMPTY_LIST).map_err(Error::IO);
+        }
 
         let mut temp = [0u8; 1 + 4];
         ptr::write(temp.as_mut_ptr(), tag_id as u8);
@@ -0,0 +1,121 @@
+//! Nibble-packed byte arrays, as used for light levels.
+//!
+//! Minecraft stores block and sky light as `ByteArray`s holding two 4-bit
+//! values per byte. Value `i` is in byte `i / 2`: even indices take the low
+//! nibble and odd indices the high one.
+//!
+//! [`get_nibble`], [`set_nibble`] and [`xor_with`] work on any slice of
+//! bytes. The byte array views have methods of the same names.
+
+use crate::{VecViewOwn, view::VecViewMut};
+
+/// Returns the 4-bit value at index `i`.
+///
+/// # Example
+///
+/// ```
+/// use na_nbt::nibble::get_nibble;
+///
+/// let light = [0x21u8 as i8, 0xf3u8 as i8];
+/// assert_eq!(get_nibble(&light, 0), 0x1);
+/// assert_eq!(get_nibble(&light, 1), 0x2);
+/// assert_eq!(get_nibble(&light, 3), 0xf);
+/// ```
+///
+/// # Panics
+///
+/// Panics if `i / 2` is out of bounds.
+#[inline]
+pub fn get_nibble(bytes: &[i8], i: usize) -> u8 {
+    let byte = bytes[i / 2] as u8;
+    if i.is_multiple_of(2) {
+        byte & 0x0f
+    } else {
+        byte >> 4
+    }
+}
+
+/// Sets the 4-bit value at index `i`, leaving the other nibble of its byte
+/// alone.
+///
+/// # Panics
+///
+/// Panics if `i / 2` is out of bounds or if `value` does not fit in 4 bits.
+#[inline]
+pub fn set_nibble(bytes: &mut [i8], i: usize, value: u8) {
+    assert!(value <= 0x0f, "nibble value {value} does not fit in 4 bits");
+    let byte = &mut bytes[i / 2];
+    *byte = if i.is_multiple_of(2) {
+        (*byte as u8 & 0xf0 | value) as i8
+    } else {
+        (*byte as u8 & 0x0f | value << 4) as i8
+    };
+}
+
+/// XORs every byte of `bytes` with the byte at the same index of `other`.
+///
+/// # Panics
+///
+/// Panics if the slices differ in length.
+pub fn xor_with(bytes: &mut [i8], other: &[i8]) {
+    assert_eq!(
+        bytes.len(),
+        other.len(),
+        "cannot XOR arrays of different lengths"
+    );
+    for (byte, other) in bytes.iter_mut().zip(other) {
+        *byte ^= other;
+    }
+}
+
+impl VecViewOwn<i8> {
+    /// Returns the 4-bit value at index `i`, low nibble first.
+    ///
+    /// See [`nibble::get_nibble`](get_nibble).
+    #[inline]
+    pub fn get_nibble(&self, i: usize) -> u8 {
+        get_nibble(self, i)
+    }
+
+    /// Sets the 4-bit value at index `i`, low nibble first.
+    ///
+    /// See [`nibble::set_nibble`](set_nibble).
+    #[inline]
+    pub fn set_nibble(&mut self, i: usize, value: u8) {
+        set_nibble(self, i, value)
+    }
+
+    /// XORs every byte with the byte at the same index of `other`.
+    ///
+    /// See [`nibble::xor_with`](xor_with).
+    #[inline]
+    pub fn xor_with(&mut self, other: &[i8]) {
+        xor_with(self, other)
+    }
+}
+
+impl VecViewMut<'_, i8> {
+    /// Returns the 4-bit value at index `i`, low nibble first.
+    ///
+    /// See [`nibble::get_nibble`](get_nibble).
+    #[inline]
+    pub fn get_nibble(&self, i: usize) -> u8 {
+        get_nibble(self, i)
+    }
+
+    /// Sets the 4-bit value at index `i`, low nibble first.
+    ///
+    /// See [`nibble::set_nibble`](set_nibble).
+    #[inline]
+    pub fn set_nibble(&mut self, i: usize, value: u8) {
+        set_nibble(self, i, value)
+    }
+
+    /// XORs every byte with the byte at the same index of `other`.
+    ///
+    /// See [`nibble::xor_with`](xor_with).
+    #[inline]
+    pub fn xor_with(&mut self, other: &[i8]) {
+        xor_with(self, other)
+    }
+}
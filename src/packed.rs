@@ -0,0 +1,148 @@
+//! Bit-packed long arrays, as used for block states and heightmaps.
+//!
+//! Since 1.16, Java Edition packs small unsigned values into the longs of a
+//! `LongArray` without letting any value span two longs: each long holds
+//! `64 / bits_per_entry` values starting from its least significant bit, and
+//! the bits left over at the top are unused.
+//!
+//! [`unpack_bits`] and [`pack_bits`] work on any slice of longs. The array
+//! views have an `unpack_bits` method of their own, and
+//! [`OwnedValue::packed_long_array`] builds a `LongArray` value directly.
+
+use alloc::vec::Vec;
+
+use zerocopy::byteorder;
+
+use crate::{ByteOrder, OwnedValue, VecViewOwn, cold_path, view::VecViewMut};
+
+fn values_per_long(bits_per_entry: u8) -> usize {
+    assert!(
+        (1..=32).contains(&bits_per_entry),
+        "bits_per_entry must be between 1 and 32, got {bits_per_entry}"
+    );
+    64 / bits_per_entry as usize
+}
+
+/// Unpacks the first `count` values of `bits_per_entry` bits each from
+/// `longs`.
+///
+/// Returns `None` if `bits_per_entry` is not between 1 and 32, or if `longs`
+/// is too short to hold `count` values, as happens with data from another
+/// version or a corrupt chunk.
+///
+/// # Example
+///
+/// ```
+/// use na_nbt::{pack_bits, unpack_bits};
+/// use zerocopy::byteorder::BigEndian;
+///
+/// let longs = pack_bits::<BigEndian>(&[1, 2, 3], 4);
+/// assert_eq!(longs[0].get(), 0x321);
+/// assert_eq!(unpack_bits(&longs, 4, 3), Some(vec![1, 2, 3]));
+/// assert_eq!(unpack_bits(&longs, 4, 17), None);
+/// ```
+pub fn unpack_bits<O: ByteOrder>(
+    longs: &[byteorder::I64<O>],
+    bits_per_entry: u8,
+    count: usize,
+) -> Option<Vec<u32>> {
+    if !(1..=32).contains(&bits_per_entry) {
+        cold_path();
+        return None;
+    }
+    let per_long = 64 / bits_per_entry as usize;
+    if longs.len() < count.div_ceil(per_long) {
+        cold_path();
+        return None;
+    }
+    let mask = (1u64 << bits_per_entry) - 1;
+    Some(
+        (0..count)
+            .map(|i| {
+                let long = longs[i / per_long].get() as u64;
+                let shift = (i % per_long) * bits_per_entry as usize;
+                ((long >> shift) & mask) as u32
+            })
+            .collect(),
+    )
+}
+
+/// Packs `values` into longs, `bits_per_entry` bits each.
+///
+/// The result has `values.len().div_ceil(64 / bits_per_entry)` longs.
+///
+/// # Panics
+///
+/// Panics if `bits_per_entry` is not between 1 and 32, or if a value does not
+/// fit in `bits_per_entry` bits.
+pub fn pack_bits<O: ByteOrder>(values: &[u32], bits_per_entry: u8) -> Vec<byteorder::I64<O>> {
+    let per_long = values_per_long(bits_per_entry);
+    values
+        .chunks(per_long)
+        .map(|chunk| {
+            let mut long = 0u64;
+            for (i, &value) in chunk.iter().enumerate() {
+                assert!(
+                    bits_per_entry == 32 || value >> bits_per_entry == 0,
+                    "{value} does not fit in {bits_per_entry} bits"
+                );
+                long |= (value as u64) << (i * bits_per_entry as usize);
+            }
+            byteorder::I64::new(long as i64)
+        })
+        .collect()
+}
+
+impl<O: ByteOrder> OwnedValue<O> {
+    /// Packs `values` into a `LongArray`, `bits_per_entry` bits each, in the
+    /// 1.16+ layout where no value spans two longs.
+    ///
+    /// The array is that of [`pack_bits`]; the values are read back with
+    /// [`unpack_bits`] or the `unpack_bits` method of the array views.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use na_nbt::OwnedValue;
+    /// use zerocopy::byteorder::BigEndian;
+    ///
+    /// // a 16×16×16 section with 5 bits per block: 12 per long, 342 longs
+    /// let states: Vec<u32> = (0..4096).map(|i| i % 20).collect();
+    /// let value = OwnedValue::<BigEndian>::packed_long_array(&states, 5);
+    /// let OwnedValue::LongArray(array) = &value else { unreachable!() };
+    /// assert_eq!(array.len(), 342);
+    /// assert_eq!(array.unpack_bits(5, 4096), Some(states));
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if `bits_per_entry` is not between 1 and 32, or if a value does
+    /// not fit in `bits_per_entry` bits.
+    pub fn packed_long_array(values: &[u32], bits_per_entry: u8) -> Self {
+        OwnedValue::LongArray(pack_bits::<O>(values, bits_per_entry).into())
+    }
+}
+
+impl<O: ByteOrder> VecViewOwn<byteorder::I64<O>> {
+    /// Unpacks the first `count` values of `bits_per_entry` bits each, in the
+    /// 1.16+ layout where no value spans two longs.
+    ///
+    /// Returns `None` if `bits_per_entry` is not between 1 and 32, or if the
+    /// array is too short to hold `count` values.
+    #[inline]
+    pub fn unpack_bits(&self, bits_per_entry: u8, count: usize) -> Option<Vec<u32>> {
+        unpack_bits(self, bits_per_entry, count)
+    }
+}
+
+impl<O: ByteOrder> VecViewMut<'_, byteorder::I64<O>> {
+    /// Unpacks the first `count` values of `bits_per_entry` bits each, in the
+    /// 1.16+ layout where no value spans two longs.
+    ///
+    /// Returns `None` if `bits_per_entry` is not between 1 and 32, or if the
+    /// array is too short to hold `count` values.
+    #[inline]
+    pub fn unpack_bits(&self, bits_per_entry: u8, count: usize) -> Option<Vec<u32>> {
+        unpack_bits(self, bits_per_entry, count)
+    }
+}
@@ -0,0 +1,148 @@
+//! Reading the root header without parsing the document.
+//!
+//! An NBT document starts with the root tag type and, unless the root is
+//! `End`, the root name. That is often all that is needed to decide what to do
+//! with a file, and reading it is far cheaper than parsing the whole tree.
+
+#[cfg(feature = "compression")]
+use alloc::string::String;
+
+use zerocopy::byteorder;
+
+use crate::{ByteOrder, Error, Result, Tag, cold_path};
+
+/// Reads the root tag type and name of an NBT document, without parsing the
+/// rest of it.
+///
+/// The name is `None` when the root is `End`, which has no name. Nothing past
+/// the name is looked at, so a document with a valid header may still fail to
+/// parse.
+///
+/// # Example
+///
+/// ```
+/// use na_nbt::{Tag, peek_root};
+/// use zerocopy::byteorder::BigEndian;
+///
+/// // a compound named "hello", followed by a payload that is never read
+/// let data = [0x0a, 0x00, 0x05, b'h', b'e', b'l', b'l', b'o', 0xff];
+/// assert_eq!(peek_root::<BigEndian>(&data)?, (Tag::Compound, Some("hello")));
+/// # Ok::<(), na_nbt::Error>(())
+/// ```
+///
+/// # Errors
+///
+/// Returns an error if:
+/// - The header is truncated ([`Error::EndOfFile`])
+/// - The root tag type is invalid ([`Error::InvalidTagType`])
+/// - The root name is not valid UTF-8 ([`Error::Message`]); this only happens
+///   for names containing NUL or characters outside the Basic Multilingual
+///   Plane, which Modified UTF-8 encodes differently
+pub fn peek_root<O: ByteOrder>(data: &[u8]) -> Result<(Tag, Option<&str>)> {
+    let Some((&tag_id, rest)) = data.split_first() else {
+        cold_path();
+        return Err(Error::EndOfFile);
+    };
+    let tag_id = root_tag(tag_id)?;
+    if tag_id == Tag::End {
+        return Ok((tag_id, None));
+    }
+
+    let Some((name_len, rest)) = rest.split_first_chunk::<2>() else {
+        cold_path();
+        return Err(Error::EndOfFile);
+    };
+    let name_len = byteorder::U16::<O>::from_bytes(*name_len).get() as usize;
+    let Some(name) = rest.get(..name_len) else {
+        cold_path();
+        return Err(Error::EndOfFile);
+    };
+
+    Ok((tag_id, Some(root_name(name)?)))
+}
+
+/// Like [`peek_root`], but for data that may be compressed.
+///
+/// Gzip and zlib data are recognized by their magic bytes and inflated only as
+/// far as needed to read the header; anything else is read as uncompressed
+/// NBT. The name is returned owned, since it does not exist in `data`.
+///
+/// # Example
+///
+/// ```
+/// use flate2::{Compression, write::GzEncoder};
+/// use na_nbt::{Tag, peek_root_compressed};
+/// use std::io::Write;
+/// use zerocopy::byteorder::BigEndian;
+///
+/// let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+/// encoder.write_all(&[0x0a, 0x00, 0x02, b'h', b'i', 0x00]).unwrap();
+/// let data = encoder.finish().unwrap();
+///
+/// let (tag_id, name) = peek_root_compressed::<BigEndian>(&data)?;
+/// assert_eq!(tag_id, Tag::Compound);
+/// assert_eq!(name.as_deref(), Some("hi"));
+/// # Ok::<(), na_nbt::Error>(())
+/// ```
+///
+/// # Errors
+///
+/// Same as [`peek_root`], plus [`Error::IO`] if the compressed data is corrupt.
+///
+/// This function requires the `compression` feature.
+#[cfg(feature = "compression")]
+pub fn peek_root_compressed<O: ByteOrder>(data: &[u8]) -> Result<(Tag, Option<String>)> {
+    use flate2::read::{MultiGzDecoder, ZlibDecoder};
+    use std::io::{self, Read};
+
+    use crate::{Compression, detect_compression};
+
+    fn peek_reader<O: ByteOrder>(mut reader: impl Read) -> Result<(Tag, Option<String>)> {
+        let read_exact = |reader: &mut dyn Read, buf: &mut [u8]| {
+            reader.read_exact(buf).map_err(|error| {
+                if error.kind() == io::ErrorKind::UnexpectedEof {
+                    Error::EndOfFile
+                } else {
+                    Error::IO(error)
+                }
+            })
+        };
+
+        let mut tag_id = [0u8];
+        read_exact(&mut reader, &mut tag_id)?;
+        let tag_id = root_tag(tag_id[0])?;
+        if tag_id == Tag::End {
+            return Ok((tag_id, None));
+        }
+
+        let mut name_len = [0u8; 2];
+        read_exact(&mut reader, &mut name_len)?;
+        let name_len = byteorder::U16::<O>::from_bytes(name_len).get() as usize;
+        let mut name = alloc::vec![0u8; name_len];
+        read_exact(&mut reader, &mut name)?;
+
+        Ok((tag_id, Some(root_name(&name)?.into())))
+    }
+
+    match detect_compression(data) {
+        Compression::Gzip => peek_reader::<O>(MultiGzDecoder::new(data)),
+        Compression::Zlib => peek_reader::<O>(ZlibDecoder::new(data)),
+        Compression::None => {
+            peek_root::<O>(data).map(|(tag_id, name)| (tag_id, name.map(Into::into)))
+        }
+    }
+}
+
+#[inline]
+fn root_tag(tag_id: u8) -> Result<Tag> {
+    if tag_id > 12 {
+        cold_path();
+        return Err(Error::InvalidTagType(tag_id));
+    }
+    Ok(unsafe { Tag::from_u8_unchecked(tag_id) })
+}
+
+#[inline]
+fn root_name(name: &[u8]) -> Result<&str> {
+    core::str::from_utf8(name).map_err(|_| Error::Message("root name is not valid UTF-8".into()))
+}
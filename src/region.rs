@@ -0,0 +1,176 @@
+//! Reading chunks from Anvil region files (`.mca`).
+//!
+//! A region file holds up to 32×32 chunks. It starts with an 8 KiB header: a
+//! table of where each chunk lives, in 4 KiB sectors, followed by a table of
+//! modification times. Each chunk is stored as a big-endian length, a
+//! compression type and the compressed NBT document.
+//!
+//! [`RegionReader`] reads the header once and then seeks to each chunk as it
+//! is requested, so a region file never has to be held in memory as a whole.
+//!
+//! This module requires the `compression` feature.
+
+use alloc::{boxed::Box, format, vec::Vec};
+use std::io::{Read, Seek, SeekFrom};
+
+use flate2::read::{MultiGzDecoder, ZlibDecoder};
+use zerocopy::BigEndian;
+
+use crate::{ByteOrder, Error, OwnedValue, Result, cold_path, read_owned};
+
+const SECTOR_SIZE: u64 = 4096;
+const CHUNKS: usize = 32 * 32;
+
+/// Reads chunks from a region file by seeking to each one.
+///
+/// Chunk coordinates may be given either relative to the region (`0..32`) or
+/// as absolute chunk coordinates; only the low five bits of each are used.
+///
+/// # Example
+///
+/// ```no_run
+/// use na_nbt::{Error, OwnedValue, RegionReader};
+/// use std::fs::File;
+/// use zerocopy::byteorder::BigEndian;
+///
+/// let file = File::open("r.0.0.mca").map_err(Error::IO)?;
+/// let mut region = RegionReader::from_reader(file)?;
+/// if let Some(chunk) = region.read_chunk::<BigEndian>(3, 7)? {
+///     let chunk: OwnedValue<BigEndian> = chunk;
+///     # let _ = chunk;
+/// }
+/// # Ok::<(), na_nbt::Error>(())
+/// ```
+pub struct RegionReader<R> {
+    reader: R,
+    locations: Box<[u32; CHUNKS]>,
+    timestamps: Box<[u32; CHUNKS]>,
+}
+
+impl<R: Read + Seek> RegionReader<R> {
+    /// Reads the header of the region file at the start of `reader`.
+    ///
+    /// Nothing else is read until a chunk is requested.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::IO`] if seeking fails or the header is incomplete.
+    pub fn from_reader(mut reader: R) -> Result<Self> {
+        let mut header = [0u8; 2 * SECTOR_SIZE as usize];
+        reader.seek(SeekFrom::Start(0)).map_err(Error::IO)?;
+        reader.read_exact(&mut header).map_err(Error::IO)?;
+
+        let mut locations = Box::new([0u32; CHUNKS]);
+        let mut timestamps = Box::new([0u32; CHUNKS]);
+        let (location_table, timestamp_table) = header.split_at(SECTOR_SIZE as usize);
+        for (slot, entry) in locations.iter_mut().zip(location_table.chunks_exact(4)) {
+            *slot = u32::from_be_bytes(entry.try_into().unwrap());
+        }
+        for (slot, entry) in timestamps.iter_mut().zip(timestamp_table.chunks_exact(4)) {
+            *slot = u32::from_be_bytes(entry.try_into().unwrap());
+        }
+
+        Ok(Self {
+            reader,
+            locations,
+            timestamps,
+        })
+    }
+
+    /// Returns `true` if the region holds the chunk at `x`, `z`.
+    #[inline]
+    pub fn has_chunk(&self, x: i32, z: i32) -> bool {
+        self.locations[index(x, z)] != 0
+    }
+
+    /// Returns the time the chunk at `x`, `z` was last saved, in seconds since
+    /// the Unix epoch, or 0 if it was never saved.
+    #[inline]
+    pub fn timestamp(&self, x: i32, z: i32) -> u32 {
+        self.timestamps[index(x, z)]
+    }
+
+    /// Reads and decompresses the chunk at `x`, `z`, returning its NBT
+    /// document without parsing it.
+    ///
+    /// Returns `Ok(None)` if the region does not hold the chunk.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if:
+    /// - Seeking or reading fails, or the data is not valid gzip or zlib
+    ///   ([`Error::IO`])
+    /// - The chunk is longer than the sectors allotted to it, uses a
+    ///   compression type other than gzip, zlib or none, or is stored outside
+    ///   the region file ([`Error::Message`])
+    pub fn read_chunk_bytes(&mut self, x: i32, z: i32) -> Result<Option<Vec<u8>>> {
+        let location = self.locations[index(x, z)];
+        if location == 0 {
+            return Ok(None);
+        }
+        let offset = (location >> 8) as u64 * SECTOR_SIZE;
+        let sectors = (location & 0xff) as u64;
+
+        let mut header = [0u8; 5];
+        self.reader
+            .seek(SeekFrom::Start(offset))
+            .map_err(Error::IO)?;
+        self.reader.read_exact(&mut header).map_err(Error::IO)?;
+        let length = u32::from_be_bytes(header[..4].try_into().unwrap()) as u64;
+        if length == 0 || length + 4 > sectors * SECTOR_SIZE {
+            cold_path();
+            return Err(Error::Message(format!(
+                "chunk length {length} exceeds its {sectors} sectors"
+            )));
+        }
+
+        // the length counts the compression type byte
+        let mut data = (&mut self.reader).take(length - 1);
+        let mut out = Vec::new();
+        match header[4] {
+            1 => MultiGzDecoder::new(data).read_to_end(&mut out),
+            2 => ZlibDecoder::new(data).read_to_end(&mut out),
+            3 => data.read_to_end(&mut out),
+            kind => {
+                cold_path();
+                return Err(Error::Message(if kind & 0x80 != 0 {
+                    "chunk is stored outside the region file".into()
+                } else {
+                    format!("unsupported chunk compression type {kind}")
+                }));
+            }
+        }
+        .map_err(Error::IO)?;
+        Ok(Some(out))
+    }
+
+    /// Reads and parses the chunk at `x`, `z`.
+    ///
+    /// Returns `Ok(None)` if the region does not hold the chunk.
+    ///
+    /// # Errors
+    ///
+    /// Same as [`read_chunk_bytes`](Self::read_chunk_bytes) and
+    /// [`read_owned`].
+    pub fn read_chunk<STORE: ByteOrder>(
+        &mut self,
+        x: i32,
+        z: i32,
+    ) -> Result<Option<OwnedValue<STORE>>> {
+        match self.read_chunk_bytes(x, z)? {
+            Some(bytes) => read_owned::<BigEndian, STORE>(&bytes).map(Some),
+            None => Ok(None),
+        }
+    }
+
+    /// Returns the underlying reader.
+    #[inline]
+    pub fn into_inner(self) -> R {
+        self.reader
+    }
+}
+
+#[inline]
+fn index(x: i32, z: i32) -> usize {
+    (x & 31) as usize + (z & 31) as usize * 32
+}
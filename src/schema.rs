@@ -0,0 +1,205 @@
+//! Structural checks of NBT values against a simple schema.
+//!
+//! A [`Schema`] describes the shape a value is expected to have: which keys
+//! a compound holds, what a list contains and which leaves are ints or
+//! strings. [`validate`] walks a value and its schema together and reports
+//! every place where they disagree, so that untrusted data can be rejected
+//! before code relies on the type of a field.
+//!
+//! Keys of a compound that the schema does not mention are allowed.
+//!
+//! # Example
+//!
+//! ```
+//! use std::collections::HashMap;
+//!
+//! use na_nbt::{OwnedCompound, OwnedValue, Tag};
+//! use na_nbt::schema::{Schema, validate};
+//! use zerocopy::byteorder::BigEndian;
+//!
+//! let schema = Schema::Compound(HashMap::from([
+//!     ("name".to_string(), Schema::String),
+//!     ("level".to_string(), Schema::Int),
+//!     ("tags".to_string(), Schema::Optional(Box::new(Schema::List(Box::new(Schema::String))))),
+//! ]));
+//!
+//! let mut player: OwnedCompound<BigEndian> = OwnedCompound::default();
+//! player.insert("name", "Steve");
+//! player.insert("level", 5);
+//! assert!(validate(&OwnedValue::Compound(player), &schema).is_ok());
+//!
+//! let mut player: OwnedCompound<BigEndian> = OwnedCompound::default();
+//! player.insert("level", "five");
+//! let errors = validate(&OwnedValue::Compound(player), &schema).unwrap_err();
+//! assert_eq!(errors.len(), 2);
+//! assert_eq!(errors[0].path, "level");
+//! assert_eq!(errors[0].found, Some(Tag::String));
+//! assert_eq!(errors[1].path, "name");
+//! assert_eq!(errors[1].found, None);
+//! ```
+
+use std::{
+    collections::HashMap,
+    fmt::{self, Write},
+    string::String,
+    vec::Vec,
+};
+
+use crate::{
+    Tag,
+    value_trait::{ScopedReadableCompound, ScopedReadableValue, ValueScoped},
+};
+
+/// The expected shape of a value.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Schema {
+    /// A compound holding at least the given keys, each matching its schema.
+    Compound(HashMap<String, Schema>),
+    /// A list whose elements all match the schema.
+    List(Box<Schema>),
+    /// An int.
+    Int,
+    /// A string.
+    String,
+    /// A compound entry that may be missing; if present, it must match the
+    /// schema. Outside a compound this is the same as the inner schema.
+    Optional(Box<Schema>),
+    /// Any value.
+    Any,
+}
+
+/// A place where a value does not match its [`Schema`], as reported by
+/// [`validate`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SchemaError {
+    /// Where the mismatch is: compound keys separated by `.` and list indices
+    /// in brackets, as in `players[2].name`. The root is the empty path.
+    pub path: String,
+    /// The tag type the schema asks for.
+    pub expected: Tag,
+    /// The tag type found, or `None` if a required entry is missing.
+    pub found: Option<Tag>,
+}
+
+impl fmt::Display for SchemaError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let path = if self.path.is_empty() {
+            "root"
+        } else {
+            &self.path
+        };
+        match self.found {
+            Some(found) => write!(f, "{path}: expected {}, found {found}", self.expected),
+            None => write!(f, "{path}: missing {}", self.expected),
+        }
+    }
+}
+
+impl std::error::Error for SchemaError {}
+
+/// Checks `value` against `schema`, collecting every mismatch.
+///
+/// The whole value is checked, not just up to the first mismatch. The
+/// entries of a compound are checked in key order, so the errors come in a
+/// stable order.
+///
+/// # Errors
+///
+/// Returns the mismatches found, if there are any.
+pub fn validate<'doc>(
+    value: &impl ScopedReadableValue<'doc>,
+    schema: &Schema,
+) -> Result<(), Vec<SchemaError>> {
+    let mut errors = Vec::new();
+    let mut path = String::new();
+    check(value, schema, &mut path, &mut errors);
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(errors)
+    }
+}
+
+fn check<'doc>(
+    value: &impl ScopedReadableValue<'doc>,
+    schema: &Schema,
+    path: &mut String,
+    errors: &mut Vec<SchemaError>,
+) {
+    let expected = match schema {
+        Schema::Any => return,
+        Schema::Optional(schema) => return check(value, schema, path, errors),
+        Schema::Compound(_) => Tag::Compound,
+        Schema::List(_) => Tag::List,
+        Schema::Int => Tag::Int,
+        Schema::String => Tag::String,
+    };
+    let found = value.tag_id();
+    if found != expected {
+        errors.push(SchemaError {
+            path: path.clone(),
+            expected,
+            found: Some(found),
+        });
+        return;
+    }
+
+    value.visit_scoped(|value| match (value, schema) {
+        (ValueScoped::Compound(compound), Schema::Compound(entries)) => {
+            check_compound(&compound, entries, path, errors)
+        }
+        (ValueScoped::List(list), Schema::List(element)) => {
+            let len = path.len();
+            for (i, item) in list.into_iter().enumerate() {
+                let _ = write!(path, "[{i}]");
+                check(&item, element, path, errors);
+                path.truncate(len);
+            }
+        }
+        _ => {}
+    })
+}
+
+fn check_compound<'a>(
+    compound: &impl ScopedReadableCompound<'a>,
+    entries: &HashMap<String, Schema>,
+    path: &mut String,
+    errors: &mut Vec<SchemaError>,
+) {
+    let mut keys: Vec<_> = entries.keys().collect();
+    keys.sort_unstable();
+
+    let len = path.len();
+    for key in keys {
+        if len > 0 {
+            path.push('.');
+        }
+        path.push_str(key);
+        let schema = &entries[key];
+        match compound.get_scoped(key) {
+            Some(item) => check(&item, schema, path, errors),
+            None => {
+                if let Some(expected) = required(schema) {
+                    errors.push(SchemaError {
+                        path: path.clone(),
+                        expected,
+                        found: None,
+                    });
+                }
+            }
+        }
+        path.truncate(len);
+    }
+}
+
+/// Returns the tag type a missing entry should have had, or `None` if the
+/// entry may be missing.
+fn required(schema: &Schema) -> Option<Tag> {
+    match schema {
+        Schema::Compound(_) => Some(Tag::Compound),
+        Schema::List(_) => Some(Tag::List),
+        Schema::Int => Some(Tag::Int),
+        Schema::String => Some(Tag::String),
+        Schema::Optional(_) | Schema::Any => None,
+    }
+}
@@ -124,7 +124,11 @@ use std::{io::Write, marker::PhantomData, ptr};
 use serde::{Serialize, ser};
 use zerocopy::byteorder;
 
-use crate::{ByteOrder, Error, Result, Tag, cold_path};
+use crate::{
+    ByteOrder, Error, Result, Tag,
+    array::{INT_ARRAY_NAME, LONG_ARRAY_NAME},
+    cold_path,
+};
 
 /// Internal mode for tracking array serialization.
 #[derive(Clone, Copy, PartialEq, Eq, Default)]
@@ -518,13 +522,13 @@ impl<'a, O: ByteOrder> ser::Serializer for &'a mut Serializer<O> {
         T: ?Sized + Serialize,
     {
         match name {
-            "na_nbt:int_array" => {
+            INT_ARRAY_NAME => {
                 self.array_mode = ArrayMode::IntArray;
                 let result = value.serialize(&mut *self);
                 self.array_mode = ArrayMode::None;
                 result
             }
-            "na_nbt:long_array" => {
+            LONG_ARRAY_NAME => {
                 self.array_mode = ArrayMode::LongArray;
                 let result = value.serialize(&mut *self);
                 self.array_mode = ArrayMode::None;
@@ -0,0 +1,580 @@
+//! SNBT, the text form of NBT used in commands and data packs.
+//!
+//! [`to_snbt`] writes any value as SNBT and [`from_snbt`] parses it back into
+//! an [`OwnedValue`]. Each tag has its own notation:
+//!
+//! | Tag | SNBT |
+//! |-----|------|
+//! | Byte | `1b`, or `true` / `false` when parsing |
+//! | Short | `1s` |
+//! | Int | `1` |
+//! | Long | `1L` |
+//! | Float | `1.5f` |
+//! | Double | `1.5d`, or `1.5` when parsing |
+//! | String | `"text"` or `'text'`, or unquoted when parsing |
+//! | List | `[1, 2]` |
+//! | Compound | `{key: 1, "other key": 2}` |
+//! | ByteArray | `[B; 1b, 2b]` |
+//! | IntArray | `[I; 1, 2]` |
+//! | LongArray | `[L; 1L, 2L]` |
+//!
+//! SNBT has no notation for a NaN or infinite float or double. An infinity is
+//! written as a literal out of range, such as `1e39f`, while a NaN cannot be
+//! written at all and makes [`to_snbt`] fail. When parsing, words such as
+//! `nan` and `inf` are strings, as in the game.
+//!
+//! Numeric suffixes are case-insensitive when parsing. A word of letters,
+//! digits and `_-.+` that is not a number, such as `stone`, is read as a
+//! string; anything else, such as `minecraft:stone`, must be quoted. Strings are always
+//! written quoted, and keys only when they contain characters other than
+//! letters, digits and `_-.+`.
+//!
+//! With the `serde` feature, [`to_snbt_string`] and [`from_snbt_str`] take
+//! any serde type through SNBT instead of binary NBT.
+
+use alloc::{
+    format,
+    string::{String, ToString},
+    vec::Vec,
+};
+use core::fmt::Write as _;
+
+use zerocopy::byteorder;
+
+use crate::{
+    ByteOrder, Error, OwnedCompound, OwnedList, OwnedValue, ReadableString, Result,
+    ScopedReadableValue, Tag, ValueScoped, cold_path,
+};
+
+/// Nesting deeper than this is rejected when parsing, as in the game.
+const MAX_DEPTH: usize = 512;
+
+/// Writes a value as SNBT.
+///
+/// The output is compact, with no spaces. A root of type `End` has no SNBT
+/// form and is written as an empty string.
+///
+/// SNBT has no literal for a NaN or infinite `Float` or `Double` either. An
+/// infinity is written as a number too large for its type, such as `1e39f`,
+/// which parses back to the same infinity.
+///
+/// # Example
+///
+/// ```
+/// use na_nbt::{OwnedCompound, OwnedValue, to_snbt};
+/// use zerocopy::byteorder::BigEndian;
+///
+/// let mut compound: OwnedCompound<BigEndian> = OwnedCompound::default();
+/// compound.insert("id", "minecraft:stone");
+/// compound.insert("Count", 64i8);
+/// compound.insert("flags", vec![1i8, 0]);
+///
+/// let snbt = to_snbt(&OwnedValue::Compound(compound))?;
+/// assert_eq!(snbt, r#"{id:"minecraft:stone",Count:64b,flags:[B;1b,0b]}"#);
+/// # Ok::<(), na_nbt::Error>(())
+/// ```
+///
+/// # Errors
+///
+/// Returns [`Error::Message`] if the value holds a NaN `Float` or `Double`,
+/// which has no SNBT form.
+pub fn to_snbt<'doc>(value: &impl ScopedReadableValue<'doc>) -> Result<String> {
+    let mut out = String::new();
+    write_value(value, &mut out)?;
+    Ok(out)
+}
+
+fn write_value<'doc>(value: &impl ScopedReadableValue<'doc>, out: &mut String) -> Result<()> {
+    value.visit_scoped(|value| {
+        match value {
+            ValueScoped::End => {}
+            ValueScoped::Byte(value) => write!(out, "{value}b").unwrap(),
+            ValueScoped::Short(value) => write!(out, "{value}s").unwrap(),
+            ValueScoped::Int(value) => write!(out, "{value}").unwrap(),
+            ValueScoped::Long(value) => write!(out, "{value}L").unwrap(),
+            ValueScoped::Float(value) if value.is_nan() => return nan(),
+            // SNBT has no literal for infinity
+            ValueScoped::Float(value) if value.is_infinite() => {
+                out.push_str(if value > 0.0 { "1e39f" } else { "-1e39f" })
+            }
+            ValueScoped::Float(value) => write!(out, "{value:?}f").unwrap(),
+            ValueScoped::Double(value) if value.is_nan() => return nan(),
+            ValueScoped::Double(value) if value.is_infinite() => {
+                out.push_str(if value > 0.0 { "1e309d" } else { "-1e309d" })
+            }
+            ValueScoped::Double(value) => write!(out, "{value:?}d").unwrap(),
+            ValueScoped::ByteArray(values) => {
+                write_array(out, 'B', values.iter().map(|value| format!("{value}b")))
+            }
+            ValueScoped::IntArray(values) => {
+                write_array(out, 'I', values.iter().map(|value| value.get().to_string()))
+            }
+            ValueScoped::LongArray(values) => write_array(
+                out,
+                'L',
+                values.iter().map(|value| format!("{}L", value.get())),
+            ),
+            ValueScoped::String(value) => write_quoted(&value.decode(), out),
+            ValueScoped::List(list) => {
+                out.push('[');
+                for (i, item) in list.into_iter().enumerate() {
+                    if i > 0 {
+                        out.push(',');
+                    }
+                    write_value(&item, out)?;
+                }
+                out.push(']');
+            }
+            ValueScoped::Compound(compound) => {
+                out.push('{');
+                for (i, (key, item)) in compound.into_iter().enumerate() {
+                    if i > 0 {
+                        out.push(',');
+                    }
+                    let key = key.decode();
+                    if !key.is_empty() && key.chars().all(is_unquoted_char) {
+                        out.push_str(&key);
+                    } else {
+                        write_quoted(&key, out);
+                    }
+                    out.push(':');
+                    write_value(&item, out)?;
+                }
+                out.push('}');
+            }
+        }
+        Ok(())
+    })
+}
+
+fn nan() -> Result<()> {
+    cold_path();
+    Err(Error::Message("NaN has no SNBT form".into()))
+}
+
+fn write_array(out: &mut String, prefix: char, values: impl Iterator<Item = String>) {
+    out.push('[');
+    out.push(prefix);
+    out.push(';');
+    for (i, value) in values.enumerate() {
+        if i > 0 {
+            out.push(',');
+        }
+        out.push_str(&value);
+    }
+    out.push(']');
+}
+
+fn write_quoted(value: &str, out: &mut String) {
+    out.push('"');
+    for c in value.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+}
+
+#[inline]
+fn is_unquoted_char(c: char) -> bool {
+    c.is_ascii_alphanumeric() || matches!(c, '_' | '-' | '.' | '+')
+}
+
+/// Parses SNBT into an owned value.
+///
+/// Whitespace is allowed between tokens. See the
+/// [module documentation](self) for the notation of each tag.
+///
+/// # Example
+///
+/// ```
+/// use na_nbt::{Tag, from_snbt};
+/// use zerocopy::byteorder::BigEndian;
+///
+/// let value = from_snbt::<BigEndian>(r#"{id: "minecraft:stone", Count: 64b, pos: [I; 1, 2, 3]}"#)?;
+/// assert_eq!(value.get("Count").unwrap().as_byte(), Some(64));
+/// assert_eq!(value.get("pos").unwrap().tag_id(), Tag::IntArray);
+/// # Ok::<(), na_nbt::Error>(())
+/// ```
+///
+/// # Errors
+///
+/// Returns an error if:
+/// - The text ends early ([`Error::EndOfFile`])
+/// - A list mixes element types ([`Error::TagMismatch`])
+/// - The text is otherwise malformed or nested too deeply ([`Error::Message`])
+/// - Text remains after the value ([`Error::TrailingData`])
+///
+/// Errors other than [`Error::TrailingData`] are wrapped in [`Error::At`]
+/// with the byte offset in `text` where they occurred.
+pub fn from_snbt<O: ByteOrder>(text: &str) -> Result<OwnedValue<O>> {
+    let mut parser = Parser {
+        text: text.as_bytes(),
+        pos: 0,
+        depth: 0,
+    };
+    let value = parser.value()?;
+    parser.skip_whitespace();
+    if parser.pos < text.len() {
+        cold_path();
+        return Err(Error::TrailingData(text.len() - parser.pos));
+    }
+    Ok(value)
+}
+
+struct Parser<'a> {
+    text: &'a [u8],
+    pos: usize,
+    depth: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn skip_whitespace(&mut self) {
+        while self.text.get(self.pos).is_some_and(u8::is_ascii_whitespace) {
+            self.pos += 1;
+        }
+    }
+
+    /// Skips whitespace and returns the next byte without consuming it.
+    fn peek(&mut self) -> Result<u8> {
+        self.skip_whitespace();
+        match self.text.get(self.pos) {
+            Some(&byte) => Ok(byte),
+            None => {
+                cold_path();
+                Err(Error::EndOfFile.at(self.pos))
+            }
+        }
+    }
+
+    fn expect(&mut self, expected: u8) -> Result<()> {
+        let byte = self.peek()?;
+        if byte != expected {
+            return error(
+                self.pos,
+                format!("expected '{}', found '{}'", expected as char, byte as char),
+            );
+        }
+        self.pos += 1;
+        Ok(())
+    }
+
+    /// Consumes `byte` if it is next, after whitespace.
+    fn eat(&mut self, byte: u8) -> Result<bool> {
+        if self.peek()? == byte {
+            self.pos += 1;
+            Ok(true)
+        } else {
+            Ok(false)
+        }
+    }
+
+    fn value<O: ByteOrder>(&mut self) -> Result<OwnedValue<O>> {
+        match self.peek()? {
+            b'{' => self.nested(|parser| parser.compound().map(OwnedValue::Compound)),
+            b'[' => self.nested(Self::list_or_array),
+            b'"' | b'\'' => Ok(self.quoted()?.as_str().into()),
+            _ => {
+                let word = self.unquoted()?;
+                Ok(parse_word(word).unwrap_or_else(|| word.into()))
+            }
+        }
+    }
+
+    /// Parses a compound or list, keeping track of the nesting depth.
+    fn nested<O: ByteOrder>(
+        &mut self,
+        parse: impl FnOnce(&mut Self) -> Result<OwnedValue<O>>,
+    ) -> Result<OwnedValue<O>> {
+        if self.depth == MAX_DEPTH {
+            return error(self.pos, format!("nesting is deeper than {MAX_DEPTH}"));
+        }
+        self.depth += 1;
+        let value = parse(self);
+        self.depth -= 1;
+        value
+    }
+
+    fn compound<O: ByteOrder>(&mut self) -> Result<OwnedCompound<O>> {
+        self.expect(b'{')?;
+        let mut compound = OwnedCompound::default();
+        if self.eat(b'}')? {
+            return Ok(compound);
+        }
+        loop {
+            let key = match self.peek()? {
+                b'"' | b'\'' => self.quoted()?,
+                _ => self.unquoted()?.to_string(),
+            };
+            self.expect(b':')?;
+            let value = self.value::<O>()?;
+            compound.insert(&key, value);
+            if !self.eat(b',')? {
+                self.expect(b'}')?;
+                return Ok(compound);
+            }
+        }
+    }
+
+    fn list_or_array<O: ByteOrder>(&mut self) -> Result<OwnedValue<O>> {
+        self.expect(b'[')?;
+        // an array starts with its type and a semicolon, as in `[I;`
+        let rest = &self.text[self.pos..];
+        if rest.len() >= 2 && rest[1] == b';' && matches!(rest[0], b'B' | b'I' | b'L') {
+            let tag_id = match rest[0] {
+                b'B' => Tag::ByteArray,
+                b'I' => Tag::IntArray,
+                _ => Tag::LongArray,
+            };
+            self.pos += 2;
+            return self.array(tag_id);
+        }
+
+        let mut list = OwnedList::default();
+        if self.eat(b']')? {
+            return Ok(OwnedValue::List(list));
+        }
+        loop {
+            self.skip_whitespace();
+            let at = self.pos;
+            let value = self.value::<O>()?;
+            if !list.is_empty() && value.tag_id() != list.tag_id() {
+                cold_path();
+                return Err(Error::TagMismatch(list.tag_id() as u8, value.tag_id() as u8).at(at));
+            }
+            list.push(value);
+            if !self.eat(b',')? {
+                self.expect(b']')?;
+                return Ok(OwnedValue::List(list));
+            }
+        }
+    }
+
+    fn array<O: ByteOrder>(&mut self, tag_id: Tag) -> Result<OwnedValue<O>> {
+        let (min, max) = match tag_id {
+            Tag::ByteArray => (i8::MIN as i64, i8::MAX as i64),
+            Tag::IntArray => (i32::MIN as i64, i32::MAX as i64),
+            _ => (i64::MIN, i64::MAX),
+        };
+        let mut values = Vec::new();
+        if !self.eat(b']')? {
+            loop {
+                self.skip_whitespace();
+                let at = self.pos;
+                let word = self.unquoted()?;
+                let value = match parse_word::<O>(word) {
+                    Some(OwnedValue::Byte(value)) => value as i64,
+                    Some(OwnedValue::Short(value)) => value.get() as i64,
+                    Some(OwnedValue::Int(value)) => value.get() as i64,
+                    Some(OwnedValue::Long(value)) => value.get(),
+                    _ => return error(at, format!("{word} is not an integer")),
+                };
+                if !(min..=max).contains(&value) {
+                    return error(at, format!("{value} is out of range for {tag_id:?}"));
+                }
+                values.push(value);
+                if !self.eat(b',')? {
+                    self.expect(b']')?;
+                    break;
+                }
+            }
+        }
+
+        Ok(match tag_id {
+            Tag::ByteArray => values
+                .into_iter()
+                .map(|value| value as i8)
+                .collect::<Vec<_>>()
+                .into(),
+            Tag::IntArray => values
+                .into_iter()
+                .map(|value| byteorder::I32::<O>::new(value as i32))
+                .collect::<Vec<_>>()
+                .into(),
+            _ => values
+                .into_iter()
+                .map(byteorder::I64::<O>::new)
+                .collect::<Vec<_>>()
+                .into(),
+        })
+    }
+
+    fn quoted(&mut self) -> Result<String> {
+        let quote = self.text[self.pos];
+        self.pos += 1;
+        let mut out = Vec::new();
+        loop {
+            let Some(&byte) = self.text.get(self.pos) else {
+                cold_path();
+                return Err(Error::EndOfFile.at(self.pos));
+            };
+            self.pos += 1;
+            match byte {
+                b'\\' => {
+                    let Some(&escaped) = self.text.get(self.pos) else {
+                        cold_path();
+                        return Err(Error::EndOfFile.at(self.pos));
+                    };
+                    out.push(match escaped {
+                        b'\\' | b'"' | b'\'' => escaped,
+                        b'n' => b'\n',
+                        b'r' => b'\r',
+                        b't' => b'\t',
+                        _ => {
+                            return error(
+                                self.pos - 1,
+                                format!("invalid escape '\\{}'", escaped as char),
+                            );
+                        }
+                    });
+                    self.pos += 1;
+                }
+                byte if byte == quote => break,
+                byte => out.push(byte),
+            }
+        }
+        // the input is a `str`, split only at ASCII quotes and escapes
+        Ok(String::from_utf8(out).unwrap())
+    }
+
+    fn unquoted(&mut self) -> Result<&'a str> {
+        self.skip_whitespace();
+        let start = self.pos;
+        while self
+            .text
+            .get(self.pos)
+            .is_some_and(|&byte| is_unquoted_char(byte as char))
+        {
+            self.pos += 1;
+        }
+        if self.pos == start {
+            return match self.text.get(self.pos) {
+                Some(&byte) => error(start, format!("unexpected '{}'", byte as char)),
+                None => {
+                    cold_path();
+                    Err(Error::EndOfFile.at(start))
+                }
+            };
+        }
+        Ok(core::str::from_utf8(&self.text[start..self.pos]).unwrap())
+    }
+}
+
+fn error<T>(at: usize, message: impl Into<String>) -> Result<T> {
+    cold_path();
+    Err(Error::Message(message.into()).at(at))
+}
+
+/// Reads a number or boolean written without quotes, or returns `None` if
+/// `word` is neither and so is a string.
+fn parse_word<O: ByteOrder>(word: &str) -> Option<OwnedValue<O>> {
+    if word.eq_ignore_ascii_case("true") {
+        return Some(OwnedValue::Byte(1));
+    }
+    if word.eq_ignore_ascii_case("false") {
+        return Some(OwnedValue::Byte(0));
+    }
+
+    let (number, suffix) = word.split_at(word.len() - 1);
+    match suffix.as_bytes()[0].to_ascii_lowercase() {
+        b'b' => return number.parse::<i8>().ok().map(Into::into),
+        b's' => return number.parse::<i16>().ok().map(Into::into),
+        b'l' => return number.parse::<i64>().ok().map(Into::into),
+        b'f' if is_decimal(number) => return number.parse::<f32>().ok().map(Into::into),
+        b'd' if is_decimal(number) => return number.parse::<f64>().ok().map(Into::into),
+        _ => {}
+    }
+
+    if let Ok(value) = word.parse::<i32>() {
+        return Some(value.into());
+    }
+    // an unsuffixed number with a fraction or exponent is a double
+    if word.contains(['.', 'e', 'E']) && is_decimal(word) {
+        return word.parse::<f64>().ok().map(Into::into);
+    }
+    None
+}
+
+/// Returns `true` if `number` is written with digits, so that words such as
+/// `nan` and `inf`, which Rust would parse as floats, stay strings.
+fn is_decimal(number: &str) -> bool {
+    number.bytes().any(|byte| byte.is_ascii_digit())
+        && number
+            .bytes()
+            .all(|byte| byte.is_ascii_digit() || matches!(byte, b'+' | b'-' | b'.' | b'e' | b'E'))
+}
+
+/// Serializes a value to SNBT text with serde.
+///
+/// The value is serialized as by [`to_vec`](crate::to_vec), with the same
+/// mapping of Rust types to tags, and then written with [`to_snbt`].
+///
+/// This function requires the `serde` feature.
+///
+/// # Example
+///
+/// ```
+/// use serde::Serialize;
+///
+/// #[derive(Serialize)]
+/// struct Item {
+///     id: String,
+///     count: i8,
+/// }
+///
+/// let item = Item { id: "minecraft:stone".into(), count: 64 };
+/// let snbt = na_nbt::to_snbt_string(&item)?;
+/// assert_eq!(snbt, r#"{id:"minecraft:stone",count:64b}"#);
+/// # Ok::<(), na_nbt::Error>(())
+/// ```
+///
+/// # Errors
+///
+/// Returns an error if the value cannot be serialized, see
+/// [`to_vec`](crate::to_vec), or if it holds a NaN float, which has no SNBT
+/// form.
+#[cfg(feature = "serde")]
+pub fn to_snbt_string(value: &(impl ?Sized + serde::Serialize)) -> Result<String> {
+    let bytes = crate::to_vec_be(value)?;
+    let doc = crate::read_borrowed::<crate::BigEndian>(&bytes)?;
+    to_snbt(&doc.root())
+}
+
+/// Deserializes a value from SNBT text with serde.
+///
+/// The text is parsed with [`from_snbt`] and the result deserialized as by
+/// [`from_slice`](crate::from_slice).
+///
+/// This function requires the `serde` feature.
+///
+/// # Example
+///
+/// ```
+/// use serde::Deserialize;
+///
+/// #[derive(Deserialize)]
+/// struct Item {
+///     id: String,
+///     count: i8,
+/// }
+///
+/// let item: Item = na_nbt::from_snbt_str(r#"{id: "minecraft:stone", count: 64b}"#)?;
+/// assert_eq!(item.id, "minecraft:stone");
+/// assert_eq!(item.count, 64);
+/// # Ok::<(), na_nbt::Error>(())
+/// ```
+///
+/// # Errors
+///
+/// Returns an error if the text is not valid SNBT, see [`from_snbt`], or if
+/// it does not match `T`.
+#[cfg(feature = "serde")]
+pub fn from_snbt_str<T: serde::de::DeserializeOwned>(text: &str) -> Result<T> {
+    let bytes = from_snbt::<crate::BigEndian>(text)?.write_to_vec::<crate::BigEndian>()?;
+    crate::from_slice_be(&bytes)
+}
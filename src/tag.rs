@@ -47,6 +47,13 @@
 /// - **Primitive** ([`is_primitive`](Tag::is_primitive)): End, Byte, Short, Int, Long, Float, Double
 /// - **Array** ([`is_array`](Tag::is_array)): ByteArray, IntArray, LongArray
 /// - **Composite** ([`is_composite`](Tag::is_composite)): List, Compound
+///
+/// # Ordering
+///
+/// Tags order by their numeric id, from `End` to `LongArray`, so sorting
+/// values by [`tag_id`](crate::ScopedReadableValue::tag_id) gives the same
+/// result on every run. [`Display`](core::fmt::Display) writes the
+/// [`name`](Tag::name), such as `TAG_Int`.
 #[repr(u8)]
 #[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
 pub enum Tag {
@@ -86,7 +93,7 @@ impl Tag {
     /// The caller must ensure that `value` is a valid tag type (0-12).
     /// Passing an invalid value results in undefined behavior.
     pub(crate) unsafe fn from_u8_unchecked(value: u8) -> Self {
-        unsafe { std::mem::transmute(value) }
+        unsafe { core::mem::transmute(value) }
     }
 
     /// Returns `true` if this is a primitive tag type.
@@ -154,5 +161,93 @@ impl Tag {
     pub const fn is_composite(self) -> bool {
         matches!(self, Self::List | Self::Compound)
     }
+
+    /// Returns the name of this tag type as used by Minecraft, such as
+    /// `"TAG_Int"`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use na_nbt::Tag;
+    ///
+    /// assert_eq!(Tag::Int.name(), "TAG_Int");
+    /// assert_eq!(Tag::ByteArray.name(), "TAG_Byte_Array");
+    /// ```
+    pub const fn name(self) -> &'static str {
+        match self {
+            Self::End => "TAG_End",
+            Self::Byte => "TAG_Byte",
+            Self::Short => "TAG_Short",
+            Self::Int => "TAG_Int",
+            Self::Long => "TAG_Long",
+            Self::Float => "TAG_Float",
+            Self::Double => "TAG_Double",
+            Self::ByteArray => "TAG_Byte_Array",
+            Self::String => "TAG_String",
+            Self::List => "TAG_List",
+            Self::Compound => "TAG_Compound",
+            Self::IntArray => "TAG_Int_Array",
+            Self::LongArray => "TAG_Long_Array",
+        }
+    }
+}
+
+impl core::fmt::Display for Tag {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str(self.name())
+    }
 }
 
+mod private {
+    pub trait Sealed {}
+}
+
+/// An NBT tag type as a Rust type, to pick the values of one tag at compile
+/// time, as [`ReadonlyCompound::iter_of`](crate::ReadonlyCompound::iter_of)
+/// does.
+///
+/// The types are uninhabited and only name a [`Tag`]. This trait is sealed
+/// and cannot be implemented outside of this crate.
+///
+/// # Example
+///
+/// ```
+/// use na_nbt::{Tag, TagIntArray, TagType};
+///
+/// assert_eq!(TagIntArray::TAG, Tag::IntArray);
+/// ```
+pub trait TagType: private::Sealed {
+    /// The tag this type stands for.
+    const TAG: Tag;
+}
+
+macro_rules! tag_types {
+    ($($name:ident => $tag:ident),* $(,)?) => {
+        $(
+            #[doc = concat!("[`Tag::", stringify!($tag), "`] as a type; see [`TagType`].")]
+            #[derive(Clone, Copy, Debug)]
+            pub enum $name {}
+
+            impl private::Sealed for $name {}
+
+            impl TagType for $name {
+                const TAG: Tag = Tag::$tag;
+            }
+        )*
+    };
+}
+
+tag_types! {
+    TagByte => Byte,
+    TagShort => Short,
+    TagInt => Int,
+    TagLong => Long,
+    TagFloat => Float,
+    TagDouble => Double,
+    TagByteArray => ByteArray,
+    TagString => String,
+    TagList => List,
+    TagCompound => Compound,
+    TagIntArray => IntArray,
+    TagLongArray => LongArray,
+}
@@ -22,10 +22,43 @@
 //! process_nbt::<LittleEndian>(&data);
 //! ```
 
+use crate::Tag;
+
 #[inline(always)]
 #[cold]
 pub(crate) fn cold_path() {}
 
+/// Decodes Modified UTF-8 bytes, replacing invalid sequences with U+FFFD.
+///
+/// ASCII, the common case for keys and ids, is already valid UTF-8 and is
+/// borrowed after a single scan.
+#[inline]
+pub(crate) fn decode_mutf8(bytes: &[u8]) -> alloc::borrow::Cow<'_, str> {
+    if bytes.is_ascii() {
+        // ASCII is valid UTF-8
+        return alloc::borrow::Cow::Borrowed(unsafe { core::str::from_utf8_unchecked(bytes) });
+    }
+    simd_cesu8::mutf8::decode_lossy(bytes)
+}
+
+/// Called where a write is skipped because the value is not of the `expected`
+/// type; returns `false` for the caller to pass on.
+///
+/// With the `strict` feature this panics in debug builds, so that tests catch
+/// writes that were silently dropped.
+#[inline(always)]
+#[cfg_attr(all(feature = "strict", debug_assertions), track_caller)]
+pub(crate) fn type_mismatch(expected: Tag, found: Tag) -> bool {
+    #[cfg(all(feature = "strict", debug_assertions))]
+    panic!("type mismatch: expected {expected:?}, found {found:?}");
+    #[cfg(not(all(feature = "strict", debug_assertions)))]
+    {
+        let _ = (expected, found);
+        cold_path();
+        false
+    }
+}
+
 /// A trait for byte order types.
 ///
 /// This trait is automatically implemented for all types that implement
@@ -112,11 +112,17 @@
 //! ```
 
 mod config;
+mod encode;
+mod eq;
+mod leaves;
+mod primitive;
 mod readable;
 mod scoped_readable;
 mod scoped_writable;
+mod stats;
 mod string;
 mod value;
+mod visitor;
 mod writable;
 
 pub use value::Value;
@@ -127,8 +133,20 @@ pub use value::ValueScoped;
 pub use config::ReadableConfig;
 pub use config::WritableConfig;
 
+pub(crate) use encode::write_to_vec_named;
+
+pub use eq::{semantically_eq, values_equal};
+
+pub use leaves::Leaves;
+
+pub use primitive::Primitive;
+
+pub use stats::NbtStats;
+
 pub use string::ReadableString;
 
+pub use visitor::{Visitor, walk};
+
 pub use readable::ReadableCompound;
 pub use readable::ReadableList;
 pub use readable::ReadableValue;
@@ -144,4 +162,3 @@ pub use writable::WritableValue;
 pub use scoped_writable::ScopedWritableCompound;
 pub use scoped_writable::ScopedWritableList;
 pub use scoped_writable::ScopedWritableValue;
-
@@ -0,0 +1,203 @@
+use alloc::vec::Vec;
+use core::any::TypeId;
+
+use zerocopy::{IntoBytes, byteorder};
+
+use crate::{
+    ByteOrder, Error, Result, Tag, cold_path,
+    value_trait::{
+        ReadableConfig, ReadableString, ScopedReadableList, ScopedReadableValue, ValueScoped,
+    },
+};
+
+/// The output of the encoder; `put` returns `None` once no more fits.
+trait Sink {
+    fn put(&mut self, bytes: &[u8]) -> Option<()>;
+}
+
+/// A cursor over the caller's buffer; every write fails once it is full.
+struct SliceWriter<'a> {
+    buf: &'a mut [u8],
+    pos: usize,
+}
+
+impl Sink for SliceWriter<'_> {
+    #[inline]
+    fn put(&mut self, bytes: &[u8]) -> Option<()> {
+        let end = self.pos.checked_add(bytes.len())?;
+        self.buf.get_mut(self.pos..end)?.copy_from_slice(bytes);
+        self.pos = end;
+        Some(())
+    }
+}
+
+impl Sink for Vec<u8> {
+    #[inline]
+    fn put(&mut self, bytes: &[u8]) -> Option<()> {
+        self.extend_from_slice(bytes);
+        Some(())
+    }
+}
+
+pub(crate) fn write_to_slice<'doc, TARGET: ByteOrder>(
+    value: &impl ScopedReadableValue<'doc>,
+    buf: &mut [u8],
+) -> Result<usize> {
+    let mut writer = SliceWriter { buf, pos: 0 };
+    match write_root::<TARGET, false>(&mut writer, value) {
+        Some(()) => Ok(writer.pos),
+        None => {
+            cold_path();
+            Err(Error::BufferTooSmall(value.serialized_len()))
+        }
+    }
+}
+
+/// Writes `value` with `name`, already encoded as MUTF-8, as its root name.
+pub(crate) fn write_to_vec_named<'doc, TARGET: ByteOrder>(
+    value: &impl ScopedReadableValue<'doc>,
+    name: &[u8],
+) -> Result<Vec<u8>> {
+    let unnamed = value.write_to_vec::<TARGET>()?;
+    if unnamed[0] == Tag::End as u8 {
+        return Ok(unnamed);
+    }
+    let Ok(name_len) = u16::try_from(name.len()) else {
+        cold_path();
+        return Err(Error::Message(
+            "root name is longer than 65535 bytes".into(),
+        ));
+    };
+    let mut out = Vec::with_capacity(unnamed.len() + name.len());
+    out.push(unnamed[0]);
+    out.extend_from_slice(&byteorder::U16::<TARGET>::new(name_len).to_bytes());
+    out.extend_from_slice(name);
+    out.extend_from_slice(&unnamed[1 + 2..]);
+    Ok(out)
+}
+
+pub(crate) fn canonical_bytes<'doc, TARGET: ByteOrder>(
+    value: &impl ScopedReadableValue<'doc>,
+) -> Vec<u8> {
+    let mut out = Vec::new();
+    // writing to a `Vec` cannot fail
+    let _ = write_root::<TARGET, true>(&mut out, value);
+    out
+}
+
+/// Writes `value` as a document with an empty root name. With `SORTED`, the
+/// entries of every compound are written in key order, each key once.
+fn write_root<'doc, TARGET: ByteOrder, const SORTED: bool>(
+    sink: &mut impl Sink,
+    value: &impl ScopedReadableValue<'doc>,
+) -> Option<()> {
+    value.visit_scoped(|value| match value {
+        ValueScoped::End => sink.put(&[0]),
+        value => {
+            sink.put(&[tag_of(&value) as u8, 0, 0])?;
+            write_payload::<_, TARGET, SORTED>(sink, value)
+        }
+    })
+}
+
+fn tag_of<C: ReadableConfig>(value: &ValueScoped<'_, C>) -> Tag {
+    match value {
+        ValueScoped::End => Tag::End,
+        ValueScoped::Byte(_) => Tag::Byte,
+        ValueScoped::Short(_) => Tag::Short,
+        ValueScoped::Int(_) => Tag::Int,
+        ValueScoped::Long(_) => Tag::Long,
+        ValueScoped::Float(_) => Tag::Float,
+        ValueScoped::Double(_) => Tag::Double,
+        ValueScoped::ByteArray(_) => Tag::ByteArray,
+        ValueScoped::String(_) => Tag::String,
+        ValueScoped::List(_) => Tag::List,
+        ValueScoped::Compound(_) => Tag::Compound,
+        ValueScoped::IntArray(_) => Tag::IntArray,
+        ValueScoped::LongArray(_) => Tag::LongArray,
+    }
+}
+
+fn write_payload<C: ReadableConfig, TARGET: ByteOrder, const SORTED: bool>(
+    writer: &mut impl Sink,
+    value: ValueScoped<'_, C>,
+) -> Option<()> {
+    let same_order = TypeId::of::<C::ByteOrder>() == TypeId::of::<TARGET>();
+    match value {
+        ValueScoped::End => Some(()),
+        ValueScoped::Byte(value) => writer.put(&[value as u8]),
+        ValueScoped::Short(value) => writer.put(&byteorder::I16::<TARGET>::new(value).to_bytes()),
+        ValueScoped::Int(value) => writer.put(&byteorder::I32::<TARGET>::new(value).to_bytes()),
+        ValueScoped::Long(value) => writer.put(&byteorder::I64::<TARGET>::new(value).to_bytes()),
+        ValueScoped::Float(value) => writer.put(&byteorder::F32::<TARGET>::new(value).to_bytes()),
+        ValueScoped::Double(value) => writer.put(&byteorder::F64::<TARGET>::new(value).to_bytes()),
+        ValueScoped::ByteArray(value) => {
+            writer.put(&byteorder::U32::<TARGET>::new(value.len() as u32).to_bytes())?;
+            writer.put(value.as_bytes())
+        }
+        ValueScoped::String(value) => {
+            let bytes = value.raw_bytes();
+            writer.put(&byteorder::U16::<TARGET>::new(bytes.len() as u16).to_bytes())?;
+            writer.put(bytes)
+        }
+        ValueScoped::List(list) => {
+            let len = list.len();
+            // empty lists are always written as End, whatever their element type
+            let tag_id = if len == 0 { Tag::End } else { list.tag_id() };
+            writer.put(&[tag_id as u8])?;
+            writer.put(&byteorder::U32::<TARGET>::new(len as u32).to_bytes())?;
+            for item in list {
+                item.visit_scoped(|item| write_payload::<_, TARGET, SORTED>(writer, item))?;
+            }
+            Some(())
+        }
+        ValueScoped::Compound(compound) if SORTED => {
+            let mut entries: Vec<_> = compound.into_iter().collect();
+            // a stable sort keeps the first of any repeated key in front
+            entries.sort_by(|(a, _), (b, _)| a.raw_bytes().cmp(b.raw_bytes()));
+            entries.dedup_by(|(a, _), (b, _)| a.raw_bytes() == b.raw_bytes());
+            for (key, item) in entries {
+                write_entry::<TARGET, SORTED>(writer, key.raw_bytes(), &item)?;
+            }
+            writer.put(&[Tag::End as u8])
+        }
+        ValueScoped::Compound(compound) => {
+            for (key, item) in compound {
+                write_entry::<TARGET, SORTED>(writer, key.raw_bytes(), &item)?;
+            }
+            writer.put(&[Tag::End as u8])
+        }
+        ValueScoped::IntArray(value) => {
+            writer.put(&byteorder::U32::<TARGET>::new(value.len() as u32).to_bytes())?;
+            if same_order {
+                return writer.put(value.as_bytes());
+            }
+            for element in value.iter() {
+                writer.put(&byteorder::I32::<TARGET>::new(element.get()).to_bytes())?;
+            }
+            Some(())
+        }
+        ValueScoped::LongArray(value) => {
+            writer.put(&byteorder::U32::<TARGET>::new(value.len() as u32).to_bytes())?;
+            if same_order {
+                return writer.put(value.as_bytes());
+            }
+            for element in value.iter() {
+                writer.put(&byteorder::I64::<TARGET>::new(element.get()).to_bytes())?;
+            }
+            Some(())
+        }
+    }
+}
+
+/// Writes one compound entry: tag, name and payload.
+fn write_entry<'doc, TARGET: ByteOrder, const SORTED: bool>(
+    writer: &mut impl Sink,
+    name: &[u8],
+    item: &impl ScopedReadableValue<'doc>,
+) -> Option<()> {
+    writer.put(&[item.tag_id() as u8])?;
+    writer.put(&byteorder::U16::<TARGET>::new(name.len() as u16).to_bytes())?;
+    writer.put(name)?;
+    item.visit_scoped(|item| write_payload::<_, TARGET, SORTED>(writer, item))
+}
@@ -0,0 +1,137 @@
+use crate::value_trait::{
+    ReadableConfig, ReadableString, ScopedReadableCompound, ScopedReadableList,
+    ScopedReadableValue, ValueScoped,
+};
+
+/// Compares two values by their logical content.
+///
+/// The values may be of different reader types and byte orders, e.g. a
+/// borrowed big-endian document against an [`OwnedValue`](crate::OwnedValue)
+/// stored in little-endian.
+///
+/// - Numbers compare by value. Floats compare bitwise, so a NaN equals an
+///   identical NaN while `0.0` and `-0.0` differ.
+/// - Compounds compare unordered: both must have the same keys, each mapping
+///   to equal values.
+/// - Lists compare element by element, in order. Two empty lists are equal
+///   even if their element types differ.
+///
+/// # Example
+///
+/// ```
+/// use na_nbt::{OwnedCompound, OwnedValue, read_borrowed, values_equal};
+/// use zerocopy::byteorder::{BigEndian, LittleEndian};
+///
+/// let mut a: OwnedCompound<LittleEndian> = OwnedCompound::default();
+/// a.insert("x", 1i32);
+/// a.insert("y", "up");
+/// let a = OwnedValue::Compound(a);
+///
+/// let mut b: OwnedCompound<BigEndian> = OwnedCompound::default();
+/// b.insert("y", "up");
+/// b.insert("x", 1i32);
+/// let bytes = OwnedValue::Compound(b).write_to_vec::<BigEndian>()?;
+/// let doc = read_borrowed::<BigEndian>(&bytes)?;
+///
+/// assert!(values_equal(&a, &doc.root()));
+/// # Ok::<(), na_nbt::Error>(())
+/// ```
+pub fn values_equal<'a, 'b>(
+    a: &impl ScopedReadableValue<'a>,
+    b: &impl ScopedReadableValue<'b>,
+) -> bool {
+    value_equal(a, b, false)
+}
+
+/// Compares two values by their logical content, ignoring numeric width.
+///
+/// This is [`values_equal`] with looser rules for numbers, for schema checks
+/// and fuzzy comparisons where `Byte 1` and `Int 1` mean the same thing:
+///
+/// - `Byte`, `Short`, `Int` and `Long` compare by value, so `Byte 1` equals
+///   `Int 1` and `Long 1`.
+/// - `Float` and `Double` compare by value after widening to `f64`, so
+///   `Float 0.5` equals `Double 0.5`, `0.0` equals `-0.0` and NaN equals
+///   nothing. `Float 0.1` does not equal `Double 0.1`, as they are different
+///   numbers.
+/// - An integer never equals a float, even `Int 1` and `Double 1.0`.
+///
+/// Structure stays strict: arrays only equal arrays of the same tag, and a
+/// list never equals an array. Lists compare element by element with these
+/// rules, so a list of `Byte` can equal a list of `Int`, and compounds compare
+/// unordered as in [`values_equal`].
+///
+/// # Example
+///
+/// ```
+/// use na_nbt::{OwnedValue, semantically_eq, values_equal};
+/// use zerocopy::byteorder::BigEndian;
+///
+/// let byte = OwnedValue::<BigEndian>::Byte(1);
+/// let long = OwnedValue::<BigEndian>::from(1i64);
+/// assert!(semantically_eq(&byte, &long));
+/// assert!(!values_equal(&byte, &long));
+///
+/// let double = OwnedValue::<BigEndian>::from(1.0f64);
+/// assert!(!semantically_eq(&byte, &double));
+/// ```
+pub fn semantically_eq<'a, 'b>(
+    a: &impl ScopedReadableValue<'a>,
+    b: &impl ScopedReadableValue<'b>,
+) -> bool {
+    value_equal(a, b, true)
+}
+
+fn value_equal<'a, 'b>(
+    a: &impl ScopedReadableValue<'a>,
+    b: &impl ScopedReadableValue<'b>,
+    semantic: bool,
+) -> bool {
+    if semantic {
+        if let (Some(a), Some(b)) = (a.as_i64_widened(), b.as_i64_widened()) {
+            return a == b;
+        }
+        if let (Some(a), Some(b)) = (a.as_f64_widened(), b.as_f64_widened()) {
+            return a == b;
+        }
+    }
+    a.visit_scoped(|a| b.visit_scoped(|b| scoped_equal(a, b, semantic)))
+}
+
+fn scoped_equal<A: ReadableConfig, B: ReadableConfig>(
+    a: ValueScoped<'_, A>,
+    b: ValueScoped<'_, B>,
+    semantic: bool,
+) -> bool {
+    let equal = |a: &A::Value<'_>, b: &B::Value<'_>| value_equal(a, b, semantic);
+
+    match (a, b) {
+        (ValueScoped::End, ValueScoped::End) => true,
+        (ValueScoped::Byte(a), ValueScoped::Byte(b)) => a == b,
+        (ValueScoped::Short(a), ValueScoped::Short(b)) => a == b,
+        (ValueScoped::Int(a), ValueScoped::Int(b)) => a == b,
+        (ValueScoped::Long(a), ValueScoped::Long(b)) => a == b,
+        (ValueScoped::Float(a), ValueScoped::Float(b)) => a.to_bits() == b.to_bits(),
+        (ValueScoped::Double(a), ValueScoped::Double(b)) => a.to_bits() == b.to_bits(),
+        (ValueScoped::ByteArray(a), ValueScoped::ByteArray(b)) => *a == *b,
+        (ValueScoped::String(a), ValueScoped::String(b)) => a.raw_bytes() == b.raw_bytes(),
+        (ValueScoped::IntArray(a), ValueScoped::IntArray(b)) => {
+            a.iter().map(|v| v.get()).eq(b.iter().map(|v| v.get()))
+        }
+        (ValueScoped::LongArray(a), ValueScoped::LongArray(b)) => {
+            a.iter().map(|v| v.get()).eq(b.iter().map(|v| v.get()))
+        }
+        (ValueScoped::List(a), ValueScoped::List(b)) => {
+            a.len() == b.len()
+                && a.iter_scoped()
+                    .zip(b.iter_scoped())
+                    .all(|(a, b)| equal(&a, &b))
+        }
+        (ValueScoped::Compound(a), ValueScoped::Compound(b)) => {
+            a.iter_scoped().count() == b.iter_scoped().count()
+                && a.iter_scoped()
+                    .all(|(key, a)| b.get_scoped(&key.decode()).is_some_and(|b| equal(&a, &b)))
+        }
+        _ => false,
+    }
+}
@@ -0,0 +1,111 @@
+use alloc::{string::String, vec::Vec};
+use core::fmt::Write as _;
+
+use crate::value_trait::{
+    ReadableCompound, ReadableConfig, ReadableList, ReadableString, ReadableValue, ValueScoped,
+};
+
+/// An iterator over the non-container values of a tree, with their paths.
+///
+/// Returned by [`ScopedReadableValue::leaves`](crate::ScopedReadableValue::leaves).
+/// Every value that is not a compound or a list is yielded, including
+/// strings and arrays; empty containers yield nothing. Values come in
+/// depth-first order, compound entries in storage order and list elements by
+/// index.
+///
+/// Paths join compound keys with `.` and list indices with `[n]`, as in
+/// `Inventory[0].id`. Keys are not escaped, so a key containing `.` or `[`
+/// gives an ambiguous path.
+///
+/// The iterator is lazy: it holds one child iterator per level of nesting
+/// and a single path buffer, and never collects the values up front.
+pub struct Leaves<'a, C: ReadableConfig> {
+    stack: Vec<Frame<'a, C>>,
+    path: String,
+}
+
+enum Frame<'a, C: ReadableConfig> {
+    Compound {
+        iter: C::CompoundIter<'a>,
+        prefix: usize,
+    },
+    List {
+        iter: C::ListIter<'a>,
+        index: usize,
+        prefix: usize,
+    },
+}
+
+impl<'a, C: ReadableConfig> Leaves<'a, C> {
+    pub(crate) fn new(root: ValueScoped<'a, C>) -> Self {
+        let frame = match root {
+            ValueScoped::Compound(compound) => Some(Frame::Compound {
+                iter: compound.iter(),
+                prefix: 0,
+            }),
+            ValueScoped::List(list) => Some(Frame::List {
+                iter: list.iter(),
+                index: 0,
+                prefix: 0,
+            }),
+            _ => None,
+        };
+        Self {
+            stack: frame.into_iter().collect(),
+            path: String::new(),
+        }
+    }
+}
+
+impl<'a, C: ReadableConfig> Iterator for Leaves<'a, C> {
+    type Item = (String, C::Value<'a>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let value = match self.stack.last_mut()? {
+                Frame::Compound { iter, prefix } => {
+                    let Some((key, value)) = iter.next() else {
+                        self.stack.pop();
+                        continue;
+                    };
+                    self.path.truncate(*prefix);
+                    if *prefix != 0 {
+                        self.path.push('.');
+                    }
+                    self.path.push_str(&key.decode());
+                    value
+                }
+                Frame::List {
+                    iter,
+                    index,
+                    prefix,
+                } => {
+                    let Some(value) = iter.next() else {
+                        self.stack.pop();
+                        continue;
+                    };
+                    self.path.truncate(*prefix);
+                    let _ = write!(self.path, "[{index}]");
+                    *index += 1;
+                    value
+                }
+            };
+
+            let prefix = self.path.len();
+            if let Some(compound) = value.as_compound() {
+                self.stack.push(Frame::Compound {
+                    iter: compound.iter(),
+                    prefix,
+                });
+            } else if let Some(list) = value.as_list() {
+                self.stack.push(Frame::List {
+                    iter: list.iter(),
+                    index: 0,
+                    prefix,
+                });
+            } else {
+                return Some((self.path.clone(), value));
+            }
+        }
+    }
+}
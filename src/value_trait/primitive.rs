@@ -0,0 +1,64 @@
+use crate::{ByteOrder, OwnedList, Tag, value_trait::ScopedReadableValue};
+
+mod private {
+    pub trait Sealed {}
+    impl Sealed for i8 {}
+    impl Sealed for i16 {}
+    impl Sealed for i32 {}
+    impl Sealed for i64 {}
+    impl Sealed for f32 {}
+    impl Sealed for f64 {}
+}
+
+/// A native Rust type that maps to exactly one primitive NBT tag.
+///
+/// | Type | Tag |
+/// |------|-----|
+/// | `i8` | [`Tag::Byte`] |
+/// | `i16` | [`Tag::Short`] |
+/// | `i32` | [`Tag::Int`] |
+/// | `i64` | [`Tag::Long`] |
+/// | `f32` | [`Tag::Float`] |
+/// | `f64` | [`Tag::Double`] |
+///
+/// This trait is sealed and cannot be implemented outside of this crate.
+pub trait Primitive: private::Sealed + Copy {
+    /// The tag type this native type is stored as.
+    const TAG: Tag;
+
+    #[doc(hidden)]
+    fn from_value<'doc>(value: &impl ScopedReadableValue<'doc>) -> Option<Self>;
+
+    /// Appends `self` to `list` without checking its element type.
+    ///
+    /// # Safety
+    ///
+    /// The element type of `list` must be [`Self::TAG`](Primitive::TAG).
+    #[doc(hidden)]
+    unsafe fn list_push_unchecked<O: ByteOrder>(self, list: &mut OwnedList<O>);
+}
+
+macro_rules! impl_primitive {
+    ($type:ty, $tag:expr, $as:ident) => {
+        impl Primitive for $type {
+            const TAG: Tag = $tag;
+
+            #[inline]
+            fn from_value<'doc>(value: &impl ScopedReadableValue<'doc>) -> Option<Self> {
+                value.$as()
+            }
+
+            #[inline]
+            unsafe fn list_push_unchecked<O: ByteOrder>(self, list: &mut OwnedList<O>) {
+                unsafe { list.push_unchecked(self) };
+            }
+        }
+    };
+}
+
+impl_primitive!(i8, Tag::Byte, as_byte);
+impl_primitive!(i16, Tag::Short, as_short);
+impl_primitive!(i32, Tag::Int, as_int);
+impl_primitive!(i64, Tag::Long, as_long);
+impl_primitive!(f32, Tag::Float, as_float);
+impl_primitive!(f64, Tag::Double, as_double);
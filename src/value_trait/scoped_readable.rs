@@ -1,9 +1,13 @@
+use alloc::vec::Vec;
+#[cfg(feature = "std")]
 use std::io::Write;
 
 use crate::{
     ByteOrder, Result, Tag,
     index::Index,
-    value_trait::{ReadableConfig, ValueScoped},
+    value_trait::{
+        Leaves, NbtStats, Primitive, ReadableConfig, ReadableString, ValueScoped, encode, stats,
+    },
 };
 
 /// Core trait for reading NBT values.
@@ -138,10 +142,364 @@ pub trait ScopedReadableValue<'doc>: Send + Sync + Sized {
         'doc: 'a;
 
     /// Writes the value to a byte vector.
+    ///
+    /// The output is the tag type, an empty name and the payload, a complete
+    /// document. This works for any value, including one taken out of a
+    /// compound or list; to write a compound entry under its key instead, use
+    /// [`ScopedReadableCompound::write_entry_to_vec`].
+    ///
+    /// Empty lists are written with the `End` element type, whatever type they
+    /// were created or read with, and whatever the byte order.
     fn write_to_vec<TARGET: ByteOrder>(&self) -> Result<Vec<u8>>;
 
+    /// Writes the value to a byte vector, with `name` as the root name.
+    ///
+    /// [`write_to_vec`](Self::write_to_vec) always writes an empty root name.
+    /// Some files, such as `level.dat`, name their root; together with
+    /// [`read_owned_named`](crate::read_owned_named) or
+    /// [`BorrowedDocument::root_name`](crate::BorrowedDocument::root_name),
+    /// this writes them back unchanged. An `End` root has no name, so `name`
+    /// is ignored for it.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use na_nbt::{ScopedReadableValue, read_owned_named};
+    /// use zerocopy::byteorder::BigEndian;
+    ///
+    /// let data = [0x0a, 0x00, 0x04, b'D', b'a', b't', b'a', 0x00];
+    /// let (name, value) = read_owned_named::<BigEndian, BigEndian>(&data)?;
+    /// assert_eq!(value.write_to_vec_named::<BigEndian>(&name)?, data);
+    /// # Ok::<(), na_nbt::Error>(())
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if writing the value fails, or if `name` is longer
+    /// than 65535 bytes once encoded ([`Error::Message`](crate::Error::Message)).
+    #[inline]
+    fn write_to_vec_named<TARGET: ByteOrder>(&self, name: &str) -> Result<Vec<u8>> {
+        encode::write_to_vec_named::<TARGET>(self, &simd_cesu8::mutf8::encode(name))
+    }
+
     /// Writes the value to a writer.
+    #[cfg(feature = "std")]
     fn write_to_writer<TARGET: ByteOrder>(&self, writer: impl Write) -> Result<()>;
+
+    /// Writes the value into `buf` and returns the number of bytes written.
+    ///
+    /// The output is the same as that of [`write_to_vec`](Self::write_to_vec),
+    /// but nothing is allocated, so this also suits `no_std` targets without a
+    /// heap to spare. Size `buf` with [`serialized_len`](Self::serialized_len)
+    /// to fit the value exactly.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use na_nbt::{OwnedCompound, OwnedValue, ScopedReadableValue};
+    /// use zerocopy::byteorder::BigEndian;
+    ///
+    /// let mut compound: OwnedCompound<BigEndian> = OwnedCompound::default();
+    /// compound.insert("name", "Steve");
+    /// let value = OwnedValue::Compound(compound);
+    ///
+    /// let mut buf = [0; 64];
+    /// let len = value.write_to_slice::<BigEndian>(&mut buf)?;
+    /// assert_eq!(buf[..len], value.write_to_vec::<BigEndian>()?);
+    /// # Ok::<(), na_nbt::Error>(())
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::BufferTooSmall`](crate::Error::BufferTooSmall), with
+    /// the length needed, if the value does not fit. The contents of `buf`
+    /// are then unspecified.
+    #[inline]
+    fn write_to_slice<TARGET: ByteOrder>(&self, buf: &mut [u8]) -> Result<usize> {
+        encode::write_to_slice::<TARGET>(self, buf)
+    }
+
+    /// Writes the value in a canonical form, for hashing or content
+    /// addressing.
+    ///
+    /// This is [`write_to_vec`](Self::write_to_vec) with the entries of every
+    /// compound sorted by key, compared as raw MUTF-8 bytes. Of a repeated key,
+    /// only the first entry is written, the one [`get`](Self::get_scoped)
+    /// finds. Values that are equal by [`values_equal`](crate::values_equal)
+    /// therefore produce identical bytes, whatever order their keys were
+    /// inserted in and whatever byte order they are stored in. As there,
+    /// floats are written bitwise, so `0.0` and `-0.0` differ.
+    ///
+    /// The output is a valid NBT document in the `TARGET` byte order.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use na_nbt::{OwnedCompound, OwnedValue, ScopedReadableValue};
+    /// use zerocopy::byteorder::{BigEndian, LittleEndian};
+    ///
+    /// let mut a: OwnedCompound<BigEndian> = OwnedCompound::default();
+    /// a.insert("x", 1i32);
+    /// a.insert("y", "up");
+    ///
+    /// let mut b: OwnedCompound<LittleEndian> = OwnedCompound::default();
+    /// b.insert("y", "up");
+    /// b.insert("x", 1i32);
+    ///
+    /// assert_eq!(
+    ///     OwnedValue::Compound(a).canonical_bytes::<BigEndian>(),
+    ///     OwnedValue::Compound(b).canonical_bytes::<BigEndian>(),
+    /// );
+    /// ```
+    #[inline]
+    fn canonical_bytes<TARGET: ByteOrder>(&self) -> Vec<u8> {
+        encode::canonical_bytes::<TARGET>(self)
+    }
+
+    /// Returns the exact length in bytes of the output of
+    /// [`write_to_vec`](Self::write_to_vec).
+    ///
+    /// This includes the root tag type and the empty root name, and is the
+    /// same for either target byte order. It walks the whole tree.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use na_nbt::{OwnedCompound, OwnedValue, ScopedReadableValue};
+    /// use zerocopy::byteorder::{BigEndian, LittleEndian};
+    ///
+    /// let mut compound: OwnedCompound<BigEndian> = OwnedCompound::default();
+    /// compound.insert("name", "Steve");
+    /// let value = OwnedValue::Compound(compound);
+    ///
+    /// assert_eq!(value.serialized_len(), value.write_to_vec::<BigEndian>().unwrap().len());
+    /// assert_eq!(value.serialized_len(), value.write_to_vec::<LittleEndian>().unwrap().len());
+    /// ```
+    #[inline]
+    fn serialized_len(&self) -> usize {
+        stats::serialized_len(self)
+    }
+
+    /// Returns the number of values in the tree, including this one.
+    ///
+    /// Every compound entry and list element counts as one node; the elements
+    /// of byte, int and long arrays do not.
+    #[inline]
+    fn node_count(&self) -> usize {
+        stats::node_count(self)
+    }
+
+    /// Walks the tree once and collects summary statistics.
+    ///
+    /// See [`NbtStats`] for the meaning of each field.
+    #[inline]
+    fn stats(&self) -> NbtStats {
+        stats::stats(self)
+    }
+
+    /// Returns an iterator over every non-container value in the tree, each
+    /// with its path, such as `Inventory[0].id`.
+    ///
+    /// See [`Leaves`] for the path format and order. Only values inside this
+    /// one are yielded: a value that is itself a scalar has no path and
+    /// yields nothing.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use na_nbt::{OwnedCompound, OwnedList, OwnedValue, ScopedReadableValue};
+    /// use zerocopy::byteorder::BigEndian;
+    ///
+    /// let mut item: OwnedCompound<BigEndian> = OwnedCompound::default();
+    /// item.insert("id", "minecraft:stone");
+    /// let mut inventory: OwnedList<BigEndian> = OwnedList::default();
+    /// inventory.push(item);
+    /// let mut root: OwnedCompound<BigEndian> = OwnedCompound::default();
+    /// root.insert("Inventory", inventory);
+    /// root.insert("Health", 20.0f32);
+    /// let root = OwnedValue::Compound(root);
+    ///
+    /// let paths: Vec<String> = root.leaves().map(|(path, _)| path).collect();
+    /// assert_eq!(paths, ["Inventory[0].id", "Health"]);
+    /// ```
+    #[inline]
+    fn leaves<'a>(&'a self) -> Leaves<'a, Self::Config>
+    where
+        'doc: 'a,
+    {
+        self.visit_scoped(Leaves::new)
+    }
+
+    /// Reads a list of exactly three `Double`, such as an entity's `Pos` or
+    /// `Motion`.
+    ///
+    /// Returns `None` if this is not a list, or if the list has another
+    /// length or element type. No numeric widening is performed, so a list of
+    /// `Float` yields `None`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use na_nbt::{OwnedValue, ScopedReadableValue};
+    /// use zerocopy::byteorder::BigEndian;
+    ///
+    /// let pos = OwnedValue::<BigEndian>::from_vec3(1.0, 2.0, 3.0);
+    /// assert_eq!(pos.as_vec3_f64(), Some([1.0, 2.0, 3.0]));
+    /// assert_eq!(pos.as_vec2_f32(), None);
+    /// ```
+    fn as_vec3_f64(&self) -> Option<[f64; 3]> {
+        let list = self.as_list_scoped()?;
+        if list.tag_id() != Tag::Double || list.len() != 3 {
+            return None;
+        }
+        let mut vec = [0.0; 3];
+        for (slot, element) in vec.iter_mut().zip(list.iter_scoped()) {
+            *slot = element.as_double()?;
+        }
+        Some(vec)
+    }
+
+    /// Reads a list of exactly two `Float`, such as an entity's `Rotation`.
+    ///
+    /// Returns `None` under the same conditions as
+    /// [`as_vec3_f64`](Self::as_vec3_f64).
+    fn as_vec2_f32(&self) -> Option<[f32; 2]> {
+        let list = self.as_list_scoped()?;
+        if list.tag_id() != Tag::Float || list.len() != 2 {
+            return None;
+        }
+        let mut vec = [0.0; 2];
+        for (slot, element) in vec.iter_mut().zip(list.iter_scoped()) {
+            *slot = element.as_float()?;
+        }
+        Some(vec)
+    }
+
+    /// Reads a UUID stored as an `IntArray` of four or a `LongArray` of two,
+    /// most significant part first.
+    ///
+    /// Java Edition has used the `IntArray` form since 1.16 and a pair of
+    /// longs before that. Returns `None` for any other value, including
+    /// arrays of another length.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use na_nbt::{OwnedValue, ScopedReadableValue};
+    /// use zerocopy::byteorder::BigEndian;
+    ///
+    /// let uuid = 0x069a79f4_44e9_4726_a5be_fca90e38aaf5;
+    /// let ints = OwnedValue::<BigEndian>::from_uuid_int_array(uuid);
+    /// let longs = OwnedValue::<BigEndian>::from_uuid_long_array(uuid);
+    /// assert_eq!(ints.as_uuid(), Some(uuid));
+    /// assert_eq!(longs.as_uuid(), Some(uuid));
+    /// ```
+    fn as_uuid(&self) -> Option<u128> {
+        if let Some(ints) = self.as_int_array_scoped() {
+            let [a, b, c, d] = <[_; 4]>::try_from(&*ints).ok()?;
+            return Some(
+                (a.get() as u32 as u128) << 96
+                    | (b.get() as u32 as u128) << 64
+                    | (c.get() as u32 as u128) << 32
+                    | d.get() as u32 as u128,
+            );
+        }
+        let longs = self.as_long_array_scoped()?;
+        let [most, least] = <[_; 2]>::try_from(&*longs).ok()?;
+        Some((most.get() as u64 as u128) << 64 | least.get() as u64 as u128)
+    }
+
+    /// Reads a `Byte`, `Short`, `Int` or `Long` widened to `i64`.
+    ///
+    /// Any other tag gives `None`; floats are not converted. This is the
+    /// widening the other numeric helpers, such as
+    /// [`get_i64`](ScopedReadableCompound::get_i64) and
+    /// [`semantically_eq`](crate::semantically_eq), are built on.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use na_nbt::{OwnedValue, ScopedReadableValue};
+    /// use zerocopy::byteorder::BigEndian;
+    ///
+    /// assert_eq!(OwnedValue::<BigEndian>::Byte(-3).as_i64_widened(), Some(-3));
+    /// assert_eq!(OwnedValue::<BigEndian>::from(1.0f64).as_i64_widened(), None);
+    /// ```
+    fn as_i64_widened(&self) -> Option<i64> {
+        match self.tag_id() {
+            Tag::Byte => self.as_byte().map(i64::from),
+            Tag::Short => self.as_short().map(i64::from),
+            Tag::Int => self.as_int().map(i64::from),
+            Tag::Long => self.as_long(),
+            _ => None,
+        }
+    }
+
+    /// Reads a `Float` or `Double` widened to `f64`.
+    ///
+    /// The floating-point counterpart of
+    /// [`as_i64_widened`](Self::as_i64_widened); integers are not converted.
+    fn as_f64_widened(&self) -> Option<f64> {
+        match self.tag_id() {
+            Tag::Float => self.as_float().map(f64::from),
+            Tag::Double => self.as_double(),
+            _ => None,
+        }
+    }
+
+    /// Reads the value as an `i64`, accepting any integer tag, a float or
+    /// double holding a whole number, or a string that parses as an integer.
+    ///
+    /// This is a lenient read for data that is loose about its types, such
+    /// as numbers stored as strings. Surrounding whitespace in a string is
+    /// ignored. A fractional or out-of-range float yields `None` rather than
+    /// being rounded, as does a string such as `"5.0"`. For a strict read,
+    /// use [`as_int`](Self::as_int) and its siblings.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use na_nbt::{OwnedValue, ScopedReadableValue};
+    /// use zerocopy::byteorder::BigEndian;
+    ///
+    /// assert_eq!(OwnedValue::<BigEndian>::from(5i32).as_i64_coerced(), Some(5));
+    /// assert_eq!(OwnedValue::<BigEndian>::from("5").as_i64_coerced(), Some(5));
+    /// assert_eq!(OwnedValue::<BigEndian>::from("abc").as_i64_coerced(), None);
+    /// ```
+    fn as_i64_coerced(&self) -> Option<i64> {
+        match self.tag_id() {
+            Tag::Byte | Tag::Short | Tag::Int | Tag::Long => self.as_i64_widened(),
+            Tag::Float | Tag::Double => {
+                let value = self.as_f64_coerced()?;
+                // 2^63 is exact as an f64, unlike i64::MAX
+                let in_range =
+                    (-9_223_372_036_854_775_808.0..9_223_372_036_854_775_808.0).contains(&value);
+                // in range the cast truncates, so only a whole number survives it
+                (in_range && value as i64 as f64 == value).then_some(value as i64)
+            }
+            Tag::String => self.as_string_scoped()?.decode().trim().parse().ok(),
+            _ => None,
+        }
+    }
+
+    /// Reads the value as an `f64`, accepting any numeric tag or a string
+    /// that parses as a number.
+    ///
+    /// The lenient counterpart of [`as_double`](Self::as_double), like
+    /// [`as_i64_coerced`](Self::as_i64_coerced). A `Long` beyond 2^53 may
+    /// lose precision. Strings are parsed as by [`str::parse`] after trimming
+    /// whitespace, so `"1e3"` and `"NaN"` are accepted.
+    fn as_f64_coerced(&self) -> Option<f64> {
+        match self.tag_id() {
+            Tag::Byte => self.as_byte().map(f64::from),
+            Tag::Short => self.as_short().map(f64::from),
+            Tag::Int => self.as_int().map(f64::from),
+            Tag::Long => self.as_long().map(|value| value as f64),
+            Tag::Float | Tag::Double => self.as_f64_widened(),
+            Tag::String => self.as_string_scoped()?.decode().trim().parse().ok(),
+            _ => None,
+        }
+    }
 }
 
 /// A trait for NBT lists with scoped lifetimes.
@@ -152,6 +510,15 @@ pub trait ScopedReadableList<'doc>: IntoIterator + Send + Sync + Sized {
     /// Returns the tag ID of the elements in the list.
     fn tag_id(&self) -> Tag;
 
+    /// Returns the tag ID of the elements in the list.
+    ///
+    /// This is [`tag_id`](Self::tag_id) under a name that cannot be mistaken
+    /// for the tag of the list itself, which is always [`Tag::List`].
+    #[inline]
+    fn element_tag(&self) -> Tag {
+        self.tag_id()
+    }
+
     /// Returns the number of elements in the list.
     fn len(&self) -> usize;
 
@@ -169,6 +536,63 @@ pub trait ScopedReadableList<'doc>: IntoIterator + Send + Sync + Sized {
     fn iter_scoped<'a>(&'a self) -> <Self::Config as ReadableConfig>::ListIter<'a>
     where
         'doc: 'a;
+
+    /// Returns `true` if the list holds an element equal to `value`, as
+    /// compared by [`values_equal`](crate::values_equal).
+    ///
+    /// A `value` whose tag differs from [`element_tag`](Self::element_tag)
+    /// is rejected without looking at the elements.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use na_nbt::{OwnedList, OwnedValue, ScopedReadableList};
+    /// use zerocopy::byteorder::BigEndian;
+    ///
+    /// let mut list: OwnedList<BigEndian> = OwnedList::default();
+    /// list.push("stone");
+    /// list.push("dirt");
+    ///
+    /// assert!(list.contains(&OwnedValue::<BigEndian>::from("dirt")));
+    /// assert!(!list.contains(&OwnedValue::<BigEndian>::from("sand")));
+    /// ```
+    fn contains<'b>(&self, value: &impl ScopedReadableValue<'b>) -> bool {
+        value.tag_id() == self.tag_id()
+            && self
+                .iter_scoped()
+                .any(|element| crate::values_equal(&element, value))
+    }
+
+    /// Decodes the elements into a `Vec<T>` if the list holds `T`s.
+    ///
+    /// Returns `None` if [`element_tag`](Self::element_tag) is not
+    /// [`T::TAG`](Primitive::TAG). An empty list gives an empty `Vec` whatever
+    /// its element tag, since empty lists are often written with `End`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use na_nbt::{OwnedList, ScopedReadableList};
+    /// use zerocopy::byteorder::BigEndian;
+    ///
+    /// let mut list: OwnedList<BigEndian> = OwnedList::default();
+    /// list.push(1i32);
+    /// list.push(2i32);
+    ///
+    /// assert_eq!(list.to_vec_::<i32>(), Some(vec![1, 2]));
+    /// assert_eq!(list.to_vec_::<i64>(), None);
+    /// ```
+    fn to_vec_<T: Primitive>(&self) -> Option<Vec<T>> {
+        if self.is_empty() {
+            return Some(Vec::new());
+        }
+        if self.tag_id() != T::TAG {
+            return None;
+        }
+        self.iter_scoped()
+            .map(|element| T::from_value(&element))
+            .collect()
+    }
 }
 
 pub trait ScopedReadableCompound<'doc>: IntoIterator + Send + Sync + Sized {
@@ -178,6 +602,169 @@ pub trait ScopedReadableCompound<'doc>: IntoIterator + Send + Sync + Sized {
     where
         'doc: 'a;
 
+    /// Gets the primitive value stored under `key`, or `default` if there is none.
+    ///
+    /// A key that is present but holds a different tag type is treated exactly
+    /// like a missing key: `default` is returned and nothing panics. No numeric
+    /// widening is performed, so asking for an `i64` where an `Int` is stored
+    /// also yields `default`; [`get_i64`](Self::get_i64) and
+    /// [`get_f64`](Self::get_f64) widen.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use na_nbt::{OwnedCompound, ScopedReadableCompound};
+    /// use zerocopy::byteorder::BigEndian;
+    ///
+    /// let mut compound: OwnedCompound<BigEndian> = OwnedCompound::default();
+    /// compound.insert("level", 42i32);
+    /// compound.insert("name", "Steve");
+    ///
+    /// assert_eq!(compound.get_or("level", 0i32), 42);
+    /// assert_eq!(compound.get_or("missing", 7i32), 7);
+    /// // Present, but not an Int.
+    /// assert_eq!(compound.get_or("name", -1i32), -1);
+    /// ```
+    #[inline]
+    fn get_or<T: Primitive>(&self, key: &str, default: T) -> T {
+        self.get_scoped(key)
+            .and_then(|value| T::from_value(&value))
+            .unwrap_or(default)
+    }
+
+    /// Gets the integer stored under `key`, widened to `i64`.
+    ///
+    /// A `Byte`, `Short`, `Int` or `Long` is accepted, since the width of a
+    /// numeric field often changes between game versions. Any other tag, or
+    /// a missing key, gives `None`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use na_nbt::{OwnedCompound, ScopedReadableCompound};
+    /// use zerocopy::byteorder::BigEndian;
+    ///
+    /// let mut compound: OwnedCompound<BigEndian> = OwnedCompound::default();
+    /// compound.insert("old", 42i32);
+    /// compound.insert("new", 42i64);
+    /// compound.insert("name", "Steve");
+    ///
+    /// assert_eq!(compound.get_i64("old"), Some(42));
+    /// assert_eq!(compound.get_i64("new"), Some(42));
+    /// assert_eq!(compound.get_i64("name"), None);
+    /// ```
+    fn get_i64(&self, key: &str) -> Option<i64> {
+        self.get_scoped(key)?.as_i64_widened()
+    }
+
+    /// Gets the floating-point number stored under `key`, widened to `f64`.
+    ///
+    /// A `Float` or `Double` is accepted. Integer tags are not converted: they,
+    /// like any other tag or a missing key, give `None`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use na_nbt::{OwnedCompound, ScopedReadableCompound};
+    /// use zerocopy::byteorder::BigEndian;
+    ///
+    /// let mut compound: OwnedCompound<BigEndian> = OwnedCompound::default();
+    /// compound.insert("speed", 0.5f32);
+    /// compound.insert("health", 20i32);
+    ///
+    /// assert_eq!(compound.get_f64("speed"), Some(0.5));
+    /// assert_eq!(compound.get_f64("health"), None);
+    /// ```
+    fn get_f64(&self, key: &str) -> Option<f64> {
+        self.get_scoped(key)?.as_f64_widened()
+    }
+
+    /// Writes the entry stored under `key` as the tag type, `key` as the name
+    /// and the payload, or returns `None` if there is no such entry.
+    ///
+    /// This is exactly how the entry is laid out inside a compound, so the
+    /// output can be spliced into the entries of another compound's encoding.
+    /// [`write_to_vec`](ScopedReadableValue::write_to_vec) on the value alone
+    /// writes the same bytes with an empty name in place of `key`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use na_nbt::{OwnedCompound, ScopedReadableCompound, ScopedReadableValue};
+    /// use zerocopy::byteorder::BigEndian;
+    ///
+    /// let mut compound: OwnedCompound<BigEndian> = OwnedCompound::default();
+    /// compound.insert("hp", 20i16);
+    ///
+    /// let entry = compound.write_entry_to_vec::<BigEndian>("hp")?.unwrap();
+    /// assert_eq!(entry, [0x02, 0x00, 0x02, b'h', b'p', 0x00, 0x14]);
+    ///
+    /// let value = compound.get_scoped("hp").unwrap().write_to_vec::<BigEndian>()?;
+    /// assert_eq!(value, [0x02, 0x00, 0x00, 0x00, 0x14]);
+    ///
+    /// assert!(compound.write_entry_to_vec::<BigEndian>("mp")?.is_none());
+    /// # Ok::<(), na_nbt::Error>(())
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if writing the value fails.
+    fn write_entry_to_vec<TARGET: ByteOrder>(&self, key: &str) -> Result<Option<Vec<u8>>> {
+        match self.get_scoped(key) {
+            Some(value) => value.write_to_vec_named::<TARGET>(key).map(Some),
+            None => Ok(None),
+        }
+    }
+
+    /// Gets the value stored under `key`, ignoring ASCII case.
+    ///
+    /// Keys are compared as modified UTF-8, with only the ASCII letters `A-Z`
+    /// folded; every other byte, including each byte of a non-ASCII character,
+    /// must match exactly. If several keys differ only by case, the first one
+    /// in iteration order wins.
+    ///
+    /// This walks the entries one by one, so it is always O(n) in the number
+    /// of entries. The exact-match [`get_scoped`](Self::get_scoped)
+    /// is unaffected by it.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use na_nbt::{OwnedCompound, ScopedReadableCompound, ScopedReadableValue};
+    /// use zerocopy::byteorder::BigEndian;
+    ///
+    /// let mut compound: OwnedCompound<BigEndian> = OwnedCompound::default();
+    /// compound.insert("CustomName", "Steve");
+    ///
+    /// let name = compound.get_ignore_ascii_case("customname").unwrap();
+    /// assert_eq!(name.as_string_scoped().unwrap().decode(), "Steve");
+    /// assert!(compound.get_scoped("customname").is_none());
+    /// ```
+    fn get_ignore_ascii_case<'a>(
+        &'a self,
+        key: &str,
+    ) -> Option<<Self::Config as ReadableConfig>::Value<'a>>
+    where
+        'doc: 'a,
+    {
+        let key = simd_cesu8::mutf8::encode(key);
+        self.iter_scoped()
+            .find(|(name, _)| name.raw_bytes().eq_ignore_ascii_case(&key))
+            .map(|(_, value)| value)
+    }
+
+    /// Same as [`get_ignore_ascii_case`](Self::get_ignore_ascii_case).
+    #[inline]
+    fn get_ignore_case<'a>(
+        &'a self,
+        key: &str,
+    ) -> Option<<Self::Config as ReadableConfig>::Value<'a>>
+    where
+        'doc: 'a,
+    {
+        self.get_ignore_ascii_case(key)
+    }
+
     fn iter_scoped<'a>(&'a self) -> <Self::Config as ReadableConfig>::CompoundIter<'a>
     where
         'doc: 'a;
@@ -29,6 +29,22 @@ pub trait ScopedWritableValue<'s>:
     /// Updates the byte value using a function. Returns `true` if successful.
     fn update_byte(&mut self, f: impl FnOnce(i8) -> i8) -> bool;
 
+    /// Updates the byte value using a function that may reject the new
+    /// value. The value is only written if `f` returns `Some`. Returns `true`
+    /// if it was written.
+    fn try_update_byte(&mut self, f: impl FnOnce(i8) -> Option<i8>) -> bool {
+        let Some(value) = self.as_byte_mut() else {
+            return false;
+        };
+        match f(*value) {
+            Some(data) => {
+                *value = data;
+                true
+            }
+            None => false,
+        }
+    }
+
     /// Returns a mutable reference to the short value, if it is one.
     fn as_short_mut<'a>(
         &'a mut self,
@@ -42,6 +58,22 @@ pub trait ScopedWritableValue<'s>:
     /// Updates the short value using a function. Returns `true` if successful.
     fn update_short(&mut self, f: impl FnOnce(i16) -> i16) -> bool;
 
+    /// Updates the short value using a function that may reject the new
+    /// value. The value is only written if `f` returns `Some`. Returns `true`
+    /// if it was written.
+    fn try_update_short(&mut self, f: impl FnOnce(i16) -> Option<i16>) -> bool {
+        let Some(value) = self.as_short_mut() else {
+            return false;
+        };
+        match f(value.get()) {
+            Some(data) => {
+                value.set(data);
+                true
+            }
+            None => false,
+        }
+    }
+
     /// Returns a mutable reference to the int value, if it is one.
     fn as_int_mut<'a>(
         &'a mut self,
@@ -55,6 +87,22 @@ pub trait ScopedWritableValue<'s>:
     /// Updates the int value using a function. Returns `true` if successful.
     fn update_int(&mut self, f: impl FnOnce(i32) -> i32) -> bool;
 
+    /// Updates the int value using a function that may reject the new
+    /// value. The value is only written if `f` returns `Some`. Returns `true`
+    /// if it was written.
+    fn try_update_int(&mut self, f: impl FnOnce(i32) -> Option<i32>) -> bool {
+        let Some(value) = self.as_int_mut() else {
+            return false;
+        };
+        match f(value.get()) {
+            Some(data) => {
+                value.set(data);
+                true
+            }
+            None => false,
+        }
+    }
+
     /// Returns a mutable reference to the long value, if it is one.
     fn as_long_mut<'a>(
         &'a mut self,
@@ -68,6 +116,22 @@ pub trait ScopedWritableValue<'s>:
     /// Updates the long value using a function. Returns `true` if successful.
     fn update_long(&mut self, f: impl FnOnce(i64) -> i64) -> bool;
 
+    /// Updates the long value using a function that may reject the new
+    /// value. The value is only written if `f` returns `Some`. Returns `true`
+    /// if it was written.
+    fn try_update_long(&mut self, f: impl FnOnce(i64) -> Option<i64>) -> bool {
+        let Some(value) = self.as_long_mut() else {
+            return false;
+        };
+        match f(value.get()) {
+            Some(data) => {
+                value.set(data);
+                true
+            }
+            None => false,
+        }
+    }
+
     /// Returns a mutable reference to the float value, if it is one.
     fn as_float_mut<'a>(
         &'a mut self,
@@ -81,6 +145,22 @@ pub trait ScopedWritableValue<'s>:
     /// Updates the float value using a function. Returns `true` if successful.
     fn update_float(&mut self, f: impl FnOnce(f32) -> f32) -> bool;
 
+    /// Updates the float value using a function that may reject the new
+    /// value. The value is only written if `f` returns `Some`. Returns `true`
+    /// if it was written.
+    fn try_update_float(&mut self, f: impl FnOnce(f32) -> Option<f32>) -> bool {
+        let Some(value) = self.as_float_mut() else {
+            return false;
+        };
+        match f(value.get()) {
+            Some(data) => {
+                value.set(data);
+                true
+            }
+            None => false,
+        }
+    }
+
     /// Returns a mutable reference to the double value, if it is one.
     fn as_double_mut<'a>(
         &'a mut self,
@@ -94,6 +174,22 @@ pub trait ScopedWritableValue<'s>:
     /// Updates the double value using a function. Returns `true` if successful.
     fn update_double(&mut self, f: impl FnOnce(f64) -> f64) -> bool;
 
+    /// Updates the double value using a function that may reject the new
+    /// value. The value is only written if `f` returns `Some`. Returns `true`
+    /// if it was written.
+    fn try_update_double(&mut self, f: impl FnOnce(f64) -> Option<f64>) -> bool {
+        let Some(value) = self.as_double_mut() else {
+            return false;
+        };
+        match f(value.get()) {
+            Some(data) => {
+                value.set(data);
+                true
+            }
+            None => false,
+        }
+    }
+
     /// Returns a mutable reference to the byte array with a scoped lifetime, if it is one.
     fn as_byte_array_mut_scoped<'a>(&'a mut self) -> Option<VecViewMut<'a, i8>>
     where
@@ -0,0 +1,151 @@
+use crate::{
+    Tag,
+    value_trait::{ReadableString, ScopedReadableValue, ValueScoped},
+};
+
+/// Summary statistics of an NBT tree, as returned by
+/// [`ScopedReadableValue::stats`].
+///
+/// # Example
+///
+/// ```
+/// use na_nbt::{NbtStats, OwnedCompound, OwnedValue, ScopedReadableValue, Tag};
+/// use zerocopy::byteorder::BigEndian;
+///
+/// let mut compound: OwnedCompound<BigEndian> = OwnedCompound::default();
+/// compound.insert("name", "Steve");
+/// compound.insert("scores", vec![1i8, 2, 3]);
+/// let value = OwnedValue::Compound(compound);
+///
+/// let stats = value.stats();
+/// assert_eq!(stats.compounds, 1);
+/// assert_eq!(stats.strings, 1);
+/// assert_eq!(stats.max_depth, 1);
+/// assert_eq!(stats.nodes, 3);
+/// assert_eq!(stats.count(Tag::ByteArray), 1);
+/// // "Steve" plus the three bytes of the array
+/// assert_eq!(stats.payload_bytes, 8);
+/// // Matches the encoded payload, which excludes the 3-byte root header.
+/// assert_eq!(stats.total_bytes, value.write_to_vec::<BigEndian>().unwrap().len() - 3);
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct NbtStats {
+    /// Number of compound values, including the root.
+    pub compounds: usize,
+    /// Number of list values, including the root.
+    pub lists: usize,
+    /// Number of string values. Compound keys are not counted.
+    pub strings: usize,
+    /// Size in bytes of the value's binary payload.
+    ///
+    /// This is the encoded size of the value without the root tag type and
+    /// name, and is independent of the byte order.
+    pub total_bytes: usize,
+    /// Deepest nesting of lists and compounds.
+    ///
+    /// A primitive or array is at depth 0, an empty compound has depth 1, and
+    /// a compound containing a list has depth 2.
+    pub max_depth: usize,
+    /// Number of values in the tree, the same as
+    /// [`ScopedReadableValue::node_count`].
+    pub nodes: usize,
+    /// Number of values of each tag type, indexed by `Tag as usize`.
+    ///
+    /// See also [`NbtStats::count`].
+    pub tag_counts: [usize; 13],
+    /// Total size in bytes of string contents and array elements.
+    ///
+    /// Length prefixes are not included. A large value here relative to
+    /// [`total_bytes`](NbtStats::total_bytes) means the tree is dominated by
+    /// bulk data rather than structure.
+    pub payload_bytes: usize,
+}
+
+impl NbtStats {
+    /// Returns how many values of type `tag` the tree contains.
+    #[inline]
+    pub fn count(&self, tag: Tag) -> usize {
+        self.tag_counts[tag as usize]
+    }
+}
+
+pub(crate) fn node_count<'doc>(value: &impl ScopedReadableValue<'doc>) -> usize {
+    value.visit_scoped(|value| match value {
+        ValueScoped::List(list) => {
+            1 + list
+                .into_iter()
+                .map(|item| node_count(&item))
+                .sum::<usize>()
+        }
+        ValueScoped::Compound(compound) => {
+            1 + compound
+                .into_iter()
+                .map(|(_, item)| node_count(&item))
+                .sum::<usize>()
+        }
+        _ => 1,
+    })
+}
+
+pub(crate) fn serialized_len<'doc>(value: &impl ScopedReadableValue<'doc>) -> usize {
+    match value.tag_id() {
+        Tag::End => 1,
+        // tag type + empty root name
+        _ => 1 + 2 + stats(value).total_bytes,
+    }
+}
+
+pub(crate) fn stats<'doc>(value: &impl ScopedReadableValue<'doc>) -> NbtStats {
+    let mut stats = NbtStats::default();
+    walk(value, 0, &mut stats);
+    stats
+}
+
+fn walk<'doc>(value: &impl ScopedReadableValue<'doc>, depth: usize, stats: &mut NbtStats) {
+    stats.nodes += 1;
+    stats.tag_counts[value.tag_id() as usize] += 1;
+    value.visit_scoped(|value| match value {
+        ValueScoped::End => {}
+        ValueScoped::Byte(_) => stats.total_bytes += 1,
+        ValueScoped::Short(_) => stats.total_bytes += 2,
+        ValueScoped::Int(_) | ValueScoped::Float(_) => stats.total_bytes += 4,
+        ValueScoped::Long(_) | ValueScoped::Double(_) => stats.total_bytes += 8,
+        ValueScoped::ByteArray(value) => {
+            stats.payload_bytes += value.len();
+            stats.total_bytes += 4 + value.len();
+        }
+        ValueScoped::IntArray(value) => {
+            stats.payload_bytes += value.len() * 4;
+            stats.total_bytes += 4 + value.len() * 4;
+        }
+        ValueScoped::LongArray(value) => {
+            stats.payload_bytes += value.len() * 8;
+            stats.total_bytes += 4 + value.len() * 8;
+        }
+        ValueScoped::String(value) => {
+            stats.strings += 1;
+            stats.payload_bytes += value.raw_bytes().len();
+            stats.total_bytes += 2 + value.raw_bytes().len();
+        }
+        ValueScoped::List(list) => {
+            stats.lists += 1;
+            stats.max_depth = stats.max_depth.max(depth + 1);
+            // element tag type + length
+            stats.total_bytes += 1 + 4;
+            for item in list {
+                walk(&item, depth + 1, stats);
+            }
+        }
+        ValueScoped::Compound(compound) => {
+            stats.compounds += 1;
+            stats.max_depth = stats.max_depth.max(depth + 1);
+            for (key, item) in compound {
+                // tag type + name length + name
+                stats.total_bytes += 1 + 2 + key.raw_bytes().len();
+                walk(&item, depth + 1, stats);
+            }
+            // TAG_End
+            stats.total_bytes += 1;
+        }
+    })
+}
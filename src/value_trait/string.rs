@@ -1,7 +1,40 @@
-use std::borrow::Cow;
+use alloc::borrow::Cow;
 
+/// A read-only view of an NBT string.
+///
+/// NBT strings are stored as Modified UTF-8 (MUTF-8), which differs from
+/// UTF-8 in two ways: NUL is encoded as the two bytes `C0 80`, and characters
+/// outside the Basic Multilingual Plane are encoded as a surrogate pair of
+/// three bytes each.
 pub trait ReadableString<'doc>: Send + Sync + Sized + Clone {
+    /// Returns the string's MUTF-8 bytes as stored.
     fn raw_bytes(&self) -> &[u8];
 
+    /// Decodes the string.
+    ///
+    /// When the stored bytes are already valid UTF-8, the result borrows them
+    /// and nothing is allocated. That holds for every string without NUL or
+    /// characters outside the Basic Multilingual Plane, including all ASCII
+    /// strings. Only strings containing those are converted into an owned
+    /// `String`. ASCII, the common case for keys and ids, is recognized in a
+    /// single fast scan before any decoding is attempted.
+    ///
+    /// Invalid sequences are replaced with U+FFFD.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use std::borrow::Cow;
+    /// use na_nbt::{OwnedValue, ReadableString, ScopedReadableValue};
+    /// use zerocopy::byteorder::BigEndian;
+    ///
+    /// let ascii = OwnedValue::<BigEndian>::from("minecraft:stone");
+    /// let string = ascii.as_string_scoped().unwrap();
+    /// assert!(matches!(string.decode(), Cow::Borrowed("minecraft:stone")));
+    ///
+    /// let nul = OwnedValue::<BigEndian>::from("a\0b");
+    /// let string = nul.as_string_scoped().unwrap();
+    /// assert!(matches!(string.decode(), Cow::Owned(s) if s == "a\0b"));
+    /// ```
     fn decode(&self) -> Cow<'_, str>;
 }
@@ -0,0 +1,79 @@
+use crate::value_trait::{
+    ReadableString, ScopedReadableCompound, ScopedReadableList, ScopedReadableValue, ValueScoped,
+};
+
+/// Callbacks for a whole-tree traversal driven by [`walk`].
+///
+/// Every method does nothing by default, so an implementation only overrides
+/// the ones it needs. The values passed in can be of any reader type; use the
+/// [`ScopedReadableValue`] methods to inspect them.
+///
+/// To change values while walking an [`OwnedValue`](crate::OwnedValue), use
+/// [`OwnedValue::rewrite`](crate::OwnedValue::rewrite) instead.
+///
+/// # Example
+///
+/// ```
+/// use na_nbt::{OwnedCompound, OwnedValue, ScopedReadableValue, Visitor, walk};
+/// use zerocopy::byteorder::BigEndian;
+///
+/// #[derive(Default)]
+/// struct Longs(Vec<i64>);
+///
+/// impl Visitor for Longs {
+///     fn visit_scalar<'doc>(&mut self, value: &impl ScopedReadableValue<'doc>) {
+///         self.0.extend(value.as_long());
+///     }
+/// }
+///
+/// let mut inner: OwnedCompound<BigEndian> = OwnedCompound::default();
+/// inner.insert("time", 2i64);
+/// let mut root: OwnedCompound<BigEndian> = OwnedCompound::default();
+/// root.insert("seed", 1i64);
+/// root.insert("inner", inner);
+///
+/// let mut longs = Longs::default();
+/// walk(&OwnedValue::Compound(root), &mut longs);
+/// assert_eq!(longs.0, [1, 2]);
+/// ```
+pub trait Visitor {
+    /// Called for each compound entry, before the entry's value is walked.
+    fn visit_compound_entry<'doc>(&mut self, key: &str, value: &impl ScopedReadableValue<'doc>) {
+        let _ = (key, value);
+    }
+
+    /// Called for each list element, before the element is walked.
+    fn visit_list_element<'doc>(&mut self, index: usize, value: &impl ScopedReadableValue<'doc>) {
+        let _ = (index, value);
+    }
+
+    /// Called for each value that is not a compound or a list, including
+    /// strings and arrays, and including the root if it is one.
+    fn visit_scalar<'doc>(&mut self, value: &impl ScopedReadableValue<'doc>) {
+        let _ = value;
+    }
+}
+
+/// Walks the whole tree under `value` depth-first, calling `visitor` for
+/// every compound entry, list element and scalar.
+///
+/// Compound entries are visited in storage order and list elements by index.
+/// For a scalar inside a container, the entry or element callback comes
+/// first, then [`visit_scalar`](Visitor::visit_scalar).
+pub fn walk<'doc>(value: &impl ScopedReadableValue<'doc>, visitor: &mut impl Visitor) {
+    value.visit_scoped(|scoped| match scoped {
+        ValueScoped::Compound(compound) => {
+            for (key, value) in compound.iter_scoped() {
+                visitor.visit_compound_entry(&key.decode(), &value);
+                walk(&value, visitor);
+            }
+        }
+        ValueScoped::List(list) => {
+            for (index, value) in list.iter_scoped().enumerate() {
+                visitor.visit_list_element(index, &value);
+                walk(&value, visitor);
+            }
+        }
+        _ => visitor.visit_scalar(value),
+    })
+}
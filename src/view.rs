@@ -1,19 +1,22 @@
-use std::{
+use alloc::{collections::TryReserveError, string::String, vec::Vec};
+use core::{
     borrow::{Borrow, BorrowMut},
     cmp::Ordering,
-    collections::TryReserveError,
     fmt,
     hash::{Hash, Hasher},
-    io::{self, IoSlice, Write},
     marker::PhantomData,
     mem::ManuallyDrop,
-    ops::{Deref, DerefMut, Index, IndexMut, RangeBounds},
+    ops::{Add, AddAssign, Deref, DerefMut, Index, IndexMut, RangeBounds},
     ptr,
     slice::{self, SliceIndex},
 };
+#[cfg(feature = "std")]
+use std::io::{self, IoSlice, Write};
 
 use zerocopy::Unalign;
 
+use crate::{cold_path, decode_mutf8};
+
 pub struct VecViewMut<'a, T> {
     pub(crate) ptr: &'a mut Unalign<usize>,
     pub(crate) len: &'a mut Unalign<usize>,
@@ -546,6 +549,7 @@ impl<'a, T: Copy + 'a> Extend<&'a T> for VecViewMut<'_, T> {
     }
 }
 
+#[cfg(feature = "std")]
 impl Write for VecViewMut<'_, u8> {
     fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
         self.with_vec(|v| v.write(buf))
@@ -601,14 +605,14 @@ impl<'a> StringViewMut<'a> {
     /// Temporarily decodes mutf8 to a String, calls a closure on it, then encodes back to mutf8.
     #[inline]
     fn with_string<R>(&mut self, f: impl FnOnce(&mut String) -> R) -> R {
-        use std::borrow::Cow;
+        use alloc::borrow::Cow;
 
         let old_ptr = self.as_mut_ptr();
         let old_len = self.len.get();
         let old_cap = self.cap.get();
 
         let data = unsafe { slice::from_raw_parts(old_ptr, old_len) };
-        let decoded = simd_cesu8::mutf8::decode_lossy(data);
+        let decoded = decode_mutf8(data);
 
         let mut string = match decoded {
             Cow::Borrowed(_) => {
@@ -680,9 +684,12 @@ impl<'a> StringViewMut<'a> {
     }
 
     /// Decodes the mutf8 content and returns the decoded string.
+    ///
+    /// Borrows without allocating when the content is valid UTF-8; see
+    /// [`ReadableString::decode`](crate::ReadableString::decode).
     #[inline]
-    pub fn decode(&self) -> std::borrow::Cow<'_, str> {
-        simd_cesu8::mutf8::decode_lossy(self.as_mutf8_bytes())
+    pub fn decode(&self) -> alloc::borrow::Cow<'_, str> {
+        decode_mutf8(self.as_mutf8_bytes())
     }
 
     /// Returns a raw pointer to the String's buffer.
@@ -902,9 +909,10 @@ impl AsRef<[u8]> for StringViewMut<'_> {
     }
 }
 
+#[cfg(feature = "std")]
 impl Write for StringViewMut<'_> {
     fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
-        match std::str::from_utf8(buf) {
+        match core::str::from_utf8(buf) {
             Ok(s) => {
                 self.push_str(s);
                 Ok(buf.len())
@@ -917,7 +925,7 @@ impl Write for StringViewMut<'_> {
     }
 
     fn write_all(&mut self, buf: &[u8]) -> io::Result<()> {
-        match std::str::from_utf8(buf) {
+        match core::str::from_utf8(buf) {
             Ok(s) => {
                 self.push_str(s);
                 Ok(())
@@ -1308,6 +1316,161 @@ impl<T> VecViewOwn<T> {
     }
 }
 
+macro_rules! native_int_methods {
+    ($($view:ident $(<$lt:lifetime>)?: $wrapper:ident => $native:ty;)*) => {$(
+        impl<O: zerocopy::ByteOrder> $view<$($lt,)? zerocopy::byteorder::$wrapper<O>> {
+            /// Resizes the vector to `new_len`, filling new slots with `value`
+            /// converted to the vector's byte order.
+            #[inline]
+            pub fn resize_native(&mut self, new_len: usize, value: $native) {
+                self.resize(new_len, zerocopy::byteorder::$wrapper::new(value));
+            }
+
+            /// Appends native values, converting each to the vector's byte order.
+            #[inline]
+            pub fn extend_from_native(&mut self, values: &[$native]) {
+                self.extend(values.iter().map(|&value| zerocopy::byteorder::$wrapper::new(value)));
+            }
+        }
+    )*};
+}
+
+native_int_methods! {
+    VecViewMut<'_>: I32 => i32;
+    VecViewMut<'_>: I64 => i64;
+    VecViewOwn: I32 => i32;
+    VecViewOwn: I64 => i64;
+}
+
+/// Copies `values` into a fixed-size array, or returns `None` if the length
+/// is not `N`.
+#[inline]
+pub(crate) fn fixed_array<T: Copy, U, const N: usize>(
+    values: &[T],
+    convert: impl Fn(T) -> U,
+) -> Option<[U; N]> {
+    if values.len() != N {
+        cold_path();
+        return None;
+    }
+    Some(core::array::from_fn(|i| convert(values[i])))
+}
+
+macro_rules! fixed_array_methods {
+    ($([$($generics:tt)*] $view:ty => $native:ty, $convert:expr;)*) => {$(
+        impl<$($generics)*> $view {
+            /// Copies the elements into an array of `N` native values, or
+            /// returns `None` if there are not exactly `N` of them.
+            #[inline]
+            pub fn as_array<const N: usize>(&self) -> Option<[$native; N]> {
+                $crate::view::fixed_array(self, $convert)
+            }
+        }
+
+        impl<const N: usize, $($generics)*> TryFrom<&$view> for [$native; N] {
+            type Error = crate::Error;
+
+            /// Fails with [`Error::LengthMismatch`](crate::Error::LengthMismatch)
+            /// if the length is not `N`.
+            #[inline]
+            fn try_from(array: &$view) -> crate::Result<Self> {
+                array
+                    .as_array()
+                    .ok_or(crate::Error::LengthMismatch(N, array.len()))
+            }
+        }
+    )*};
+}
+
+pub(crate) use fixed_array_methods;
+
+macro_rules! native_vec_methods {
+    ($([$($generics:tt)*] $i32_view:ty, $i64_view:ty;)*) => {$(
+        impl<$($generics)*> $i32_view {
+            /// Decodes the elements into a `Vec` of native integers.
+            #[inline]
+            pub fn to_vec_i32(&self) -> Vec<i32> {
+                self.iter().map(|value| value.get()).collect()
+            }
+        }
+
+        impl<$($generics)*> $i64_view {
+            /// Decodes the elements into a `Vec` of native integers.
+            #[inline]
+            pub fn to_vec_i64(&self) -> Vec<i64> {
+                self.iter().map(|value| value.get()).collect()
+            }
+        }
+    )*};
+}
+
+pub(crate) use native_vec_methods;
+
+fixed_array_methods! {
+    [] VecViewOwn<i8> => i8, |value| value;
+    [O: zerocopy::ByteOrder] VecViewOwn<zerocopy::byteorder::I32<O>> => i32, |value: zerocopy::byteorder::I32<O>| value.get();
+    [O: zerocopy::ByteOrder] VecViewOwn<zerocopy::byteorder::I64<O>> => i64, |value: zerocopy::byteorder::I64<O>| value.get();
+    [] VecViewMut<'_, i8> => i8, |value| value;
+    [O: zerocopy::ByteOrder] VecViewMut<'_, zerocopy::byteorder::I32<O>> => i32, |value: zerocopy::byteorder::I32<O>| value.get();
+    [O: zerocopy::ByteOrder] VecViewMut<'_, zerocopy::byteorder::I64<O>> => i64, |value: zerocopy::byteorder::I64<O>| value.get();
+}
+
+native_vec_methods! {
+    [O: crate::ByteOrder] VecViewOwn<zerocopy::byteorder::I32<O>>, VecViewOwn<zerocopy::byteorder::I64<O>>;
+    [O: crate::ByteOrder] VecViewMut<'_, zerocopy::byteorder::I32<O>>, VecViewMut<'_, zerocopy::byteorder::I64<O>>;
+}
+
+impl VecViewOwn<i8> {
+    /// Copies native bytes into a byte array.
+    ///
+    /// Bytes have no byte order, so this is the same as `VecViewOwn::from`;
+    /// it is here to match [`from_i32_slice`](VecViewOwn::from_i32_slice)
+    /// and [`from_i64_slice`](VecViewOwn::from_i64_slice).
+    #[inline]
+    pub fn from_i8_slice(values: &[i8]) -> Self {
+        values.into()
+    }
+}
+
+impl<O: crate::ByteOrder> VecViewOwn<zerocopy::byteorder::I32<O>> {
+    /// Encodes native integers into an int array stored in byte order `O`.
+    ///
+    /// The inverse of [`to_vec_i32`](Self::to_vec_i32).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use na_nbt::VecViewOwn;
+    /// use zerocopy::byteorder::{BigEndian, I32};
+    ///
+    /// let array: VecViewOwn<I32<BigEndian>> = VecViewOwn::from_i32_slice(&[1, -1]);
+    /// assert_eq!(zerocopy::IntoBytes::as_bytes(&*array), [0, 0, 0, 1, 0xff, 0xff, 0xff, 0xff]);
+    /// assert_eq!(array.to_vec_i32(), [1, -1]);
+    /// ```
+    #[inline]
+    pub fn from_i32_slice(values: &[i32]) -> Self {
+        values
+            .iter()
+            .map(|&value| zerocopy::byteorder::I32::new(value))
+            .collect::<Vec<_>>()
+            .into()
+    }
+}
+
+impl<O: crate::ByteOrder> VecViewOwn<zerocopy::byteorder::I64<O>> {
+    /// Encodes native integers into a long array stored in byte order `O`.
+    ///
+    /// The inverse of [`to_vec_i64`](Self::to_vec_i64).
+    #[inline]
+    pub fn from_i64_slice(values: &[i64]) -> Self {
+        values
+            .iter()
+            .map(|&value| zerocopy::byteorder::I64::new(value))
+            .collect::<Vec<_>>()
+            .into()
+    }
+}
+
 // ============ Trait Implementations ============
 
 impl<T> Deref for VecViewOwn<T> {
@@ -1458,6 +1621,7 @@ impl<'a, T: Copy + 'a> Extend<&'a T> for VecViewOwn<T> {
     }
 }
 
+#[cfg(feature = "std")]
 impl Write for VecViewOwn<u8> {
     fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
         self.with_vec(|v| v.write(buf))
@@ -1531,17 +1695,28 @@ impl StringViewOwn {
         Self { ptr, len, cap }
     }
 
+    /// Creates a String by encoding `string` as mutf8.
+    ///
+    /// Unlike UTF-8, mutf8 encodes U+0000 as the two bytes `C0 80` and
+    /// supplementary characters as a pair of 3-byte surrogates, so the result
+    /// of [`as_mutf8_bytes`](Self::as_mutf8_bytes) can differ from `string.as_bytes()`.
+    /// This is the same encoding performed by `From<&str>`.
+    #[inline]
+    pub fn from_str_mutf8(string: &str) -> Self {
+        Self::from(string)
+    }
+
     /// Temporarily decodes mutf8 to a String, calls a closure on it, then encodes back to mutf8.
     #[inline]
     fn with_string<R>(&mut self, f: impl FnOnce(&mut String) -> R) -> R {
-        use std::borrow::Cow;
+        use alloc::borrow::Cow;
 
         let old_ptr = self.as_mut_ptr();
         let old_len = self.len.get();
         let old_cap = self.cap.get();
 
         let data = unsafe { slice::from_raw_parts(old_ptr, old_len) };
-        let decoded = simd_cesu8::mutf8::decode_lossy(data);
+        let decoded = decode_mutf8(data);
 
         let mut string = match decoded {
             Cow::Borrowed(_) => {
@@ -1599,6 +1774,15 @@ impl StringViewOwn {
         self.len.get() == 0
     }
 
+    /// Returns the number of `char`s in this String.
+    ///
+    /// A supplementary character counts once even though mutf8 stores it as a
+    /// surrogate pair.
+    #[inline]
+    pub fn char_len(&self) -> usize {
+        self.decode().chars().count()
+    }
+
     /// Returns the total number of bytes the String can hold without reallocating.
     #[inline]
     pub fn capacity(&self) -> usize {
@@ -1613,9 +1797,12 @@ impl StringViewOwn {
     }
 
     /// Decodes the mutf8 content and returns the decoded string.
+    ///
+    /// Borrows without allocating when the content is valid UTF-8; see
+    /// [`ReadableString::decode`](crate::ReadableString::decode).
     #[inline]
-    pub fn decode(&self) -> std::borrow::Cow<'_, str> {
-        simd_cesu8::mutf8::decode_lossy(self.as_mutf8_bytes())
+    pub fn decode(&self) -> alloc::borrow::Cow<'_, str> {
+        decode_mutf8(self.as_mutf8_bytes())
     }
 
     /// Returns a raw pointer to the String's buffer.
@@ -1830,9 +2017,27 @@ impl AsRef<[u8]> for StringViewOwn {
     }
 }
 
+impl Add<&str> for StringViewOwn {
+    type Output = StringViewOwn;
+
+    #[inline]
+    fn add(mut self, rhs: &str) -> Self::Output {
+        self.push_str(rhs);
+        self
+    }
+}
+
+impl AddAssign<&str> for StringViewOwn {
+    #[inline]
+    fn add_assign(&mut self, rhs: &str) {
+        self.push_str(rhs);
+    }
+}
+
+#[cfg(feature = "std")]
 impl Write for StringViewOwn {
     fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
-        match std::str::from_utf8(buf) {
+        match core::str::from_utf8(buf) {
             Ok(s) => {
                 self.push_str(s);
                 Ok(buf.len())
@@ -1845,7 +2050,7 @@ impl Write for StringViewOwn {
     }
 
     fn write_all(&mut self, buf: &[u8]) -> io::Result<()> {
-        match std::str::from_utf8(buf) {
+        match core::str::from_utf8(buf) {
             Ok(s) => {
                 self.push_str(s);
                 Ok(())
@@ -1922,7 +2127,7 @@ impl Drop for StringViewOwn {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use std::mem::ManuallyDrop;
+    use core::mem::ManuallyDrop;
     use zerocopy::Unalign;
 
     #[test]
@@ -1973,7 +2178,7 @@ mod tests {
 
         // Reconstruct Vec back for drop to avoid leak
         let restore =
-            unsafe { std::vec::Vec::from_raw_parts(ptr.get() as *mut u8, len.get(), cap.get()) };
+            unsafe { alloc::vec::Vec::from_raw_parts(ptr.get() as *mut u8, len.get(), cap.get()) };
         drop(restore);
     }
 
@@ -1992,7 +2197,7 @@ mod tests {
 
         // restore into a Vec so it is dropped correctly
         let restore_vec =
-            unsafe { std::vec::Vec::from_raw_parts(ptr.get() as *mut u8, len.get(), cap.get()) };
+            unsafe { alloc::vec::Vec::from_raw_parts(ptr.get() as *mut u8, len.get(), cap.get()) };
         drop(restore_vec);
     }
 
@@ -2234,7 +2439,7 @@ mod tests {
         assert_eq!(view.as_slice(), &[10u8, 20]);
 
         let restore =
-            unsafe { std::vec::Vec::from_raw_parts(ptr.get() as *mut u8, len.get(), cap.get()) };
+            unsafe { alloc::vec::Vec::from_raw_parts(ptr.get() as *mut u8, len.get(), cap.get()) };
         drop(restore);
     }
 
@@ -2252,7 +2457,7 @@ mod tests {
         assert!(view.is_empty());
 
         let restore =
-            unsafe { std::vec::Vec::from_raw_parts(ptr.get() as *mut u8, len.get(), cap.get()) };
+            unsafe { alloc::vec::Vec::from_raw_parts(ptr.get() as *mut u8, len.get(), cap.get()) };
         drop(restore);
     }
 
@@ -2271,7 +2476,7 @@ mod tests {
         assert_eq!(removed, 20);
 
         let restore =
-            unsafe { std::vec::Vec::from_raw_parts(ptr.get() as *mut u8, len.get(), cap.get()) };
+            unsafe { alloc::vec::Vec::from_raw_parts(ptr.get() as *mut u8, len.get(), cap.get()) };
         drop(restore);
     }
 
@@ -2288,7 +2493,7 @@ mod tests {
         assert_eq!(view.as_slice(), &[1, 2, 3, 4, 5]);
 
         let restore =
-            unsafe { std::vec::Vec::from_raw_parts(ptr.get() as *mut u8, len.get(), cap.get()) };
+            unsafe { alloc::vec::Vec::from_raw_parts(ptr.get() as *mut u8, len.get(), cap.get()) };
         drop(restore);
     }
 
@@ -2308,7 +2513,7 @@ mod tests {
         assert_eq!(view.pop(), Some('!'));
 
         let restore_vec =
-            unsafe { std::vec::Vec::from_raw_parts(ptr.get() as *mut u8, len.get(), cap.get()) };
+            unsafe { alloc::vec::Vec::from_raw_parts(ptr.get() as *mut u8, len.get(), cap.get()) };
         drop(restore_vec);
     }
 
@@ -2327,7 +2532,7 @@ mod tests {
         assert!(view.is_empty());
 
         let restore_vec =
-            unsafe { std::vec::Vec::from_raw_parts(ptr.get() as *mut u8, len.get(), cap.get()) };
+            unsafe { alloc::vec::Vec::from_raw_parts(ptr.get() as *mut u8, len.get(), cap.get()) };
         drop(restore_vec);
     }
 
@@ -2371,7 +2576,7 @@ mod tests {
     #[test]
     fn vec_view_own_splice_drop_empty_replace() {
         let mut v = VecViewOwn::from(vec![1u8, 2, 3]);
-        v.splice_drop(1..2, std::iter::empty());
+        v.splice_drop(1..2, core::iter::empty());
         assert_eq!(v.as_slice(), &[1, 3]);
     }
 
@@ -2621,7 +2826,7 @@ mod tests {
         assert_eq!(view.as_slice(), &[1, 4, 5]);
 
         let restore =
-            unsafe { std::vec::Vec::from_raw_parts(ptr.get() as *mut u8, len.get(), cap.get()) };
+            unsafe { alloc::vec::Vec::from_raw_parts(ptr.get() as *mut u8, len.get(), cap.get()) };
         drop(restore);
     }
 
@@ -2638,7 +2843,7 @@ mod tests {
         assert_eq!(view.as_slice(), &[1, 10, 20, 3]);
 
         let restore =
-            unsafe { std::vec::Vec::from_raw_parts(ptr.get() as *mut u8, len.get(), cap.get()) };
+            unsafe { alloc::vec::Vec::from_raw_parts(ptr.get() as *mut u8, len.get(), cap.get()) };
         drop(restore);
     }
 
@@ -2655,7 +2860,7 @@ mod tests {
         assert_eq!(view.decode().to_string(), "HelloWorld");
 
         let restore_vec =
-            unsafe { std::vec::Vec::from_raw_parts(ptr.get() as *mut u8, len.get(), cap.get()) };
+            unsafe { alloc::vec::Vec::from_raw_parts(ptr.get() as *mut u8, len.get(), cap.get()) };
         drop(restore_vec);
     }
 }
@@ -0,0 +1,327 @@
+//! A bounds-checked walk over encoded NBT that skips values without building
+//! anything.
+//!
+//! The readers that only need to check or measure a document, rather than
+//! decode it, share this walk: the header checks of
+//! [`read_owned_strict`](crate::read_owned_strict) and the limited readers,
+//! the cut of [`read_owned_lenient`](crate::read_owned_lenient), the skipping
+//! of the lazy and preserving readers and the framing of `read_async`. Each
+//! hooks in through [`Hooks`] for what it does on top.
+//!
+//! A [`Walk`] can be resumed: when the input runs out it reports how much is
+//! needed and keeps its place, so that a stream reader can fetch more bytes
+//! and carry on.
+
+use alloc::vec::Vec;
+
+use zerocopy::byteorder;
+
+use crate::{ByteOrder, Error, Result, cold_path};
+
+/// A container the walk is inside of.
+pub(crate) enum Frame {
+    Compound,
+    /// `header` is where the element tag of the list sits, followed by its
+    /// length `len`; `remaining` elements have not been started.
+    List {
+        tag_id: u8,
+        header: usize,
+        len: u32,
+        remaining: u32,
+    },
+}
+
+/// Where an unknown tag type was found.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Place {
+    Root,
+    Entry,
+    /// The element type of the list whose header is at the position given.
+    Element,
+}
+
+/// What the walkers do on top of skipping.
+///
+/// Each hook is called once, as soon as the bytes it describes are in the
+/// input; returning an error stops the walk with it.
+pub(crate) trait Hooks {
+    /// Called for each value, or with the length of a list of numbers, whose
+    /// elements are skipped at once.
+    #[inline]
+    fn values(&mut self, _count: usize) -> Result<()> {
+        Ok(())
+    }
+
+    /// Called with the length of each string.
+    #[inline]
+    fn string(&mut self, _len: usize) -> Result<()> {
+        Ok(())
+    }
+
+    /// Called with each list header, and the number of input bytes after it.
+    #[inline]
+    fn list(&mut self, _tag_id: u8, _len: usize, _rest: usize) -> Result<()> {
+        Ok(())
+    }
+
+    /// Called at a tag type this crate does not know, with the position of
+    /// the tag byte, or of the list header for [`Place::Element`], and the
+    /// containers open around it. Returning `Ok` stops the walk there.
+    #[inline]
+    fn unknown_tag(
+        &mut self,
+        tag_id: u8,
+        pos: usize,
+        _place: Place,
+        _stack: &[Frame],
+    ) -> Result<()> {
+        cold_path();
+        Err(Error::InvalidTagType(tag_id).at(pos))
+    }
+}
+
+impl Hooks for () {}
+
+/// How far a [`Walk`] got.
+pub(crate) enum Progress {
+    /// The value ends at this position.
+    Done(usize),
+    /// [`Hooks::unknown_tag`] stopped the walk.
+    Stopped,
+    /// The input must reach `end` bytes for the walk to go on;
+    /// `list_header` tells whether it ran out inside a list header.
+    Need {
+        // only stream readers fetch more input
+        #[cfg_attr(not(feature = "tokio"), allow(dead_code))]
+        end: usize,
+        list_header: bool,
+    },
+}
+
+#[derive(Clone, Copy)]
+enum State {
+    /// The tag and name of a document.
+    Header,
+    Payload(u8),
+    /// Bytes with nothing to look at.
+    Skip(usize),
+    /// The next entry or element of the innermost container.
+    Next,
+}
+
+/// A walk over one document, or one payload, at a time.
+pub(crate) struct Walk<'a> {
+    stack: &'a mut Vec<Frame>,
+    pos: usize,
+    state: State,
+}
+
+impl<'a> Walk<'a> {
+    /// Starts a walk over a whole document, tag and name included, at `pos`.
+    ///
+    /// `stack` is scratch space, passed in so that it can be reused.
+    pub(crate) fn document(stack: &'a mut Vec<Frame>, pos: usize) -> Self {
+        stack.clear();
+        Self {
+            stack,
+            pos,
+            state: State::Header,
+        }
+    }
+
+    /// Starts a walk over a payload of the known tag type `tag_id` at `pos`.
+    #[cfg(feature = "shared")]
+    pub(crate) fn payload(stack: &'a mut Vec<Frame>, pos: usize, tag_id: u8) -> Self {
+        stack.clear();
+        Self {
+            stack,
+            pos,
+            state: State::Payload(tag_id),
+        }
+    }
+
+    /// Walks `data` as far as it goes.
+    ///
+    /// On [`Progress::Need`] the walk stays where it ran out, and can be run
+    /// again once `data` has grown.
+    pub(crate) fn run<O: ByteOrder>(
+        &mut self,
+        data: &[u8],
+        hooks: &mut impl Hooks,
+    ) -> Result<Progress> {
+        macro_rules! need {
+            ($n:expr) => {
+                need!($n, false)
+            };
+            ($n:expr, $list_header:expr) => {{
+                match self.pos.checked_add($n) {
+                    Some(end) if end <= data.len() => end,
+                    Some(end) => {
+                        cold_path();
+                        return Ok(Progress::Need {
+                            end,
+                            list_header: $list_header,
+                        });
+                    }
+                    None => {
+                        cold_path();
+                        return Err(Error::EndOfFile);
+                    }
+                }
+            }};
+        }
+        macro_rules! u16_at {
+            ($pos:expr) => {
+                byteorder::U16::<O>::from_bytes(data[$pos..$pos + 2].try_into().unwrap()).get()
+                    as usize
+            };
+        }
+        macro_rules! u32_at {
+            ($pos:expr) => {
+                byteorder::U32::<O>::from_bytes(data[$pos..$pos + 4].try_into().unwrap()).get()
+                    as usize
+            };
+        }
+        /// The size of one payload of each tag that has a fixed size.
+        const FIXED_SIZE: [usize; 13] = [0, 1, 2, 4, 8, 4, 8, 1, 0, 0, 0, 4, 8];
+
+        loop {
+            match self.state {
+                State::Header => {
+                    need!(1);
+                    let tag_id = data[self.pos];
+                    if tag_id == 0 {
+                        return Ok(Progress::Done(self.pos + 1));
+                    }
+                    if tag_id > 12 {
+                        hooks.unknown_tag(tag_id, self.pos, Place::Root, self.stack)?;
+                        return Ok(Progress::Stopped);
+                    }
+                    need!(3);
+                    let end = need!(3 + u16_at!(self.pos + 1));
+                    hooks.values(1)?;
+                    self.pos = end;
+                    self.state = State::Payload(tag_id);
+                }
+                State::Payload(tag_id) => match tag_id {
+                    1..=6 => self.state = State::Skip(FIXED_SIZE[tag_id as usize]),
+                    7 | 11 | 12 => {
+                        need!(4);
+                        let len = u32_at!(self.pos);
+                        let Some(size) = len.checked_mul(FIXED_SIZE[tag_id as usize]) else {
+                            cold_path();
+                            return Err(Error::EndOfFile);
+                        };
+                        self.pos += 4;
+                        self.state = State::Skip(size);
+                    }
+                    8 => {
+                        need!(2);
+                        let len = u16_at!(self.pos);
+                        hooks.string(len)?;
+                        self.pos += 2;
+                        self.state = State::Skip(len);
+                    }
+                    9 => {
+                        let header = self.pos;
+                        let end = need!(5, true);
+                        let element = data[header];
+                        let len = u32_at!(header + 1);
+                        if element > 12 {
+                            hooks.unknown_tag(element, header, Place::Element, self.stack)?;
+                            return Ok(Progress::Stopped);
+                        }
+                        if element == 0 && len > 0 {
+                            cold_path();
+                            return Err(Error::InvalidTagType(0).at(header));
+                        }
+                        hooks.list(element, len, data.len() - end)?;
+                        self.pos = end;
+                        if (1..=6).contains(&element) {
+                            hooks.values(len)?;
+                            let Some(size) = len.checked_mul(FIXED_SIZE[element as usize]) else {
+                                cold_path();
+                                return Err(Error::EndOfFile);
+                            };
+                            self.state = State::Skip(size);
+                        } else {
+                            self.stack.push(Frame::List {
+                                tag_id: element,
+                                header,
+                                len: len as u32,
+                                remaining: len as u32,
+                            });
+                            self.state = State::Next;
+                        }
+                    }
+                    10 => {
+                        self.stack.push(Frame::Compound);
+                        self.state = State::Next;
+                    }
+                    _ => self.state = State::Next,
+                },
+                State::Skip(n) => {
+                    self.pos = need!(n);
+                    self.state = State::Next;
+                }
+                State::Next => match self.stack.last_mut() {
+                    None => return Ok(Progress::Done(self.pos)),
+                    Some(Frame::Compound) => {
+                        need!(1);
+                        let tag_id = data[self.pos];
+                        if tag_id == 0 {
+                            self.pos += 1;
+                            self.stack.pop();
+                            continue;
+                        }
+                        if tag_id > 12 {
+                            hooks.unknown_tag(tag_id, self.pos, Place::Entry, self.stack)?;
+                            return Ok(Progress::Stopped);
+                        }
+                        need!(3);
+                        let end = need!(3 + u16_at!(self.pos + 1));
+                        hooks.values(1)?;
+                        self.pos = end;
+                        self.state = State::Payload(tag_id);
+                    }
+                    Some(Frame::List {
+                        tag_id, remaining, ..
+                    }) => {
+                        if *remaining == 0 || *tag_id == 0 {
+                            self.stack.pop();
+                            continue;
+                        }
+                        *remaining -= 1;
+                        let tag_id = *tag_id;
+                        hooks.values(1)?;
+                        self.state = State::Payload(tag_id);
+                    }
+                },
+            }
+        }
+    }
+
+    /// Walks the whole of `data`, failing where it runs out.
+    ///
+    /// Returns where the value ends, or `None` if a hook stopped the walk.
+    pub(crate) fn run_to_end<O: ByteOrder>(
+        &mut self,
+        data: &[u8],
+        hooks: &mut impl Hooks,
+    ) -> Result<Option<usize>> {
+        match self.run::<O>(data, hooks)? {
+            Progress::Done(end) => Ok(Some(end)),
+            Progress::Stopped => Ok(None),
+            Progress::Need {
+                list_header: true, ..
+            } => {
+                cold_path();
+                Err(Error::truncated_list_header())
+            }
+            Progress::Need { .. } => {
+                cold_path();
+                Err(Error::EndOfFile)
+            }
+        }
+    }
+}
@@ -0,0 +1,197 @@
+use na_nbt::{ByteOrder, OwnedList, OwnedValue, Tag, read_owned};
+use zerocopy::byteorder::{self, BigEndian as BE, LittleEndian as LE};
+
+fn list_of<O: ByteOrder, T: Copy>(
+    values: &[T],
+    push: impl Fn(&mut OwnedList<O>, T),
+) -> OwnedValue<O> {
+    let mut list = OwnedList::<O>::default();
+    for &value in values {
+        push(&mut list, value);
+    }
+    OwnedValue::List(list)
+}
+
+#[test]
+fn int_list_round_trip() {
+    let list = list_of::<BE, i32>(&[1, -2, i32::MAX, i32::MIN], |l, v| l.push(v));
+    let before = list.write_to_vec::<BE>().unwrap();
+
+    let array = list.list_to_int_array().unwrap();
+    assert_eq!(array.tag_id(), Tag::IntArray);
+    let values: Vec<i32> = array
+        .as_int_array()
+        .unwrap()
+        .iter()
+        .map(|v| v.get())
+        .collect();
+    assert_eq!(values, [1, -2, i32::MAX, i32::MIN]);
+
+    let list = array.int_array_to_list().unwrap();
+    assert_eq!(list.write_to_vec::<BE>().unwrap(), before);
+}
+
+#[test]
+fn long_list_round_trip_little_endian() {
+    let list = list_of::<LE, i64>(&[0, i64::MIN, 0x0102_0304_0506_0708], |l, v| l.push(v));
+    let before = list.write_to_vec::<LE>().unwrap();
+
+    let array = list.list_to_long_array().unwrap();
+    let values: Vec<i64> = array
+        .as_long_array()
+        .unwrap()
+        .iter()
+        .map(|v| v.get())
+        .collect();
+    assert_eq!(values, [0, i64::MIN, 0x0102_0304_0506_0708]);
+
+    let list = array.long_array_to_list().unwrap();
+    assert_eq!(list.write_to_vec::<LE>().unwrap(), before);
+}
+
+#[test]
+fn byte_array_round_trip() {
+    let array: OwnedValue<BE> = vec![-128i8, 0, 127].into();
+    let before = array.as_byte_array().unwrap().to_vec();
+
+    let list = array.byte_array_to_list().unwrap();
+    let list_ref = list.as_list().unwrap();
+    assert_eq!(list_ref.tag_id(), Tag::Byte);
+    assert_eq!(list_ref.len(), 3);
+    assert_eq!(list_ref.get(0).and_then(|v| v.as_byte()), Some(-128));
+
+    let array = list.list_to_byte_array().unwrap();
+    assert_eq!(array.as_byte_array().unwrap(), before.as_slice());
+}
+
+#[test]
+fn array_to_list_matches_parsed_list_bytes() {
+    // List<Int>[1, 2] as it appears on the wire
+    let data = [
+        0x09, 0x00, 0x00, 0x03, 0x00, 0x00, 0x00, 0x02, 0x00, 0x00, 0x00, 0x01, 0x00, 0x00, 0x00,
+        0x02,
+    ];
+    let parsed = read_owned::<BE, BE>(&data).unwrap();
+
+    let array =
+        OwnedValue::<BE>::IntArray(vec![byteorder::I32::new(1), byteorder::I32::new(2)].into());
+    let converted = array.int_array_to_list().unwrap();
+    assert_eq!(
+        converted.write_to_vec::<BE>().unwrap(),
+        parsed.write_to_vec::<BE>().unwrap()
+    );
+}
+
+#[test]
+fn empty_list_converts_to_empty_array() {
+    let empty = || OwnedValue::<BE>::List(OwnedList::default());
+    assert_eq!(
+        empty()
+            .list_to_byte_array()
+            .unwrap()
+            .as_byte_array()
+            .unwrap()
+            .len(),
+        0
+    );
+    assert_eq!(
+        empty()
+            .list_to_int_array()
+            .unwrap()
+            .as_int_array()
+            .unwrap()
+            .len(),
+        0
+    );
+    assert_eq!(
+        empty()
+            .list_to_long_array()
+            .unwrap()
+            .as_long_array()
+            .unwrap()
+            .len(),
+        0
+    );
+
+    let list = OwnedValue::<BE>::LongArray(Vec::new().into())
+        .long_array_to_list()
+        .unwrap();
+    assert_eq!(list.as_list().unwrap().len(), 0);
+}
+
+#[test]
+fn mismatched_element_type_returns_none() {
+    let ints = || list_of::<BE, i32>(&[1, 2], |l, v| l.push(v));
+    assert!(ints().list_to_byte_array().is_none());
+    assert!(ints().list_to_long_array().is_none());
+
+    let strings = list_of::<BE, &str>(&["a"], |l, v| l.push(v));
+    assert!(strings.list_to_int_array().is_none());
+
+    let not_a_list = OwnedValue::<BE>::Int(byteorder::I32::new(1));
+    assert!(not_a_list.list_to_int_array().is_none());
+
+    let byte_array: OwnedValue<BE> = vec![1i8].into();
+    assert!(byte_array.int_array_to_list().is_none());
+    let int_array = OwnedValue::<BE>::IntArray(Vec::new().into());
+    assert!(int_array.long_array_to_list().is_none());
+}
+
+#[test]
+fn borrowed_conversions_match_owned() {
+    let ints = list_of::<BE, i32>(&[7, -7], |l, v| l.push(v));
+    let data = ints.write_to_vec::<BE>().unwrap();
+    let doc = na_nbt::read_borrowed::<BE>(&data).unwrap();
+
+    let array = doc.root().list_to_int_array().unwrap();
+    let values: Vec<i32> = array
+        .as_int_array()
+        .unwrap()
+        .iter()
+        .map(|v| v.get())
+        .collect();
+    assert_eq!(values, [7, -7]);
+    assert!(doc.root().list_to_byte_array().is_none());
+    assert!(doc.root().list_to_long_array().is_none());
+
+    let array_data = array.write_to_vec::<BE>().unwrap();
+    let doc = na_nbt::read_borrowed::<BE>(&array_data).unwrap();
+    let list = doc.root().int_array_to_list().unwrap();
+    assert_eq!(list.write_to_vec::<BE>().unwrap(), data);
+    assert!(doc.root().long_array_to_list().is_none());
+}
+
+#[test]
+fn borrowed_byte_and_long_conversions() {
+    let bytes: OwnedValue<LE> = vec![-1i8, 0, 1].into();
+    let data = bytes.write_to_vec::<LE>().unwrap();
+    let doc = na_nbt::read_borrowed::<LE>(&data).unwrap();
+    let list = doc.root().byte_array_to_list().unwrap();
+    assert_eq!(list.as_list().unwrap().tag_id(), Tag::Byte);
+    assert_eq!(
+        list.list_to_byte_array().unwrap().as_byte_array().unwrap(),
+        [-1, 0, 1]
+    );
+
+    let longs = list_of::<LE, i64>(&[i64::MIN, 3], |l, v| l.push(v));
+    let data = longs.write_to_vec::<LE>().unwrap();
+    let doc = na_nbt::read_borrowed::<LE>(&data).unwrap();
+    let array = doc.root().list_to_long_array().unwrap();
+    assert_eq!(array.as_long_array().unwrap()[0].get(), i64::MIN);
+    assert_eq!(array.as_long_array().unwrap()[1].get(), 3);
+
+    let empty = OwnedValue::<LE>::List(OwnedList::default())
+        .write_to_vec::<LE>()
+        .unwrap();
+    let doc = na_nbt::read_borrowed::<LE>(&empty).unwrap();
+    assert_eq!(
+        doc.root()
+            .list_to_byte_array()
+            .unwrap()
+            .as_byte_array()
+            .unwrap()
+            .len(),
+        0
+    );
+    assert!(doc.root().byte_array_to_list().is_none());
+}
@@ -0,0 +1,82 @@
+//! Tests for resizing typed arrays in place.
+
+use na_nbt::{ByteOrder, OwnedValue, read_owned};
+use zerocopy::byteorder::{BigEndian as BE, I32, LittleEndian as LE};
+
+fn int_array<O: ByteOrder>(values: &[i32]) -> OwnedValue<O> {
+    OwnedValue::from(values.iter().map(|&v| I32::<O>::new(v)).collect::<Vec<_>>())
+}
+
+#[test]
+fn test_owned_int_array_resize_native_both_orders() {
+    let mut be: OwnedValue<BE> = int_array(&[1, 2]);
+    let mut le: OwnedValue<LE> = int_array(&[1, 2]);
+
+    let OwnedValue::IntArray(arr) = &mut be else {
+        panic!("expected an int array");
+    };
+    arr.resize_native(4, -1);
+    arr.extend_from_native(&[7, 8]);
+    arr.truncate(5);
+    assert_eq!(
+        arr.iter().map(|v| v.get()).collect::<Vec<_>>(),
+        [1, 2, -1, -1, 7]
+    );
+
+    let OwnedValue::IntArray(arr) = &mut le else {
+        panic!("expected an int array");
+    };
+    arr.resize_native(4, -1);
+    arr.extend_from_native(&[7, 8]);
+    arr.truncate(5);
+    assert_eq!(
+        arr.iter().map(|v| v.get()).collect::<Vec<_>>(),
+        [1, 2, -1, -1, 7]
+    );
+
+    // both encode to the same document
+    assert_eq!(
+        be.write_to_vec::<BE>().unwrap(),
+        le.write_to_vec::<BE>().unwrap()
+    );
+    let round: OwnedValue<BE> = read_owned::<BE, BE>(&le.write_to_vec::<BE>().unwrap()).unwrap();
+    assert_eq!(
+        round
+            .as_int_array()
+            .unwrap()
+            .iter()
+            .map(|v| v.get())
+            .collect::<Vec<_>>(),
+        [1, 2, -1, -1, 7]
+    );
+}
+
+#[test]
+fn test_int_array_mut_resize_native() {
+    let mut value: OwnedValue<LE> = int_array(&[5, 6, 7]);
+    {
+        let mut arr = value.as_int_array_mut().unwrap();
+        arr.resize_native(1, 0);
+        arr.resize_native(3, 0x0102_0304);
+    }
+    let bytes = value.write_to_vec::<BE>().unwrap();
+    assert_eq!(
+        bytes,
+        [
+            0x0B, 0x00, 0x00, // root int array
+            0x00, 0x00, 0x00, 0x03, // length
+            0x00, 0x00, 0x00, 0x05, 0x01, 0x02, 0x03, 0x04, 0x01, 0x02, 0x03, 0x04,
+        ]
+    );
+}
+
+#[test]
+fn test_long_array_extend_from_native() {
+    let mut value: OwnedValue<BE> = OwnedValue::from(Vec::<zerocopy::byteorder::I64<BE>>::new());
+    let mut arr = value.as_long_array_mut().unwrap();
+    arr.extend_from_native(&[i64::MIN, 0, i64::MAX]);
+    assert_eq!(
+        arr.iter().map(|v| v.get()).collect::<Vec<_>>(),
+        [i64::MIN, 0, i64::MAX]
+    );
+}
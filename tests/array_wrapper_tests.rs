@@ -0,0 +1,83 @@
+use na_nbt::{
+    ByteArray, Error, IntArray, LongArray, Tag, from_slice_be, from_slice_le, to_vec_be, to_vec_le,
+};
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+struct Section {
+    biomes: IntArray,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+struct Plain {
+    biomes: Vec<i32>,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+struct Chunk {
+    light: ByteArray,
+    heights: IntArray,
+    states: LongArray,
+    palette: Vec<i32>,
+}
+
+#[test]
+fn int_array_field_round_trips_as_int_array() {
+    let section = Section {
+        biomes: vec![1, -2, 3].into(),
+    };
+    let bytes = to_vec_be(&section).unwrap();
+    // root compound header, then the field's tag
+    assert_eq!(bytes[3], Tag::IntArray as u8);
+    assert_eq!(bytes[3], 0x0b);
+    assert_eq!(from_slice_be::<Section>(&bytes).unwrap(), section);
+}
+
+#[test]
+fn plain_vec_stays_a_list() {
+    let plain = Plain {
+        biomes: vec![1, 2, 3],
+    };
+    let bytes = to_vec_be(&plain).unwrap();
+    assert_eq!(bytes[3], Tag::List as u8);
+
+    // a list where an IntArray is required is rejected
+    match from_slice_be::<Section>(&bytes) {
+        Err(Error::TagMismatch(expected, actual)) => {
+            assert_eq!(expected, Tag::IntArray as u8);
+            assert_eq!(actual, Tag::List as u8);
+        }
+        other => panic!("expected TagMismatch, got {other:?}"),
+    }
+
+    // while the plain Vec still reads an IntArray
+    let section = Section {
+        biomes: vec![4, 5].into(),
+    };
+    let bytes = to_vec_be(&section).unwrap();
+    assert_eq!(from_slice_be::<Plain>(&bytes).unwrap().biomes, vec![4, 5]);
+}
+
+#[test]
+fn all_wrappers_round_trip() {
+    let chunk = Chunk {
+        light: vec![0, 15, -1].into(),
+        heights: vec![64; 4].into(),
+        states: vec![i64::MIN, 0, i64::MAX].into(),
+        palette: vec![7],
+    };
+    let bytes = to_vec_be(&chunk).unwrap();
+    assert_eq!(from_slice_be::<Chunk>(&bytes).unwrap(), chunk);
+
+    let bytes = to_vec_le(&chunk).unwrap();
+    assert_eq!(from_slice_le::<Chunk>(&bytes).unwrap(), chunk);
+}
+
+#[test]
+fn wrappers_deref_to_vec() {
+    let mut states = LongArray::default();
+    states.push(1);
+    states.extend([2, 3]);
+    assert_eq!(states.len(), 3);
+    assert_eq!(Vec::from(states), vec![1, 2, 3]);
+}
@@ -0,0 +1,109 @@
+#![cfg(feature = "tokio")]
+
+use std::{
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+use na_nbt::{
+    Error, OwnedCompound, OwnedList, OwnedValue, read_async, read_owned, values_equal, write_async,
+};
+use tokio::io::{AsyncRead, ReadBuf};
+use zerocopy::byteorder::{self, BigEndian as BE, LittleEndian as LE};
+
+fn sample() -> OwnedValue<BE> {
+    let mut items = OwnedList::<BE>::default();
+    for count in [1i8, 2, 3] {
+        let mut item = OwnedCompound::<BE>::default();
+        item.insert("id", "minecraft:stone");
+        item.insert("count", count);
+        items.push(item);
+    }
+
+    let mut root = OwnedCompound::<BE>::default();
+    root.insert("name", "Steve");
+    root.insert("items", items);
+    root.insert("bytes", vec![1i8, 2, 3]);
+    root.insert("ids", [7i32, 8].map(byteorder::I32::<BE>::new));
+    root.insert("times", [9i64].map(byteorder::I64::<BE>::new));
+    root.insert("empty", OwnedList::<BE>::default());
+    OwnedValue::Compound(root)
+}
+
+/// Hands out one byte per poll, like a very slow socket.
+struct Trickle<'a>(&'a [u8]);
+
+impl AsyncRead for Trickle<'_> {
+    fn poll_read(
+        mut self: Pin<&mut Self>,
+        _cx: &mut Context<'_>,
+        buf: &mut ReadBuf<'_>,
+    ) -> Poll<std::io::Result<()>> {
+        if let Some((&first, rest)) = self.0.split_first() {
+            buf.put_slice(&[first]);
+            self.0 = rest;
+        }
+        Poll::Ready(Ok(()))
+    }
+}
+
+#[tokio::test]
+async fn write_then_read() {
+    let value = sample();
+    let mut out = Vec::new();
+    write_async::<LE>(&value, &mut out).await.unwrap();
+    assert_eq!(out, value.write_to_vec::<LE>().unwrap());
+
+    let read: OwnedValue<BE> = read_async::<LE, BE>(&mut out.as_slice()).await.unwrap();
+    assert!(values_equal(&read, &value));
+}
+
+#[tokio::test]
+async fn reads_byte_by_byte() {
+    let bytes = sample().write_to_vec::<BE>().unwrap();
+    let read: OwnedValue<BE> = read_async::<BE, BE>(&mut Trickle(&bytes)).await.unwrap();
+    let expected: OwnedValue<BE> = read_owned::<BE, BE>(&bytes).unwrap();
+    assert!(values_equal(&read, &expected));
+}
+
+#[tokio::test]
+async fn reads_documents_back_to_back() {
+    let mut stream = sample().write_to_vec::<BE>().unwrap();
+    stream.extend(OwnedValue::<BE>::from(5i32).write_to_vec::<BE>().unwrap());
+
+    let mut reader = stream.as_slice();
+    let first: OwnedValue<BE> = read_async::<BE, BE>(&mut reader).await.unwrap();
+    assert!(values_equal(&first, &sample()));
+    let second: OwnedValue<BE> = read_async::<BE, BE>(&mut reader).await.unwrap();
+    assert_eq!(second.as_int(), Some(5));
+    assert!(reader.is_empty());
+}
+
+#[tokio::test]
+async fn truncated_stream() {
+    let bytes = sample().write_to_vec::<BE>().unwrap();
+    for len in [0, 1, 3, bytes.len() / 2, bytes.len() - 1] {
+        let result = read_async::<BE, BE>(&mut &bytes[..len]).await;
+        assert!(matches!(result, Err(Error::EndOfFile)), "len {len}");
+    }
+}
+
+#[tokio::test]
+async fn invalid_tag() {
+    let bytes = [0x0a, 0x00, 0x00, 0x0d, 0x00, 0x00];
+    let err = read_async::<BE, BE>(&mut &bytes[..]).await.err().unwrap();
+    assert!(matches!(err.kind(), Error::InvalidTagType(0x0d)));
+    assert_eq!(err.offset(), Some(3));
+}
+
+#[tokio::test]
+async fn truncated_list_header() {
+    let data = [0x09, 0x00, 0x00, 0x03, 0x00, 0x00];
+    let result = read_async::<BE, BE>(&mut &data[..]).await;
+    assert!(matches!(
+        result,
+        Err(Error::Truncated {
+            context: "list header"
+        })
+    ));
+}
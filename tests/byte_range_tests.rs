@@ -0,0 +1,113 @@
+//! Tests for `raw_payload` and `byte_range`.
+
+use bytes::Bytes;
+use na_nbt::{OwnedCompound, OwnedList, OwnedValue, read_borrowed, read_owned, read_shared};
+use zerocopy::byteorder::BigEndian as BE;
+
+fn tree() -> Vec<u8> {
+    let mut list: OwnedList<BE> = OwnedList::default();
+    list.push("a");
+    list.push("bc");
+    let mut inner: OwnedCompound<BE> = OwnedCompound::default();
+    inner.insert("x", 1i32);
+    let mut root: OwnedCompound<BE> = OwnedCompound::default();
+    root.insert("level", 3i32);
+    root.insert("name", "Steve");
+    root.insert("list", list);
+    root.insert("inner", inner);
+    root.insert("bytes", vec![1i8, 2, 3]);
+    root.insert(
+        "ints",
+        OwnedValue::IntArray(vec![7.into(), 8.into()].into()),
+    );
+    OwnedValue::Compound(root).write_to_vec::<BE>().unwrap()
+}
+
+#[test]
+fn test_ranges_reextract_entries() {
+    let bytes = tree();
+    let doc = read_borrowed::<BE>(&bytes).unwrap();
+    let root = doc.root();
+    assert_eq!(root.byte_range(), Some(3..bytes.len()));
+
+    let compound = root.as_compound().unwrap();
+    let mut seen = 0;
+    for (key, value) in compound.iter() {
+        let range = compound.get_byte_range(&key.decode()).unwrap();
+        match value.raw_payload() {
+            Some(payload) => {
+                assert_eq!(&bytes[range.clone()], payload);
+                assert_eq!(value.byte_range(), Some(range.clone()));
+            }
+            None => assert_eq!(value.byte_range(), None),
+        }
+
+        // a tag byte and an empty name turn the payload back into a document
+        let mut standalone = vec![value.tag_id() as u8, 0, 0];
+        standalone.extend_from_slice(&bytes[range]);
+        let reread: OwnedValue<BE> = read_owned::<BE, BE>(&standalone).unwrap();
+        let original: OwnedValue<BE> =
+            read_owned::<BE, BE>(&value.write_to_vec::<BE>().unwrap()).unwrap();
+        assert!(na_nbt::values_equal(&reread, &original), "{}", key.decode());
+        seen += 1;
+    }
+    assert_eq!(seen, 6);
+    assert_eq!(compound.get_byte_range("missing"), None);
+}
+
+#[test]
+fn test_nested_and_numeric_values() {
+    let bytes = tree();
+    let doc = read_borrowed::<BE>(&bytes).unwrap();
+    let element = doc.root().get("list").unwrap().get(1).unwrap();
+    assert_eq!(&bytes[element.byte_range().unwrap()], &[0, 2, b'b', b'c']);
+    let list = doc.root().get("list").unwrap();
+    let list = list.as_list().unwrap();
+    assert_eq!(list.get_byte_range(1), element.byte_range());
+    assert_eq!(list.get_byte_range(2), None);
+
+    let root = doc.root();
+    let root = root.as_compound().unwrap();
+    let level = root.get_byte_range("level").unwrap();
+    assert_eq!(&bytes[level], &3i32.to_be_bytes());
+    assert_eq!(root.get("level").unwrap().byte_range(), None);
+    let inner = root.get("inner").unwrap();
+    let x = inner.as_compound().unwrap().get_byte_range("x").unwrap();
+    assert_eq!(&bytes[x], &1i32.to_be_bytes());
+}
+
+#[test]
+fn test_numeric_list_elements() {
+    let mut root: OwnedCompound<BE> = OwnedCompound::default();
+    let mut longs: OwnedList<BE> = OwnedList::default();
+    longs.push(5i64);
+    longs.push(6i64);
+    root.insert("longs", longs);
+    let bytes = OwnedValue::Compound(root).write_to_vec::<BE>().unwrap();
+
+    let doc = read_borrowed::<BE>(&bytes).unwrap();
+    let list = doc.root().get("longs").unwrap();
+    let list = list.as_list().unwrap();
+    for (index, expected) in [5i64, 6].into_iter().enumerate() {
+        let range = list.get_byte_range(index).unwrap();
+        assert_eq!(&bytes[range], &expected.to_be_bytes());
+    }
+    assert_eq!(list.get_byte_range(2), None);
+
+    let end = read_borrowed::<BE>(&[0]).unwrap();
+    assert_eq!(end.root().byte_range(), None);
+}
+
+#[test]
+fn test_shared_payload_slices_source() {
+    let bytes = Bytes::from(tree());
+    let root = read_shared::<BE>(bytes.clone()).unwrap();
+    let name = root.get("name").unwrap();
+    let payload = bytes.slice_ref(name.raw_payload().unwrap());
+    assert_eq!(&payload[..], b"\x00\x05Steve");
+    assert_eq!(bytes.slice(name.byte_range().unwrap()), payload);
+
+    let compound = root.as_compound().unwrap();
+    let level = compound.get_byte_range("level").unwrap();
+    assert_eq!(&bytes[level], &3i32.to_be_bytes());
+}
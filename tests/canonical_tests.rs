@@ -0,0 +1,99 @@
+//! Tests for `canonical_bytes`.
+
+use std::hash::{BuildHasher, RandomState};
+
+use na_nbt::{
+    ByteOrder, OwnedCompound, OwnedList, OwnedValue, ScopedReadableValue, read_borrowed, read_owned,
+};
+use zerocopy::byteorder::{BigEndian as BE, LittleEndian as LE};
+
+fn entries() -> Vec<(&'static str, OwnedValue<BE>)> {
+    vec![
+        ("zeta", OwnedValue::from(1i32)),
+        ("alpha", OwnedValue::from("text")),
+        ("Beta", OwnedValue::from(2.5f64)),
+        ("mid", OwnedValue::from(vec![1i8, 2])),
+    ]
+}
+
+fn build<O: ByteOrder>(order: &[usize]) -> OwnedValue<O> {
+    let entries = entries();
+    let mut inner: OwnedCompound<O> = OwnedCompound::default();
+    let mut root: OwnedCompound<O> = OwnedCompound::default();
+    for &i in order {
+        let (key, value) = &entries[i];
+        let bytes = value.write_to_vec::<O>().unwrap();
+        let value = read_owned::<O, O>(&bytes).unwrap();
+        inner.insert(key, value);
+        let value = read_owned::<O, O>(&bytes).unwrap();
+        root.insert(key, value);
+    }
+    let mut list: OwnedList<O> = OwnedList::default();
+    list.push(inner);
+    root.insert("list", list);
+    OwnedValue::Compound(root)
+}
+
+#[test]
+fn test_insertion_order_does_not_matter() {
+    let a = build::<BE>(&[0, 1, 2, 3]).canonical_bytes::<BE>();
+    let b = build::<BE>(&[3, 1, 0, 2]).canonical_bytes::<BE>();
+    let c = build::<LE>(&[2, 3, 1, 0]).canonical_bytes::<BE>();
+    assert_eq!(a, b);
+    assert_eq!(a, c);
+
+    let state = RandomState::new();
+    assert_eq!(state.hash_one(&a), state.hash_one(&c));
+}
+
+#[test]
+fn test_keys_are_sorted() {
+    let bytes = build::<BE>(&[0, 1, 2, 3]).canonical_bytes::<BE>();
+    let doc = read_borrowed::<BE>(&bytes).unwrap();
+    let root = doc.root();
+    let keys: Vec<String> = root
+        .as_compound()
+        .unwrap()
+        .iter()
+        .map(|(key, _)| key.decode().into_owned())
+        .collect();
+    assert_eq!(keys, ["Beta", "alpha", "list", "mid", "zeta"]);
+}
+
+#[test]
+fn test_borrowed_matches_owned() {
+    let value = build::<BE>(&[1, 0, 3, 2]);
+    let bytes = value.write_to_vec::<LE>().unwrap();
+    let doc = read_borrowed::<LE>(&bytes).unwrap();
+    assert_eq!(
+        doc.root().canonical_bytes::<LE>(),
+        value.canonical_bytes::<LE>()
+    );
+}
+
+#[test]
+fn test_sorted_input_is_unchanged() {
+    let mut compound: OwnedCompound<BE> = OwnedCompound::default();
+    compound.insert("a", 1i8);
+    compound.insert("b", 2i8);
+    let value = OwnedValue::Compound(compound);
+    assert_eq!(
+        value.canonical_bytes::<BE>(),
+        value.write_to_vec::<BE>().unwrap()
+    );
+}
+
+#[test]
+fn test_repeated_key_keeps_first() {
+    // two "k" entries, built by hand since `insert` replaces
+    let bytes = [
+        10, 0, 0, //
+        1, 0, 1, b'k', 2, //
+        1, 0, 1, b'a', 0, //
+        1, 0, 1, b'k', 3, //
+        0,
+    ];
+    let doc = read_borrowed::<BE>(&bytes).unwrap();
+    let canonical = doc.root().canonical_bytes::<BE>();
+    assert_eq!(canonical, [10, 0, 0, 1, 0, 1, b'a', 0, 1, 0, 1, b'k', 2, 0]);
+}
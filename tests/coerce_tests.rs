@@ -0,0 +1,78 @@
+use na_nbt::{OwnedValue, Tag};
+use zerocopy::byteorder::{BigEndian as BE, LittleEndian as LE};
+
+#[test]
+fn coerce_widening() {
+    let mut value = OwnedValue::<BE>::Byte(-5);
+    assert!(value.coerce_to(Tag::Short));
+    assert_eq!(value.as_short(), Some(-5));
+    assert!(value.coerce_to(Tag::Int));
+    assert_eq!(value.as_int(), Some(-5));
+    assert!(value.coerce_to(Tag::Long));
+    assert_eq!(value.as_long(), Some(-5));
+    assert!(value.coerce_to(Tag::Double));
+    assert_eq!(value.as_double(), Some(-5.0));
+
+    let mut value = OwnedValue::<LE>::from(1.5f32);
+    assert!(value.coerce_to(Tag::Double));
+    assert_eq!(value.as_double(), Some(1.5));
+}
+
+#[test]
+fn coerce_narrowing_in_range() {
+    let mut value = OwnedValue::<BE>::from(5i32);
+    assert!(value.coerce_to(Tag::Byte));
+    assert_eq!(value.as_byte(), Some(5));
+
+    let mut value = OwnedValue::<LE>::from(-1234i64);
+    assert!(value.coerce_to(Tag::Short));
+    assert_eq!(value.as_short(), Some(-1234));
+
+    let mut value = OwnedValue::<BE>::from(-7.9f64);
+    assert!(value.coerce_to(Tag::Int));
+    assert_eq!(value.as_int(), Some(-7));
+
+    let mut value = OwnedValue::<BE>::from(0.25f64);
+    assert!(value.coerce_to(Tag::Float));
+    assert_eq!(value.as_float(), Some(0.25));
+}
+
+#[test]
+fn coerce_narrowing_saturates() {
+    let mut value = OwnedValue::<BE>::from(300i32);
+    assert!(value.coerce_to(Tag::Byte));
+    assert_eq!(value.as_byte(), Some(i8::MAX));
+
+    let mut value = OwnedValue::<LE>::from(i64::MIN);
+    assert!(value.coerce_to(Tag::Int));
+    assert_eq!(value.as_int(), Some(i32::MIN));
+
+    let mut value = OwnedValue::<BE>::from(1e300f64);
+    assert!(value.coerce_to(Tag::Short));
+    assert_eq!(value.as_short(), Some(i16::MAX));
+
+    let mut value = OwnedValue::<BE>::from(-1e300f64);
+    assert!(value.coerce_to(Tag::Float));
+    assert_eq!(value.as_float(), Some(f32::MIN));
+
+    let mut value = OwnedValue::<BE>::from(f64::NAN);
+    assert!(value.coerce_to(Tag::Long));
+    assert_eq!(value.as_long(), Some(0));
+}
+
+#[test]
+fn coerce_non_numeric() {
+    let mut value = OwnedValue::<BE>::from(5i32);
+    assert!(!value.coerce_to(Tag::String));
+    assert!(!value.coerce_to(Tag::End));
+    assert!(!value.coerce_to(Tag::IntArray));
+    assert_eq!(value.as_int(), Some(5));
+
+    let mut value = OwnedValue::<BE>::from("5");
+    assert!(!value.coerce_to(Tag::Int));
+    assert!(value.is_string());
+
+    let mut value = OwnedValue::<BE>::from(3i16);
+    assert!(value.coerce_to(Tag::Short));
+    assert_eq!(value.as_short(), Some(3));
+}
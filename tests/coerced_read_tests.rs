@@ -0,0 +1,67 @@
+//! Tests for the lenient `as_i64_coerced` and `as_f64_coerced` reads.
+
+use na_nbt::{OwnedCompound, OwnedValue, ScopedReadableValue, read_borrowed};
+use zerocopy::byteorder::BigEndian as BE;
+
+fn document() -> Vec<u8> {
+    let mut root = OwnedCompound::<BE>::default();
+    root.insert("int", 5i32);
+    root.insert("string", "5");
+    root.insert("padded", " -12 ");
+    root.insert("word", "abc");
+    root.insert("decimal", "2.5");
+    root.insert("byte", -1i8);
+    root.insert("long", i64::MAX);
+    root.insert("whole", 7.0f32);
+    root.insert("fraction", 7.5f64);
+    root.insert("huge", 1e300f64);
+    root.insert("list", na_nbt::OwnedList::<BE>::default());
+    OwnedValue::Compound(root).write_to_vec::<BE>().unwrap()
+}
+
+#[test]
+fn i64_from_numbers_and_strings() {
+    let data = document();
+    let doc = read_borrowed::<BE>(&data).unwrap();
+    let root = doc.root();
+    let get = |key: &str| root.get(key).unwrap().as_i64_coerced();
+
+    assert_eq!(get("int"), Some(5));
+    assert_eq!(get("string"), Some(5));
+    assert_eq!(get("padded"), Some(-12));
+    assert_eq!(get("word"), None);
+    assert_eq!(get("decimal"), None);
+    assert_eq!(get("byte"), Some(-1));
+    assert_eq!(get("long"), Some(i64::MAX));
+    assert_eq!(get("whole"), Some(7));
+    assert_eq!(get("fraction"), None);
+    assert_eq!(get("huge"), None);
+    assert_eq!(get("list"), None);
+
+    // the strict read is unaffected
+    assert_eq!(root.get("string").unwrap().as_int(), None);
+}
+
+#[test]
+fn f64_from_numbers_and_strings() {
+    let data = document();
+    let doc = read_borrowed::<BE>(&data).unwrap();
+    let root = doc.root();
+    let get = |key: &str| root.get(key).unwrap().as_f64_coerced();
+
+    assert_eq!(get("int"), Some(5.0));
+    assert_eq!(get("string"), Some(5.0));
+    assert_eq!(get("decimal"), Some(2.5));
+    assert_eq!(get("word"), None);
+    assert_eq!(get("whole"), Some(7.0));
+    assert_eq!(get("fraction"), Some(7.5));
+    assert_eq!(get("list"), None);
+}
+
+#[test]
+fn owned_values() {
+    assert_eq!(OwnedValue::<BE>::from(5i32).as_i64_coerced(), Some(5));
+    assert_eq!(OwnedValue::<BE>::from("5").as_i64_coerced(), Some(5));
+    assert_eq!(OwnedValue::<BE>::from("abc").as_i64_coerced(), None);
+    assert_eq!(OwnedValue::<BE>::from("1e3").as_f64_coerced(), Some(1000.0));
+}
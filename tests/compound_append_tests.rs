@@ -0,0 +1,73 @@
+//! Tests for `OwnedCompound::append`.
+
+use na_nbt::{OwnedCompound, OwnedList, OwnedValue};
+use zerocopy::byteorder::BigEndian as BE;
+
+fn keys(compound: &OwnedCompound<BE>) -> Vec<String> {
+    compound
+        .iter()
+        .map(|(key, _)| key.decode().into_owned())
+        .collect()
+}
+
+#[test]
+fn test_append_moves_entries_and_empties_other() {
+    let mut a: OwnedCompound<BE> = OwnedCompound::default();
+    a.insert("a", 1i8);
+    let mut list: OwnedList<BE> = OwnedList::default();
+    list.push("item");
+    let mut b: OwnedCompound<BE> = OwnedCompound::default();
+    b.insert("list", list);
+    b.insert("name", "Steve");
+
+    a.append(&mut b);
+    assert_eq!(keys(&a), ["a", "list", "name"]);
+    assert_eq!(
+        a.get("name").unwrap().as_string().unwrap().decode(),
+        "Steve"
+    );
+    assert_eq!(a.get("list").unwrap().as_list().unwrap().len(), 1);
+
+    assert_eq!(b.iter().count(), 0);
+    b.insert("again", 2i8);
+    assert_eq!(keys(&b), ["again"]);
+}
+
+#[test]
+fn test_append_conflicts_overwrite_without_recursion() {
+    let mut inner_a: OwnedCompound<BE> = OwnedCompound::default();
+    inner_a.insert("kept", 1i32);
+    let mut a: OwnedCompound<BE> = OwnedCompound::default();
+    a.insert("x", 1i32);
+    a.insert("inner", inner_a);
+    a.insert("y", 2i32);
+
+    let mut inner_b: OwnedCompound<BE> = OwnedCompound::default();
+    inner_b.insert("new", 2i32);
+    let mut b: OwnedCompound<BE> = OwnedCompound::default();
+    b.insert("x", "replaced");
+    b.insert("inner", inner_b);
+
+    a.append(&mut b);
+    assert_eq!(keys(&a), ["y", "x", "inner"]);
+    assert!(a.get("x").unwrap().as_string().is_some());
+    let inner = a.get("inner").unwrap();
+    assert!(inner.get("kept").is_none());
+    assert_eq!(inner.get("new").unwrap().as_int(), Some(2));
+    assert_eq!(b.iter().count(), 0);
+
+    let value = OwnedValue::Compound(a);
+    assert_eq!(value.write_to_vec::<BE>().unwrap().last(), Some(&0));
+}
+
+#[test]
+fn test_append_empty() {
+    let mut a: OwnedCompound<BE> = OwnedCompound::default();
+    let mut b: OwnedCompound<BE> = OwnedCompound::default();
+    a.append(&mut b);
+    assert_eq!(a.iter().count(), 0);
+    b.insert("k", 1i8);
+    a.append(&mut b);
+    assert_eq!(keys(&a), ["k"]);
+    assert_eq!(b.iter().count(), 0);
+}
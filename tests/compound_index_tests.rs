@@ -0,0 +1,67 @@
+//! Tests for `ReadonlyCompound::index`.
+
+use bytes::Bytes;
+use na_nbt::{OwnedCompound, OwnedValue, read_borrowed, read_shared};
+use zerocopy::byteorder::BigEndian as BE;
+
+fn document() -> Vec<u8> {
+    let mut compound: OwnedCompound<BE> = OwnedCompound::default();
+    for i in 0..200 {
+        compound.insert(&format!("key{i}"), i);
+    }
+    compound.insert("名前", "Steve");
+    compound.insert("nul\0key", 1i8);
+    OwnedValue::Compound(compound).write_to_vec::<BE>().unwrap()
+}
+
+#[test]
+fn test_index_matches_get() {
+    let bytes = document();
+    let doc = read_borrowed::<BE>(&bytes).unwrap();
+    let root = doc.root();
+    let compound = root.as_compound().unwrap();
+    let index = compound.index();
+    assert_eq!(index.len(), 202);
+    assert!(!index.is_empty());
+
+    for (key, value) in compound.iter() {
+        let key = key.decode();
+        assert_eq!(index.get(&key).unwrap().tag_id(), value.tag_id());
+        assert_eq!(
+            index.get(&key).unwrap().as_int(),
+            compound.get(&key).unwrap().as_int()
+        );
+    }
+    let name = index.get("名前").unwrap();
+    assert_eq!(name.as_string().unwrap().decode(), "Steve");
+    assert_eq!(index.get("nul\0key").unwrap().as_byte(), Some(1));
+    assert!(index.get("key200").is_none());
+}
+
+#[test]
+fn test_repeated_key_resolves_to_first() {
+    let bytes = [
+        0x0a, 0x00, 0x00, // compound
+        0x01, 0x00, 0x01, b'a', 0x01, // byte "a" = 1
+        0x01, 0x00, 0x01, b'a', 0x02, // byte "a" = 2
+        0x00,
+    ];
+    let doc = read_borrowed::<BE>(&bytes).unwrap();
+    let root = doc.root();
+    let index = root.as_compound().unwrap().index();
+    assert_eq!(index.len(), 1);
+    assert_eq!(index.get("a").unwrap().as_byte(), Some(1));
+}
+
+#[test]
+fn test_index_of_shared_and_empty_compounds() {
+    let root = read_shared::<BE>(Bytes::from(document())).unwrap();
+    let index = root.as_compound().unwrap().index();
+    drop(root);
+    assert_eq!(index.get("key7").unwrap().as_int(), Some(7));
+
+    let bytes = [0x0a, 0x00, 0x00, 0x00];
+    let doc = read_borrowed::<BE>(&bytes).unwrap();
+    let root = doc.root();
+    assert!(root.as_compound().unwrap().index().is_empty());
+}
@@ -0,0 +1,75 @@
+//! Tests for `iter_str` and `iter_mut_str`, which decode compound keys.
+
+use std::borrow::Cow;
+
+use bytes::Bytes;
+use na_nbt::{MutableValue, OwnedCompound, OwnedValue, read_borrowed, read_shared};
+use zerocopy::byteorder::BigEndian as BE;
+
+fn compound() -> OwnedCompound<BE> {
+    let mut compound: OwnedCompound<BE> = OwnedCompound::default();
+    compound.insert("health", 20i32);
+    compound.insert("name", "Steve");
+    compound.insert("nul\0key", 1i8);
+    compound.insert("日本", 2i8);
+    compound
+}
+
+const KEYS: [&str; 4] = ["health", "name", "nul\0key", "日本"];
+
+#[test]
+fn test_iter_str_keys() {
+    let compound = compound();
+    let keys: Vec<Cow<str>> = compound.iter_str().map(|(key, _)| key).collect();
+    assert_eq!(keys, KEYS);
+    // ASCII keys are borrowed; a NUL is encoded as two bytes in MUTF-8
+    assert!(matches!(keys[0], Cow::Borrowed(_)));
+    assert!(matches!(keys[2], Cow::Owned(_)));
+
+    let bytes = OwnedValue::Compound(compound).write_to_vec::<BE>().unwrap();
+    let doc = read_borrowed::<BE>(&bytes).unwrap();
+    let root = doc.root();
+    let root = root.as_compound().unwrap();
+    let keys: Vec<Cow<str>> = root.iter_str().map(|(key, _)| key).collect();
+    assert_eq!(keys, KEYS);
+    assert!(matches!(keys[1], Cow::Borrowed(_)));
+
+    let shared = read_shared::<BE>(Bytes::from(bytes)).unwrap();
+    let shared = shared.as_compound().unwrap();
+    let health = shared
+        .iter_str()
+        .find_map(|(key, value)| (key == "health").then_some(value))
+        .unwrap();
+    assert_eq!(health.as_int(), Some(20));
+}
+
+#[test]
+fn test_iter_mut_str_matches_keys() {
+    let mut compound = compound();
+    for (key, mut value) in compound.iter_mut_str() {
+        match &*key {
+            "health" => assert!(value.set_int(19)),
+            "日本" => assert!(value.set_byte(3)),
+            _ => {}
+        }
+    }
+    assert_eq!(compound.get("health").unwrap().as_int(), Some(19));
+    assert_eq!(compound.get("日本").unwrap().as_byte(), Some(3));
+
+    let mut root: OwnedCompound<BE> = OwnedCompound::default();
+    root.insert("player", compound);
+    let Some(MutableValue::Compound(mut player)) = root.get_mut("player") else {
+        panic!("expected a compound");
+    };
+    for (key, mut value) in player.iter_mut_str() {
+        if key == "health" {
+            assert!(value.set_int(18));
+        }
+    }
+    let keys: Vec<String> = player.iter_str().map(|(key, _)| key.into_owned()).collect();
+    assert_eq!(keys, KEYS);
+    assert_eq!(
+        root.get("player").unwrap().get("health").unwrap().as_int(),
+        Some(18)
+    );
+}
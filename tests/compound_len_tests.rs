@@ -0,0 +1,80 @@
+//! Tests for the entry count of read-only compounds.
+
+use na_nbt::{OwnedCompound, OwnedList, OwnedValue, read_borrowed, read_shared};
+use zerocopy::byteorder::{BigEndian as BE, LittleEndian as LE};
+
+fn inner() -> OwnedCompound<BE> {
+    let mut inner = OwnedCompound::default();
+    inner.insert("x", 1);
+    inner.insert("name", "Steve");
+    inner
+}
+
+fn nested() -> OwnedCompound<BE> {
+    let mut list = OwnedList::default();
+    list.push(inner());
+    list.push(OwnedCompound::<BE>::default());
+
+    let mut root = OwnedCompound::default();
+    root.insert("byte", 1i8);
+    root.insert("bytes", vec![1i8, 2, 3]);
+    root.insert("inner", inner());
+    root.insert("list", list);
+    root.insert("zeros", vec![0i8; 100]);
+    root.insert("after", "end");
+    root
+}
+
+#[test]
+fn test_len_matches_iter_count() {
+    let bytes = OwnedValue::Compound(nested()).write_to_vec::<BE>().unwrap();
+    let doc = read_borrowed::<BE>(&bytes).unwrap();
+    let root = doc.root();
+    let compound = root.as_compound().unwrap();
+    assert_eq!(compound.len(), 6);
+    assert_eq!(compound.len(), compound.iter().count());
+    assert!(!compound.is_empty());
+
+    let inner = compound.get("inner").unwrap();
+    assert_eq!(inner.as_compound().unwrap().len(), 2);
+
+    let list = compound.get("list").unwrap();
+    let list = list.as_list().unwrap();
+    assert_eq!(list.get(0).unwrap().as_compound().unwrap().len(), 2);
+    let empty = list.get(1).unwrap();
+    assert_eq!(empty.as_compound().unwrap().len(), 0);
+    assert!(empty.as_compound().unwrap().is_empty());
+}
+
+#[test]
+fn test_len_cached_across_clones() {
+    let bytes = OwnedValue::Compound(nested()).write_to_vec::<LE>().unwrap();
+    let doc = read_borrowed::<LE>(&bytes).unwrap();
+    let root = doc.root();
+    let compound = root.as_compound().unwrap();
+    assert_eq!(compound.len(), 6);
+    let clone = compound.clone();
+    assert_eq!(clone.len(), 6);
+    assert_eq!(
+        clone.get("after").unwrap().as_string().unwrap().decode(),
+        "end"
+    );
+}
+
+#[test]
+fn test_len_counts_repeated_keys() {
+    // Compound { a: 1b, a: 2b }
+    let data = [
+        0x0a, 0x00, 0x00, 0x01, 0x00, 0x01, b'a', 0x01, 0x01, 0x00, 0x01, b'a', 0x02, 0x00,
+    ];
+    let doc = read_borrowed::<BE>(&data).unwrap();
+    let root = doc.root();
+    assert_eq!(root.as_compound().unwrap().len(), 2);
+}
+
+#[test]
+fn test_len_shared() {
+    let bytes = OwnedValue::Compound(nested()).write_to_vec::<BE>().unwrap();
+    let value = read_shared::<BE>(bytes.into()).unwrap();
+    assert_eq!(value.as_compound().unwrap().len(), 6);
+}
@@ -0,0 +1,75 @@
+//! Tests that compounds keep their key order through reading and writing.
+
+use na_nbt::{OwnedCompound, OwnedValue, read_borrowed, read_owned};
+use zerocopy::byteorder::{BigEndian as BE, LittleEndian as LE};
+
+fn keys(compound: &OwnedCompound<BE>) -> Vec<String> {
+    compound
+        .iter()
+        .map(|(key, _)| key.decode().into_owned())
+        .collect()
+}
+
+// Compound { "zz": Byte(1), "aa": Compound { "y": Short(2), "b": String("s") },
+// "mm": Int(3) }, with keys deliberately out of sorted order
+fn unsorted() -> Vec<u8> {
+    [
+        &[0x0a, 0x00, 0x00][..],
+        &[0x01, 0x00, 0x02, b'z', b'z', 0x01],
+        &[0x0a, 0x00, 0x02, b'a', b'a'],
+        &[0x02, 0x00, 0x01, b'y', 0x00, 0x02],
+        &[0x08, 0x00, 0x01, b'b', 0x00, 0x01, b's'],
+        &[0x00],
+        &[0x03, 0x00, 0x02, b'm', b'm', 0x00, 0x00, 0x00, 0x03],
+        &[0x00],
+    ]
+    .concat()
+}
+
+#[test]
+fn owned_read_write_is_byte_identical() {
+    let data = unsorted();
+    let value = read_owned::<BE, BE>(&data).unwrap();
+    assert_eq!(value.write_to_vec::<BE>().unwrap(), data);
+
+    let OwnedValue::Compound(root) = value else {
+        panic!("expected a compound");
+    };
+    assert_eq!(keys(&root), ["zz", "aa", "mm"]);
+}
+
+#[test]
+fn order_survives_a_byte_order_change() {
+    let data = unsorted();
+    let value = read_owned::<BE, LE>(&data).unwrap();
+    let le = value.write_to_vec::<LE>().unwrap();
+    let back = read_owned::<LE, BE>(&le).unwrap();
+    assert_eq!(back.write_to_vec::<BE>().unwrap(), data);
+
+    let doc = read_borrowed::<LE>(&le).unwrap();
+    assert_eq!(doc.root().write_to_vec::<BE>().unwrap(), data);
+}
+
+#[test]
+fn insertion_order_is_kept() {
+    let mut compound: OwnedCompound<BE> = OwnedCompound::default();
+    for key in ["c", "a", "b"] {
+        compound.insert(key, 1i32);
+    }
+    assert_eq!(keys(&compound), ["c", "a", "b"]);
+
+    compound.remove("a");
+    assert_eq!(keys(&compound), ["c", "b"]);
+
+    compound.replace("c", "now a string");
+    assert_eq!(keys(&compound), ["c", "b"]);
+
+    compound.insert("c", 2i32);
+    assert_eq!(keys(&compound), ["b", "c"]);
+
+    let bytes = OwnedValue::Compound(compound).write_to_vec::<BE>().unwrap();
+    let Ok(OwnedValue::Compound(compound)) = read_owned::<BE, BE>(&bytes) else {
+        panic!("expected a compound");
+    };
+    assert_eq!(keys(&compound), ["b", "c"]);
+}
@@ -0,0 +1,56 @@
+//! Tests for `read_owned_compound_root`.
+
+use na_nbt::{Error, read_owned, read_owned_compound_root};
+use zerocopy::byteorder::{BigEndian as BE, LittleEndian as LE};
+
+#[test]
+fn compound_root_is_returned_directly() {
+    let data = [
+        0x0a, 0x00, 0x04, b'D', b'a', b't', b'a', //
+        0x03, 0x00, 0x01, b'n', 0x00, 0x00, 0x00, 0x2a, //
+        0x00,
+    ];
+    let root = read_owned_compound_root::<BE, LE>(&data).unwrap();
+    assert_eq!(root.get("n").and_then(|v| v.as_int()), Some(42));
+}
+
+#[test]
+fn other_roots_are_rejected() {
+    // a bare Int root, which read_owned accepts
+    let int_root = [0x03, 0x00, 0x00, 0x00, 0x00, 0x00, 0x07];
+    assert!(read_owned::<BE, BE>(&int_root).is_ok());
+    assert!(matches!(
+        read_owned_compound_root::<BE, BE>(&int_root),
+        Err(Error::TagMismatch(10, 3))
+    ));
+
+    let list_root = [0x09, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00];
+    assert!(matches!(
+        read_owned_compound_root::<BE, BE>(&list_root),
+        Err(Error::TagMismatch(10, 9))
+    ));
+
+    assert!(matches!(
+        read_owned_compound_root::<BE, BE>(&[0x00]),
+        Err(Error::TagMismatch(10, 0))
+    ));
+    assert!(matches!(
+        read_owned_compound_root::<BE, BE>(&[]),
+        Err(Error::EndOfFile)
+    ));
+}
+
+#[test]
+fn read_errors_pass_through() {
+    let truncated = [0x0a, 0x00, 0x00, 0x01, 0x00, 0x01, b'x'];
+    assert!(matches!(
+        read_owned_compound_root::<BE, BE>(&truncated),
+        Err(Error::EndOfFile)
+    ));
+
+    let trailing = [0x0a, 0x00, 0x00, 0x00, 0xff];
+    assert!(matches!(
+        read_owned_compound_root::<BE, BE>(&trailing),
+        Err(Error::TrailingData(1))
+    ));
+}
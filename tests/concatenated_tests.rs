@@ -0,0 +1,85 @@
+//! Tests for `read_all_owned` and `iter_owned`.
+
+use na_nbt::{Error, OwnedCompound, OwnedValue, iter_owned, read_all_owned, read_owned};
+use zerocopy::byteorder::{BigEndian as BE, LittleEndian as LE};
+
+fn documents() -> Vec<OwnedValue<BE>> {
+    let mut first: OwnedCompound<BE> = OwnedCompound::default();
+    first.insert("id", "minecraft:chest");
+    first.insert("x", 12i32);
+    let mut second: OwnedCompound<BE> = OwnedCompound::default();
+    second.insert("data", vec![1i8, 2, 3]);
+    vec![
+        OwnedValue::Compound(first),
+        OwnedValue::from(5i64),
+        OwnedValue::Compound(second),
+    ]
+}
+
+fn concat(values: &[OwnedValue<BE>]) -> Vec<u8> {
+    values
+        .iter()
+        .flat_map(|value| value.write_to_vec::<BE>().unwrap())
+        .collect()
+}
+
+#[test]
+fn test_read_all() {
+    let values = documents();
+    let bytes = concat(&values);
+    let read = read_all_owned::<BE, BE>(&bytes).unwrap();
+    assert_eq!(read.len(), values.len());
+    for (a, b) in values.iter().zip(&read) {
+        assert!(na_nbt::values_equal(a, b));
+    }
+
+    let read = read_all_owned::<BE, LE>(&bytes).unwrap();
+    assert_eq!(read[1].as_long(), Some(5));
+
+    // a single document still reads with the single-document reader
+    let single = values[0].write_to_vec::<BE>().unwrap();
+    assert_eq!(read_all_owned::<BE, BE>(&single).unwrap().len(), 1);
+    assert!(matches!(
+        read_owned::<BE, BE>(&bytes),
+        Err(Error::TrailingData(_))
+    ));
+}
+
+#[test]
+fn test_empty_input_and_end_roots() {
+    assert!(read_all_owned::<BE, BE>(&[]).unwrap().is_empty());
+    assert_eq!(iter_owned::<BE, BE>(&[]).count(), 0);
+
+    let read = read_all_owned::<BE, BE>(&[0, 0]).unwrap();
+    assert!(matches!(read[..], [OwnedValue::End, OwnedValue::End]));
+}
+
+#[test]
+fn test_truncated_document() {
+    let bytes = concat(&documents());
+    let cut = &bytes[..bytes.len() - 1];
+    assert!(
+        read_all_owned::<BE, BE>(cut)
+            .err()
+            .unwrap()
+            .is_end_of_input()
+    );
+
+    let mut iter = iter_owned::<BE, BE>(cut);
+    assert!(iter.next().unwrap().is_ok());
+    assert!(iter.next().unwrap().is_ok());
+    assert!(iter.next().unwrap().is_err());
+    assert!(iter.next().is_none());
+}
+
+#[test]
+fn test_error_offset_is_relative_to_input() {
+    let mut bytes = concat(&documents()[..1]);
+    let start = bytes.len();
+    // a compound holding an entry of invalid type 0x0d
+    bytes.extend_from_slice(&[0x0a, 0x00, 0x00, 0x0d, 0x00, 0x00]);
+
+    let err = iter_owned::<BE, BE>(&bytes).nth(1).unwrap().err().unwrap();
+    assert!(matches!(err.kind(), Error::InvalidTagType(0x0d)));
+    assert_eq!(err.offset(), Some(start + 3));
+}
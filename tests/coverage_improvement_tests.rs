@@ -2907,6 +2907,10 @@ fn test_mutable_value_scoped_writable_set_methods() {
 }
 
 #[test]
+#[cfg_attr(
+    all(feature = "strict", debug_assertions),
+    should_panic(expected = "type mismatch")
+)]
 fn test_mutable_value_scoped_writable_set_wrong_type() {
     let data = create_all_types_be();
     let mut doc = read_owned::<BE, BE>(&data).unwrap();
@@ -3174,6 +3178,10 @@ fn test_owned_value_scoped_readable_trait_get_scoped() {
 
 /// Test ScopedWritableValue trait on OwnedValue directly
 #[test]
+#[cfg_attr(
+    all(feature = "strict", debug_assertions),
+    should_panic(expected = "type mismatch")
+)]
 fn test_owned_value_scoped_writable_trait_set_methods() {
     // Create individual OwnedValue instances to test trait on them directly
     let mut byte_val = OwnedValue::<BE>::Byte(0x7F);
@@ -3211,6 +3219,10 @@ fn test_owned_value_scoped_writable_trait_set_methods() {
 }
 
 #[test]
+#[cfg_attr(
+    all(feature = "strict", debug_assertions),
+    should_panic(expected = "type mismatch")
+)]
 fn test_owned_value_scoped_writable_trait_update_methods() {
     // Create individual OwnedValue instances to test trait on them directly
     let mut byte_val = OwnedValue::<BE>::Byte(0x7F);
@@ -0,0 +1,53 @@
+//! Tests for the default owned value and the empty constructors.
+
+use na_nbt::{OwnedValue, Tag, read_owned};
+use zerocopy::byteorder::BigEndian as BE;
+
+#[test]
+fn test_default_is_empty_compound() {
+    let value = OwnedValue::<BE>::default();
+    assert!(value.is_compound());
+    assert_eq!(value.tag_id(), Tag::Compound);
+    let bytes = value.write_to_vec::<BE>().unwrap();
+    assert_eq!(bytes, [0x0a, 0x00, 0x00, 0x00]);
+}
+
+#[test]
+fn test_default_in_generic_code() {
+    fn make<T: Default>() -> T {
+        T::default()
+    }
+    let mut value: OwnedValue<BE> = make();
+    value.as_compound_mut().unwrap().insert("x", 1);
+    let bytes = value.write_to_vec::<BE>().unwrap();
+    let read = read_owned::<BE, BE>(&bytes).unwrap();
+    assert_eq!(read.get("x").unwrap().as_int(), Some(1));
+}
+
+#[test]
+fn test_empty_constructors() {
+    let compound = OwnedValue::<BE>::empty_compound();
+    assert_eq!(compound.tag_id(), Tag::Compound);
+
+    let list = OwnedValue::<BE>::empty_list();
+    assert_eq!(list.tag_id(), Tag::List);
+    let list = list.as_list().unwrap();
+    assert!(list.is_empty());
+    assert_eq!(list.tag_id(), Tag::End);
+
+    let string = OwnedValue::<BE>::empty_string();
+    assert_eq!(string.tag_id(), Tag::String);
+    assert_eq!(string.as_string().unwrap().decode(), "");
+}
+
+#[test]
+fn test_empty_list_takes_first_element_type() {
+    let mut value = OwnedValue::<BE>::empty_list();
+    let OwnedValue::List(list) = &mut value else {
+        panic!("expected a list");
+    };
+    list.push(1i16);
+    list.push(2i16);
+    assert_eq!(list.tag_id(), Tag::Short);
+    assert_eq!(list.len(), 2);
+}
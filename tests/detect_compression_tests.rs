@@ -0,0 +1,85 @@
+//! Tests for `detect_compression` and `decompress_to_vec`.
+#![cfg(feature = "compression")]
+
+use na_nbt::{
+    Compression, Error, OwnedCompound, OwnedValue, decompress_to_vec, detect_compression,
+};
+use zerocopy::byteorder::BigEndian as BE;
+
+fn value() -> OwnedValue<BE> {
+    let mut root: OwnedCompound<BE> = OwnedCompound::default();
+    root.insert("LevelName", "world");
+    root.insert("Time", 1200i64);
+    OwnedValue::Compound(root)
+}
+
+fn raw() -> Vec<u8> {
+    value().write_to_vec::<BE>().unwrap()
+}
+
+fn compress(compression: Compression) -> Vec<u8> {
+    value().to_file_bytes::<BE>("", compression).unwrap()
+}
+
+#[test]
+fn test_detect() {
+    assert_eq!(
+        detect_compression(&compress(Compression::Gzip)),
+        Compression::Gzip
+    );
+    assert_eq!(
+        detect_compression(&compress(Compression::Zlib)),
+        Compression::Zlib
+    );
+    assert_eq!(detect_compression(&raw()), Compression::None);
+    assert_eq!(detect_compression(&[]), Compression::None);
+    assert_eq!(detect_compression(&[0x1f]), Compression::None);
+}
+
+#[test]
+fn test_detect_every_zlib_level() {
+    for flags in [0x01, 0x5e, 0x9c, 0xda] {
+        assert_eq!(detect_compression(&[0x78, flags]), Compression::Zlib);
+    }
+}
+
+#[test]
+fn test_ambiguous_0x78_is_raw() {
+    // 0x78 is not a tag type, but without valid zlib flags after it the data
+    // is not taken for zlib either
+    for second in [0x00, 0x0a, 0x78, 0xff] {
+        assert_eq!(detect_compression(&[0x78, second]), Compression::None);
+    }
+    assert_eq!(detect_compression(&[0x78]), Compression::None);
+    assert_eq!(decompress_to_vec(&[0x78, 0x00]).unwrap(), [0x78, 0x00]);
+}
+
+#[test]
+fn test_decompress_to_vec() {
+    let raw = raw();
+    for compression in [Compression::None, Compression::Gzip, Compression::Zlib] {
+        assert_eq!(decompress_to_vec(&compress(compression)).unwrap(), raw);
+    }
+}
+
+#[test]
+fn test_decompress_concatenated_gzip() {
+    let mut data = compress(Compression::Gzip);
+    data.extend_from_slice(&compress(Compression::Gzip));
+    let raw = raw();
+    assert_eq!(
+        decompress_to_vec(&data).unwrap(),
+        [raw.clone(), raw].concat()
+    );
+}
+
+#[test]
+fn test_decompress_corrupt() {
+    let mut data = compress(Compression::Zlib);
+    data.truncate(data.len() / 2);
+    assert!(matches!(decompress_to_vec(&data), Err(Error::IO(_))));
+
+    let mut data = compress(Compression::Gzip);
+    data[10] ^= 0xff;
+    assert!(matches!(decompress_to_vec(&data), Err(Error::IO(_))));
+}
@@ -0,0 +1,45 @@
+//! Tests for `OwnedCompound::get_disjoint_mut`.
+
+use na_nbt::{MutableValue, OwnedCompound};
+use zerocopy::byteorder::BigEndian as BE;
+
+fn compound() -> OwnedCompound<BE> {
+    let mut compound: OwnedCompound<BE> = OwnedCompound::default();
+    compound.insert("x", 1i32);
+    compound.insert("name", "Steve");
+    compound.insert("y", 2i32);
+    compound
+}
+
+fn swap_ints(a: MutableValue<'_, BE>, b: MutableValue<'_, BE>) {
+    let (MutableValue::Int(a), MutableValue::Int(b)) = (a, b) else {
+        panic!("expected two ints");
+    };
+    let (old_a, old_b) = (a.get(), b.get());
+    a.set(old_b);
+    b.set(old_a);
+}
+
+#[test]
+fn test_update_two_fields() {
+    let mut compound = compound();
+    let [x, y] = compound.get_disjoint_mut(["x", "y"]).unwrap();
+    swap_ints(x, y);
+    assert_eq!(compound.get("x").unwrap().as_int(), Some(2));
+    assert_eq!(compound.get("y").unwrap().as_int(), Some(1));
+
+    let [name, mut x] = compound.get_disjoint_mut(["name", "x"]).unwrap();
+    assert!(x.set_int(7));
+    assert!(!matches!(name, MutableValue::Int(_)));
+    assert_eq!(compound.get("x").unwrap().as_int(), Some(7));
+}
+
+#[test]
+fn test_rejects_duplicate_and_missing_keys() {
+    let mut compound = compound();
+    assert!(compound.get_disjoint_mut(["x", "x"]).is_none());
+    assert!(compound.get_disjoint_mut(["x", "y", "x"]).is_none());
+    assert!(compound.get_disjoint_mut(["x", "z"]).is_none());
+    assert!(compound.get_disjoint_mut([]).is_some());
+    assert_eq!(compound.get_disjoint_mut(["y"]).unwrap().len(), 1);
+}
@@ -0,0 +1,151 @@
+use bytes::Bytes;
+use na_nbt::{Error, read_borrowed, read_owned, read_owned_strict, read_shared};
+use zerocopy::byteorder::{BigEndian as BE, LittleEndian as LE};
+
+fn push_byte(data: &mut Vec<u8>, name: &[u8], value: i8) {
+    data.push(0x01);
+    data.extend_from_slice(&(name.len() as u16).to_be_bytes());
+    data.extend_from_slice(name);
+    data.push(value as u8);
+}
+
+fn create_duplicate_x_be() -> Vec<u8> {
+    // Root compound { x: 1b, y: 3b, x: 2b }
+    let mut data = vec![0x0A, 0x00, 0x00];
+    push_byte(&mut data, b"x", 1);
+    push_byte(&mut data, b"y", 3);
+    push_byte(&mut data, b"x", 2);
+    data.push(0x00);
+    data
+}
+
+#[test]
+fn read_owned_drops_earlier_duplicates() {
+    let data = create_duplicate_x_be();
+    let value = read_owned::<BE, BE>(&data).unwrap();
+
+    assert_eq!(value.get("x").and_then(|x| x.as_byte()), Some(2));
+    let compound = value.as_compound().unwrap();
+    let keys: Vec<String> = compound
+        .iter()
+        .map(|(key, _)| key.decode().into_owned())
+        .collect();
+    assert_eq!(keys, ["y", "x"]);
+
+    // Root compound { y: 3b, x: 2b }
+    let mut expected = vec![0x0A, 0x00, 0x00];
+    push_byte(&mut expected, b"y", 3);
+    push_byte(&mut expected, b"x", 2);
+    expected.push(0x00);
+    assert_eq!(value.write_to_vec::<BE>().unwrap(), expected);
+}
+
+#[test]
+fn read_owned_drops_earlier_duplicates_in_every_reader() {
+    // Root compound { list: [ { a: 1b, a: 2b, a: 3b } ] }
+    let mut data = vec![0x0A, 0x00, 0x00];
+    data.push(0x09);
+    data.extend_from_slice(&4u16.to_be_bytes());
+    data.extend_from_slice(b"list");
+    data.push(0x0A);
+    data.extend_from_slice(&1u32.to_be_bytes());
+    push_byte(&mut data, b"a", 1);
+    push_byte(&mut data, b"a", 2);
+    push_byte(&mut data, b"a", 3);
+    data.push(0x00);
+    data.push(0x00);
+
+    let check = |value: na_nbt::OwnedValue<_>| {
+        let inner = value.get("list").and_then(|list| list.get(0)).unwrap();
+        assert_eq!(inner.get("a").and_then(|a| a.as_byte()), Some(3));
+        assert_eq!(inner.as_compound().unwrap().iter().count(), 1);
+    };
+    check(read_owned::<BE, BE>(&data).unwrap());
+    check(na_nbt::read_owned_from_reader::<BE, BE>(data.as_slice()).unwrap());
+    let value = read_owned::<BE, LE>(&data).unwrap();
+    let inner = value.get("list").and_then(|list| list.get(0)).unwrap();
+    assert_eq!(inner.get("a").and_then(|a| a.as_byte()), Some(3));
+    assert_eq!(inner.as_compound().unwrap().iter().count(), 1);
+}
+
+#[test]
+fn read_owned_strict_rejects_duplicate() {
+    let data = create_duplicate_x_be();
+    match read_owned_strict::<BE, LE>(&data) {
+        Err(Error::DuplicateKey(key)) => assert_eq!(key, "x"),
+        other => panic!("expected DuplicateKey, got {:?}", other.err()),
+    }
+}
+
+#[test]
+fn read_owned_strict_rejects_nested_duplicate() {
+    // Root compound { list: [ {}, { a: 1b, a: 1b } ] }
+    let mut data = vec![0x0A, 0x00, 0x00];
+    data.push(0x09);
+    data.extend_from_slice(&4u16.to_be_bytes());
+    data.extend_from_slice(b"list");
+    data.push(0x0A);
+    data.extend_from_slice(&2u32.to_be_bytes());
+    data.push(0x00);
+    push_byte(&mut data, b"a", 1);
+    push_byte(&mut data, b"a", 1);
+    data.push(0x00);
+    data.push(0x00);
+
+    assert!(read_owned::<BE, BE>(&data).is_ok());
+    let err = read_owned_strict::<BE, BE>(&data).err().unwrap();
+    assert!(matches!(&err, Error::DuplicateKey(key) if key == "a"));
+    assert_eq!(err.to_string(), "duplicate compound key: \"a\"");
+}
+
+#[test]
+fn read_owned_strict_accepts_unique_keys() {
+    // Same key in sibling compounds is not a duplicate.
+    let mut data = vec![0x0A, 0x00, 0x00];
+    data.push(0x0A);
+    data.extend_from_slice(&1u16.to_be_bytes());
+    data.push(b'p');
+    push_byte(&mut data, b"x", 1);
+    data.push(0x00);
+    push_byte(&mut data, b"x", 2);
+    data.push(0x00);
+
+    let value = read_owned_strict::<BE, BE>(&data).unwrap();
+    assert_eq!(value.get("x").and_then(|x| x.as_byte()), Some(2));
+    assert_eq!(
+        value
+            .get("p")
+            .and_then(|p| p.get("x"))
+            .and_then(|x| x.as_byte()),
+        Some(1)
+    );
+}
+
+#[test]
+fn read_owned_strict_propagates_read_errors() {
+    let data = [0x0A, 0x00, 0x00, 0x01];
+    assert!(matches!(
+        read_owned_strict::<BE, BE>(&data),
+        Err(Error::EndOfFile)
+    ));
+}
+
+#[test]
+fn read_borrowed_get_returns_first_match() {
+    let data = create_duplicate_x_be();
+    let doc = read_borrowed::<BE>(&data).unwrap();
+    let root = doc.root();
+
+    assert_eq!(root.get("x").and_then(|x| x.as_byte()), Some(1));
+    assert_eq!(root.as_compound().unwrap().iter().count(), 3);
+    assert_eq!(root.write_to_vec::<BE>().unwrap(), data);
+}
+
+#[test]
+fn read_shared_get_returns_first_match() {
+    let data = create_duplicate_x_be();
+    let root = read_shared::<BE>(Bytes::from(data)).unwrap();
+
+    assert_eq!(root.get("x").and_then(|x| x.as_byte()), Some(1));
+    assert_eq!(root.as_compound().unwrap().iter().count(), 3);
+}
@@ -36,7 +36,7 @@ fn test_invalid_tag_type_borrowed() {
     let data = vec![0xFF, 0x00, 0x00];
     let res = read_borrowed::<BE>(&data);
     match res {
-        Err(Error::InvalidTagType(0xFF)) => {}
+        Err(e) if matches!(e.kind(), Error::InvalidTagType(0xFF)) => {}
         _ => panic!("unexpected result"),
     }
 }
@@ -46,7 +46,7 @@ fn test_invalid_tag_type_owned() {
     let data = vec![0xFF, 0x00, 0x00];
     let res = read_owned::<BE, BE>(&data);
     match res {
-        Err(Error::InvalidTagType(0xFF)) => {}
+        Err(e) if matches!(e.kind(), Error::InvalidTagType(0xFF)) => {}
         _ => panic!("unexpected result"),
     }
 }
@@ -57,7 +57,7 @@ fn test_invalid_element_type_in_list() {
     let data = vec![0x09, 0x00, 0x00, 0xFF, 0x00, 0x00, 0x00, 0x01];
     let res = read_borrowed::<BE>(&data);
     match res {
-        Err(e) => match e {
+        Err(e) => match e.kind() {
             Error::InvalidTagType(0xFF) => {}
             _ => panic!("unexpected result: {:?}", e),
         },
@@ -0,0 +1,257 @@
+//! Tests for `read_java`, `read_bedrock`, `read_bedrock_network` and the
+//! Bedrock `level.dat` reader and writer.
+
+use na_nbt::{
+    BigEndian, Error, LittleEndian, OwnedCompound, OwnedList, OwnedValue, read_bedrock,
+    read_bedrock_leveldat, read_bedrock_network, read_borrowed, read_java, read_owned,
+    values_equal, write_bedrock_leveldat,
+};
+use zerocopy::byteorder::{I32, I64};
+
+fn varint(mut value: u64, out: &mut Vec<u8>) {
+    loop {
+        let byte = (value & 0x7f) as u8;
+        value >>= 7;
+        if value == 0 {
+            out.push(byte);
+            return;
+        }
+        out.push(byte | 0x80);
+    }
+}
+
+fn zigzag(value: i64, out: &mut Vec<u8>) {
+    varint(((value << 1) ^ (value >> 63)) as u64, out);
+}
+
+fn name(name: &str, out: &mut Vec<u8>) {
+    varint(name.len() as u64, out);
+    out.extend_from_slice(name.as_bytes());
+}
+
+/// `{ i: -3, l: i64::MIN, s: "hi", b: [1, 2], ia: [-1, 300], la: [7], f: 1.5,
+/// list: [[1b], []], nested: { x: 2s } }` in the network format.
+fn network_sample() -> Vec<u8> {
+    let mut out = vec![10];
+    name("", &mut out);
+
+    out.push(3);
+    name("i", &mut out);
+    zigzag(-3, &mut out);
+
+    out.push(4);
+    name("l", &mut out);
+    zigzag(i64::MIN, &mut out);
+
+    out.push(8);
+    name("s", &mut out);
+    name("hi", &mut out);
+
+    out.push(7);
+    name("b", &mut out);
+    zigzag(2, &mut out);
+    out.extend_from_slice(&[1, 2]);
+
+    out.push(11);
+    name("ia", &mut out);
+    zigzag(2, &mut out);
+    zigzag(-1, &mut out);
+    zigzag(300, &mut out);
+
+    out.push(12);
+    name("la", &mut out);
+    zigzag(1, &mut out);
+    zigzag(7, &mut out);
+
+    out.push(5);
+    name("f", &mut out);
+    out.extend_from_slice(&1.5f32.to_le_bytes());
+
+    out.push(9);
+    name("list", &mut out);
+    out.push(9);
+    zigzag(2, &mut out);
+    out.push(1);
+    zigzag(1, &mut out);
+    out.push(1);
+    out.push(0);
+    zigzag(0, &mut out);
+
+    out.push(10);
+    name("nested", &mut out);
+    out.push(2);
+    name("x", &mut out);
+    out.extend_from_slice(&2i16.to_le_bytes());
+    out.push(0);
+
+    out.push(0);
+    out
+}
+
+fn expected() -> OwnedValue<LittleEndian> {
+    let mut inner: OwnedList<LittleEndian> = OwnedList::default();
+    inner.push(1i8);
+    let mut list: OwnedList<LittleEndian> = OwnedList::default();
+    list.push(inner);
+    list.push(OwnedList::<LittleEndian>::default());
+
+    let mut nested: OwnedCompound<LittleEndian> = OwnedCompound::default();
+    nested.insert("x", 2i16);
+
+    let mut root: OwnedCompound<LittleEndian> = OwnedCompound::default();
+    root.insert("i", -3i32);
+    root.insert("l", i64::MIN);
+    root.insert("s", "hi");
+    root.insert("b", vec![1i8, 2]);
+    root.insert("ia", vec![I32::<LittleEndian>::new(-1), I32::new(300)]);
+    root.insert("la", vec![I64::<LittleEndian>::new(7)]);
+    root.insert("f", 1.5f32);
+    root.insert("list", list);
+    root.insert("nested", nested);
+    OwnedValue::Compound(root)
+}
+
+#[test]
+fn test_read_java_and_bedrock() {
+    let value = expected();
+    let le = value.write_to_vec::<LittleEndian>().unwrap();
+    let be = value.write_to_vec::<BigEndian>().unwrap();
+
+    let java = read_java(&be).unwrap();
+    assert_eq!(java.write_to_vec::<BigEndian>().unwrap(), be);
+    let bedrock = read_bedrock(&le).unwrap();
+    assert_eq!(bedrock.write_to_vec::<LittleEndian>().unwrap(), le);
+
+    // each reader takes its own edition's byte order only
+    assert!(read_java(&le).is_err());
+    assert!(read_bedrock(&be).is_err());
+}
+
+#[test]
+fn test_read_bedrock_network() {
+    let value = read_bedrock_network(&network_sample()).unwrap();
+    assert_eq!(
+        value.write_to_vec::<LittleEndian>().unwrap(),
+        expected().write_to_vec::<LittleEndian>().unwrap()
+    );
+    assert_eq!(value.get("l").unwrap().as_long(), Some(i64::MIN));
+    assert_eq!(value.get("i").unwrap().as_int(), Some(-3));
+}
+
+#[test]
+fn test_network_matches_read_owned() {
+    let bytes = read_bedrock_network(&network_sample())
+        .unwrap()
+        .write_to_vec::<LittleEndian>()
+        .unwrap();
+    let value = read_owned::<LittleEndian, LittleEndian>(&bytes).unwrap();
+    assert_eq!(value.write_to_vec::<LittleEndian>().unwrap(), bytes);
+}
+
+#[test]
+fn test_network_errors() {
+    let sample = network_sample();
+    for len in 0..sample.len() {
+        assert!(read_bedrock_network(&sample[..len]).is_err(), "len {len}");
+    }
+
+    let mut trailing = sample.clone();
+    trailing.push(0);
+    assert!(matches!(
+        read_bedrock_network(&trailing),
+        Err(Error::TrailingData(1))
+    ));
+
+    // a varint that never ends
+    let bytes = [3, 0, 0xff, 0xff, 0xff, 0xff, 0xff, 0x01];
+    assert!(matches!(
+        read_bedrock_network(&bytes),
+        Err(Error::At { offset: 2, .. })
+    ));
+
+    // a negative byte array length
+    let mut bytes = vec![7, 0];
+    zigzag(-1, &mut bytes);
+    assert!(matches!(
+        read_bedrock_network(&bytes),
+        Err(Error::At { offset: 2, .. })
+    ));
+
+    // an unknown tag inside a compound
+    let bytes = [10, 0, 13, 0];
+    assert!(matches!(
+        read_bedrock_network(&bytes),
+        Err(Error::At { offset: 2, kind }) if matches!(*kind, Error::InvalidTagType(13))
+    ));
+}
+
+#[test]
+fn test_network_end_root() {
+    let value = read_bedrock_network(&[0]).unwrap();
+    assert!(matches!(value, OwnedValue::End));
+}
+
+fn bedrock_level() -> OwnedValue<LittleEndian> {
+    let mut root = OwnedCompound::<LittleEndian>::default();
+    root.insert("LevelName", "Bedrock level");
+    root.insert("StorageVersion", 10i32);
+    root.insert("RandomSeed", -42i64);
+    OwnedValue::Compound(root)
+}
+
+#[test]
+fn test_bedrock_leveldat_header() {
+    let file = write_bedrock_leveldat(10, &bedrock_level()).unwrap();
+    let document = bedrock_level().write_to_vec::<LittleEndian>().unwrap();
+    assert_eq!(file[..4], 10u32.to_le_bytes());
+    assert_eq!(file[4..8], (document.len() as u32).to_le_bytes());
+    assert_eq!(file[8..], document);
+
+    let (version, value) = read_bedrock_leveldat(&file).unwrap();
+    assert_eq!(version, 10);
+    assert!(values_equal(&value, &bedrock_level()));
+}
+
+#[test]
+fn test_bedrock_leveldat_length_recomputed() {
+    let (version, mut value) =
+        read_bedrock_leveldat(&write_bedrock_leveldat(9, &bedrock_level()).unwrap()).unwrap();
+    value
+        .as_compound_mut()
+        .unwrap()
+        .insert("LevelName", "a much longer level name than before");
+    let file = write_bedrock_leveldat(version, &value).unwrap();
+    let (_, read) = read_bedrock_leveldat(&file).unwrap();
+    assert_eq!(
+        read.get("LevelName").unwrap().as_string().unwrap().decode(),
+        "a much longer level name than before"
+    );
+
+    // a borrowed big-endian value is rewritten little-endian
+    let java = bedrock_level().write_to_vec::<BigEndian>().unwrap();
+    let doc = read_borrowed::<BigEndian>(&java).unwrap();
+    let file = write_bedrock_leveldat(version, &doc.root()).unwrap();
+    let (_, read) = read_bedrock_leveldat(&file).unwrap();
+    assert!(values_equal(&read, &bedrock_level()));
+}
+
+#[test]
+fn test_bedrock_leveldat_rejects_bad_length() {
+    let file = write_bedrock_leveldat(10, &bedrock_level()).unwrap();
+
+    assert!(matches!(
+        read_bedrock_leveldat(&file[..6]),
+        Err(Error::EndOfFile)
+    ));
+    assert!(matches!(
+        read_bedrock_leveldat(&file[..file.len() - 1]),
+        Err(Error::EndOfFile)
+    ));
+
+    let mut longer = file.clone();
+    longer.extend_from_slice(&[0, 0, 0]);
+    assert!(matches!(
+        read_bedrock_leveldat(&longer),
+        Err(Error::TrailingData(3))
+    ));
+}
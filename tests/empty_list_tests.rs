@@ -0,0 +1,177 @@
+//! Tests for lists with the `End` element type.
+
+use na_nbt::{
+    Error, OwnedList, OwnedValue, read_borrowed, read_owned, read_owned_from_reader, read_shared,
+};
+use zerocopy::byteorder::{BigEndian as BE, LittleEndian as LE};
+
+fn end_list_with_elements() -> Vec<u8> {
+    vec![0x09, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x02]
+}
+
+fn nested_empty_int_list() -> Vec<u8> {
+    // compound { "a": list of int, length 0 }
+    vec![
+        0x0A, 0x00, 0x00, // root compound
+        0x09, 0x00, 0x01, b'a', 0x03, 0x00, 0x00, 0x00, 0x00, // "a"
+        0x00, // end
+    ]
+}
+
+fn nested_empty_end_list() -> Vec<u8> {
+    vec![
+        0x0A, 0x00, 0x00, // root compound
+        0x09, 0x00, 0x01, b'a', 0x00, 0x00, 0x00, 0x00, 0x00, // "a"
+        0x00, // end
+    ]
+}
+
+#[test]
+fn test_reject_end_list_with_elements() {
+    let data = end_list_with_elements();
+
+    // every reader points at the element tag of the list header
+    let at_header = |err: Error| {
+        assert!(matches!(err.kind(), Error::InvalidTagType(0)));
+        assert_eq!(err.offset(), Some(3));
+    };
+    at_header(read_borrowed::<BE>(&data).err().unwrap());
+    at_header(read_owned::<BE, BE>(&data).err().unwrap());
+    at_header(read_owned::<BE, LE>(&data).err().unwrap());
+    at_header(
+        read_owned_from_reader::<BE, BE>(data.as_slice())
+            .err()
+            .unwrap(),
+    );
+}
+
+#[test]
+fn test_reject_nested_end_list_with_elements() {
+    let data = vec![
+        0x0A, 0x00, 0x00, // root compound
+        0x09, 0x00, 0x01, b'a', 0x00, 0x00, 0x00, 0x00, 0x01, // "a"
+        0x00, // end
+    ];
+
+    for err in [
+        read_borrowed::<BE>(&data).err().unwrap(),
+        read_owned::<BE, BE>(&data).err().unwrap(),
+        read_owned::<BE, LE>(&data).err().unwrap(),
+        read_owned_from_reader::<BE, LE>(data.as_slice())
+            .err()
+            .unwrap(),
+    ] {
+        assert!(matches!(err.kind(), Error::InvalidTagType(0)));
+        assert_eq!(err.offset(), Some(7));
+    }
+}
+
+#[test]
+fn test_empty_end_list_still_reads() {
+    let data = vec![0x09, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00];
+    assert!(read_borrowed::<BE>(&data).is_ok());
+    assert!(read_owned::<BE, LE>(&data).is_ok());
+    assert!(read_owned_from_reader::<BE, BE>(data.as_slice()).is_ok());
+}
+
+#[test]
+fn test_owned_empty_list_written_as_end() {
+    let data = nested_empty_int_list();
+    let expected = nested_empty_end_list();
+
+    let same: OwnedValue<BE> = read_owned::<BE, BE>(&data).unwrap();
+    assert_eq!(same.write_to_vec::<BE>().unwrap(), expected);
+    let mut out = Vec::new();
+    same.write_to_writer::<BE>(&mut out).unwrap();
+    assert_eq!(out, expected);
+
+    let swapped: OwnedValue<LE> = read_owned::<BE, LE>(&data).unwrap();
+    assert_eq!(swapped.write_to_vec::<BE>().unwrap(), expected);
+    let mut out = Vec::new();
+    swapped.write_to_writer::<BE>(&mut out).unwrap();
+    assert_eq!(out, expected);
+}
+
+#[test]
+fn test_owned_root_list_emptied_written_as_end() {
+    let mut list: OwnedList<BE> = OwnedList::default();
+    list.push(1i32);
+    list.pop();
+    let value = OwnedValue::List(list);
+
+    let expected = vec![0x09, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00];
+    assert_eq!(value.write_to_vec::<BE>().unwrap(), expected);
+    assert_eq!(value.write_to_vec::<LE>().unwrap(), expected);
+    let mut out = Vec::new();
+    value.write_to_writer::<LE>(&mut out).unwrap();
+    assert_eq!(out, expected);
+}
+
+#[test]
+fn test_borrowed_swapped_empty_list_written_as_end() {
+    let data = nested_empty_int_list();
+    let expected = vec![
+        0x0A, 0x00, 0x00, // root compound
+        0x09, 0x01, 0x00, b'a', 0x00, 0x00, 0x00, 0x00, 0x00, // "a", little endian
+        0x00, // end
+    ];
+
+    let doc = read_borrowed::<BE>(&data).unwrap();
+    let le = doc.root().write_to_vec::<LE>().unwrap();
+    assert_eq!(le, expected);
+    let mut out = Vec::new();
+    doc.root().write_to_writer::<LE>(&mut out).unwrap();
+    assert_eq!(out, expected);
+}
+
+#[test]
+fn test_borrowed_same_order_empty_list_written_as_end() {
+    let data = nested_empty_int_list();
+    let expected = nested_empty_end_list();
+
+    let doc = read_borrowed::<BE>(&data).unwrap();
+    assert_eq!(doc.root().write_to_vec::<BE>().unwrap(), expected);
+    let mut out = Vec::new();
+    doc.root().write_to_writer::<BE>(&mut out).unwrap();
+    assert_eq!(out, expected);
+
+    let root = read_shared::<BE>(data.into()).unwrap();
+    assert_eq!(root.write_to_vec::<BE>().unwrap(), expected);
+}
+
+#[test]
+fn test_borrowed_same_order_root_list_of_empty_lists() {
+    // list of two lists: an empty list of compounds and a list of one byte
+    let data = vec![
+        0x09, 0x00, 0x00, 0x09, 0x00, 0x00, 0x00, 0x02, // root list of lists
+        0x0A, 0x00, 0x00, 0x00, 0x00, // empty list of compounds
+        0x01, 0x00, 0x00, 0x00, 0x01, 0x07, // [7b]
+    ];
+    let mut expected = data.clone();
+    expected[8] = 0x00;
+
+    let doc = read_borrowed::<BE>(&data).unwrap();
+    assert_eq!(doc.root().write_to_vec::<BE>().unwrap(), expected);
+    let mut out = Vec::new();
+    doc.root().write_to_writer::<BE>(&mut out).unwrap();
+    assert_eq!(out, expected);
+}
+
+#[test]
+fn test_borrowed_document_same_order_empty_list_written_as_end() {
+    // the nested documents with the root named "r"
+    let mut data = nested_empty_int_list();
+    data.splice(1..3, [0x00, 0x01, b'r']);
+    let mut expected = nested_empty_end_list();
+    expected.splice(1..3, [0x00, 0x01, b'r']);
+
+    let doc = read_borrowed::<BE>(&data).unwrap();
+    assert_eq!(doc.write_to_vec::<BE>().unwrap(), expected);
+    assert_eq!(
+        read_borrowed::<BE>(&expected)
+            .unwrap()
+            .write_to_vec::<BE>()
+            .unwrap(),
+        expected
+    );
+}
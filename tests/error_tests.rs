@@ -53,3 +53,17 @@ fn test_result_type() {
     let err_result: na_nbt::Result<i32> = Err(Error::EndOfFile);
     assert!(err_result.is_err());
 }
+
+#[test]
+fn test_error_display_at() {
+    let error = Error::At {
+        offset: 17,
+        kind: Box::new(Error::InvalidTagType(0x0d)),
+    };
+    assert_eq!(
+        format!("{}", error),
+        "invalid NBT tag type: 0x0d at byte offset 17"
+    );
+    assert_eq!(error.offset(), Some(17));
+    assert!(matches!(error.kind(), Error::InvalidTagType(0x0d)));
+}
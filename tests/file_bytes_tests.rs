@@ -0,0 +1,130 @@
+//! Tests for `OwnedValue::to_file_bytes`, `write_leveldat_to_writer` and
+//! `read_owned_compressed`.
+#![cfg(feature = "compression")]
+
+use na_nbt::{
+    Compression, OwnedCompound, OwnedList, OwnedValue, peek_root_compressed, read_borrowed,
+    read_owned, read_owned_compressed, values_equal, write_leveldat_to_writer,
+};
+use std::io::{self, Write};
+
+use zerocopy::byteorder::{BigEndian as BE, LittleEndian as LE};
+
+fn level() -> OwnedValue<BE> {
+    let mut list: OwnedList<BE> = OwnedList::default();
+    list.push(1i32);
+    list.push(2i32);
+    let mut data: OwnedCompound<BE> = OwnedCompound::default();
+    data.insert("LevelName", "world");
+    data.insert("Time", 1200i64);
+    data.insert("List", list);
+    let mut root: OwnedCompound<BE> = OwnedCompound::default();
+    root.insert("Data", data);
+    OwnedValue::Compound(root)
+}
+
+#[test]
+fn test_round_trip_all_compressions() {
+    let value = level();
+    for compression in [Compression::None, Compression::Gzip, Compression::Zlib] {
+        let file = value.to_file_bytes::<BE>("level", compression).unwrap();
+        let (tag, name) = peek_root_compressed::<BE>(&file).unwrap();
+        assert_eq!(tag, na_nbt::Tag::Compound);
+        assert_eq!(name.as_deref(), Some("level"));
+
+        let read = read_owned_compressed::<BE, BE>(&file).unwrap();
+        assert!(values_equal(&value, &read));
+    }
+}
+
+#[test]
+fn test_compression_magic_and_byte_order() {
+    let value = level();
+    let gzip = value.to_file_bytes::<BE>("", Compression::Gzip).unwrap();
+    assert_eq!(&gzip[..2], [0x1f, 0x8b]);
+    let zlib = value.to_file_bytes::<BE>("", Compression::Zlib).unwrap();
+    assert_eq!(zlib[0], 0x78);
+
+    let raw = value
+        .to_file_bytes::<LE>("root", Compression::None)
+        .unwrap();
+    assert_eq!(&raw[..7], [10, 4, 0, b'r', b'o', b'o', b't']);
+    let read = read_owned::<LE, BE>(&raw).unwrap();
+    assert!(values_equal(&value, &read));
+
+    let unnamed = value.to_file_bytes::<BE>("", Compression::None).unwrap();
+    assert_eq!(unnamed, value.write_to_vec::<BE>().unwrap());
+}
+
+#[test]
+fn test_root_name_too_long() {
+    let name = "x".repeat(u16::MAX as usize + 1);
+    let Err(err) = level().to_file_bytes::<BE>(&name, Compression::None) else {
+        panic!("expected an error");
+    };
+    assert!(matches!(err, na_nbt::Error::Message(_)));
+}
+
+#[test]
+fn test_read_owned_compressed_rejects_corrupt_data() {
+    let mut file = level().to_file_bytes::<BE>("", Compression::Gzip).unwrap();
+    file.truncate(file.len() / 2);
+    assert!(read_owned_compressed::<BE, BE>(&file).is_err());
+}
+
+#[test]
+fn test_write_leveldat_matches_file_bytes() {
+    let value = level();
+    for compression in [Compression::None, Compression::Gzip, Compression::Zlib] {
+        let mut file = Vec::new();
+        write_leveldat_to_writer::<BE>(&mut file, "level", &value, compression).unwrap();
+        assert_eq!(
+            file,
+            value.to_file_bytes::<BE>("level", compression).unwrap()
+        );
+    }
+}
+
+#[test]
+fn test_write_leveldat_gzip_round_trip() {
+    let value = level();
+    let mut file = Vec::new();
+    write_leveldat_to_writer::<BE>(&mut file, "", &value, Compression::Gzip).unwrap();
+    assert_eq!(&file[..2], [0x1f, 0x8b]);
+    let read = read_owned_compressed::<BE, BE>(&file).unwrap();
+    assert!(values_equal(&value, &read));
+
+    // a borrowed value, written little-endian for Bedrock
+    let bytes = value.write_to_vec::<BE>().unwrap();
+    let doc = read_borrowed::<BE>(&bytes).unwrap();
+    let mut file = Vec::new();
+    write_leveldat_to_writer::<LE>(&mut file, "Data", &doc.root(), Compression::Gzip).unwrap();
+    assert_eq!(
+        peek_root_compressed::<LE>(&file).unwrap().1.as_deref(),
+        Some("Data")
+    );
+    let read = read_owned_compressed::<LE, BE>(&file).unwrap();
+    assert!(values_equal(&value, &read));
+}
+
+#[test]
+fn test_write_leveldat_writer_error() {
+    struct FailingWriter;
+
+    impl Write for FailingWriter {
+        fn write(&mut self, _buf: &[u8]) -> io::Result<usize> {
+            Err(io::Error::other("disk full"))
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    for compression in [Compression::None, Compression::Gzip] {
+        let err = write_leveldat_to_writer::<BE>(FailingWriter, "", &level(), compression)
+            .err()
+            .unwrap();
+        assert!(matches!(err, na_nbt::Error::IO(_)));
+    }
+}
@@ -0,0 +1,82 @@
+//! Tests for converting array views into fixed-size arrays.
+
+use na_nbt::{Error, OwnedCompound, OwnedValue, read_borrowed};
+use zerocopy::byteorder::{BigEndian as BE, I32, I64};
+
+fn entity() -> OwnedValue<BE> {
+    let mut compound: OwnedCompound<BE> = OwnedCompound::default();
+    compound.insert(
+        "UUID",
+        vec![I64::<BE>::new(0x0123_4567_89ab_cdef), I64::new(-1)],
+    );
+    compound.insert("Pos", vec![I32::<BE>::new(10), I32::new(64), I32::new(-10)]);
+    compound.insert("Flags", vec![1i8, 0, 1, 1]);
+    OwnedValue::Compound(compound)
+}
+
+#[test]
+fn test_uuid_from_borrowed_long_array() {
+    let bytes = entity().write_to_vec::<BE>().unwrap();
+    let doc = read_borrowed::<BE>(&bytes).unwrap();
+    let root = doc.root();
+    let uuid = root.get("UUID").unwrap();
+    let uuid = uuid.as_long_array().unwrap();
+
+    let [most, least]: [i64; 2] = uuid.try_into().unwrap();
+    assert_eq!(most, 0x0123_4567_89ab_cdef);
+    assert_eq!(least, -1);
+    assert_eq!(uuid.as_array::<2>(), Some([most, least]));
+    assert_eq!(uuid.as_array::<4>(), None);
+
+    let pos = root.get("Pos").unwrap();
+    let pos: [i32; 3] = pos.as_int_array().unwrap().try_into().unwrap();
+    assert_eq!(pos, [10, 64, -10]);
+
+    let flags = root.get("Flags").unwrap();
+    assert_eq!(
+        flags.as_byte_array().unwrap().as_array::<4>(),
+        Some([1, 0, 1, 1])
+    );
+}
+
+#[test]
+fn test_owned_and_mutable_views() {
+    let mut value = entity();
+    let OwnedValue::Compound(compound) = &mut value else {
+        unreachable!()
+    };
+    let mut uuid = compound.get_mut("UUID").unwrap();
+    let uuid = uuid.as_long_array_mut().unwrap();
+    assert_eq!(uuid.as_array::<2>(), Some([0x0123_4567_89ab_cdef, -1]));
+
+    let owned = OwnedValue::<BE>::from(vec![I32::<BE>::new(1), I32::new(2), I32::new(3)]);
+    let OwnedValue::IntArray(array) = &owned else {
+        unreachable!()
+    };
+    let pos: [i32; 3] = array.try_into().unwrap();
+    assert_eq!(pos, [1, 2, 3]);
+
+    let bytes = OwnedValue::<BE>::from(vec![5i8, 6]);
+    let OwnedValue::ByteArray(array) = &bytes else {
+        unreachable!()
+    };
+    assert_eq!(<[i8; 2]>::try_from(array).unwrap(), [5, 6]);
+}
+
+#[test]
+fn test_length_mismatch() {
+    let owned = OwnedValue::<BE>::from(vec![I64::<BE>::new(1)]);
+    let OwnedValue::LongArray(array) = &owned else {
+        unreachable!()
+    };
+    let err = <[i64; 2]>::try_from(array).err().unwrap();
+    assert!(matches!(err, Error::LengthMismatch(2, 1)));
+    assert_eq!(err.to_string(), "array length mismatch: expected 2, got 1");
+
+    let empty = OwnedValue::<BE>::from(Vec::<i8>::new());
+    let OwnedValue::ByteArray(array) = &empty else {
+        unreachable!()
+    };
+    assert_eq!(array.as_array::<0>(), Some([]));
+    assert!(<[i8; 1]>::try_from(array).is_err());
+}
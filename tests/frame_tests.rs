@@ -0,0 +1,96 @@
+//! Tests for `write_framed_value_to_vec`.
+
+use na_nbt::{
+    LengthPrefix, OwnedCompound, OwnedValue, VarInt, read_borrowed, write_framed_value_to_vec,
+};
+use zerocopy::byteorder::{BigEndian as BE, LittleEndian as LE, U16, U32};
+
+fn value(entries: usize) -> OwnedValue<BE> {
+    let mut compound: OwnedCompound<BE> = OwnedCompound::default();
+    for i in 0..entries {
+        compound.insert(&format!("key{i}"), i as i64);
+    }
+    OwnedValue::Compound(compound)
+}
+
+fn decode_varint(bytes: &[u8]) -> (usize, usize) {
+    let mut value = 0;
+    for (i, byte) in bytes.iter().enumerate() {
+        value |= ((byte & 0x7f) as usize) << (7 * i);
+        if byte & 0x80 == 0 {
+            return (value, i + 1);
+        }
+    }
+    panic!("unterminated varint");
+}
+
+#[test]
+fn test_prefix_equals_body_length() {
+    for entries in [0, 10, 1000] {
+        let value = value(entries);
+        let body = value.write_to_vec::<LE>().unwrap();
+
+        let frame = write_framed_value_to_vec::<LE, U16<BE>>(&value).unwrap();
+        assert_eq!(
+            u16::from_be_bytes([frame[0], frame[1]]) as usize,
+            body.len()
+        );
+        assert_eq!(frame[2..], body);
+
+        let frame = write_framed_value_to_vec::<LE, U16<LE>>(&value).unwrap();
+        assert_eq!(
+            u16::from_le_bytes([frame[0], frame[1]]) as usize,
+            body.len()
+        );
+        assert_eq!(frame[2..], body);
+
+        let frame = write_framed_value_to_vec::<LE, U32<BE>>(&value).unwrap();
+        assert_eq!(
+            u32::from_be_bytes(frame[..4].try_into().unwrap()) as usize,
+            body.len()
+        );
+        assert_eq!(frame[4..], body);
+
+        let frame = write_framed_value_to_vec::<LE, U32<LE>>(&value).unwrap();
+        assert_eq!(
+            u32::from_le_bytes(frame[..4].try_into().unwrap()) as usize,
+            body.len()
+        );
+        assert_eq!(frame[4..], body);
+
+        let frame = write_framed_value_to_vec::<LE, VarInt>(&value).unwrap();
+        let (len, prefix_len) = decode_varint(&frame);
+        assert_eq!(len, body.len());
+        assert_eq!(frame[prefix_len..], body);
+    }
+}
+
+#[test]
+fn test_varint_encoding() {
+    let mut out = [0; 5];
+    for (len, expected) in [
+        (0, &[0x00][..]),
+        (127, &[0x7f]),
+        (128, &[0x80, 0x01]),
+        (300, &[0xac, 0x02]),
+        (u32::MAX as usize, &[0xff, 0xff, 0xff, 0xff, 0x0f]),
+    ] {
+        let n = VarInt::encode(len, &mut out).unwrap();
+        assert_eq!(&out[..n], expected);
+    }
+    assert_eq!(VarInt::encode(u32::MAX as usize + 1, &mut out), None);
+}
+
+#[test]
+fn test_borrowed_value_and_overflow() {
+    let bytes = value(3).write_to_vec::<BE>().unwrap();
+    let doc = read_borrowed::<BE>(&bytes).unwrap();
+    let frame = write_framed_value_to_vec::<BE, VarInt>(&doc.root()).unwrap();
+    assert_eq!(frame[0] as usize, bytes.len());
+    assert_eq!(frame[1..], bytes);
+
+    // more than 65535 bytes
+    let big = value(5000);
+    assert!(big.write_to_vec::<BE>().unwrap().len() > u16::MAX as usize);
+    assert!(write_framed_value_to_vec::<BE, U16<BE>>(&big).is_err());
+}
@@ -0,0 +1,68 @@
+//! Regression tests for inputs found by fuzzing.
+//!
+//! The allocator records the largest single allocation, so this file holds one
+//! test to keep other tests from disturbing the measurement.
+
+use std::{
+    alloc::{GlobalAlloc, Layout, System},
+    sync::atomic::{AtomicUsize, Ordering},
+};
+
+use na_nbt::{Error, read_owned};
+use zerocopy::byteorder::{BigEndian as BE, LittleEndian as LE};
+
+struct PeakAlloc;
+
+static LARGEST: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for PeakAlloc {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        LARGEST.fetch_max(layout.size(), Ordering::Relaxed);
+        unsafe { System.alloc(layout) }
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        unsafe { System.dealloc(ptr, layout) }
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        LARGEST.fetch_max(new_size, Ordering::Relaxed);
+        unsafe { System.realloc(ptr, layout, new_size) }
+    }
+}
+
+#[global_allocator]
+static GLOBAL: PeakAlloc = PeakAlloc;
+
+fn largest_allocation<R>(f: impl FnOnce() -> R) -> (R, usize) {
+    LARGEST.store(0, Ordering::Relaxed);
+    let result = f();
+    (result, LARGEST.load(Ordering::Relaxed))
+}
+
+/// Lists whose count fits the remaining bytes at one byte per element, but
+/// not at the smallest possible size of their element type.
+#[test]
+fn test_overlong_list_counts_fail_before_reserving() {
+    // a list of 4096 strings followed by 4096 zero bytes: only 2048 empty
+    // strings fit, but the count used to reserve room for all 4096
+    let strings = include_bytes!("../fuzz/in/list_of_strings_overlong_count.nbt");
+
+    let mut lists = vec![0x09, 0x00, 0x00, 0x09];
+    lists.extend_from_slice(&4096u32.to_be_bytes());
+    lists.resize(lists.len() + 4096, 0);
+
+    let mut arrays = vec![0x09, 0x00, 0x00, 0x0b];
+    arrays.extend_from_slice(&4096u32.to_be_bytes());
+    arrays.resize(arrays.len() + 4096, 0);
+
+    for data in [&strings[..], &lists, &arrays] {
+        let (result, largest) = largest_allocation(|| read_owned::<BE, BE>(data));
+        assert!(matches!(result, Err(Error::EndOfFile)));
+        assert!(largest < data.len(), "reserved {largest} bytes");
+
+        let (result, largest) = largest_allocation(|| read_owned::<BE, LE>(data));
+        assert!(matches!(result, Err(Error::EndOfFile)));
+        assert!(largest < data.len(), "reserved {largest} bytes");
+    }
+}
@@ -0,0 +1,73 @@
+use na_nbt::{OwnedCompound, ScopedReadableCompound, read_borrowed};
+use zerocopy::byteorder::BigEndian as BE;
+
+fn push_int(data: &mut Vec<u8>, name: &[u8], value: i32) {
+    data.push(0x03);
+    data.extend_from_slice(&(name.len() as u16).to_be_bytes());
+    data.extend_from_slice(name);
+    data.extend_from_slice(&value.to_be_bytes());
+}
+
+fn create_compound_be() -> Vec<u8> {
+    // Root compound { Name: 1, NAME: 2, "Größe": 3 }
+    let mut data = vec![0x0A, 0x00, 0x00];
+    push_int(&mut data, b"Name", 1);
+    push_int(&mut data, b"NAME", 2);
+    push_int(&mut data, "Größe".as_bytes(), 3);
+    data.push(0x00);
+    data
+}
+
+#[test]
+fn borrowed_ignore_case_first_match_wins() {
+    let data = create_compound_be();
+    let doc = read_borrowed::<BE>(&data).unwrap();
+    let root = doc.root();
+    let comp = root.as_compound().unwrap();
+
+    assert_eq!(comp.get_ignore_case("name").unwrap().as_int(), Some(1));
+    assert_eq!(comp.get_ignore_case("nAmE").unwrap().as_int(), Some(1));
+    // exact lookup still distinguishes the two
+    assert_eq!(comp.get_scoped("NAME").unwrap().as_int(), Some(2));
+    assert!(comp.get_scoped("name").is_none());
+}
+
+#[test]
+fn borrowed_ignore_case_non_ascii_is_exact() {
+    let data = create_compound_be();
+    let doc = read_borrowed::<BE>(&data).unwrap();
+    let root = doc.root();
+    let comp = root.as_compound().unwrap();
+
+    assert_eq!(comp.get_ignore_case("größe").unwrap().as_int(), Some(3));
+    assert_eq!(comp.get_ignore_case("GRößE").unwrap().as_int(), Some(3));
+    // only ASCII letters fold; `ö` and `Ö` are different bytes
+    assert!(comp.get_ignore_case("GRÖßE").is_none());
+    assert!(comp.get_ignore_case("gross").is_none());
+}
+
+#[test]
+fn owned_ignore_case() {
+    let mut comp = OwnedCompound::<BE>::default();
+    comp.insert("CustomName", "Steve");
+    comp.insert("customname", "Alex");
+
+    let value = comp.get_ignore_case("CUSTOMNAME").unwrap();
+    assert_eq!(value.as_string().unwrap().decode(), "Steve");
+    assert!(comp.get_ignore_case("Custom").is_none());
+    assert!(OwnedCompound::<BE>::default().get_ignore_case("").is_none());
+}
+
+#[test]
+fn ignore_ascii_case_finds_pos() {
+    let mut pos = OwnedCompound::<BE>::default();
+    pos.insert("x", 1i32);
+    let mut comp = OwnedCompound::<BE>::default();
+    comp.insert("id", "zombie");
+    comp.insert("Pos", pos);
+
+    let value = comp.get_ignore_ascii_case("pos").unwrap();
+    assert_eq!(value.get("x").and_then(|x| x.as_int()), Some(1));
+    assert!(comp.get_scoped("pos").is_none());
+    assert!(comp.get_ignore_ascii_case("position").is_none());
+}
@@ -0,0 +1,60 @@
+use na_nbt::{OwnedCompound, ScopedReadableCompound, read_borrowed};
+use zerocopy::byteorder::BigEndian as BE;
+
+fn create_compound_be() -> Vec<u8> {
+    // Root compound { i: Int 5, d: Double 1.5, s: String "x" }
+    let mut data = vec![0x0A, 0x00, 0x00];
+    data.push(0x03);
+    data.extend_from_slice(&1u16.to_be_bytes());
+    data.push(b'i');
+    data.extend_from_slice(&5i32.to_be_bytes());
+    data.push(0x06);
+    data.extend_from_slice(&1u16.to_be_bytes());
+    data.push(b'd');
+    data.extend_from_slice(&1.5f64.to_be_bytes());
+    data.push(0x08);
+    data.extend_from_slice(&1u16.to_be_bytes());
+    data.push(b's');
+    data.extend_from_slice(&1u16.to_be_bytes());
+    data.push(b'x');
+    data.push(0x00);
+    data
+}
+
+#[test]
+fn borrowed_get_or_present() {
+    let data = create_compound_be();
+    let doc = read_borrowed::<BE>(&data).unwrap();
+    let root = doc.root();
+    let comp = root.as_compound().unwrap();
+    assert_eq!(comp.get_or("i", 0i32), 5);
+    assert_eq!(comp.get_or("d", 0.0f64), 1.5);
+}
+
+#[test]
+fn borrowed_get_or_missing_and_wrong_type() {
+    let data = create_compound_be();
+    let doc = read_borrowed::<BE>(&data).unwrap();
+    let root = doc.root();
+    let comp = root.as_compound().unwrap();
+    assert_eq!(comp.get_or("nope", 9i32), 9);
+    // Wrong primitive type is not widened.
+    assert_eq!(comp.get_or("i", 9i64), 9);
+    assert_eq!(comp.get_or("d", 2.0f32), 2.0);
+    // Non-primitive value falls back too.
+    assert_eq!(comp.get_or("s", 3i8), 3);
+}
+
+#[test]
+fn owned_get_or() {
+    let mut comp = OwnedCompound::<BE>::default();
+    comp.insert("b", 1i8);
+    comp.insert("s", 2i16);
+    comp.insert("l", 3i64);
+    comp.insert("f", 4.0f32);
+    assert_eq!(comp.get_or("b", 0i8), 1);
+    assert_eq!(comp.get_or("s", 0i16), 2);
+    assert_eq!(comp.get_or("l", 0i64), 3);
+    assert_eq!(comp.get_or("f", 0.0f32), 4.0);
+    assert_eq!(comp.get_or("b", -1i16), -1);
+}
@@ -0,0 +1,73 @@
+//! Tests for indexing a list of compounds by a field.
+
+use na_nbt::{OwnedCompound, OwnedList, OwnedValue, read_borrowed};
+use zerocopy::byteorder::BigEndian as BE;
+
+fn named(name: &str, level: i32) -> OwnedCompound<BE> {
+    let mut compound = OwnedCompound::default();
+    compound.insert("name", name);
+    compound.insert("level", level);
+    compound
+}
+
+#[test]
+fn test_index_by_name() {
+    let mut list = OwnedList::<BE>::default();
+    list.push(named("Alex", 3));
+    list.push(named("Steve", 7));
+    list.push(named("Notch", 99));
+    let bytes = OwnedValue::List(list).write_to_vec::<BE>().unwrap();
+
+    let doc = read_borrowed::<BE>(&bytes).unwrap();
+    let root = doc.root();
+    let map = root.as_list().unwrap().index_by("name");
+    assert_eq!(map.len(), 3);
+    for (name, level) in [("Alex", 3), ("Steve", 7), ("Notch", 99)] {
+        assert_eq!(map[name].get("level").unwrap().as_int(), Some(level));
+    }
+}
+
+#[test]
+fn test_index_by_skips_unkeyed_elements() {
+    let mut missing = OwnedCompound::<BE>::default();
+    missing.insert("level", 1);
+    let mut not_string = OwnedCompound::<BE>::default();
+    not_string.insert("name", 5);
+
+    let mut list = OwnedList::<BE>::default();
+    list.push(missing);
+    list.push(named("Steve", 7));
+    list.push(not_string);
+    let bytes = OwnedValue::List(list).write_to_vec::<BE>().unwrap();
+
+    let doc = read_borrowed::<BE>(&bytes).unwrap();
+    let root = doc.root();
+    let map = root.as_list().unwrap().index_by("name");
+    assert_eq!(map.keys().collect::<Vec<_>>(), ["Steve"]);
+}
+
+#[test]
+fn test_index_by_keeps_first_duplicate() {
+    let mut list = OwnedList::<BE>::default();
+    list.push(named("Steve", 1));
+    list.push(named("Steve", 2));
+    let bytes = OwnedValue::List(list).write_to_vec::<BE>().unwrap();
+
+    let doc = read_borrowed::<BE>(&bytes).unwrap();
+    let root = doc.root();
+    let map = root.as_list().unwrap().index_by("name");
+    assert_eq!(map.len(), 1);
+    assert_eq!(map["Steve"].get("level").unwrap().as_int(), Some(1));
+}
+
+#[test]
+fn test_index_by_non_compound_list() {
+    let mut list = OwnedList::<BE>::default();
+    list.push(1i32);
+    list.push(2i32);
+    let bytes = OwnedValue::List(list).write_to_vec::<BE>().unwrap();
+
+    let doc = read_borrowed::<BE>(&bytes).unwrap();
+    let root = doc.root();
+    assert!(root.as_list().unwrap().index_by("name").is_empty());
+}
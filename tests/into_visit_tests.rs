@@ -0,0 +1,95 @@
+//! Tests for `OwnedValue::into_visit`.
+
+use na_nbt::{IntoVisit, OwnedCompound, OwnedList, OwnedValue, Tag};
+use zerocopy::byteorder::BigEndian as BE;
+
+#[test]
+fn test_move_nested_list_without_copy() {
+    let mut sections: OwnedList<BE> = OwnedList::default();
+    sections.push(vec![1i8, 2, 3]);
+    sections.push(vec![4i8, 5]);
+    let mut level: OwnedCompound<BE> = OwnedCompound::default();
+    level.insert("sections", sections);
+    let mut root: OwnedCompound<BE> = OwnedCompound::default();
+    root.insert("level", level);
+    let root = OwnedValue::Compound(root);
+
+    let before = root
+        .get("level")
+        .unwrap()
+        .get("sections")
+        .unwrap()
+        .get(0)
+        .unwrap()
+        .as_byte_array()
+        .unwrap()
+        .as_ptr();
+
+    let sections = root.into_visit(|value| match value {
+        IntoVisit::Compound(mut root) => {
+            let level = root.remove("level").unwrap();
+            level.into_visit(|value| match value {
+                IntoVisit::Compound(mut level) => level.remove("sections"),
+                _ => None,
+            })
+        }
+        _ => None,
+    });
+    let sections = sections.unwrap().into_visit(|value| match value {
+        IntoVisit::List(list) => list,
+        _ => panic!("expected a list"),
+    });
+
+    let mut moved: OwnedCompound<BE> = OwnedCompound::default();
+    moved.insert("sections", sections);
+    let first = moved.get("sections").unwrap();
+    let first = first.get(0).unwrap();
+    let array = first.as_byte_array().unwrap();
+    // the array's buffer is the same one: nothing was copied
+    assert_eq!(array.as_ptr(), before);
+    assert_eq!(**array, [1, 2, 3]);
+}
+
+#[test]
+fn test_scalars_by_value() {
+    let cases: Vec<(OwnedValue<BE>, Tag, f64)> = vec![
+        (OwnedValue::End, Tag::End, 0.0),
+        (1i8.into(), Tag::Byte, 1.0),
+        (2i16.into(), Tag::Short, 2.0),
+        (3i32.into(), Tag::Int, 3.0),
+        (4i64.into(), Tag::Long, 4.0),
+        (5.5f32.into(), Tag::Float, 5.5),
+        (6.5f64.into(), Tag::Double, 6.5),
+    ];
+    for (value, tag, expected) in cases {
+        let (found, number) = value.into_visit(|value| match value {
+            IntoVisit::End => (Tag::End, 0.0),
+            IntoVisit::Byte(n) => (Tag::Byte, n as f64),
+            IntoVisit::Short(n) => (Tag::Short, n as f64),
+            IntoVisit::Int(n) => (Tag::Int, n as f64),
+            IntoVisit::Long(n) => (Tag::Long, n as f64),
+            IntoVisit::Float(n) => (Tag::Float, n as f64),
+            IntoVisit::Double(n) => (Tag::Double, n),
+            _ => panic!("expected a scalar"),
+        });
+        assert_eq!(found, tag);
+        assert_eq!(number, expected);
+    }
+}
+
+#[test]
+fn test_string_and_arrays() {
+    let value: OwnedValue<BE> = "hello".into();
+    let s = value.into_visit(|value| match value {
+        IntoVisit::String(s) => s,
+        _ => panic!("expected a string"),
+    });
+    assert_eq!(s.decode(), "hello");
+
+    let value = OwnedValue::<BE>::packed_long_array(&[1, 2, 3], 4);
+    let longs = value.into_visit(|value| match value {
+        IntoVisit::LongArray(longs) => longs,
+        _ => panic!("expected a long array"),
+    });
+    assert_eq!(longs.len(), 1);
+}
@@ -0,0 +1,169 @@
+//! Tests for `ReadonlyCompound::iter_of`.
+
+use na_nbt::{
+    OwnedCompound, OwnedValue, TagByte, TagByteArray, TagCompound, TagDouble, TagFloat, TagInt,
+    TagIntArray, TagList, TagLong, TagLongArray, TagShort, TagString, read_borrowed, read_shared,
+};
+use zerocopy::byteorder::{BigEndian, LittleEndian};
+
+fn mixed() -> OwnedCompound<BigEndian> {
+    let mut compound = OwnedCompound::default();
+    compound.insert("a", 1);
+    compound.insert("b", 2.5f64);
+    compound.insert("c", "text");
+    compound.insert("d", 3);
+    compound.insert("e", 4i64);
+    compound.insert("f", 5i8);
+    compound
+}
+
+#[test]
+fn yields_only_matching_entries_in_order() {
+    let bytes = OwnedValue::Compound(mixed())
+        .write_to_vec::<BigEndian>()
+        .unwrap();
+    let doc = read_borrowed::<BigEndian>(&bytes).unwrap();
+    let root = doc.root();
+    let compound = root.as_compound().unwrap();
+
+    let ints: Vec<_> = compound.iter_of::<TagInt>().collect();
+    assert_eq!(ints, [("a".into(), 1), ("d".into(), 3)]);
+
+    let doubles: Vec<_> = compound
+        .iter_of::<TagDouble>()
+        .map(|(_, value)| value)
+        .collect();
+    assert_eq!(doubles, [2.5]);
+    let longs: Vec<_> = compound
+        .iter_of::<TagLong>()
+        .map(|(_, value)| value)
+        .collect();
+    assert_eq!(longs, [4]);
+    let bytes: Vec<_> = compound
+        .iter_of::<TagByte>()
+        .map(|(_, value)| value)
+        .collect();
+    assert_eq!(bytes, [5]);
+    assert_eq!(compound.iter_of::<TagShort>().count(), 0);
+    assert_eq!(compound.iter_of::<TagFloat>().count(), 0);
+}
+
+#[test]
+fn decodes_in_the_source_byte_order() {
+    let mut compound: OwnedCompound<LittleEndian> = OwnedCompound::default();
+    compound.insert("x", 0x0102_0304);
+    compound.insert("y", -7);
+    let bytes = OwnedValue::Compound(compound)
+        .write_to_vec::<LittleEndian>()
+        .unwrap();
+    let doc = read_borrowed::<LittleEndian>(&bytes).unwrap();
+    let root = doc.root();
+    let values: Vec<_> = root
+        .as_compound()
+        .unwrap()
+        .iter_of::<TagInt>()
+        .map(|(_, value)| value)
+        .collect();
+    assert_eq!(values, [0x0102_0304, -7]);
+}
+
+#[test]
+fn empty_compound_yields_nothing() {
+    let bytes = OwnedValue::Compound(OwnedCompound::<BigEndian>::default())
+        .write_to_vec::<BigEndian>()
+        .unwrap();
+    let doc = read_borrowed::<BigEndian>(&bytes).unwrap();
+    let root = doc.root();
+    assert_eq!(root.as_compound().unwrap().iter_of::<TagInt>().count(), 0);
+}
+
+#[test]
+fn iterator_is_cloneable() {
+    let bytes = OwnedValue::Compound(mixed())
+        .write_to_vec::<BigEndian>()
+        .unwrap();
+    let doc = read_borrowed::<BigEndian>(&bytes).unwrap();
+    let root = doc.root();
+    let compound = root.as_compound().unwrap();
+    let mut iter = compound.iter_of::<TagInt>();
+    iter.next();
+    let rest: Vec<_> = iter.clone().map(|(_, value)| value).collect();
+    assert_eq!(rest, [3]);
+    assert_eq!(iter.count(), 1);
+}
+
+#[test]
+fn yields_strings_with_decoded_keys() {
+    let mut compound: OwnedCompound<BigEndian> = OwnedCompound::default();
+    compound.insert("caf\u{e9}", "cr\u{e8}me");
+    compound.insert("n", 1);
+    compound.insert("\u{1F600}", "smile");
+    let bytes = OwnedValue::Compound(compound)
+        .write_to_vec::<BigEndian>()
+        .unwrap();
+    let doc = read_borrowed::<BigEndian>(&bytes).unwrap();
+    let root = doc.root();
+    let strings: Vec<_> = root
+        .as_compound()
+        .unwrap()
+        .iter_of::<TagString>()
+        .map(|(key, value)| (key.into_owned(), value.decode().into_owned()))
+        .collect();
+    assert_eq!(
+        strings,
+        [
+            ("caf\u{e9}".to_string(), "cr\u{e8}me".to_string()),
+            ("\u{1F600}".to_string(), "smile".to_string()),
+        ]
+    );
+}
+
+#[test]
+fn yields_arrays_lists_and_compounds() {
+    let mut compound: OwnedCompound<LittleEndian> = OwnedCompound::default();
+    compound.insert("bytes", vec![1i8, -2]);
+    compound.insert(
+        "ints",
+        OwnedValue::IntArray(vec![3.into(), 4.into()].into()),
+    );
+    compound.insert("longs", OwnedValue::LongArray(vec![5.into()].into()));
+    compound.insert("nested", OwnedCompound::<LittleEndian>::default());
+    compound.insert("more_ints", OwnedValue::IntArray(vec![6.into()].into()));
+    let bytes = OwnedValue::Compound(compound)
+        .write_to_vec::<LittleEndian>()
+        .unwrap();
+    let root = read_shared::<LittleEndian>(bytes.into()).unwrap();
+    let compound = root.as_compound().unwrap();
+
+    let byte_arrays: Vec<_> = compound
+        .iter_of::<TagByteArray>()
+        .map(|(key, value)| (key.into_owned(), value.to_vec()))
+        .collect();
+    assert_eq!(byte_arrays, [("bytes".to_string(), vec![1, -2])]);
+
+    let int_arrays: Vec<_> = compound
+        .iter_of::<TagIntArray>()
+        .map(|(key, value)| {
+            let values: Vec<i32> = value.iter().map(|v| v.get()).collect();
+            (key.into_owned(), values)
+        })
+        .collect();
+    assert_eq!(
+        int_arrays,
+        [
+            ("ints".to_string(), vec![3, 4]),
+            ("more_ints".to_string(), vec![6])
+        ]
+    );
+
+    let long_arrays: Vec<_> = compound
+        .iter_of::<TagLongArray>()
+        .map(|(_, value)| value.iter().map(|v| v.get()).collect::<Vec<i64>>())
+        .collect();
+    assert_eq!(long_arrays, [vec![5]]);
+
+    let (key, nested) = compound.iter_of::<TagCompound>().next().unwrap();
+    assert_eq!(key, "nested");
+    assert_eq!(nested.iter().count(), 0);
+    assert_eq!(compound.iter_of::<TagList>().count(), 0);
+}
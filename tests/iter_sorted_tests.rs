@@ -0,0 +1,94 @@
+//! Tests for `ReadonlyCompound::iter_sorted`.
+
+use bytes::Bytes;
+use na_nbt::{OwnedCompound, OwnedValue, read_borrowed, read_shared};
+use zerocopy::byteorder::{BigEndian as BE, LittleEndian as LE};
+
+fn bytes() -> Vec<u8> {
+    let mut compound: OwnedCompound<BE> = OwnedCompound::default();
+    for (i, key) in ["delta", "Alpha", "charlie", "bravo", "alpha", "echo"]
+        .into_iter()
+        .enumerate()
+    {
+        compound.insert(key, i as i32);
+    }
+    OwnedValue::Compound(compound).write_to_vec::<BE>().unwrap()
+}
+
+#[test]
+fn test_sorted_order() {
+    let bytes = bytes();
+    let doc = read_borrowed::<BE>(&bytes).unwrap();
+    let root = doc.root();
+    let compound = root.as_compound().unwrap();
+
+    let entries: Vec<(String, i32)> = compound
+        .iter_sorted()
+        .map(|(key, value)| (key.decode().into_owned(), value.as_int().unwrap()))
+        .collect();
+    assert_eq!(
+        entries,
+        [
+            ("Alpha".to_string(), 1),
+            ("alpha".to_string(), 4),
+            ("bravo".to_string(), 3),
+            ("charlie".to_string(), 2),
+            ("delta".to_string(), 0),
+            ("echo".to_string(), 5),
+        ]
+    );
+}
+
+#[test]
+fn test_storage_untouched() {
+    let bytes = bytes();
+    let doc = read_borrowed::<BE>(&bytes).unwrap();
+    let root = doc.root();
+    let compound = root.as_compound().unwrap();
+
+    let before: Vec<String> = compound
+        .iter()
+        .map(|(key, _)| key.decode().into_owned())
+        .collect();
+    assert_eq!(compound.iter_sorted().count(), before.len());
+    let after: Vec<String> = compound
+        .iter()
+        .map(|(key, _)| key.decode().into_owned())
+        .collect();
+    assert_eq!(before, after);
+    assert_eq!(before[0], "delta");
+}
+
+#[test]
+fn test_repeated_keys_keep_order() {
+    let bytes = [
+        10, 0, 0, //
+        1, 1, 0, b'k', 2, //
+        1, 1, 0, b'a', 0, //
+        1, 1, 0, b'k', 3, //
+        0,
+    ];
+    let doc = read_borrowed::<LE>(&bytes).unwrap();
+    let root = doc.root();
+    let values: Vec<i8> = root
+        .as_compound()
+        .unwrap()
+        .iter_sorted()
+        .map(|(_, value)| value.as_byte().unwrap())
+        .collect();
+    assert_eq!(values, [0, 2, 3]);
+}
+
+#[test]
+fn test_shared_and_empty() {
+    let root = read_shared::<BE>(Bytes::from(bytes())).unwrap();
+    let first = root.as_compound().unwrap().iter_sorted().next().unwrap();
+    assert_eq!(first.0.decode(), "Alpha");
+    assert_eq!(first.1.as_int(), Some(1));
+
+    let empty = OwnedValue::Compound(OwnedCompound::<BE>::default())
+        .write_to_vec::<BE>()
+        .unwrap();
+    let doc = read_borrowed::<BE>(&empty).unwrap();
+    assert_eq!(doc.root().as_compound().unwrap().iter_sorted().count(), 0);
+}
@@ -1,4 +1,4 @@
-use na_nbt::{read_borrowed, read_owned, OwnedValue};
+use na_nbt::{read_borrowed, read_owned, OwnedCompound, OwnedList, OwnedValue, Tag};
 use zerocopy::byteorder::BigEndian as BE;
 
 fn create_int_list_be() -> Vec<u8> {
@@ -209,3 +209,36 @@ fn owned_nested_list_into_iter_drop() {
         panic!("expected list");
     }
 }
+
+#[test]
+fn owned_compound_drain_and_rebuild() {
+    let data = create_compound_multi_be();
+    let owned = read_owned::<BE, BE>(&data).unwrap();
+    if let OwnedValue::Compound(comp) = owned {
+        let mut rebuilt = OwnedCompound::<BE>::default();
+        for (key, value) in comp {
+            assert!(rebuilt.insert(&key, value).is_none());
+        }
+        let bytes = OwnedValue::Compound(rebuilt).write_to_vec::<BE>().unwrap();
+        assert_eq!(bytes, data);
+    } else {
+        panic!("expected compound");
+    }
+}
+
+#[test]
+fn owned_list_drain_and_rebuild() {
+    let data = create_string_list_be();
+    let owned = read_owned::<BE, BE>(&data).unwrap();
+    if let OwnedValue::List(list) = owned {
+        let mut rebuilt = OwnedList::<BE>::default();
+        for value in list {
+            assert_eq!(value.tag_id(), Tag::String);
+            rebuilt.push(value);
+        }
+        let bytes = OwnedValue::List(rebuilt).write_to_vec::<BE>().unwrap();
+        assert_eq!(bytes, data);
+    } else {
+        panic!("expected list");
+    }
+}
@@ -0,0 +1,108 @@
+#![cfg(feature = "json")]
+
+use na_nbt::{Error, OwnedValue, Tag, from_json};
+use serde_json::json;
+use zerocopy::byteorder::{BigEndian as BE, LittleEndian as LE};
+
+#[test]
+fn scalars() {
+    assert_eq!(from_json::<BE>(&json!(true)).unwrap().as_byte(), Some(1));
+    assert_eq!(from_json::<BE>(&json!(false)).unwrap().as_byte(), Some(0));
+    assert_eq!(from_json::<BE>(&json!(7)).unwrap().as_int(), Some(7));
+    assert_eq!(
+        from_json::<BE>(&json!(i32::MIN)).unwrap().as_int(),
+        Some(i32::MIN)
+    );
+    assert_eq!(
+        from_json::<BE>(&json!(i32::MAX as i64 + 1))
+            .unwrap()
+            .as_long(),
+        Some(i32::MAX as i64 + 1)
+    );
+    assert_eq!(
+        from_json::<LE>(&json!(i64::MIN)).unwrap().as_long(),
+        Some(i64::MIN)
+    );
+    assert_eq!(from_json::<BE>(&json!(2.5)).unwrap().as_double(), Some(2.5));
+
+    let string = from_json::<BE>(&json!("hi")).unwrap();
+    assert_eq!(string.as_string().unwrap().decode(), "hi");
+}
+
+#[test]
+fn numeric_arrays_pick_narrowest_list() {
+    let list_tag = |value: OwnedValue<BE>| value.as_list().unwrap().tag_id();
+
+    assert_eq!(list_tag(from_json(&json!([1, 2, 3])).unwrap()), Tag::Int);
+    assert_eq!(
+        list_tag(from_json(&json!([1, 1u64 << 40])).unwrap()),
+        Tag::Long
+    );
+    assert_eq!(
+        list_tag(from_json(&json!([1, 2.5, 1u64 << 40])).unwrap()),
+        Tag::Double
+    );
+    assert_eq!(list_tag(from_json(&json!([])).unwrap()), Tag::End);
+
+    let longs = from_json::<BE>(&json!([-1, 1u64 << 40])).unwrap();
+    assert_eq!(longs.get(0).and_then(|v| v.as_long()), Some(-1));
+    let doubles = from_json::<BE>(&json!([3, 0.5])).unwrap();
+    assert_eq!(doubles.get(0).and_then(|v| v.as_double()), Some(3.0));
+}
+
+#[test]
+fn nested_objects_and_arrays() {
+    let value = from_json::<BE>(&json!({
+        "name": "Steve",
+        "flags": [true, false],
+        "tags": ["a", "b"],
+        "items": [{ "id": "stone", "count": 64 }, { "id": "dirt" }],
+        "matrix": [[1, 2], [3.5]],
+    }))
+    .unwrap();
+
+    assert_eq!(
+        value.get("name").unwrap().as_string().unwrap().decode(),
+        "Steve"
+    );
+    assert_eq!(
+        value.get("flags").unwrap().as_list().unwrap().tag_id(),
+        Tag::Byte
+    );
+    assert_eq!(
+        value.get("tags").unwrap().as_list().unwrap().tag_id(),
+        Tag::String
+    );
+
+    let items = value.get("items").unwrap();
+    assert_eq!(items.as_list().unwrap().tag_id(), Tag::Compound);
+    assert_eq!(
+        items
+            .get(0)
+            .and_then(|i| i.get("count"))
+            .and_then(|c| c.as_int()),
+        Some(64)
+    );
+
+    // inner lists only need to share the List tag, not their element types
+    let matrix = value.get("matrix").unwrap();
+    assert_eq!(matrix.as_list().unwrap().tag_id(), Tag::List);
+    assert_eq!(
+        matrix.get(1).unwrap().as_list().unwrap().tag_id(),
+        Tag::Double
+    );
+}
+
+#[test]
+fn rejected_values() {
+    let is_message =
+        |result: na_nbt::Result<OwnedValue<BE>>| matches!(result, Err(Error::Message(_)));
+
+    assert!(is_message(from_json(&json!(null))));
+    assert!(is_message(from_json(&json!({ "a": null }))));
+    assert!(is_message(from_json(&json!(u64::MAX))));
+    assert!(is_message(from_json(&json!([1, u64::MAX]))));
+    assert!(is_message(from_json(&json!([1, "two"]))));
+    assert!(is_message(from_json(&json!(["a", { "b": 1 }]))));
+    assert!(is_message(from_json(&json!([true, 1]))));
+}
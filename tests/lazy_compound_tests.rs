@@ -0,0 +1,91 @@
+//! Tests for `read_shared_lazy` and `LazyCompound`.
+#![cfg(feature = "shared")]
+
+use bytes::Bytes;
+use na_nbt::{
+    Error, OwnedCompound, OwnedList, OwnedValue, read_shared, read_shared_lazy, values_equal,
+};
+use zerocopy::byteorder::{BigEndian as BE, LittleEndian as LE};
+
+fn document() -> Vec<u8> {
+    let mut list: OwnedList<LE> = OwnedList::default();
+    list.push(OwnedCompound::<LE>::default());
+    let mut inner: OwnedCompound<LE> = OwnedCompound::default();
+    inner.insert("list", list);
+    inner.insert("ints", vec![1i8, 2, 3]);
+    let mut root: OwnedCompound<LE> = OwnedCompound::default();
+    root.insert("a", 1i8);
+    root.insert("inner", inner);
+    root.insert("name", "lazy");
+    root.insert("z", 2i64);
+    OwnedValue::Compound(root).write_to_vec::<LE>().unwrap()
+}
+
+#[test]
+fn test_get_matches_eager() {
+    let bytes = Bytes::from(document());
+    let eager = read_shared::<LE>(bytes.clone()).unwrap();
+    let mut lazy = read_shared_lazy::<LE>(bytes).unwrap();
+    assert_eq!(lazy.scanned_len(), 0);
+
+    let inner = lazy.get("inner").unwrap().unwrap();
+    assert!(values_equal(&inner, &eager.get("inner").unwrap()));
+    assert_eq!(lazy.scanned_len(), 2);
+
+    let name = lazy.get("name").unwrap().unwrap();
+    assert_eq!(name.as_string().unwrap().decode(), "lazy");
+    assert_eq!(lazy.scanned_len(), 3);
+
+    // cached entries are found without scanning further
+    assert_eq!(lazy.get("a").unwrap().unwrap().as_byte(), Some(1));
+    assert_eq!(lazy.scanned_len(), 3);
+
+    assert!(lazy.get("missing").unwrap().is_none());
+    assert_eq!(lazy.scanned_len(), 4);
+    assert_eq!(lazy.get("z").unwrap().unwrap().as_long(), Some(2));
+}
+
+#[test]
+fn test_duplicate_keys_return_first() {
+    let mut data = vec![0x0a, 0x00, 0x00];
+    for value in [1u8, 2] {
+        data.extend_from_slice(&[0x01, 0x00, 0x01, b'k', value]);
+    }
+    data.push(0x00);
+    let mut lazy = read_shared_lazy::<BE>(Bytes::from(data)).unwrap();
+    assert_eq!(lazy.get("k").unwrap().unwrap().as_byte(), Some(1));
+}
+
+#[test]
+fn test_errors() {
+    let Err(err) = read_shared_lazy::<BE>(Bytes::from_static(&[0x03, 0x00, 0x00, 0, 0, 0, 1]))
+    else {
+        panic!("expected an error");
+    };
+    assert!(matches!(err, Error::TagMismatch(10, 3)));
+    assert!(matches!(
+        read_shared_lazy::<BE>(Bytes::from_static(&[0x0a, 0x00])),
+        Err(Error::EndOfFile)
+    ));
+
+    // the header is fine, the data is truncated
+    let mut data = document();
+    data.truncate(data.len() - 3);
+    let mut lazy = read_shared_lazy::<LE>(Bytes::from(data)).unwrap();
+    assert_eq!(lazy.get("a").unwrap().unwrap().as_byte(), Some(1));
+    assert!(matches!(lazy.get("missing"), Err(Error::EndOfFile)));
+
+    let mut data = document();
+    data.push(0xff);
+    let mut lazy = read_shared_lazy::<LE>(Bytes::from(data)).unwrap();
+    assert!(matches!(lazy.get("missing"), Err(Error::TrailingData(1))));
+
+    let data = [
+        0x0a, 0x00, 0x00, 0x09, 0x00, 0x01, b'l', 0x0d, 0, 0, 0, 0, 0x00,
+    ];
+    let mut lazy = read_shared_lazy::<BE>(Bytes::copy_from_slice(&data)).unwrap();
+    let Err(err) = lazy.get("l") else {
+        panic!("expected an error");
+    };
+    assert!(matches!(err, Error::At { offset: 7, .. }));
+}
@@ -0,0 +1,105 @@
+//! Tests for `ScopedReadableValue::leaves`.
+
+use na_nbt::{OwnedCompound, OwnedList, OwnedValue, ScopedReadableValue, read_borrowed};
+use zerocopy::byteorder::{BigEndian as BE, LittleEndian as LE};
+
+fn tree() -> OwnedValue<BE> {
+    let mut item: OwnedCompound<BE> = OwnedCompound::default();
+    item.insert("id", "minecraft:stone");
+    item.insert("Count", 3i8);
+    let mut inventory: OwnedList<BE> = OwnedList::default();
+    inventory.push(item);
+    inventory.push(OwnedCompound::<BE>::default());
+
+    let mut nested: OwnedList<BE> = OwnedList::default();
+    let mut inner: OwnedList<BE> = OwnedList::default();
+    inner.push("deep");
+    nested.push(inner);
+
+    let mut root: OwnedCompound<BE> = OwnedCompound::default();
+    root.insert("Inventory", inventory);
+    root.insert("Pos", OwnedValue::from_vec3(1.0, 2.0, 3.0));
+    root.insert("Nested", nested);
+    root.insert("Data", vec![1i8, 2]);
+    root.insert("Empty", OwnedList::<BE>::default());
+    OwnedValue::Compound(root)
+}
+
+const PATHS: [&str; 7] = [
+    "Inventory[0].id",
+    "Inventory[0].Count",
+    "Pos[0]",
+    "Pos[1]",
+    "Pos[2]",
+    "Nested[0][0]",
+    "Data",
+];
+
+#[test]
+fn test_leaf_paths() {
+    let value = tree();
+    let paths: Vec<String> = value.leaves().map(|(path, _)| path).collect();
+    assert_eq!(paths, PATHS);
+
+    let bytes = value.write_to_vec::<LE>().unwrap();
+    let doc = read_borrowed::<LE>(&bytes).unwrap();
+    let root = doc.root();
+    let paths: Vec<String> = root.leaves().map(|(path, _)| path).collect();
+    assert_eq!(paths, PATHS);
+}
+
+#[test]
+fn test_leaf_values() {
+    let value = tree();
+    let strings: Vec<(String, String)> = value
+        .leaves()
+        .filter_map(|(path, leaf)| Some((path, leaf.as_string()?.decode().into_owned())))
+        .collect();
+    assert_eq!(
+        strings,
+        [
+            ("Inventory[0].id".to_string(), "minecraft:stone".to_string()),
+            ("Nested[0][0]".to_string(), "deep".to_string()),
+        ]
+    );
+
+    let (_, count) = value.leaves().nth(1).unwrap();
+    assert_eq!(count.as_byte(), Some(3));
+}
+
+#[test]
+fn test_leaves_of_root_list_and_scalar() {
+    let mut list: OwnedList<BE> = OwnedList::default();
+    list.push(1i32);
+    list.push(2i32);
+    let list = OwnedValue::List(list);
+    let paths: Vec<String> = list.leaves().map(|(path, _)| path).collect();
+    assert_eq!(paths, ["[0]", "[1]"]);
+
+    assert_eq!(OwnedValue::<BE>::from(1i32).leaves().count(), 0);
+}
+
+#[test]
+fn test_leaf_values_of_borrowed_document() {
+    let bytes = tree().write_to_vec::<BE>().unwrap();
+    let doc = read_borrowed::<BE>(&bytes).unwrap();
+    let root = doc.root();
+
+    let pos: Vec<(String, f64)> = root
+        .leaves()
+        .filter_map(|(path, leaf)| Some((path, leaf.as_double()?)))
+        .collect();
+    assert_eq!(
+        pos,
+        [
+            ("Pos[0]".to_string(), 1.0),
+            ("Pos[1]".to_string(), 2.0),
+            ("Pos[2]".to_string(), 3.0),
+        ]
+    );
+
+    // an array is a single leaf
+    let (path, data) = root.leaves().last().unwrap();
+    assert_eq!(path, "Data");
+    assert_eq!(**data.as_byte_array().unwrap(), [1, 2]);
+}
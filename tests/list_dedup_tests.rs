@@ -0,0 +1,95 @@
+//! Tests for `OwnedList::dedup`, `dedup_by` and `ScopedReadableList::contains`.
+
+use na_nbt::{
+    OwnedCompound, OwnedList, OwnedValue, ScopedReadableList, ScopedReadableValue, read_borrowed,
+};
+use zerocopy::byteorder::{BigEndian as BE, LittleEndian as LE};
+
+fn ints(list: &OwnedList<LE>) -> Vec<i32> {
+    list.iter().map(|v| v.as_int().unwrap()).collect()
+}
+
+#[test]
+fn test_dedup_int_list() {
+    let mut list: OwnedList<LE> = OwnedList::default();
+    for value in [3i32, 3, 3, 1, 2, 2, 3, 3] {
+        list.push(value);
+    }
+    list.dedup();
+    assert_eq!(ints(&list), [3, 1, 2, 3]);
+    assert_eq!(list.len(), 4);
+
+    // still a valid list after shrinking
+    list.push(9i32);
+    let bytes = OwnedValue::List(list).write_to_vec::<BE>().unwrap();
+    let doc = read_borrowed::<BE>(&bytes).unwrap();
+    assert_eq!(doc.root().as_list_scoped().unwrap().len(), 5);
+}
+
+#[test]
+fn test_dedup_float_list_is_bitwise() {
+    let mut list: OwnedList<BE> = OwnedList::default();
+    for value in [0.0f32, -0.0, -0.0, f32::NAN, f32::NAN] {
+        list.push(value);
+    }
+    list.dedup();
+    assert_eq!(list.len(), 3);
+}
+
+#[test]
+fn test_dedup_compound_list_ignores_key_order() {
+    let mut a: OwnedCompound<BE> = OwnedCompound::default();
+    a.insert("x", 1i32);
+    a.insert("y", 2i32);
+    let mut b: OwnedCompound<BE> = OwnedCompound::default();
+    b.insert("y", 2i32);
+    b.insert("x", 1i32);
+    let mut c: OwnedCompound<BE> = OwnedCompound::default();
+    c.insert("x", 3i32);
+
+    let mut list: OwnedList<BE> = OwnedList::default();
+    list.push(a);
+    list.push(b);
+    list.push(c);
+    list.dedup();
+
+    assert_eq!(list.len(), 2);
+    assert_eq!(list.get(1).unwrap().get("x").unwrap().as_int(), Some(3));
+}
+
+#[test]
+fn test_dedup_by() {
+    let mut list: OwnedList<LE> = OwnedList::default();
+    for value in ["a", "ab", "b", "bcd", "c"] {
+        list.push(value);
+    }
+    // collapse runs sharing a first letter
+    list.dedup_by(|a, b| {
+        a.as_string().unwrap().decode().chars().next()
+            == b.as_string().unwrap().decode().chars().next()
+    });
+    let rest: Vec<String> = list
+        .iter()
+        .map(|v| v.as_string().unwrap().decode().into_owned())
+        .collect();
+    assert_eq!(rest, ["a", "b", "c"]);
+}
+
+#[test]
+fn test_contains_string_list() {
+    let mut list: OwnedList<BE> = OwnedList::default();
+    list.push("stone");
+    list.push("dirt");
+
+    assert!(list.contains(&OwnedValue::<BE>::from("stone")));
+    assert!(list.contains(&OwnedValue::<LE>::from("dirt")));
+    assert!(!list.contains(&OwnedValue::<BE>::from("sand")));
+    assert!(!list.contains(&OwnedValue::<BE>::from(1i32)));
+
+    let bytes = OwnedValue::List(list).write_to_vec::<BE>().unwrap();
+    let doc = read_borrowed::<BE>(&bytes).unwrap();
+    let borrowed = doc.root();
+    let borrowed = borrowed.as_list_scoped().unwrap();
+    assert!(borrowed.contains(&OwnedValue::<BE>::from("dirt")));
+    assert!(!borrowed.contains(&OwnedValue::<BE>::from("gravel")));
+}
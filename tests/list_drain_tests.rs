@@ -0,0 +1,124 @@
+//! Tests for `OwnedList::drain` and `OwnedList::drain_`.
+
+use na_nbt::{OwnedCompound, OwnedList, OwnedValue, Tag, read_owned};
+use zerocopy::byteorder::BigEndian as BE;
+
+fn entities(n: i32) -> OwnedList<BE> {
+    let mut list = OwnedList::default();
+    for i in 0..n {
+        let mut entity: OwnedCompound<BE> = OwnedCompound::default();
+        entity.insert("id", "minecraft:zombie");
+        entity.insert("n", i);
+        list.push(entity);
+    }
+    list
+}
+
+fn ns(list: &OwnedList<BE>) -> Vec<i32> {
+    list.iter()
+        .map(|v| v.get("n").unwrap().as_int().unwrap())
+        .collect()
+}
+
+#[test]
+fn test_drain_middle() {
+    let mut list = entities(6);
+    let drained: Vec<OwnedValue<BE>> = list.drain(2..4).collect();
+    assert_eq!(drained.len(), 2);
+    assert_eq!(drained[0].get("n").unwrap().as_int(), Some(2));
+    assert_eq!(drained[1].get("n").unwrap().as_int(), Some(3));
+    assert_eq!(ns(&list), [0, 1, 4, 5]);
+    assert_eq!(list.element_tag(), Tag::Compound);
+}
+
+#[test]
+fn test_drain_in_batches() {
+    let mut list = entities(10);
+    let mut batches = Vec::new();
+    while !list.is_empty() {
+        let n = list.len().min(4);
+        let batch: Vec<i32> = list
+            .drain(..n)
+            .map(|v| v.get("n").unwrap().as_int().unwrap())
+            .collect();
+        batches.push(batch);
+    }
+    assert_eq!(batches, [vec![0, 1, 2, 3], vec![4, 5, 6, 7], vec![8, 9]]);
+    assert_eq!(list.element_tag(), Tag::End);
+}
+
+#[test]
+fn test_full_drain_resets_element_type() {
+    let mut list = entities(3);
+    assert_eq!(list.drain(..).len(), 3);
+    assert!(list.is_empty());
+    assert_eq!(list.element_tag(), Tag::End);
+
+    // the emptied list takes a new element type
+    list.push(1i32);
+    assert_eq!(list.element_tag(), Tag::Int);
+    let bytes = OwnedValue::List(list).write_to_vec::<BE>().unwrap();
+    let read: OwnedValue<BE> = read_owned::<BE, BE>(&bytes).unwrap();
+    assert_eq!(read.as_list().unwrap().get(0).unwrap().as_int(), Some(1));
+}
+
+#[test]
+fn test_empty_drain_keeps_list() {
+    let mut list = entities(2);
+    assert_eq!(list.drain(1..1).len(), 0);
+    assert_eq!(ns(&list), [0, 1]);
+    assert_eq!(list.element_tag(), Tag::Compound);
+}
+
+#[test]
+fn test_unconsumed_drain_removes_elements() {
+    let mut list = entities(5);
+    let mut drain = list.drain(1..=3);
+    assert_eq!(drain.next().unwrap().get("n").unwrap().as_int(), Some(1));
+    drop(drain);
+    assert_eq!(ns(&list), [0, 4]);
+}
+
+#[test]
+fn test_drain_then_write() {
+    let mut list: OwnedList<BE> = OwnedList::default();
+    for s in ["a", "b", "c"] {
+        list.push(s);
+    }
+    let drained: Vec<OwnedValue<BE>> = list.drain(..1).collect();
+    assert_eq!(drained[0].as_string().unwrap().decode(), "a");
+
+    let bytes = OwnedValue::List(list).write_to_vec::<BE>().unwrap();
+    let read: OwnedValue<BE> = read_owned::<BE, BE>(&bytes).unwrap();
+    let read = read.as_list().unwrap();
+    assert_eq!(read.len(), 2);
+    assert_eq!(read.get(1).unwrap().as_string().unwrap().decode(), "c");
+}
+
+#[test]
+fn test_typed_drain() {
+    let mut list: OwnedList<BE> = OwnedList::default();
+    for v in [1.5f64, 2.5, 3.5] {
+        list.push(v);
+    }
+    assert_eq!(list.drain_::<f32>(..), None);
+    assert_eq!(list.len(), 3);
+    assert_eq!(list.drain_::<f64>(1..), Some(vec![2.5, 3.5]));
+    assert_eq!(list.drain_::<f64>(..), Some(vec![1.5]));
+    assert_eq!(list.element_tag(), Tag::End);
+}
+
+#[test]
+#[should_panic]
+fn test_drain_out_of_bounds_panics() {
+    let mut list = entities(2);
+    list.drain(1..3);
+}
+
+#[test]
+#[should_panic]
+fn test_drain_reversed_range_panics() {
+    let mut list = entities(3);
+    #[allow(clippy::reversed_empty_ranges)]
+    list.drain(2..1);
+}
@@ -0,0 +1,85 @@
+//! Tests for `OwnedList::remove_first_where`.
+
+use na_nbt::{OwnedCompound, OwnedList, Tag};
+use zerocopy::byteorder::BigEndian as BE;
+
+fn item(id: &str, count: i8) -> OwnedCompound<BE> {
+    let mut item = OwnedCompound::default();
+    item.insert("id", id);
+    item.insert("Count", count);
+    item
+}
+
+fn ids(list: &OwnedList<BE>) -> Vec<String> {
+    list.iter()
+        .map(|item| {
+            let id = item.get("id").unwrap();
+            id.as_string().unwrap().decode().into_owned()
+        })
+        .collect()
+}
+
+#[test]
+fn test_removes_first_match_and_keeps_order() {
+    let mut list = OwnedList::<BE>::default();
+    list.push(item("a", 1));
+    list.push(item("b", 2));
+    list.push(item("c", 3));
+    list.push(item("b", 4));
+
+    let removed = list
+        .remove_first_where(|item| item.get("id").unwrap().as_string().unwrap().decode() == "b")
+        .unwrap();
+    assert_eq!(removed.get("Count").unwrap().as_byte(), Some(2));
+    assert_eq!(ids(&list), ["a", "c", "b"]);
+}
+
+#[test]
+fn test_no_match_leaves_list_untouched() {
+    let mut list = OwnedList::<BE>::default();
+    list.push(item("a", 1));
+    list.push(item("b", 2));
+    assert!(
+        list.remove_first_where(|item| item.get("Count").unwrap().as_byte() == Some(9))
+            .is_none()
+    );
+    assert_eq!(ids(&list), ["a", "b"]);
+}
+
+#[test]
+fn test_primitive_list() {
+    let mut list = OwnedList::<BE>::default();
+    for value in [5i32, -1, 7, -2] {
+        list.push(value);
+    }
+    let removed = list.remove_first_where(|value| value.as_int().unwrap() < 0);
+    assert_eq!(removed.unwrap().as_int(), Some(-1));
+    let rest: Vec<_> = list.iter().map(|value| value.as_int().unwrap()).collect();
+    assert_eq!(rest, [5, 7, -2]);
+}
+
+#[test]
+fn test_stops_calling_after_match() {
+    let mut list = OwnedList::<BE>::default();
+    for value in [1i16, 2, 3, 4] {
+        list.push(value);
+    }
+    let mut calls = 0;
+    list.remove_first_where(|value| {
+        calls += 1;
+        value.as_short() == Some(2)
+    });
+    assert_eq!(calls, 2);
+    assert_eq!(list.len(), 3);
+}
+
+#[test]
+fn test_remove_last_element() {
+    let mut list = OwnedList::<BE>::default();
+    list.push("only");
+    assert!(list.remove_first_where(|_| true).is_some());
+    assert!(list.is_empty());
+    assert!(list.remove_first_where(|_| true).is_none());
+    list.push(1i8);
+    assert_eq!(list.element_tag(), Tag::Byte);
+}
@@ -0,0 +1,136 @@
+//! Tests for `OwnedList::sort_by_key_field`.
+
+use na_nbt::{Error, OwnedCompound, OwnedList, OwnedValue};
+use zerocopy::byteorder::BigEndian as BE;
+
+fn item(name: &str, field: Option<OwnedValue<BE>>) -> OwnedCompound<BE> {
+    let mut item = OwnedCompound::default();
+    item.insert("name", name);
+    if let Some(field) = field {
+        item.insert("k", field);
+    }
+    item
+}
+
+fn names(list: &OwnedList<BE>) -> Vec<String> {
+    list.iter()
+        .map(|item| {
+            item.get("name")
+                .unwrap()
+                .as_string()
+                .unwrap()
+                .decode()
+                .into_owned()
+        })
+        .collect()
+}
+
+#[test]
+fn sorts_numbers_across_tag_types() {
+    let mut list = OwnedList::<BE>::default();
+    list.push(item("long", Some(5i64.into())));
+    list.push(item("byte", Some((-3i8).into())));
+    list.push(item("double", Some(2.5f64.into())));
+    list.push(item("short", Some(2i16.into())));
+    list.push(item("float", Some((-10.0f32).into())));
+
+    list.sort_by_key_field("k").unwrap();
+    assert_eq!(names(&list), ["float", "byte", "short", "double", "long"]);
+}
+
+#[test]
+fn sorts_strings_lexically_and_large_longs_exactly() {
+    let mut list = OwnedList::<BE>::default();
+    list.push(item("b", Some("pear".into())));
+    list.push(item("a", Some("apple".into())));
+    list.push(item("c", Some("Zebra".into())));
+    list.sort_by_key_field("k").unwrap();
+    assert_eq!(names(&list), ["c", "a", "b"]);
+
+    // too close together to tell apart as f64
+    let mut list = OwnedList::<BE>::default();
+    list.push(item("big", Some(i64::MAX.into())));
+    list.push(item("smaller", Some((i64::MAX - 1).into())));
+    list.sort_by_key_field("k").unwrap();
+    assert_eq!(names(&list), ["smaller", "big"]);
+}
+
+#[test]
+fn sorts_mixed_large_longs_and_doubles_exactly() {
+    let mut list = OwnedList::<BE>::default();
+    list.push(item("nan", Some(f64::NAN.into())));
+    list.push(item("two_63", Some(9_223_372_036_854_775_808.0f64.into())));
+    list.push(item("zero", Some(0.0f64.into())));
+    list.push(item("max_long", Some(i64::MAX.into())));
+    list.push(item(
+        "min_double",
+        Some((-9_223_372_036_854_775_808.0f64).into()),
+    ));
+    list.push(item(
+        "below_max",
+        Some(9_223_372_036_854_774_784.0f64.into()),
+    ));
+    list.push(item("neg_zero", Some((-0.0f64).into())));
+    list.push(item("max_minus_one", Some((i64::MAX - 1).into())));
+    list.push(item("min_long", Some(i64::MIN.into())));
+    list.push(item("zero_long", Some(0i64.into())));
+    list.push(item("neg_inf", Some(f64::NEG_INFINITY.into())));
+    list.push(item("half", Some(0.5f64.into())));
+
+    list.sort_by_key_field("k").unwrap();
+    assert_eq!(
+        names(&list),
+        [
+            "neg_inf",
+            "min_long",
+            "min_double",
+            "zero_long",
+            "neg_zero",
+            "zero",
+            "half",
+            "below_max",
+            "max_minus_one",
+            "max_long",
+            "two_63",
+            "nan",
+        ]
+    );
+}
+
+#[test]
+fn stable_with_mixed_and_missing_fields() {
+    let mut list = OwnedList::<BE>::default();
+    list.push(item("missing1", None));
+    list.push(item("s", Some("x".into())));
+    list.push(item("one_a", Some(1i32.into())));
+    list.push(item("list", Some(OwnedList::<BE>::default().into())));
+    list.push(item("one_b", Some(1i32.into())));
+    list.push(item("missing2", None));
+    list.push(item("zero", Some(0i32.into())));
+
+    list.sort_by_key_field("k").unwrap();
+    assert_eq!(
+        names(&list),
+        [
+            "zero", "one_a", "one_b", "s", "missing1", "list", "missing2"
+        ]
+    );
+
+    // the sorted list is still a valid list of compounds
+    let bytes = OwnedValue::List(list).write_to_vec::<BE>().unwrap();
+    let value = na_nbt::read_owned::<BE, BE>(&bytes).unwrap();
+    assert_eq!(value.as_list().unwrap().len(), 7);
+}
+
+#[test]
+fn non_compound_list_is_rejected() {
+    let mut list = OwnedList::<BE>::default();
+    list.push(3i32);
+    list.push(1i32);
+    let err = list.sort_by_key_field("k").err().unwrap();
+    assert!(matches!(err, Error::TagMismatch(10, 3)));
+    assert_eq!(list.get(0).unwrap().as_int(), Some(3));
+
+    let mut empty = OwnedList::<BE>::default();
+    assert!(empty.sort_by_key_field("k").is_ok());
+}
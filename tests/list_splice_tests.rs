@@ -0,0 +1,105 @@
+//! Tests for `OwnedList::splice`.
+
+use na_nbt::{Error, OwnedList, OwnedValue, Tag, read_owned};
+use zerocopy::byteorder::BigEndian as BE;
+
+fn strings(values: &[&str]) -> OwnedList<BE> {
+    let mut list = OwnedList::default();
+    for value in values {
+        list.push(*value);
+    }
+    list
+}
+
+fn decode(list: &OwnedList<BE>) -> Vec<String> {
+    list.iter()
+        .map(|v| v.as_string().unwrap().decode().into_owned())
+        .collect()
+}
+
+#[test]
+fn test_splice_middle_range() {
+    let mut list = strings(&["a", "b", "c", "d", "e"]);
+    let removed: Vec<String> = list
+        .splice(1..4, ["x", "y"])
+        .unwrap()
+        .map(|v| v.as_string().unwrap().decode().into_owned())
+        .collect();
+    assert_eq!(removed, ["b", "c", "d"]);
+    assert_eq!(decode(&list), ["a", "x", "y", "e"]);
+
+    // the list still round-trips
+    let bytes = OwnedValue::List(list).write_to_vec::<BE>().unwrap();
+    let value = read_owned::<BE, BE>(&bytes).unwrap();
+    assert_eq!(value.as_list().unwrap().len(), 4);
+}
+
+#[test]
+fn test_splice_insert_at_end() {
+    let mut list = strings(&["a", "b"]);
+    let removed = list.splice(2..2, vec!["c", "d", "e"]).unwrap();
+    assert_eq!(removed.count(), 0);
+    assert_eq!(decode(&list), ["a", "b", "c", "d", "e"]);
+
+    let mut list = strings(&["b"]);
+    list.splice(..0, ["a"]).unwrap();
+    assert_eq!(decode(&list), ["a", "b"]);
+}
+
+#[test]
+fn test_splice_mismatch_leaves_list_unchanged() {
+    let mut list = strings(&["a", "b", "c"]);
+    let replacement: Vec<OwnedValue<BE>> = vec!["x".into(), 1i32.into()];
+    let err = list.splice(1..2, replacement).err().unwrap();
+    assert!(matches!(err, Error::TagMismatch(8, 3)));
+    assert_eq!(decode(&list), ["a", "b", "c"]);
+
+    let err = list.splice(0..0, [1i8]).err().unwrap();
+    assert!(matches!(err, Error::TagMismatch(8, 1)));
+    assert_eq!(decode(&list), ["a", "b", "c"]);
+}
+
+#[test]
+fn test_splice_whole_list_changes_type() {
+    let mut list = strings(&["a", "b"]);
+    list.splice(.., [1i32, 2, 3]).unwrap();
+    assert_eq!(list.element_tag(), Tag::Int);
+    assert_eq!(list.get(2).unwrap().as_int(), Some(3));
+
+    list.splice(.., Vec::<i32>::new()).unwrap();
+    assert!(list.is_empty());
+    assert_eq!(list.element_tag(), Tag::End);
+
+    let mut list = OwnedList::<BE>::default();
+    list.splice(.., [1i64]).unwrap();
+    assert_eq!(list.element_tag(), Tag::Long);
+}
+
+#[test]
+#[should_panic]
+fn test_splice_out_of_bounds_panics() {
+    let mut list = strings(&["a"]);
+    let _ = list.splice(1..3, ["b"]);
+}
+
+#[test]
+fn test_splice_owning_elements() {
+    let mut list = OwnedList::<BE>::default();
+    for name in ["a", "b", "c"] {
+        let mut inner = OwnedList::<BE>::default();
+        inner.push(name);
+        list.push(inner);
+    }
+    let replacement: Vec<OwnedList<BE>> = (0..2)
+        .map(|_| {
+            let mut inner = OwnedList::default();
+            inner.push("new");
+            inner
+        })
+        .collect();
+    let removed: Vec<_> = list.splice(..2, replacement).unwrap().collect();
+    assert_eq!(removed.len(), 2);
+    assert_eq!(list.len(), 3);
+    let last = list.get(2).unwrap();
+    assert_eq!(last.get(0).unwrap().as_string().unwrap().decode(), "c");
+}
@@ -0,0 +1,100 @@
+//! Tests for `OwnedList::split_off` and `OwnedList::append`.
+
+use na_nbt::{Error, OwnedList, OwnedValue, Tag, read_owned};
+use zerocopy::byteorder::BigEndian as BE;
+
+fn strings(values: &[&str]) -> OwnedList<BE> {
+    let mut list = OwnedList::default();
+    for value in values {
+        list.push(*value);
+    }
+    list
+}
+
+fn decode(list: &OwnedList<BE>) -> Vec<String> {
+    list.iter()
+        .map(|v| v.as_string().unwrap().decode().into_owned())
+        .collect()
+}
+
+#[test]
+fn test_split_off() {
+    let mut list = strings(&["a", "b", "c", "d"]);
+    let tail = list.split_off(3);
+    assert_eq!(decode(&list), ["a", "b", "c"]);
+    assert_eq!(decode(&tail), ["d"]);
+    assert_eq!(tail.element_tag(), Tag::String);
+
+    let rest = list.split_off(0);
+    assert!(list.is_empty());
+    assert_eq!(list.element_tag(), Tag::End);
+    assert_eq!(decode(&rest), ["a", "b", "c"]);
+
+    let empty = list.split_off(0);
+    assert!(empty.is_empty());
+}
+
+#[test]
+fn test_chunks_round_trip() {
+    let mut list = strings(&["1", "2", "3", "4", "5", "6", "7"]);
+    let mut chunks = Vec::new();
+    while list.len() > 3 {
+        let tail = list.split_off(3);
+        chunks.push(core::mem::replace(&mut list, tail));
+    }
+    chunks.push(list);
+    assert_eq!(chunks.len(), 3);
+
+    let mut joined: OwnedList<BE> = OwnedList::default();
+    for mut chunk in chunks {
+        joined.append(&mut chunk).unwrap();
+        assert!(chunk.is_empty());
+    }
+    assert_eq!(decode(&joined), ["1", "2", "3", "4", "5", "6", "7"]);
+
+    let bytes = OwnedValue::List(joined).write_to_vec::<BE>().unwrap();
+    let read: OwnedValue<BE> = read_owned::<BE, BE>(&bytes).unwrap();
+    assert_eq!(read.as_list().unwrap().len(), 7);
+}
+
+#[test]
+fn test_append_adopts_type() {
+    let mut list: OwnedList<BE> = OwnedList::default();
+    let mut longs: OwnedList<BE> = OwnedList::default();
+    longs.push(1i64);
+    longs.push(2i64);
+    list.append(&mut longs).unwrap();
+    assert_eq!(list.element_tag(), Tag::Long);
+    assert_eq!(list.len(), 2);
+    assert_eq!(longs.element_tag(), Tag::End);
+
+    // the emptied list can be reused for any type
+    longs.push("x");
+    assert_eq!(longs.element_tag(), Tag::String);
+}
+
+#[test]
+fn test_append_empty_other() {
+    let mut list = strings(&["a"]);
+    let mut empty: OwnedList<BE> = OwnedList::default();
+    list.append(&mut empty).unwrap();
+    assert_eq!(decode(&list), ["a"]);
+}
+
+#[test]
+fn test_append_mismatch_is_rejected() {
+    let mut list = strings(&["a"]);
+    let mut ints: OwnedList<BE> = OwnedList::default();
+    ints.push(1i32);
+    let err = list.append(&mut ints).err().unwrap();
+    assert!(matches!(err, Error::TagMismatch(8, 3)));
+    assert_eq!(decode(&list), ["a"]);
+    assert_eq!(ints.len(), 1);
+}
+
+#[test]
+#[should_panic]
+fn test_split_off_out_of_bounds_panics() {
+    let mut list = strings(&["a"]);
+    list.split_off(2);
+}
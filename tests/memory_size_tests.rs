@@ -0,0 +1,56 @@
+//! Tests for `memory_size`.
+
+use std::mem::size_of;
+
+use na_nbt::{OwnedCompound, OwnedList, OwnedValue};
+use zerocopy::byteorder::BigEndian as BE;
+
+#[test]
+fn test_scalars_take_no_heap() {
+    assert_eq!(
+        OwnedValue::<BE>::from(1i32).memory_size(),
+        size_of::<OwnedValue<BE>>()
+    );
+    assert_eq!(
+        OwnedValue::<BE>::End.memory_size(),
+        size_of::<OwnedValue<BE>>()
+    );
+}
+
+#[test]
+fn test_grows_with_elements() {
+    let mut list: OwnedList<BE> = OwnedList::default();
+    let mut last = list.memory_size();
+    for i in 0..50 {
+        list.push(format!("string number {i}").as_str());
+        let size = list.memory_size();
+        assert!(size > last);
+        last = size;
+    }
+    // every string's buffer is counted, not just the list's own buffer
+    assert!(last >= 50 * "string number 00".len());
+
+    let mut compound: OwnedCompound<BE> = OwnedCompound::default();
+    let empty = compound.memory_size();
+    compound.insert("strings", list);
+    compound.insert("bytes", vec![0i8; 1000]);
+    assert!(compound.memory_size() >= empty + last + 1000);
+
+    let compound_size = compound.memory_size();
+    let value = OwnedValue::Compound(compound);
+    assert_eq!(
+        value.memory_size(),
+        size_of::<OwnedValue<BE>>() + compound_size - size_of::<OwnedCompound<BE>>()
+    );
+}
+
+#[test]
+fn test_nested_containers() {
+    let mut inner: OwnedCompound<BE> = OwnedCompound::default();
+    inner.insert("data", vec![0i8; 4096]);
+    let mut list: OwnedList<BE> = OwnedList::default();
+    list.push(inner);
+    let mut outer: OwnedList<BE> = OwnedList::default();
+    outer.push(list);
+    assert!(OwnedValue::List(outer).memory_size() >= 4096);
+}
@@ -0,0 +1,78 @@
+#![cfg(feature = "mmap")]
+
+use std::path::PathBuf;
+
+use na_nbt::{
+    Error, MmapDocument, OwnedCompound, OwnedValue, ScopedReadableValue, read_borrowed, read_mmap,
+    values_equal,
+};
+use zerocopy::byteorder::BigEndian as BE;
+
+fn temp_file(name: &str, contents: &[u8]) -> PathBuf {
+    let path = std::env::temp_dir().join(format!("na_nbt_mmap_{}_{name}", std::process::id()));
+    std::fs::write(&path, contents).unwrap();
+    path
+}
+
+fn sample() -> Vec<u8> {
+    let mut root = OwnedCompound::<BE>::default();
+    root.insert("name", "Steve");
+    root.insert("level", 42i32);
+    root.insert("inventory", vec![1i8, 2, 3]);
+    OwnedValue::Compound(root).write_to_vec::<BE>().unwrap()
+}
+
+#[test]
+fn mmap_matches_borrowed() {
+    let bytes = sample();
+    let path = temp_file("matches", &bytes);
+
+    let doc = unsafe { read_mmap::<BE>(&path) }.unwrap();
+    assert_eq!(doc.as_bytes(), bytes.as_slice());
+    let borrowed = read_borrowed::<BE>(&bytes).unwrap();
+    assert!(values_equal(&doc.root(), &borrowed.root()));
+    assert_eq!(doc.root().get("level").and_then(|v| v.as_int()), Some(42));
+
+    drop(doc);
+    std::fs::remove_file(path).unwrap();
+}
+
+#[test]
+fn mmap_document_can_move() {
+    let path = temp_file("move", &sample());
+
+    let docs: Vec<MmapDocument<BE>> = (0..2)
+        .map(|_| unsafe { read_mmap::<BE>(&path) }.unwrap())
+        .collect();
+    let handle = std::thread::spawn(move || {
+        docs.iter()
+            .map(|doc| doc.root().node_count())
+            .collect::<Vec<_>>()
+    });
+    assert_eq!(handle.join().unwrap(), vec![4, 4]);
+
+    std::fs::remove_file(path).unwrap();
+}
+
+#[test]
+fn mmap_errors() {
+    let missing = std::env::temp_dir().join("na_nbt_mmap_does_not_exist.nbt");
+    assert!(matches!(
+        unsafe { read_mmap::<BE>(&missing) },
+        Err(Error::IO(_))
+    ));
+
+    let bytes = sample();
+    let path = temp_file("truncated", &bytes[..bytes.len() - 2]);
+    assert!(unsafe { read_mmap::<BE>(&path) }.is_err());
+    std::fs::remove_file(path).unwrap();
+}
+
+#[test]
+fn mmap_root_name() {
+    let path = temp_file("root_name", &[0x0a, 0x00, 0x02, b'h', b'i', 0x00]);
+    let doc = unsafe { read_mmap::<BE>(&path) }.unwrap();
+    assert_eq!(doc.root_name().unwrap().decode(), "hi");
+    drop(doc);
+    std::fs::remove_file(path).unwrap();
+}
@@ -1,4 +1,4 @@
-use na_nbt::{OwnedList, OwnedCompound, OwnedValue};
+use na_nbt::{MutableValue, OwnedCompound, OwnedList, OwnedValue, ScopedReadableValue, Tag};
 use zerocopy::byteorder::BigEndian as BE;
 
 #[test]
@@ -130,3 +130,62 @@ fn test_list_remove_oob() {
     list.remove(0);
 }
 
+
+#[test]
+fn test_transform_scalars_negates_ints() {
+    // { a: 1, nested: { b: 2, list: [ {c: 3}, {c: 4} ], ints: [5, 6] }, name: "x", arr: IntArray[7] }
+    let mut inner_a = OwnedCompound::<BE>::default();
+    inner_a.insert("c", 3i32);
+    let mut inner_b = OwnedCompound::<BE>::default();
+    inner_b.insert("c", 4i32);
+    let mut list = OwnedList::<BE>::default();
+    list.push(inner_a);
+    list.push(inner_b);
+    let mut ints = OwnedList::<BE>::default();
+    ints.push(5i32);
+    ints.push(6i32);
+    let mut nested = OwnedCompound::<BE>::default();
+    nested.insert("b", 2i32);
+    nested.insert("list", list);
+    nested.insert("ints", ints);
+    let mut root = OwnedCompound::<BE>::default();
+    root.insert("a", 1i32);
+    root.insert("nested", nested);
+    root.insert("name", "x");
+    root.insert("arr", [zerocopy::byteorder::I32::<BE>::new(7)]);
+    let mut root = OwnedValue::Compound(root);
+
+    let before = root.stats();
+    let mut visited = Vec::new();
+    root.transform_scalars(|value| {
+        visited.push(value.tag_id());
+        if let MutableValue::Int(value) = value {
+            value.set(-value.get());
+        }
+    });
+
+    // containers are never passed to the callback
+    assert_eq!(
+        visited,
+        [Tag::Int, Tag::Int, Tag::Int, Tag::Int, Tag::Int, Tag::Int, Tag::String, Tag::IntArray]
+    );
+    assert_eq!(root.stats(), before);
+
+    let int = |path: &[&str], index: Option<usize>| {
+        let mut value = root.get(path[0]).unwrap();
+        for key in &path[1..] {
+            value = value.get(*key).unwrap();
+        }
+        match index {
+            Some(index) => value.get(index).unwrap().as_int(),
+            None => value.as_int(),
+        }
+    };
+    assert_eq!(int(&["a"], None), Some(-1));
+    assert_eq!(int(&["nested", "b"], None), Some(-2));
+    assert_eq!(int(&["nested", "ints"], Some(1)), Some(-6));
+    let list = root.get("nested").unwrap().get("list").unwrap();
+    assert_eq!(list.get(1).unwrap().get("c").unwrap().as_int(), Some(-4));
+    assert_eq!(root.get("arr").unwrap().as_int_array().unwrap()[0].get(), 7);
+    assert_eq!(root.get("name").unwrap().as_string().unwrap().decode(), "x");
+}
@@ -3,6 +3,10 @@ use zerocopy::byteorder::BigEndian as BE;
 use zerocopy::byteorder::{I32, I64};
 
 #[test]
+#[cfg_attr(
+    all(feature = "strict", debug_assertions),
+    should_panic(expected = "type mismatch")
+)]
 fn test_mutable_value_accessors_and_mutators() {
     let mut data = vec![0x0A, 0x00, 0x00]; // Root compound
 
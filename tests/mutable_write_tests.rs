@@ -1,6 +1,8 @@
 //! Tests for mutable::write functions
 
-use na_nbt::{read_owned, OwnedValue, ImmutableValue};
+use na_nbt::{
+    ImmutableValue, OwnedCompound, OwnedList, OwnedValue, ScopedReadableValue, read_owned,
+};
 use zerocopy::byteorder::{BigEndian as BE, LittleEndian as LE};
 
 // ==================== Helper Functions ====================
@@ -558,3 +560,53 @@ fn test_write_empty_list() {
         panic!("expected list");
     }
 }
+
+// ==================== Pre-sized Output ====================
+
+fn create_large_owned() -> OwnedValue<BE> {
+    let mut root = OwnedCompound::<BE>::default();
+    for i in 0..200 {
+        let mut entry = OwnedCompound::<BE>::default();
+        entry.insert("id", format!("minecraft:item_{i}").as_str());
+        entry.insert("count", i as i8);
+        let mut lore = OwnedList::<BE>::default();
+        lore.push("line");
+        entry.insert("lore", lore);
+        entry.insert("bits", vec![zerocopy::byteorder::I64::<BE>::new(i); 4]);
+        root.insert(&format!("slot{i}"), entry);
+    }
+    OwnedValue::Compound(root)
+}
+
+#[test]
+fn test_write_to_vec_is_exactly_sized() {
+    let value = create_large_owned();
+
+    let be = value.write_to_vec::<BE>().unwrap();
+    assert_eq!(be.len(), value.serialized_len());
+    assert_eq!(be.capacity(), be.len());
+
+    let le = value.write_to_vec::<LE>().unwrap();
+    assert_eq!(le.len(), value.serialized_len());
+    assert_eq!(le.capacity(), le.len());
+
+    let parsed = read_owned::<BE, BE>(&be).unwrap();
+    assert_eq!(parsed.write_to_vec::<BE>().unwrap(), be);
+}
+
+#[test]
+fn test_write_to_vec_root_arrays() {
+    use zerocopy::byteorder::{I32, I64};
+
+    let ints = OwnedValue::<BE>::IntArray(vec![I32::new(1), I32::new(-2)].into());
+    let bytes = ints.write_to_vec::<LE>().unwrap();
+    assert_eq!(bytes.len(), 1 + 2 + 4 + 8);
+    assert_eq!(bytes.len(), ints.serialized_len());
+    assert_eq!(&bytes[7..], [1, 0, 0, 0, 0xFE, 0xFF, 0xFF, 0xFF]);
+
+    let longs = OwnedValue::<BE>::LongArray(vec![I64::new(3)].into());
+    let bytes = longs.write_to_vec::<BE>().unwrap();
+    assert_eq!(bytes.len(), 1 + 2 + 4 + 8);
+    assert_eq!(bytes.len(), longs.serialized_len());
+    assert_eq!(read_owned::<BE, BE>(&bytes).unwrap().as_long_array().unwrap()[0].get(), 3);
+}
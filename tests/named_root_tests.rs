@@ -0,0 +1,94 @@
+//! Tests for writing a named root with `write_to_vec_named` and
+//! `BorrowedDocument::write_to_vec`.
+
+use na_nbt::{
+    Error, OwnedCompound, OwnedValue, ScopedReadableValue, read_borrowed, read_owned_named,
+};
+use zerocopy::byteorder::{BigEndian as BE, LittleEndian as LE};
+
+/// A `level.dat`-like document: a compound named "Data".
+fn level_dat() -> Vec<u8> {
+    let mut data: OwnedCompound<BE> = OwnedCompound::default();
+    data.insert("LevelName", "world");
+    data.insert("Time", 1200i64);
+    data.insert("Seeds", vec![1i8, 2, 3]);
+    OwnedValue::Compound(data)
+        .write_to_vec_named::<BE>("Data")
+        .unwrap()
+}
+
+#[test]
+fn test_named_header() {
+    let bytes = level_dat();
+    assert_eq!(bytes[..7], [10, 0, 4, b'D', b'a', b't', b'a']);
+
+    let le = read_borrowed::<BE>(&bytes)
+        .unwrap()
+        .write_to_vec::<LE>()
+        .unwrap();
+    assert_eq!(le[..7], [10, 4, 0, b'D', b'a', b't', b'a']);
+}
+
+#[test]
+fn test_owned_round_trip() {
+    let bytes = level_dat();
+    let (name, value) = read_owned_named::<BE, BE>(&bytes).unwrap();
+    assert_eq!(name, "Data");
+    assert_eq!(value.write_to_vec_named::<BE>(&name).unwrap(), bytes);
+
+    // stored in the other byte order, still written back identically
+    let (name, value) = read_owned_named::<BE, LE>(&bytes).unwrap();
+    assert_eq!(value.write_to_vec_named::<BE>(&name).unwrap(), bytes);
+}
+
+#[test]
+fn test_borrowed_round_trip() {
+    let bytes = level_dat();
+    let doc = read_borrowed::<BE>(&bytes).unwrap();
+    assert_eq!(doc.write_to_vec::<BE>().unwrap(), bytes);
+
+    let le = doc.write_to_vec::<LE>().unwrap();
+    let doc = read_borrowed::<LE>(&le).unwrap();
+    assert_eq!(doc.root_name().unwrap().decode(), "Data");
+    assert_eq!(doc.write_to_vec::<BE>().unwrap(), bytes);
+}
+
+#[test]
+fn test_borrowed_value_named() {
+    let bytes = level_dat();
+    let doc = read_borrowed::<BE>(&bytes).unwrap();
+    let renamed = doc.root().write_to_vec_named::<BE>("Other").unwrap();
+    assert_eq!(
+        read_borrowed::<BE>(&renamed)
+            .unwrap()
+            .root_name()
+            .unwrap()
+            .decode(),
+        "Other"
+    );
+
+    // an empty name is what write_to_vec writes
+    assert_eq!(
+        doc.root().write_to_vec_named::<BE>("").unwrap(),
+        doc.root().write_to_vec::<BE>().unwrap()
+    );
+}
+
+#[test]
+fn test_end_root_ignores_name() {
+    let value = OwnedValue::<BE>::End;
+    assert_eq!(value.write_to_vec_named::<BE>("ignored").unwrap(), [0]);
+    let doc = read_borrowed::<BE>(&[0]).unwrap();
+    assert_eq!(doc.write_to_vec::<LE>().unwrap(), [0]);
+}
+
+#[test]
+fn test_name_too_long() {
+    let value = OwnedValue::<BE>::from(1i32);
+    let name = "x".repeat(65536);
+    assert!(matches!(
+        value.write_to_vec_named::<BE>(&name),
+        Err(Error::Message(_))
+    ));
+    assert!(value.write_to_vec_named::<BE>(&name[1..]).is_ok());
+}
@@ -0,0 +1,135 @@
+//! Tests for the bulk array conversions between native integers and
+//! stored byte orders.
+
+use na_nbt::{OwnedCompound, OwnedValue, VecViewOwn, read_borrowed, read_owned};
+use zerocopy::byteorder::{BigEndian as BE, I32, I64, LittleEndian as LE};
+
+fn ints() -> Vec<i32> {
+    (0..1000).map(|i: i32| i.wrapping_mul(-104_729)).collect()
+}
+
+fn longs() -> Vec<i64> {
+    (0..1000)
+        .map(|i: i64| i.wrapping_mul(-15_485_863_000_001))
+        .collect()
+}
+
+fn document() -> Vec<u8> {
+    let mut compound = OwnedCompound::<BE>::default();
+    compound.insert(
+        "ints",
+        ints().into_iter().map(I32::new).collect::<Vec<I32<BE>>>(),
+    );
+    compound.insert(
+        "longs",
+        longs().into_iter().map(I64::new).collect::<Vec<I64<BE>>>(),
+    );
+    OwnedValue::Compound(compound).write_to_vec::<BE>().unwrap()
+}
+
+#[test]
+fn test_owned_both_byte_orders() {
+    let data = document();
+
+    let mut value = read_owned::<BE, BE>(&data).unwrap();
+    let mut ints_value = value.get_mut("ints").unwrap();
+    assert_eq!(ints_value.as_int_array_mut().unwrap().to_vec_i32(), ints());
+    let mut longs_value = value.get_mut("longs").unwrap();
+    assert_eq!(
+        longs_value.as_long_array_mut().unwrap().to_vec_i64(),
+        longs()
+    );
+
+    let value = read_owned::<BE, LE>(&data).unwrap();
+    let OwnedValue::Compound(mut compound) = value else {
+        panic!("expected a compound");
+    };
+    let Some(OwnedValue::IntArray(array)) = compound.remove("ints") else {
+        panic!("expected an int array");
+    };
+    assert_eq!(array.to_vec_i32(), ints());
+    let Some(OwnedValue::LongArray(array)) = compound.remove("longs") else {
+        panic!("expected a long array");
+    };
+    assert_eq!(array.to_vec_i64(), longs());
+}
+
+#[test]
+fn test_borrowed() {
+    let data = document();
+    let doc = read_borrowed::<BE>(&data).unwrap();
+    let root = doc.root();
+    let ints_value = root.get("ints").unwrap();
+    assert_eq!(ints_value.as_int_array().unwrap().to_vec_i32(), ints());
+    let longs_value = root.get("longs").unwrap();
+    assert_eq!(longs_value.as_long_array().unwrap().to_vec_i64(), longs());
+
+    let data = read_owned::<BE, LE>(&data)
+        .unwrap()
+        .write_to_vec::<LE>()
+        .unwrap();
+    let doc = read_borrowed::<LE>(&data).unwrap();
+    let root = doc.root();
+    let ints_value = root.get("ints").unwrap();
+    assert_eq!(ints_value.as_int_array().unwrap().to_vec_i32(), ints());
+}
+
+#[test]
+fn test_empty() {
+    let value = OwnedValue::<LE>::from(Vec::<I32<LE>>::new());
+    let OwnedValue::IntArray(array) = &value else {
+        panic!("expected an int array");
+    };
+    assert!(array.to_vec_i32().is_empty());
+}
+
+#[test]
+fn test_from_i32_slice_matches_manual() {
+    let array: VecViewOwn<I32<BE>> = VecViewOwn::from_i32_slice(&[1, 2, 3]);
+    let manual: VecViewOwn<I32<BE>> = vec![I32::new(1), I32::new(2), I32::new(3)].into();
+    assert_eq!(array, manual);
+    assert_eq!(
+        zerocopy::IntoBytes::as_bytes(&*array),
+        [0, 0, 0, 1, 0, 0, 0, 2, 0, 0, 0, 3]
+    );
+
+    let array: VecViewOwn<I32<LE>> = VecViewOwn::from_i32_slice(&[1, 2, 3]);
+    assert_eq!(
+        zerocopy::IntoBytes::as_bytes(&*array),
+        [1, 0, 0, 0, 2, 0, 0, 0, 3, 0, 0, 0]
+    );
+}
+
+#[test]
+fn test_from_slice_round_trips() {
+    let array: VecViewOwn<I32<LE>> = VecViewOwn::from_i32_slice(&ints());
+    assert_eq!(array.to_vec_i32(), ints());
+    let array: VecViewOwn<I64<BE>> = VecViewOwn::from_i64_slice(&longs());
+    assert_eq!(array.to_vec_i64(), longs());
+    let array: VecViewOwn<I64<LE>> = VecViewOwn::from_i64_slice(&longs());
+    assert_eq!(array.to_vec_i64(), longs());
+    assert_eq!(VecViewOwn::from_i8_slice(&[-1, 2]), [-1i8, 2]);
+}
+
+#[test]
+fn test_array_from_constructors() {
+    let value = OwnedValue::<BE>::int_array_from(&ints());
+    let data = document();
+    let doc = read_borrowed::<BE>(&data).unwrap();
+    let expected = doc
+        .root()
+        .get("ints")
+        .unwrap()
+        .write_to_vec::<BE>()
+        .unwrap();
+    assert_eq!(value.write_to_vec::<BE>().unwrap(), expected);
+
+    let value = OwnedValue::<LE>::long_array_from(&longs());
+    let values: Vec<i64> = value
+        .as_long_array()
+        .unwrap()
+        .iter()
+        .map(|v| v.get())
+        .collect();
+    assert_eq!(values, longs());
+}
@@ -0,0 +1,126 @@
+//! Tests for nibble access and XOR on byte arrays.
+
+use na_nbt::{
+    OwnedCompound, OwnedValue,
+    nibble::{get_nibble, set_nibble, xor_with},
+    read_borrowed,
+};
+use zerocopy::byteorder::BigEndian as BE;
+
+/// Light levels 0..=15 followed by 15..=0, two per byte.
+fn light_levels() -> Vec<u8> {
+    (0..16).chain((0..16).rev()).collect()
+}
+
+fn light_bytes() -> Vec<i8> {
+    // low nibble first: [0, 1] -> 0x10, [2, 3] -> 0x32, ...
+    vec![
+        0x10,
+        0x32,
+        0x54,
+        0x76,
+        0x98u8 as i8,
+        0xbau8 as i8,
+        0xdcu8 as i8,
+        0xfeu8 as i8,
+        0xefu8 as i8,
+        0xcdu8 as i8,
+        0xabu8 as i8,
+        0x89u8 as i8,
+        0x67,
+        0x45,
+        0x23,
+        0x01,
+    ]
+}
+
+#[test]
+fn test_get_nibble_known_array() {
+    let bytes = light_bytes();
+    for (i, level) in light_levels().into_iter().enumerate() {
+        assert_eq!(get_nibble(&bytes, i), level, "index {i}");
+    }
+}
+
+#[test]
+fn test_set_nibble_builds_known_array() {
+    let mut bytes = vec![0i8; 16];
+    for (i, level) in light_levels().into_iter().enumerate() {
+        set_nibble(&mut bytes, i, level);
+    }
+    assert_eq!(bytes, light_bytes());
+}
+
+#[test]
+fn test_set_nibble_keeps_neighbour() {
+    let mut bytes = vec![0x5au8 as i8];
+    set_nibble(&mut bytes, 0, 0x3);
+    assert_eq!(bytes[0] as u8, 0x53);
+    set_nibble(&mut bytes, 1, 0xc);
+    assert_eq!(bytes[0] as u8, 0xc3);
+}
+
+#[test]
+#[should_panic]
+fn test_set_nibble_rejects_wide_value() {
+    set_nibble(&mut [0], 0, 16);
+}
+
+#[test]
+#[should_panic]
+fn test_get_nibble_out_of_bounds() {
+    get_nibble(&[0, 0], 4);
+}
+
+#[test]
+fn test_xor_with() {
+    let mut bytes = light_bytes();
+    let original = bytes.clone();
+    let mask = vec![-1i8; 16];
+    xor_with(&mut bytes, &mask);
+    for (i, level) in light_levels().into_iter().enumerate() {
+        assert_eq!(get_nibble(&bytes, i), 15 - level);
+    }
+    xor_with(&mut bytes, &mask);
+    assert_eq!(bytes, original);
+}
+
+#[test]
+#[should_panic]
+fn test_xor_with_length_mismatch() {
+    xor_with(&mut [0, 0], &[0]);
+}
+
+#[test]
+fn test_owned_byte_array_methods() {
+    let mut value = OwnedValue::<BE>::from(vec![0i8; 16]);
+    let mut array = value.as_byte_array_mut().unwrap();
+    for (i, level) in light_levels().into_iter().enumerate() {
+        array.set_nibble(i, level);
+    }
+    assert_eq!(array.get_nibble(17), 14);
+    array.xor_with(&light_bytes());
+    assert_eq!(value.as_byte_array().unwrap(), &[0; 16]);
+}
+
+#[test]
+fn test_mutable_and_readonly_views() {
+    let mut compound = OwnedCompound::<BE>::default();
+    compound.insert("BlockLight", light_bytes());
+    let data = OwnedValue::Compound(compound).write_to_vec::<BE>().unwrap();
+
+    let doc = read_borrowed::<BE>(&data).unwrap();
+    let root = doc.root();
+    let light = root.get("BlockLight").unwrap();
+    let light = light.as_byte_array().unwrap();
+    for (i, level) in light_levels().into_iter().enumerate() {
+        assert_eq!(light.get_nibble(i), level);
+    }
+
+    let mut owned = na_nbt::read_owned::<BE, BE>(&data).unwrap();
+    let mut light = owned.get_mut("BlockLight").unwrap();
+    let light = light.as_byte_array_mut().unwrap();
+    light.set_nibble(0, 9);
+    assert_eq!(light.get_nibble(0), 9);
+    assert_eq!(light.get_nibble(1), 1);
+}
@@ -0,0 +1,96 @@
+//! Tests for `ReadLimits::max_nodes`.
+
+use na_nbt::{
+    Error, OwnedCompound, OwnedList, OwnedValue, Parser, ReadLimits, ScopedReadableValue,
+    read_owned_from_reader_with_limits, read_owned_with_limits,
+};
+use zerocopy::byteorder::{BigEndian as BE, LittleEndian as LE};
+
+fn limits(max_nodes: usize) -> ReadLimits {
+    ReadLimits {
+        max_nodes,
+        ..ReadLimits::default()
+    }
+}
+
+/// A flat compound of `n` byte entries: `n + 1` values with the root.
+fn flat_compound(n: usize) -> Vec<u8> {
+    let mut compound: OwnedCompound<BE> = OwnedCompound::default();
+    for i in 0..n {
+        compound.insert(&format!("k{i}"), i as i8);
+    }
+    OwnedValue::Compound(compound).write_to_vec::<BE>().unwrap()
+}
+
+#[test]
+fn test_default_is_finite() {
+    let default = ReadLimits::default();
+    assert_eq!(default.max_nodes, ReadLimits::DEFAULT_MAX_NODES);
+    assert!(default.max_nodes < usize::MAX);
+    assert_eq!(default.max_bytes, usize::MAX);
+}
+
+#[test]
+fn test_flat_compound_over_the_limit() {
+    let data = flat_compound(1000);
+    let value = read_owned_with_limits::<BE, BE>(&data, limits(1001)).unwrap();
+    assert_eq!(value.node_count(), 1001);
+
+    assert!(matches!(
+        read_owned_with_limits::<BE, BE>(&data, limits(1000)),
+        Err(Error::TooManyNodes(1000))
+    ));
+    assert!(matches!(
+        read_owned_from_reader_with_limits::<BE, BE>(&data[..], limits(1000)),
+        Err(Error::TooManyNodes(1000))
+    ));
+    assert!(read_owned_from_reader_with_limits::<BE, BE>(&data[..], limits(1001)).is_ok());
+
+    let mut parser = Parser::with_limits(limits(1000));
+    assert!(matches!(
+        parser.read_owned_from_reader::<BE, LE>(&data[..]),
+        Err(Error::TooManyNodes(1000))
+    ));
+}
+
+#[test]
+fn test_list_elements_and_nested_values_count() {
+    let mut ints: OwnedList<LE> = OwnedList::default();
+    for i in 0..10 {
+        ints.push(i);
+    }
+    let mut nested: OwnedList<LE> = OwnedList::default();
+    for _ in 0..5 {
+        nested.push(OwnedCompound::<LE>::default());
+    }
+    let mut root: OwnedCompound<LE> = OwnedCompound::default();
+    root.insert("ints", ints);
+    root.insert("nested", nested);
+    root.insert("bytes", vec![0i8; 100]);
+    let value = OwnedValue::Compound(root);
+    // root, three entries, ten ints and five compounds
+    assert_eq!(value.node_count(), 19);
+    let data = value.write_to_vec::<LE>().unwrap();
+
+    assert!(read_owned_with_limits::<LE, LE>(&data, limits(19)).is_ok());
+    assert!(read_owned_from_reader_with_limits::<LE, BE>(&data[..], limits(19)).is_ok());
+    for max in [0, 1, 10, 18] {
+        assert!(matches!(
+            read_owned_with_limits::<LE, LE>(&data, limits(max)),
+            Err(Error::TooManyNodes(m)) if m == max
+        ));
+        assert!(matches!(
+            read_owned_from_reader_with_limits::<LE, BE>(&data[..], limits(max)),
+            Err(Error::TooManyNodes(m)) if m == max
+        ));
+    }
+}
+
+#[test]
+fn test_end_root_and_error_message() {
+    assert!(read_owned_with_limits::<BE, BE>(&[0x00], limits(0)).is_ok());
+    assert_eq!(
+        Error::TooManyNodes(100).to_string(),
+        "document has more than 100 values"
+    );
+}
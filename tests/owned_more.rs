@@ -121,6 +121,10 @@ fn owned_compound_insert_replace_arr_int() {
 }
 
 #[test]
+#[cfg_attr(
+    all(feature = "strict", debug_assertions),
+    should_panic(expected = "type mismatch")
+)]
 fn owned_construct() {
     let mut v = OwnedValue::<BE>::from(());
     assert_eq!(v.tag_id(), Tag::End);
@@ -1,6 +1,9 @@
 //! Tests for OwnedList and OwnedCompound mutation APIs
 
-use na_nbt::{OwnedValue, read_owned};
+use na_nbt::{
+    OwnedCompound, OwnedList, OwnedValue, TagByteArray, TagCompound, TagFloat, TagInt, TagList,
+    TagLong, TagShort, TagString, read_owned,
+};
 use zerocopy::byteorder::BigEndian as BE;
 
 fn create_int_list_nbt_be(values: &[i32]) -> Vec<u8> {
@@ -121,3 +124,79 @@ fn owned_compound_insert_remove_get_mut() {
         panic!("expected compound");
     }
 }
+
+#[test]
+fn test_owned_compound_take_typed() {
+    let mut comp = OwnedCompound::<BE>::default();
+    comp.insert("name", "Steve");
+    comp.insert("level", 7i32);
+    comp.insert("xp", 1.5f32);
+
+    // wrong type leaves the entry in place
+    assert_eq!(comp.take_::<TagInt>("name"), None);
+    assert_eq!(
+        comp.get("name").unwrap().as_string().unwrap().decode(),
+        "Steve"
+    );
+    assert_eq!(comp.take_::<TagLong>("level"), None);
+    assert_eq!(comp.take_::<TagInt>("missing"), None);
+
+    assert_eq!(comp.take_::<TagInt>("level"), Some(7));
+    assert!(comp.get("level").is_none());
+    assert_eq!(comp.take_::<TagFloat>("xp"), Some(1.5));
+    assert_eq!(comp.iter().count(), 1);
+}
+
+#[test]
+fn test_owned_list_take_typed() {
+    let mut list = OwnedList::<BE>::default();
+    list.push(10i16);
+    list.push(20i16);
+    list.push(30i16);
+
+    assert_eq!(list.take_::<TagInt>(0), None);
+    assert_eq!(list.take_::<TagShort>(3), None);
+    assert_eq!(list.len(), 3);
+
+    assert_eq!(list.take_::<TagShort>(1), Some(20));
+    assert_eq!(list.len(), 2);
+    assert_eq!(list.get(1).and_then(|v| v.as_short()), Some(30));
+}
+
+#[test]
+fn test_owned_compound_take_non_numeric() {
+    let mut inner = OwnedCompound::<BE>::default();
+    inner.insert("id", 1i32);
+    let mut comp = OwnedCompound::<BE>::default();
+    comp.insert("name", "Steve");
+    comp.insert("inner", inner);
+    comp.insert("bytes", vec![1i8, 2]);
+
+    assert!(comp.take_::<TagCompound>("name").is_none());
+    assert!(comp.take_::<TagString>("inner").is_none());
+    assert!(comp.take_::<TagList>("bytes").is_none());
+    assert_eq!(comp.iter().count(), 3);
+
+    assert_eq!(comp.take_::<TagString>("name").unwrap().decode(), "Steve");
+    let inner = comp.take_::<TagCompound>("inner").unwrap();
+    assert_eq!(inner.get("id").and_then(|v| v.as_int()), Some(1));
+    assert_eq!(&*comp.take_::<TagByteArray>("bytes").unwrap(), &[1, 2]);
+    assert_eq!(comp.iter().count(), 0);
+}
+
+#[test]
+fn test_owned_list_take_non_numeric() {
+    let mut list = OwnedList::<BE>::default();
+    let mut inner = OwnedList::<BE>::default();
+    inner.push("a");
+    list.push(inner);
+    list.push(OwnedList::<BE>::default());
+
+    assert!(list.take_::<TagCompound>(0).is_none());
+    assert!(list.take_::<TagList>(2).is_none());
+    assert_eq!(list.len(), 2);
+
+    let taken = list.take_::<TagList>(0).unwrap();
+    assert_eq!(taken.len(), 1);
+    assert_eq!(list.len(), 1);
+}
@@ -0,0 +1,109 @@
+//! Tests for packed long arrays.
+
+use na_nbt::{OwnedCompound, OwnedValue, pack_bits, read_borrowed, unpack_bits};
+use zerocopy::byteorder::{BigEndian as BE, I64};
+
+fn block_states() -> Vec<u32> {
+    // a section with a palette of 20 entries, scattered across the blocks
+    (0..4096u32).map(|i| (i * 7 + i / 16) % 20).collect()
+}
+
+#[test]
+fn test_round_trip_block_states() {
+    let states = block_states();
+    let value = OwnedValue::<BE>::packed_long_array(&states, 5);
+    let OwnedValue::LongArray(array) = &value else {
+        panic!("expected a long array");
+    };
+    // 12 entries per long, the top 4 bits unused
+    assert_eq!(array.len(), 342);
+    assert_eq!(array.unpack_bits(5, 4096).as_ref(), Some(&states));
+}
+
+#[test]
+fn test_known_packing() {
+    // 5 bits per entry: entry i at bits 5*i..5*i+5 of the first long
+    let values = [1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13];
+    let longs = pack_bits::<BE>(&values, 5);
+    assert_eq!(longs.len(), 2);
+    let mut expected = 0u64;
+    for (i, v) in values[..12].iter().enumerate() {
+        expected |= (*v as u64) << (5 * i);
+    }
+    assert_eq!(longs[0].get() as u64, expected);
+    // the 13th entry starts the second long rather than spanning both
+    assert_eq!(longs[1].get(), 13);
+    assert_eq!(unpack_bits(&longs, 5, 13), Some(values.to_vec()));
+}
+
+#[test]
+fn test_entry_widths() {
+    for bits in [1u8, 4, 7, 15, 16, 31, 32] {
+        let max = if bits == 32 {
+            u32::MAX
+        } else {
+            (1 << bits) - 1
+        };
+        let values: Vec<u32> = (0..100u32)
+            .map(|i| if i % 2 == 0 { max } else { i & max })
+            .collect();
+        let longs = pack_bits::<BE>(&values, bits);
+        assert_eq!(longs.len(), 100usize.div_ceil(64 / bits as usize));
+        assert_eq!(unpack_bits(&longs, bits, 100), Some(values));
+    }
+}
+
+#[test]
+fn test_unpack_fewer_than_stored() {
+    let longs = [I64::<BE>::new(-1)];
+    assert_eq!(unpack_bits(&longs, 4, 3), Some(vec![15, 15, 15]));
+    assert_eq!(unpack_bits(&longs, 4, 0), Some(vec![]));
+}
+
+#[test]
+fn test_borrowed_and_mutable_views() {
+    let states = block_states();
+    let mut compound: OwnedCompound<BE> = OwnedCompound::default();
+    compound.insert("data", OwnedValue::<BE>::packed_long_array(&states, 5));
+    let mut value = OwnedValue::Compound(compound);
+    let bytes = value.write_to_vec::<BE>().unwrap();
+
+    let doc = read_borrowed::<BE>(&bytes).unwrap();
+    let root = doc.root();
+    let data = root.get("data").unwrap();
+    assert_eq!(
+        data.as_long_array().unwrap().unpack_bits(5, 4096).as_ref(),
+        Some(&states)
+    );
+
+    let mut data = value.get_mut("data").unwrap();
+    let array = data.as_long_array_mut().unwrap();
+    assert_eq!(array.unpack_bits(5, 4096).as_ref(), Some(&states));
+}
+
+#[test]
+#[should_panic]
+fn test_value_too_wide_panics() {
+    pack_bits::<BE>(&[32], 5);
+}
+
+#[test]
+#[should_panic]
+fn test_zero_bits_panics() {
+    pack_bits::<BE>(&[0], 0);
+}
+
+#[test]
+fn test_array_too_short_is_none() {
+    let longs = pack_bits::<BE>(&[1; 12], 5);
+    assert_eq!(unpack_bits(&longs, 5, 13), None);
+    assert_eq!(unpack_bits::<BE>(&[], 5, 1), None);
+}
+
+#[test]
+fn test_invalid_bits_per_entry_is_none() {
+    let longs = [I64::<BE>::new(-1)];
+    assert_eq!(unpack_bits(&longs, 0, 1), None);
+    assert_eq!(unpack_bits(&longs, 33, 1), None);
+    assert_eq!(unpack_bits(&longs, 32, 2), Some(vec![u32::MAX, u32::MAX]));
+}
@@ -0,0 +1,172 @@
+use std::io;
+
+use na_nbt::{
+    Error, OwnedCompound, OwnedValue, Parser, ReadLimits, read_owned, read_owned_from_reader,
+};
+use zerocopy::byteorder::{BigEndian as BE, LittleEndian as LE};
+
+fn create_document(i: i32) -> Vec<u8> {
+    let mut root = OwnedCompound::<BE>::default();
+    root.insert("id", i);
+    root.insert("name", "minecraft:zombie");
+    root.insert("tags", vec![1i8, 2, 3]);
+    OwnedValue::Compound(root).write_to_vec::<BE>().unwrap()
+}
+
+#[test]
+fn parser_matches_free_functions() {
+    let mut parser = Parser::new();
+    for i in 0..10 {
+        let data = create_document(i);
+        let expected = read_owned::<BE, LE>(&data)
+            .unwrap()
+            .write_to_vec::<BE>()
+            .unwrap();
+
+        let value = parser.read_owned::<BE, LE>(&data).unwrap();
+        assert_eq!(value.write_to_vec::<BE>().unwrap(), expected);
+        let value = parser.read_owned_strict::<BE, LE>(&data).unwrap();
+        assert_eq!(value.write_to_vec::<BE>().unwrap(), expected);
+        let value = parser
+            .read_owned_from_reader::<BE, LE>(data.as_slice())
+            .unwrap();
+        assert_eq!(value.write_to_vec::<BE>().unwrap(), expected);
+        let value = read_owned_from_reader::<BE, LE>(data.as_slice()).unwrap();
+        assert_eq!(value.write_to_vec::<BE>().unwrap(), expected);
+    }
+}
+
+#[test]
+fn parser_errors_do_not_poison_it() {
+    let mut parser = Parser::new();
+    let data = create_document(1);
+
+    assert!(
+        parser
+            .read_owned_strict::<BE, BE>(&data[..data.len() - 2])
+            .is_err()
+    );
+    assert!(matches!(
+        parser.read_owned_from_reader::<BE, BE>(&data[..5]),
+        Err(Error::EndOfFile)
+    ));
+
+    let mut trailing = data.clone();
+    trailing.push(0xff);
+    assert!(matches!(
+        parser.read_owned_from_reader::<BE, BE>(trailing.as_slice()),
+        Err(Error::TrailingData(1))
+    ));
+
+    assert!(parser.read_owned_strict::<BE, BE>(&data).is_ok());
+    assert!(
+        parser
+            .read_owned_from_reader::<BE, BE>(data.as_slice())
+            .is_ok()
+    );
+}
+
+#[test]
+fn parser_limits() {
+    let data = create_document(1);
+
+    let mut parser = Parser::with_limits(ReadLimits {
+        max_bytes: data.len(),
+        ..ReadLimits::default()
+    });
+    assert!(
+        parser
+            .read_owned_from_reader::<BE, BE>(data.as_slice())
+            .is_ok()
+    );
+
+    let mut parser = Parser::with_limits(ReadLimits {
+        max_bytes: data.len() - 1,
+        ..ReadLimits::default()
+    });
+    match parser.read_owned_from_reader::<BE, BE>(data.as_slice()) {
+        Err(Error::IO(e)) => assert_eq!(e.kind(), io::ErrorKind::InvalidData),
+        other => panic!("expected a limit error, got {:?}", other.err()),
+    }
+}
+
+#[test]
+fn parser_reset() {
+    let mut parser = Parser::new();
+    let data = create_document(1);
+    assert!(
+        parser
+            .read_owned_from_reader::<BE, BE>(data.as_slice())
+            .is_ok()
+    );
+    parser.reset();
+    assert!(
+        parser
+            .read_owned_from_reader::<BE, BE>(data.as_slice())
+            .is_ok()
+    );
+    assert!(parser.read_owned_strict::<BE, BE>(&data).is_ok());
+}
+
+/// Builds a compound from `(key, byte value)` entries and nested compounds,
+/// repeated keys included.
+fn raw_compound(entries: &[(&str, Option<u8>, &[u8])]) -> Vec<u8> {
+    let mut out = vec![];
+    for (key, value, nested) in entries {
+        out.push(if value.is_some() { 0x01 } else { 0x0a });
+        out.extend_from_slice(&(key.len() as u16).to_be_bytes());
+        out.extend_from_slice(key.as_bytes());
+        match value {
+            Some(value) => out.push(*value),
+            None => out.extend_from_slice(nested),
+        }
+    }
+    out.push(0x00);
+    out
+}
+
+#[test]
+fn parser_duplicate_keys_and_clear() {
+    let inner = raw_compound(&[("x", Some(1), &[]), ("x", Some(2), &[])]);
+    let nested = [
+        &[0x0a, 0x00, 0x00][..],
+        &raw_compound(&[
+            ("a", None, &inner),
+            ("b", Some(3), &[]),
+            ("b", Some(4), &[]),
+        ]),
+    ]
+    .concat();
+    let flat = [
+        &[0x0a, 0x00, 0x00][..],
+        &raw_compound(&[
+            ("c", Some(1), &[]),
+            ("b", Some(2), &[]),
+            ("b", Some(3), &[]),
+            ("c", Some(4), &[]),
+        ]),
+    ]
+    .concat();
+
+    let mut parser = Parser::new();
+    for (data, key) in [(&nested, "x"), (&flat, "b"), (&nested, "x")] {
+        for result in [
+            parser.read_owned_strict::<BE, BE>(data),
+            na_nbt::read_owned_strict::<BE, BE>(data),
+        ] {
+            match result {
+                Err(Error::DuplicateKey(found)) => assert_eq!(found, key),
+                other => panic!("expected a duplicate key, got {:?}", other.err()),
+            }
+        }
+    }
+
+    parser.clear();
+    let data = create_document(1);
+    assert!(parser.read_owned_strict::<BE, BE>(&data).is_ok());
+    assert!(
+        parser
+            .read_owned_from_reader::<BE, BE>(data.as_slice())
+            .is_ok()
+    );
+}
@@ -0,0 +1,104 @@
+use na_nbt::{Error, OwnedCompound, OwnedValue, Tag, peek_root};
+use zerocopy::byteorder::{BigEndian as BE, LittleEndian as LE};
+
+/// Encodes `value` with the given root name.
+fn named(value: &OwnedValue<BE>, name: &str, big_endian: bool) -> Vec<u8> {
+    let body = if big_endian {
+        value.write_to_vec::<BE>().unwrap()
+    } else {
+        value.write_to_vec::<LE>().unwrap()
+    };
+    let len = name.len() as u16;
+    let mut out = vec![body[0]];
+    out.extend(if big_endian {
+        len.to_be_bytes()
+    } else {
+        len.to_le_bytes()
+    });
+    out.extend(name.as_bytes());
+    out.extend(&body[3..]);
+    out
+}
+
+fn player() -> OwnedValue<BE> {
+    let mut root = OwnedCompound::<BE>::default();
+    root.insert("name", "Steve");
+    root.insert("level", 42i32);
+    OwnedValue::Compound(root)
+}
+
+#[test]
+fn peek_compound_file() {
+    let data = named(&player(), "Level", true);
+    assert_eq!(
+        peek_root::<BE>(&data).unwrap(),
+        (Tag::Compound, Some("Level"))
+    );
+
+    let data = named(&player(), "", false);
+    assert_eq!(peek_root::<LE>(&data).unwrap(), (Tag::Compound, Some("")));
+}
+
+#[test]
+fn peek_other_roots() {
+    let data = OwnedValue::<BE>::from(1i32).write_to_vec::<BE>().unwrap();
+    assert_eq!(peek_root::<BE>(&data).unwrap(), (Tag::Int, Some("")));
+    assert_eq!(peek_root::<BE>(&[0x00]).unwrap(), (Tag::End, None));
+}
+
+#[test]
+fn peek_only_reads_header() {
+    // the payload is garbage, but it is never looked at
+    let data = [0x09, 0x00, 0x01, b'x', 0xff, 0xff];
+    assert_eq!(peek_root::<BE>(&data).unwrap(), (Tag::List, Some("x")));
+}
+
+#[test]
+fn peek_errors() {
+    assert!(matches!(peek_root::<BE>(&[]), Err(Error::EndOfFile)));
+    assert!(matches!(
+        peek_root::<BE>(&[0x0a, 0x00]),
+        Err(Error::EndOfFile)
+    ));
+    assert!(matches!(
+        peek_root::<BE>(&[0x0a, 0x00, 0x03, b'a']),
+        Err(Error::EndOfFile)
+    ));
+    assert!(matches!(
+        peek_root::<BE>(&[0x0d, 0x00, 0x00]),
+        Err(Error::InvalidTagType(0x0d))
+    ));
+}
+
+#[cfg(feature = "compression")]
+#[test]
+fn peek_compressed() {
+    use flate2::{
+        Compression,
+        write::{GzEncoder, ZlibEncoder},
+    };
+    use na_nbt::peek_root_compressed;
+    use std::io::Write;
+
+    let data = named(&player(), "Level", true);
+
+    let mut gzip = GzEncoder::new(Vec::new(), Compression::default());
+    gzip.write_all(&data).unwrap();
+    let gzip = gzip.finish().unwrap();
+
+    let mut zlib = ZlibEncoder::new(Vec::new(), Compression::default());
+    zlib.write_all(&data).unwrap();
+    let zlib = zlib.finish().unwrap();
+
+    for input in [&gzip, &zlib, &data] {
+        let (tag_id, name) = peek_root_compressed::<BE>(input).unwrap();
+        assert_eq!(tag_id, Tag::Compound);
+        assert_eq!(name.as_deref(), Some("Level"));
+    }
+
+    // only the gzip header, no deflate data
+    assert!(matches!(
+        peek_root_compressed::<BE>(&gzip[..10]),
+        Err(Error::EndOfFile | Error::IO(_))
+    ));
+}
@@ -0,0 +1,200 @@
+//! Tests for `read_shared_preserving` and `PreservingDocument`.
+#![cfg(feature = "shared")]
+
+use bytes::Bytes;
+use na_nbt::{Error, OwnedCompound, OwnedValue, read_shared_preserving};
+use zerocopy::byteorder::{BigEndian as BE, LittleEndian as LE};
+
+fn entry(data: &mut Vec<u8>, tag: u8, name: &str) {
+    data.push(tag);
+    data.extend_from_slice(&(name.len() as u16).to_be_bytes());
+    data.extend_from_slice(name.as_bytes());
+}
+
+/// A document with a named root, repeated keys and an empty list of ints,
+/// none of which survive a decode and re-encode.
+fn document() -> Vec<u8> {
+    let mut data = Vec::new();
+    entry(&mut data, 0x0a, "Data");
+    entry(&mut data, 0x03, "a");
+    data.extend_from_slice(&1i32.to_be_bytes());
+    entry(&mut data, 0x03, "a");
+    data.extend_from_slice(&2i32.to_be_bytes());
+    entry(&mut data, 0x09, "empty");
+    data.push(0x03);
+    data.extend_from_slice(&0u32.to_be_bytes());
+    entry(&mut data, 0x0a, "nested");
+    entry(&mut data, 0x03, "score");
+    data.extend_from_slice(&10i32.to_be_bytes());
+    entry(&mut data, 0x08, "name");
+    data.extend_from_slice(&1u16.to_be_bytes());
+    data.push(b'x');
+    entry(&mut data, 0x03, "score");
+    data.extend_from_slice(&99i32.to_be_bytes());
+    data.push(0x00);
+    entry(&mut data, 0x04, "z");
+    data.extend_from_slice(&5i64.to_be_bytes());
+    data.push(0x00);
+    data
+}
+
+fn find(data: &[u8], needle: &[u8]) -> usize {
+    data.windows(needle.len())
+        .position(|window| window == needle)
+        .unwrap()
+}
+
+#[test]
+fn test_unchanged_document_is_written_verbatim() {
+    let data = document();
+    let doc = read_shared_preserving::<BE>(Bytes::from(data.clone())).unwrap();
+    assert_eq!(doc.write_to_vec().unwrap(), data);
+    assert_eq!(doc.source().len(), data.len());
+}
+
+#[test]
+fn test_modifying_one_int_leaves_other_bytes_identical() {
+    let data = document();
+    let mut doc = read_shared_preserving::<BE>(Bytes::from(data.clone())).unwrap();
+    doc.set(&["nested", "score"], 11).unwrap();
+    let written = doc.write_to_vec().unwrap();
+
+    assert_eq!(written.len(), data.len());
+    let payload = find(&data, b"score") + 5;
+    let changed: Vec<_> = (0..data.len()).filter(|&i| data[i] != written[i]).collect();
+    assert_eq!(changed, [payload + 3]);
+    assert_eq!(written[payload..payload + 4], 11i32.to_be_bytes());
+
+    let nested = doc.get(&["nested"]).unwrap().unwrap();
+    assert_eq!(nested.get("score").unwrap().as_int(), Some(11));
+    assert_eq!(doc.get(&["a"]).unwrap().unwrap().as_int(), Some(1));
+}
+
+#[test]
+fn test_set_appends_and_remove_drops_entries() {
+    let data = document();
+    let mut doc = read_shared_preserving::<BE>(Bytes::from(data.clone())).unwrap();
+    doc.set(&["added"], "new").unwrap();
+    assert!(doc.remove(&["a"]).unwrap());
+    assert!(!doc.remove(&["missing"]).unwrap());
+    assert!(!doc.remove(&["missing", "child"]).unwrap());
+    assert!(!doc.remove(&[]).unwrap());
+
+    let written = doc.write_to_vec().unwrap();
+    // only the first "a" is removed, and the new entry goes at the end
+    let first_a = find(&data, b"\x03\x00\x01a");
+    assert_eq!(written[..first_a], data[..first_a]);
+    assert_eq!(
+        written[first_a..first_a + 8],
+        data[first_a + 8..first_a + 16]
+    );
+    let mut tail = Vec::new();
+    entry(&mut tail, 0x08, "added");
+    tail.extend_from_slice(&3u16.to_be_bytes());
+    tail.extend_from_slice(b"new");
+    tail.push(0x00);
+    assert!(written.ends_with(&tail));
+
+    assert_eq!(doc.get(&["a"]).unwrap().unwrap().as_int(), Some(2));
+    let added = doc.get(&["added"]).unwrap().unwrap();
+    assert_eq!(added.as_string().unwrap().decode(), "new");
+    assert!(doc.get(&["missing"]).unwrap().is_none());
+    assert!(doc.get(&["nested", "missing"]).unwrap().is_none());
+}
+
+#[test]
+fn test_changes_inside_a_replaced_compound() {
+    let mut doc = read_shared_preserving::<LE>(Bytes::from(
+        OwnedValue::Compound(OwnedCompound::<LE>::default())
+            .write_to_vec::<LE>()
+            .unwrap(),
+    ))
+    .unwrap();
+    let mut inner = OwnedCompound::<LE>::default();
+    inner.insert("deep", OwnedCompound::<LE>::default());
+    doc.set(&["inner"], inner).unwrap();
+    doc.set(&["inner", "deep", "x"], 7i16).unwrap();
+    doc.set(&["inner", "y"], 1i8).unwrap();
+    assert!(doc.remove(&["inner", "y"]).unwrap());
+    assert!(!doc.remove(&["inner", "deep", "z"]).unwrap());
+    assert!(matches!(
+        doc.remove(&["inner", "deep", "x", "z"]),
+        Err(Error::TagMismatch(10, 2))
+    ));
+
+    let x = doc.get(&["inner", "deep", "x"]).unwrap().unwrap();
+    assert_eq!(x.as_short(), Some(7));
+    assert!(doc.get(&["inner", "y"]).unwrap().is_none());
+    assert!(matches!(
+        doc.set(&["inner", "missing", "x"], 1),
+        Err(Error::Message(_))
+    ));
+    assert!(matches!(
+        doc.set(&["inner", "deep", "x", "z"], 1),
+        Err(Error::TagMismatch(10, 2))
+    ));
+
+    let written = doc.write_to_vec().unwrap();
+    let root = na_nbt::read_shared::<LE>(Bytes::from(written)).unwrap();
+    let x = root.into_shared_child(&["inner", "deep", "x"]).unwrap();
+    assert_eq!(x.as_short(), Some(7));
+}
+
+#[test]
+fn test_set_errors() {
+    let mut doc = read_shared_preserving::<BE>(Bytes::from(document())).unwrap();
+    assert!(matches!(doc.set(&[], 1), Err(Error::Message(_))));
+    assert!(matches!(
+        doc.set(&["missing", "x"], 1),
+        Err(Error::Message(_))
+    ));
+    assert!(matches!(
+        doc.set(&["z", "x"], 1),
+        Err(Error::TagMismatch(10, 4))
+    ));
+    assert!(matches!(
+        doc.set(&["x"], OwnedValue::End),
+        Err(Error::InvalidTagType(0))
+    ));
+    // failed changes leave the document as it was
+    assert_eq!(doc.write_to_vec().unwrap(), document());
+}
+
+#[test]
+fn test_set_below_a_replaced_non_compound() {
+    let mut doc = read_shared_preserving::<BE>(Bytes::from(document())).unwrap();
+    doc.set(&["added"], 5i32).unwrap();
+    assert!(matches!(
+        doc.set(&["added", "x"], 1),
+        Err(Error::TagMismatch(10, 3))
+    ));
+    assert!(matches!(
+        doc.remove(&["added", "x"]),
+        Err(Error::TagMismatch(10, 3))
+    ));
+    assert_eq!(doc.get(&["added"]).unwrap().unwrap().as_int(), Some(5));
+}
+
+#[test]
+fn test_read_errors() {
+    assert!(matches!(
+        read_shared_preserving::<BE>(Bytes::new()),
+        Err(Error::EndOfFile)
+    ));
+    assert!(matches!(
+        read_shared_preserving::<BE>(Bytes::from_static(&[0x03, 0x00, 0x00, 0, 0, 0, 1])),
+        Err(Error::TagMismatch(10, 3))
+    ));
+    let mut data = document();
+    data.push(0x00);
+    assert!(matches!(
+        read_shared_preserving::<BE>(Bytes::from(data)),
+        Err(Error::TrailingData(1))
+    ));
+    let mut data = document();
+    data.truncate(data.len() - 3);
+    assert!(matches!(
+        read_shared_preserving::<BE>(Bytes::from(data)),
+        Err(Error::EndOfFile)
+    ));
+}
@@ -0,0 +1,44 @@
+//! Tests for `read_auto`.
+#![cfg(feature = "compression")]
+
+use na_nbt::{Compression, OwnedCompound, OwnedValue, read_auto, values_equal};
+use zerocopy::byteorder::{BigEndian as BE, LittleEndian as LE};
+
+fn level() -> OwnedValue<BE> {
+    let mut data: OwnedCompound<BE> = OwnedCompound::default();
+    data.insert("LevelName", "world");
+    data.insert("DataVersion", 3700i32);
+    OwnedValue::Compound(data)
+}
+
+#[test]
+fn test_detects_each_compression() {
+    let value = level();
+    for compression in [Compression::None, Compression::Gzip, Compression::Zlib] {
+        let file = value.to_file_bytes::<BE>("Data", compression).unwrap();
+        let (read, detected) = read_auto::<BE, BE>(&file).unwrap();
+        assert_eq!(detected, compression);
+        assert!(values_equal(&read, &value));
+    }
+}
+
+#[test]
+fn test_write_back_in_same_container() {
+    let file = level().to_file_bytes::<BE>("", Compression::Gzip).unwrap();
+    let (mut value, compression) = read_auto::<BE, LE>(&file).unwrap();
+    value.get_mut("DataVersion").unwrap().set_int(3800);
+
+    let saved = value.to_file_bytes::<BE>("", compression).unwrap();
+    assert_eq!(&saved[..2], [0x1f, 0x8b]);
+    let (read, detected) = read_auto::<BE, BE>(&saved).unwrap();
+    assert_eq!(detected, Compression::Gzip);
+    assert_eq!(read.get("DataVersion").unwrap().as_int(), Some(3800));
+}
+
+#[test]
+fn test_errors() {
+    // gzip magic with a corrupt body
+    assert!(read_auto::<BE, BE>(&[0x1f, 0x8b, 0x00, 0x00]).is_err());
+    // raw data that is not valid NBT
+    assert!(read_auto::<BE, BE>(&[0x0a, 0x00]).is_err());
+}
@@ -0,0 +1,47 @@
+//! Tests for guessing the byte order with `read_borrowed_auto`.
+
+use na_nbt::{AutoDocument, Error, read_borrowed_auto};
+
+// Compound { "a": Int(1) } in each byte order
+const BE_SAMPLE: [u8; 12] = [
+    0x0a, 0x00, 0x00, 0x03, 0x00, 0x01, b'a', 0x00, 0x00, 0x00, 0x01, 0x00,
+];
+const LE_SAMPLE: [u8; 12] = [
+    0x0a, 0x00, 0x00, 0x03, 0x01, 0x00, b'a', 0x01, 0x00, 0x00, 0x00, 0x00,
+];
+
+#[test]
+fn picks_big_endian() {
+    match read_borrowed_auto(&BE_SAMPLE).unwrap() {
+        AutoDocument::BigEndian(doc) => {
+            assert_eq!(doc.root().get("a").and_then(|v| v.as_int()), Some(1));
+        }
+        AutoDocument::LittleEndian(_) => panic!("expected big-endian"),
+    }
+}
+
+#[test]
+fn picks_little_endian() {
+    match read_borrowed_auto(&LE_SAMPLE).unwrap() {
+        AutoDocument::LittleEndian(doc) => {
+            assert_eq!(doc.root().get("a").and_then(|v| v.as_int()), Some(1));
+        }
+        AutoDocument::BigEndian(_) => panic!("expected little-endian"),
+    }
+}
+
+#[test]
+fn ambiguous_data_prefers_big_endian() {
+    let empty = [0x0a, 0x00, 0x00, 0x00];
+    assert!(matches!(
+        read_borrowed_auto(&empty),
+        Ok(AutoDocument::BigEndian(_))
+    ));
+}
+
+#[test]
+fn unreadable_data_reports_big_endian_error() {
+    let truncated = &BE_SAMPLE[..8];
+    let err = read_borrowed_auto(truncated).err().unwrap();
+    assert!(matches!(err, Error::EndOfFile));
+}
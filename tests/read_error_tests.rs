@@ -1,4 +1,5 @@
-use na_nbt::{Error, read_borrowed, read_owned};
+use na_nbt::{Error, read_borrowed, read_owned, read_owned_from_reader};
+use std::io::Cursor;
 use zerocopy::byteorder::{BigEndian as BE, LittleEndian as LE};
 
 // ==================== Helper Functions ====================
@@ -44,8 +45,10 @@ fn test_borrowed_eof_in_list_header() {
     let data = vec![0x09, 0x00, 0x00, 0x01, 0x00];
     let res = read_borrowed::<BE>(&data);
     match res {
-        Err(Error::EndOfFile) => {}
-        _ => panic!("Expected EndOfFile, got Ok"),
+        Err(Error::Truncated {
+            context: "list header",
+        }) => {}
+        _ => panic!("Expected Truncated, got Ok"),
     }
 }
 
@@ -96,7 +99,7 @@ fn test_borrowed_invalid_tag_in_compound() {
 
     let res = read_borrowed::<BE>(&data);
     match res {
-        Err(Error::InvalidTagType(0xFF)) => {}
+        Err(e) if matches!(e.kind(), Error::InvalidTagType(0xFF)) => {}
         _ => panic!("Expected InvalidTagType, got Ok"),
     }
 }
@@ -118,8 +121,10 @@ fn test_owned_eof_in_list_header() {
     let data = vec![0x09, 0x00, 0x00, 0x01, 0x00];
     let res = read_owned::<BE, BE>(&data);
     match res {
-        Err(Error::EndOfFile) => {}
-        _ => panic!("Expected EndOfFile, got Ok"),
+        Err(Error::Truncated {
+            context: "list header",
+        }) => {}
+        _ => panic!("Expected Truncated, got Ok"),
     }
 }
 
@@ -141,7 +146,7 @@ fn test_owned_invalid_tag_in_compound() {
     data.extend_from_slice(&0u16.to_be_bytes());
     let res = read_owned::<BE, BE>(&data);
     match res {
-        Err(Error::InvalidTagType(0xFF)) => {}
+        Err(e) if matches!(e.kind(), Error::InvalidTagType(0xFF)) => {}
         _ => panic!("Expected InvalidTagType, got Ok"),
     }
 }
@@ -155,8 +160,8 @@ fn test_owned_invalid_tag_in_list() {
 
     let res = read_owned::<BE, BE>(&data);
     match res {
-        Err(Error::EndOfFile) => {}
-        _ => panic!("Expected EndOfFile, got Ok"),
+        Err(err) => assert!(matches!(err.kind(), Error::InvalidTagType(0xFF))),
+        _ => panic!("Expected InvalidTagType, got Ok"),
     }
 }
 
@@ -172,8 +177,10 @@ fn test_owned_fallback_eof_in_list_header() {
     let data = vec![0x09, 0x00, 0x00, 0x01, 0x00];
     let res = read_owned::<BE, LE>(&data);
     match res {
-        Err(Error::EndOfFile) => {}
-        _ => panic!("Expected EndOfFile, got Ok"),
+        Err(Error::Truncated {
+            context: "list header",
+        }) => {}
+        _ => panic!("Expected Truncated, got Ok"),
     }
 }
 
@@ -208,7 +215,7 @@ fn test_owned_fallback_invalid_tag_in_compound() {
 
     let res = read_owned::<BE, LE>(&data);
     match res {
-        Err(Error::InvalidTagType(0xFF)) => {}
+        Err(e) if matches!(e.kind(), Error::InvalidTagType(0xFF)) => {}
         _ => panic!("Expected InvalidTagType, got Ok"),
     }
 }
@@ -221,7 +228,7 @@ fn test_owned_fallback_invalid_tag_in_list() {
 
     let res = read_owned::<BE, LE>(&data);
     match res {
-        Err(Error::InvalidTagType(0xFF)) => {}
+        Err(e) if matches!(e.kind(), Error::InvalidTagType(0xFF)) => {}
         _ => panic!("Expected InvalidTagType, got Ok"),
     }
 }
@@ -239,7 +246,7 @@ fn test_owned_fallback_invalid_tag_nested_in_list() {
 
     let res = read_owned::<BE, LE>(&data);
     match res {
-        Err(Error::InvalidTagType(0xFF)) => {}
+        Err(e) if matches!(e.kind(), Error::InvalidTagType(0xFF)) => {}
         _ => panic!("Expected InvalidTagType, got Ok"),
     }
 }
@@ -261,3 +268,202 @@ fn test_owned_fallback_eof_in_nested_list() {
         _ => panic!("Expected EndOfFile, got Ok"),
     }
 }
+
+// ==================== Error Offsets ====================
+
+fn create_nested_invalid_tag() -> Vec<u8> {
+    // { a: Int 1, b: { <tag 0x0d> } }
+    let mut data = create_compound_start();
+    data.push(0x03);
+    data.extend_from_slice(&1u16.to_be_bytes());
+    data.push(b'a');
+    data.extend_from_slice(&1i32.to_be_bytes());
+    data.push(0x0A);
+    data.extend_from_slice(&1u16.to_be_bytes());
+    data.push(b'b');
+    // offset 15
+    data.push(0x0D);
+    data.extend_from_slice(&0u16.to_be_bytes());
+    data.push(0x00);
+    data.push(0x00);
+    data
+}
+
+fn create_nested_invalid_list() -> Vec<u8> {
+    // { l: List<List> [ List<0x0d> ] }
+    let mut data = create_compound_start();
+    data.push(0x09);
+    data.extend_from_slice(&1u16.to_be_bytes());
+    data.push(b'l');
+    data.push(0x09);
+    data.extend_from_slice(&1u32.to_be_bytes());
+    // offset 12
+    data.push(0x0D);
+    data.extend_from_slice(&0u32.to_be_bytes());
+    data.push(0x00);
+    data
+}
+
+#[test]
+fn test_invalid_tag_offset_in_compound() {
+    let data = create_nested_invalid_tag();
+
+    let err = read_borrowed::<BE>(&data).err().unwrap();
+    assert!(matches!(err.kind(), Error::InvalidTagType(0x0D)));
+    assert_eq!(err.offset(), Some(15));
+
+    let err = read_owned::<BE, BE>(&data).err().unwrap();
+    assert!(matches!(err.kind(), Error::InvalidTagType(0x0D)));
+    assert_eq!(err.offset(), Some(15));
+
+    let err = read_owned::<BE, LE>(&data).err().unwrap();
+    assert!(matches!(err.kind(), Error::InvalidTagType(0x0D)));
+    assert_eq!(err.offset(), Some(15));
+
+    let err = read_owned_from_reader::<BE, BE>(Cursor::new(&data))
+        .err()
+        .unwrap();
+    assert!(matches!(err.kind(), Error::InvalidTagType(0x0D)));
+    assert_eq!(err.offset(), Some(15));
+
+    let err = na_nbt::read_owned_strict::<BE, BE>(&data).err().unwrap();
+    assert!(matches!(err.kind(), Error::InvalidTagType(0x0D)));
+    assert_eq!(err.offset(), Some(15));
+}
+
+#[test]
+fn test_invalid_tag_offset_in_list() {
+    let data = create_nested_invalid_list();
+
+    let err = read_borrowed::<BE>(&data).err().unwrap();
+    assert_eq!(err.offset(), Some(12));
+
+    let err = read_owned::<BE, LE>(&data).err().unwrap();
+    assert_eq!(err.offset(), Some(12));
+
+    let err = read_owned_from_reader::<BE, LE>(Cursor::new(&data))
+        .err()
+        .unwrap();
+    assert_eq!(err.offset(), Some(12));
+}
+
+#[test]
+fn test_invalid_list_element_tag_in_every_reader() {
+    // a root list, a list in a compound and a list in a list, each declaring
+    // element tag 0x0d and a positive count, with too little data after it
+    let mut root = create_list_header(0x0D, 3);
+    root.push(0x00);
+
+    let mut in_compound = create_compound_start();
+    in_compound.extend_from_slice(&[0x09, 0x00, 0x01, b'l']);
+    in_compound.push(0x0D);
+    in_compound.extend_from_slice(&2u32.to_be_bytes());
+    in_compound.push(0x00);
+
+    let mut in_list = create_list_header(0x09, 1);
+    in_list.push(0x0D);
+    in_list.extend_from_slice(&1u32.to_be_bytes());
+
+    for (data, offset) in [(root, 3), (in_compound, 7), (in_list, 8)] {
+        let errors = [
+            read_borrowed::<BE>(&data).err().unwrap(),
+            read_owned::<BE, BE>(&data).err().unwrap(),
+            read_owned::<BE, LE>(&data).err().unwrap(),
+            na_nbt::read_owned_strict::<BE, BE>(&data).err().unwrap(),
+            read_owned_from_reader::<BE, BE>(Cursor::new(&data))
+                .err()
+                .unwrap(),
+            na_nbt::read_shared::<BE>(bytes::Bytes::copy_from_slice(&data))
+                .err()
+                .unwrap(),
+        ];
+        for err in errors {
+            assert!(
+                matches!(err.kind(), Error::InvalidTagType(0x0D)),
+                "{err:?} at {offset}"
+            );
+            assert_eq!(err.offset(), Some(offset));
+        }
+    }
+}
+
+#[test]
+fn test_invalid_root_tag_offset() {
+    let data = vec![0xFF, 0x00, 0x00];
+    assert_eq!(read_borrowed::<BE>(&data).err().unwrap().offset(), Some(0));
+    assert_eq!(read_owned::<BE, BE>(&data).err().unwrap().offset(), Some(0));
+    assert_eq!(
+        na_nbt::read_owned_strict::<BE, BE>(&data)
+            .err()
+            .unwrap()
+            .offset(),
+        Some(0)
+    );
+    assert_eq!(
+        read_owned_from_reader::<BE, BE>(Cursor::new(&data))
+            .err()
+            .unwrap()
+            .offset(),
+        Some(0)
+    );
+}
+
+#[test]
+fn test_eof_has_no_offset() {
+    let data = vec![0x0A, 0x00];
+    let err = read_owned::<BE, BE>(&data).err().unwrap();
+    assert!(matches!(err, Error::EndOfFile));
+    assert_eq!(err.offset(), None);
+}
+
+#[test]
+fn test_truncated_list_header_in_every_reader() {
+    // compound with a list entry "l" whose length is cut off
+    let data = [0x0A, 0x00, 0x00, 0x09, 0x00, 0x01, b'l', 0x01, 0x00, 0x00];
+    let errors = [
+        read_borrowed::<BE>(&data).err().unwrap(),
+        read_owned::<BE, BE>(&data).err().unwrap(),
+        read_owned::<BE, LE>(&data).err().unwrap(),
+        read_owned_from_reader::<BE, LE>(Cursor::new(&data))
+            .err()
+            .unwrap(),
+        na_nbt::read_shared_lazy::<BE>(bytes::Bytes::copy_from_slice(&data))
+            .unwrap()
+            .get("l")
+            .err()
+            .unwrap(),
+    ];
+    for err in errors {
+        assert!(matches!(
+            err,
+            Error::Truncated {
+                context: "list header"
+            }
+        ));
+        assert!(err.is_end_of_input());
+        assert_eq!(err.to_string(), "unexpected end of input in list header");
+    }
+
+    // running out inside the elements is still EndOfFile
+    let data = [0x09, 0x00, 0x00, 0x03, 0x00, 0x00, 0x00, 0x02, 0x00];
+    let err = read_owned_from_reader::<BE, BE>(Cursor::new(&data))
+        .err()
+        .unwrap();
+    assert!(!matches!(err, Error::Truncated { .. }));
+    let err = read_borrowed::<BE>(&data).err().unwrap();
+    assert!(matches!(err, Error::EndOfFile));
+    assert!(err.is_end_of_input());
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn test_truncated_list_header_serde() {
+    let data = [0x09, 0x00, 0x00, 0x01, 0x00];
+    let err = na_nbt::from_slice_be::<Vec<i8>>(&data).err().unwrap();
+    assert!(matches!(
+        err,
+        Error::Truncated {
+            context: "list header"
+        }
+    ));
+}
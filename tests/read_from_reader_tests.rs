@@ -0,0 +1,210 @@
+use std::io::{self, BufReader, Cursor, Read};
+
+use na_nbt::{
+    Error, OwnedCompound, OwnedList, OwnedValue, ReadLimits, read_owned,
+    read_owned_from_buf_reader, read_owned_from_reader, read_owned_from_reader_with_limits,
+};
+use zerocopy::byteorder::{BigEndian as BE, LittleEndian as LE};
+
+/// Hands out at most `chunk` bytes per `read` call.
+struct ShortReader<'a> {
+    data: &'a [u8],
+    chunk: usize,
+}
+
+impl Read for ShortReader<'_> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let n = self.chunk.min(buf.len()).min(self.data.len());
+        buf[..n].copy_from_slice(&self.data[..n]);
+        self.data = &self.data[n..];
+        Ok(n)
+    }
+}
+
+struct FailingReader;
+
+impl Read for FailingReader {
+    fn read(&mut self, _buf: &mut [u8]) -> io::Result<usize> {
+        Err(io::Error::other("boom"))
+    }
+}
+
+fn create_nbt_be() -> Vec<u8> {
+    let mut list = OwnedList::<BE>::default();
+    list.push(1i64);
+    list.push(2i64);
+
+    let mut root = OwnedCompound::<BE>::default();
+    root.insert("name", "Steve");
+    root.insert("health", 20i32);
+    root.insert("ids", list);
+    root.insert("bytes", vec![1i8, 2, 3]);
+    OwnedValue::Compound(root).write_to_vec::<BE>().unwrap()
+}
+
+#[test]
+fn read_from_cursor_matches_read_owned() {
+    let data = create_nbt_be();
+    let mut cursor = Cursor::new(&data);
+    let value = read_owned_from_reader::<BE, BE>(&mut cursor).unwrap();
+    assert_eq!(cursor.position() as usize, data.len());
+
+    let expected = read_owned::<BE, BE>(&data).unwrap();
+    assert_eq!(
+        value.write_to_vec::<BE>().unwrap(),
+        expected.write_to_vec::<BE>().unwrap()
+    );
+}
+
+#[test]
+fn read_from_short_reader() {
+    let data = create_nbt_be();
+    for chunk in [1, 2, 3, 7] {
+        let value = read_owned_from_reader::<BE, LE>(ShortReader { data: &data, chunk }).unwrap();
+        assert_eq!(value.write_to_vec::<BE>().unwrap(), data);
+        let name = value.get("name").unwrap();
+        assert_eq!(name.as_string().unwrap().decode(), "Steve");
+    }
+}
+
+#[test]
+fn read_from_truncated_reader() {
+    let data = create_nbt_be();
+    for len in [0, 1, 3, data.len() / 2, data.len() - 1] {
+        let reader = ShortReader {
+            data: &data[..len],
+            chunk: 4,
+        };
+        match read_owned_from_reader::<BE, BE>(reader) {
+            Err(Error::IO(e)) => assert_eq!(e.kind(), io::ErrorKind::UnexpectedEof),
+            Err(Error::EndOfFile | Error::Truncated { .. }) => {}
+            other => panic!(
+                "expected an end-of-stream error at {len}, got {:?}",
+                other.err()
+            ),
+        }
+    }
+}
+
+#[test]
+fn read_from_failing_reader() {
+    match read_owned_from_reader::<BE, BE>(FailingReader) {
+        Err(Error::IO(e)) => assert_eq!(e.to_string(), "boom"),
+        other => panic!("expected IO error, got {:?}", other.err()),
+    }
+}
+
+#[test]
+fn read_huge_declared_lengths_without_data() {
+    // each claims far more data than follows; none may reserve it up front
+    let cases: [&[u8]; 4] = [
+        &[0x07, 0x00, 0x00, 0x7f, 0xff, 0xff, 0xff, 1, 2, 3],
+        &[0x0c, 0x00, 0x00, 0x7f, 0xff, 0xff, 0xff, 1, 2, 3],
+        &[0x09, 0x00, 0x00, 0x04, 0x7f, 0xff, 0xff, 0xff, 1, 2, 3],
+        &[0x09, 0x00, 0x00, 0x0a, 0x7f, 0xff, 0xff, 0xff, 0x00, 0x00],
+    ];
+    for data in cases {
+        match read_owned_from_reader::<BE, LE>(data) {
+            Err(Error::IO(e)) => assert_eq!(e.kind(), io::ErrorKind::UnexpectedEof),
+            other => panic!("expected an end-of-stream error, got {:?}", other.err()),
+        }
+    }
+}
+
+#[test]
+fn read_with_limits() {
+    let data = create_nbt_be();
+
+    let limits = ReadLimits {
+        max_bytes: data.len(),
+        ..ReadLimits::default()
+    };
+    let value = read_owned_from_reader_with_limits::<BE, BE>(data.as_slice(), limits).unwrap();
+    assert_eq!(value.write_to_vec::<BE>().unwrap(), data);
+
+    let limits = ReadLimits {
+        max_bytes: data.len() - 1,
+        ..ReadLimits::default()
+    };
+    match read_owned_from_reader_with_limits::<BE, BE>(data.as_slice(), limits) {
+        Err(Error::IO(e)) => assert_eq!(e.kind(), io::ErrorKind::InvalidData),
+        Err(Error::ListTooLong(_)) => {}
+        other => panic!("expected a limit error, got {:?}", other.err()),
+    }
+}
+
+#[test]
+fn read_with_limits_rejects_declared_length() {
+    let limits = ReadLimits {
+        max_bytes: 1024,
+        ..ReadLimits::default()
+    };
+
+    let data = [0x0b, 0x00, 0x00, 0x00, 0x00, 0x01, 0x00];
+    match read_owned_from_reader_with_limits::<BE, BE>(&data[..], limits) {
+        Err(Error::ListTooLong(len)) => assert_eq!(len, 256),
+        other => panic!("expected ListTooLong, got {:?}", other.err()),
+    }
+
+    // a list of 2000 compounds needs at least 2000 bytes
+    let data = [0x09, 0x00, 0x00, 0x0a, 0x00, 0x00, 0x07, 0xd0];
+    match read_owned_from_reader_with_limits::<BE, BE>(&data[..], limits) {
+        Err(Error::ListTooLong(len)) => assert_eq!(len, 2000),
+        other => panic!("expected ListTooLong, got {:?}", other.err()),
+    }
+}
+
+#[test]
+fn read_from_buf_reader_stops_after_document() {
+    let data = create_nbt_be();
+    let mut stream = data.clone();
+    stream.extend_from_slice(&data);
+    stream.extend_from_slice(&[0xde, 0xad]);
+
+    let mut cursor = Cursor::new(&stream);
+    let (first, len) = read_owned_from_buf_reader::<BE, BE>(&mut cursor).unwrap();
+    assert_eq!(len, data.len());
+    assert_eq!(cursor.position() as usize, data.len());
+    assert_eq!(first.write_to_vec::<BE>().unwrap(), data);
+
+    let (second, len) = read_owned_from_buf_reader::<BE, LE>(&mut cursor).unwrap();
+    assert_eq!(len, data.len());
+    assert_eq!(second.write_to_vec::<BE>().unwrap(), data);
+
+    let mut rest = Vec::new();
+    cursor.read_to_end(&mut rest).unwrap();
+    assert_eq!(rest, [0xde, 0xad]);
+}
+
+#[test]
+fn read_from_buf_reader_over_short_reads() {
+    let data = create_nbt_be();
+    let mut stream = data.clone();
+    stream.push(0x00);
+    let mut reader = BufReader::with_capacity(
+        3,
+        ShortReader {
+            data: &stream,
+            chunk: 2,
+        },
+    );
+    let (value, len) = read_owned_from_buf_reader::<BE, BE>(&mut reader).unwrap();
+    assert_eq!(len, data.len());
+    assert_eq!(value.write_to_vec::<BE>().unwrap(), data);
+
+    // the trailing end tag is still there to read as a document of its own
+    let (end, len) = read_owned_from_buf_reader::<BE, BE>(&mut reader).unwrap();
+    assert!(matches!(end, OwnedValue::End));
+    assert_eq!(len, 1);
+}
+
+#[test]
+fn read_from_buf_reader_truncated() {
+    let data = create_nbt_be();
+    let mut cursor = Cursor::new(&data[..data.len() - 1]);
+    match read_owned_from_buf_reader::<BE, BE>(&mut cursor) {
+        Err(Error::IO(e)) => assert_eq!(e.kind(), io::ErrorKind::UnexpectedEof),
+        Err(Error::EndOfFile | Error::Truncated { .. }) => {}
+        other => panic!("expected an end-of-stream error, got {:?}", other.err()),
+    }
+}
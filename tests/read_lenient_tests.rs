@@ -0,0 +1,112 @@
+//! Tests for `read_owned_lenient`, which stops at unknown tag types.
+
+use na_nbt::{Error, OwnedValue, read_owned, read_owned_lenient};
+use zerocopy::byteorder::{BigEndian as BE, LittleEndian as LE};
+
+#[test]
+fn known_tags_read_in_full() {
+    let data = [0x0a, 0x00, 0x00, 0x01, 0x00, 0x01, b'a', 0x05, 0x00];
+    let (value, truncated) = read_owned_lenient::<BE, BE>(&data).unwrap();
+    assert!(!truncated);
+    assert_eq!(
+        value.write_to_vec::<BE>().unwrap(),
+        read_owned::<BE, BE>(&data)
+            .unwrap()
+            .write_to_vec::<BE>()
+            .unwrap()
+    );
+}
+
+#[test]
+fn unknown_entry_mid_compound() {
+    let data = [
+        0x0a, 0x00, 0x00, //
+        0x03, 0x00, 0x01, b'a', 0x00, 0x00, 0x00, 0x07, // Int "a" = 7
+        0x14, 0x00, 0x01, b'x', 0x01, 0x02, 0x03, // tag 20, unknown
+        0x01, 0x00, 0x01, b'b', 0x01, // Byte "b" = 1, after the cut
+        0x00,
+    ];
+    assert!(read_owned::<BE, BE>(&data).is_err());
+
+    let (value, truncated) = read_owned_lenient::<BE, LE>(&data).unwrap();
+    assert!(truncated);
+    assert_eq!(value.get("a").and_then(|v| v.as_int()), Some(7));
+    assert!(value.get("x").is_none());
+    assert!(value.get("b").is_none());
+}
+
+#[test]
+fn unknown_tag_at_end_of_input() {
+    let data = [0x0a, 0x00, 0x00, 0x01, 0x00, 0x01, b'a', 0x05, 0x63];
+    let (value, truncated) = read_owned_lenient::<BE, BE>(&data).unwrap();
+    assert!(truncated);
+    assert_eq!(value.get("a").and_then(|v| v.as_byte()), Some(5));
+}
+
+#[test]
+fn nested_unknown_closes_every_container() {
+    // { "l": [ { "k": 1b }, { "k": 2b, <tag 13> ... } ], "after": 3b }
+    let data = [
+        0x0a, 0x00, 0x00, //
+        0x09, 0x00, 0x01, b'l', 0x0a, 0x00, 0x00, 0x00, 0x03, // List "l" of 3 compounds
+        0x01, 0x00, 0x01, b'k', 0x01, 0x00, //
+        0x01, 0x00, 0x01, b'k', 0x02, //
+        0x0d, 0x00, 0x00, 0xff, //
+        0x00, //
+        0x01, 0x00, 0x05, b'a', b'f', b't', b'e', b'r', 0x03, //
+        0x00,
+    ];
+    let (value, truncated) = read_owned_lenient::<BE, BE>(&data).unwrap();
+    assert!(truncated);
+    let list = value.get("l").unwrap();
+    let list = list.as_list().unwrap();
+    assert_eq!(list.len(), 2);
+    assert_eq!(
+        list.get(1).unwrap().get("k").and_then(|v| v.as_byte()),
+        Some(2)
+    );
+    assert!(value.get("after").is_none());
+
+    // the result is a valid document of its own
+    let bytes = value.write_to_vec::<BE>().unwrap();
+    assert!(read_owned::<BE, BE>(&bytes).is_ok());
+}
+
+#[test]
+fn unknown_list_element_type_reads_as_empty() {
+    let data = [
+        0x0a, 0x00, 0x00, //
+        0x01, 0x01, 0x00, b'a', 0x05, //
+        0x09, 0x01, 0x00, b'l', 0x20, 0x02, 0x00, 0x00, 0x00, 0xaa,
+        0xbb, // List "l" of tag 32
+        0x00,
+    ];
+    let (value, truncated) = read_owned_lenient::<LE, LE>(&data).unwrap();
+    assert!(truncated);
+    assert_eq!(value.get("a").and_then(|v| v.as_byte()), Some(5));
+    assert_eq!(value.get("l").unwrap().as_list().unwrap().len(), 0);
+}
+
+#[test]
+fn unknown_root_is_an_error() {
+    let err = read_owned_lenient::<BE, BE>(&[0x20, 0x00, 0x00])
+        .err()
+        .unwrap();
+    assert!(matches!(err.kind(), Error::InvalidTagType(0x20)));
+    assert_eq!(err.offset(), Some(0));
+
+    // errors before the unknown tag are still reported
+    let data = [0x0a, 0x00, 0x00, 0x03, 0x00, 0x01, b'a', 0x00];
+    assert!(matches!(
+        read_owned_lenient::<BE, BE>(&data),
+        Err(Error::EndOfFile)
+    ));
+}
+
+#[test]
+fn lenient_result_is_owned_value() {
+    let data = [0x0a, 0x00, 0x00, 0x7f];
+    let (value, truncated): (OwnedValue<BE>, bool) = read_owned_lenient::<BE, BE>(&data).unwrap();
+    assert!(truncated);
+    assert!(value.is_compound());
+}
@@ -0,0 +1,147 @@
+#![cfg(feature = "compression")]
+
+use std::io::{Cursor, Read, Seek, SeekFrom, Write};
+
+use flate2::{
+    Compression,
+    write::{GzEncoder, ZlibEncoder},
+};
+use na_nbt::{Error, OwnedCompound, OwnedValue, RegionReader};
+use zerocopy::byteorder::{BigEndian as BE, LittleEndian as LE};
+
+fn chunk_nbt(x: i32, z: i32) -> Vec<u8> {
+    let mut compound: OwnedCompound<BE> = OwnedCompound::default();
+    compound.insert("xPos", x);
+    compound.insert("zPos", z);
+    OwnedValue::Compound(compound).write_to_vec::<BE>().unwrap()
+}
+
+/// Builds a region file with one chunk per entry, each in its own sectors.
+fn region_file(chunks: &[(i32, i32, u8, Vec<u8>)]) -> Vec<u8> {
+    let mut file = vec![0u8; 8192];
+    for (x, z, kind, payload) in chunks {
+        let index = (*x as usize & 31) + (*z as usize & 31) * 32;
+        let offset = file.len() / 4096;
+        let mut body = ((payload.len() + 1) as u32).to_be_bytes().to_vec();
+        body.push(*kind);
+        body.extend_from_slice(payload);
+        let sectors = body.len().div_ceil(4096);
+        body.resize(sectors * 4096, 0);
+        file.extend_from_slice(&body);
+
+        let location = ((offset as u32) << 8) | sectors as u32;
+        file[index * 4..index * 4 + 4].copy_from_slice(&location.to_be_bytes());
+        file[4096 + index * 4..4096 + index * 4 + 4]
+            .copy_from_slice(&(1_700_000_000 + index as u32).to_be_bytes());
+    }
+    file
+}
+
+fn gzip(data: &[u8]) -> Vec<u8> {
+    let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+    encoder.write_all(data).unwrap();
+    encoder.finish().unwrap()
+}
+
+fn zlib(data: &[u8]) -> Vec<u8> {
+    let mut encoder = ZlibEncoder::new(Vec::new(), Compression::default());
+    encoder.write_all(data).unwrap();
+    encoder.finish().unwrap()
+}
+
+/// A reader that counts the bytes read through it.
+struct Tracking {
+    inner: Cursor<Vec<u8>>,
+    read: usize,
+}
+
+impl Read for Tracking {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let n = self.inner.read(buf)?;
+        self.read += n;
+        Ok(n)
+    }
+}
+
+impl Seek for Tracking {
+    fn seek(&mut self, pos: SeekFrom) -> std::io::Result<u64> {
+        self.inner.seek(pos)
+    }
+}
+
+#[test]
+fn test_read_chunks_of_each_compression() {
+    let file = region_file(&[
+        (0, 0, 2, zlib(&chunk_nbt(0, 0))),
+        (5, 1, 1, gzip(&chunk_nbt(5, 1))),
+        (31, 31, 3, chunk_nbt(31, 31)),
+    ]);
+    let mut region = RegionReader::from_reader(Cursor::new(file)).unwrap();
+
+    for (x, z) in [(0, 0), (5, 1), (31, 31)] {
+        assert!(region.has_chunk(x, z));
+        let chunk: OwnedValue<LE> = region.read_chunk::<LE>(x, z).unwrap().unwrap();
+        assert_eq!(chunk.get("xPos").unwrap().as_int(), Some(x));
+        assert_eq!(chunk.get("zPos").unwrap().as_int(), Some(z));
+    }
+    assert_eq!(region.timestamp(5, 1), 1_700_000_000 + 5 + 32);
+
+    // absolute chunk coordinates map into the region
+    assert!(region.has_chunk(-1, -1));
+    assert!(region.read_chunk::<BE>(37, 33).unwrap().is_some());
+
+    assert!(!region.has_chunk(1, 0));
+    assert!(region.read_chunk::<BE>(1, 0).unwrap().is_none());
+    assert_eq!(region.timestamp(1, 0), 0);
+}
+
+#[test]
+fn test_reads_only_requested_chunk() {
+    let big = vec![0u8; 100_000];
+    let file = region_file(&[(0, 0, 3, chunk_nbt(0, 0)), (1, 0, 3, big)]);
+    let mut region = RegionReader::from_reader(Tracking {
+        inner: Cursor::new(file),
+        read: 0,
+    })
+    .unwrap();
+    region.read_chunk_bytes(0, 0).unwrap().unwrap();
+
+    let read = region.into_inner().read;
+    assert!(read < 8192 + 4096, "read {read} bytes");
+}
+
+#[test]
+fn test_errors() {
+    // truncated header
+    let Err(err) = RegionReader::from_reader(Cursor::new(vec![0u8; 100])) else {
+        panic!("expected an error");
+    };
+    assert!(matches!(err, Error::IO(_)));
+
+    // unknown compression type
+    let file = region_file(&[(0, 0, 4, chunk_nbt(0, 0))]);
+    let mut region = RegionReader::from_reader(Cursor::new(file)).unwrap();
+    assert!(matches!(
+        region.read_chunk_bytes(0, 0),
+        Err(Error::Message(_))
+    ));
+
+    // stored in an external file
+    let file = region_file(&[(0, 0, 0x82, Vec::new())]);
+    let mut region = RegionReader::from_reader(Cursor::new(file)).unwrap();
+    assert!(matches!(
+        region.read_chunk_bytes(0, 0),
+        Err(Error::Message(_))
+    ));
+
+    // corrupt compressed data
+    let file = region_file(&[(0, 0, 2, vec![1, 2, 3, 4])]);
+    let mut region = RegionReader::from_reader(Cursor::new(file)).unwrap();
+    assert!(matches!(region.read_chunk::<BE>(0, 0), Err(Error::IO(_))));
+
+    // sector table points past the end of the file
+    let mut file = vec![0u8; 8192];
+    file[..4].copy_from_slice(&((2u32 << 8) | 1).to_be_bytes());
+    let mut region = RegionReader::from_reader(Cursor::new(file)).unwrap();
+    assert!(matches!(region.read_chunk_bytes(0, 0), Err(Error::IO(_))));
+}
@@ -0,0 +1,114 @@
+//! Tests for in-place `replace` on compounds and `set` on lists.
+
+use na_nbt::{OwnedCompound, OwnedList, OwnedValue, read_owned};
+use zerocopy::byteorder::{BigEndian as BE, LittleEndian as LE};
+
+fn keys(compound: &OwnedCompound<BE>) -> Vec<String> {
+    compound
+        .iter()
+        .map(|(key, _)| key.decode().into_owned())
+        .collect()
+}
+
+#[test]
+fn test_compound_replace_keeps_slot() {
+    let mut compound: OwnedCompound<BE> = OwnedCompound::default();
+    compound.insert("a", 1i8);
+    compound.insert("b", 2i32);
+    compound.insert("c", "three");
+
+    // same size, larger and smaller payloads
+    assert_eq!(compound.replace("b", 20i32).unwrap().as_int(), Some(2));
+    assert_eq!(compound.replace("a", "one").unwrap().as_byte(), Some(1));
+    assert_eq!(
+        compound
+            .replace("c", 3i64)
+            .unwrap()
+            .as_string()
+            .unwrap()
+            .decode(),
+        "three"
+    );
+    assert_eq!(keys(&compound), ["a", "b", "c"]);
+
+    // absent keys are appended
+    assert!(compound.replace("d", 4.0f64).is_none());
+    assert_eq!(keys(&compound), ["a", "b", "c", "d"]);
+
+    let value = OwnedValue::Compound(compound);
+    assert_eq!(value.get("a").unwrap().as_string().unwrap().decode(), "one");
+    assert_eq!(value.get("b").unwrap().as_int(), Some(20));
+    assert_eq!(value.get("c").unwrap().as_long(), Some(3));
+    assert_eq!(value.get("d").unwrap().as_double(), Some(4.0));
+
+    // the layout is still valid after shifting entries around
+    let bytes = value.write_to_vec::<BE>().unwrap();
+    let round: OwnedValue<LE> = read_owned::<BE, LE>(&bytes).unwrap();
+    assert!(na_nbt::values_equal(&value, &round));
+}
+
+#[test]
+fn test_mutable_compound_replace() {
+    let mut inner: OwnedCompound<LE> = OwnedCompound::default();
+    inner.insert("x", 1i32);
+    inner.insert("y", 2i32);
+    let mut root: OwnedCompound<LE> = OwnedCompound::default();
+    root.insert("inner", inner);
+    let mut root = OwnedValue::Compound(root);
+
+    let mut inner = root.get_mut("inner").unwrap();
+    let inner = inner.as_compound_mut().unwrap();
+    let old = inner.replace("x", OwnedValue::from_vec3(1.0, 2.0, 3.0));
+    assert_eq!(old.unwrap().as_int(), Some(1));
+
+    let inner = root.get("inner").unwrap();
+    assert_eq!(inner.get("x").unwrap().as_list().unwrap().len(), 3);
+    assert_eq!(inner.get("y").unwrap().as_int(), Some(2));
+}
+
+#[test]
+#[cfg_attr(
+    all(feature = "strict", debug_assertions),
+    should_panic(expected = "type mismatch")
+)]
+fn test_list_set() {
+    let mut list: OwnedList<BE> = OwnedList::default();
+    list.push("a");
+    list.push("b");
+
+    let old = list.set(1, "c").unwrap();
+    assert_eq!(old.as_string().unwrap().decode(), "b");
+    assert_eq!(list.get(1).unwrap().as_string().unwrap().decode(), "c");
+
+    // mismatched tag and out of bounds are rejected
+    assert!(list.set(0, 1i32).is_none());
+    assert!(list.set(2, "d").is_none());
+    assert_eq!(list.len(), 2);
+    assert_eq!(list.get(0).unwrap().as_string().unwrap().decode(), "a");
+}
+
+#[test]
+#[cfg_attr(
+    all(feature = "strict", debug_assertions),
+    should_panic(expected = "type mismatch")
+)]
+fn test_mutable_list_set() {
+    let mut list: OwnedList<LE> = OwnedList::default();
+    for value in [1i32, 2, 3] {
+        list.push(value);
+    }
+    let mut value = OwnedValue::List(list);
+    let mut view = value.to_mut();
+    let list = view.as_list_mut().unwrap();
+
+    assert_eq!(list.set(0, 10i32).unwrap().as_int(), Some(1));
+    assert!(list.set(1, 2i64).is_none());
+
+    let ints: Vec<i32> = value
+        .as_list()
+        .unwrap()
+        .iter()
+        .map(|v| v.as_int().unwrap())
+        .collect();
+    assert_eq!(ints, [10, 2, 3]);
+}
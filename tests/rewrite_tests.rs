@@ -0,0 +1,137 @@
+//! Tests for `OwnedValue::rewrite`.
+
+use na_nbt::{OwnedCompound, OwnedList, OwnedValue, RewriteAction};
+use zerocopy::byteorder::BigEndian as BE;
+
+fn block(id: &str) -> OwnedCompound<BE> {
+    let mut compound = OwnedCompound::default();
+    compound.insert("id", id);
+    compound.insert("count", 1i32);
+    compound
+}
+
+fn is_block(value: &na_nbt::MutableValue<'_, BE>, id: &str) -> bool {
+    value
+        .get("id")
+        .and_then(|id| id.as_string().map(|s| s.decode().into_owned()))
+        .is_some_and(|found| found == id)
+}
+
+#[test]
+fn test_replace_nested_matches() {
+    let mut inner: OwnedCompound<BE> = OwnedCompound::default();
+    inner.insert("held", block("minecraft:stone"));
+    let mut blocks: OwnedList<BE> = OwnedList::default();
+    blocks.push(block("minecraft:stone"));
+    blocks.push(block("minecraft:dirt"));
+    blocks.push(block("minecraft:stone"));
+    let mut root: OwnedCompound<BE> = OwnedCompound::default();
+    root.insert("inner", inner);
+    root.insert("blocks", blocks);
+    root.insert("top", block("minecraft:stone"));
+    let mut root = OwnedValue::Compound(root);
+
+    let mut visited = 0;
+    root.rewrite(|value| {
+        visited += 1;
+        if is_block(value, "minecraft:stone") {
+            RewriteAction::Replace(block("minecraft:granite").into())
+        } else {
+            RewriteAction::Keep
+        }
+    });
+
+    let id = |value: na_nbt::ImmutableValue<'_, BE>| {
+        value
+            .get("id")
+            .unwrap()
+            .as_string()
+            .unwrap()
+            .decode()
+            .into_owned()
+    };
+    assert_eq!(
+        id(root.get("inner").unwrap().get("held").unwrap()),
+        "minecraft:granite"
+    );
+    assert_eq!(id(root.get("top").unwrap()), "minecraft:granite");
+    let blocks = root.get("blocks").unwrap();
+    let ids: Vec<String> = blocks.as_list().unwrap().iter().map(id).collect();
+    assert_eq!(
+        ids,
+        ["minecraft:granite", "minecraft:dirt", "minecraft:granite"]
+    );
+
+    // replacements are not descended into, so their children went unvisited
+    // root, inner, held, blocks, 3 elements, dirt's id and count, top
+    assert_eq!(visited, 10);
+}
+
+#[test]
+fn test_remove_list_elements_by_predicate() {
+    let mut list: OwnedList<BE> = OwnedList::default();
+    for value in [1i32, 2, 3, 4, 5, 6, 7, 8] {
+        list.push(value);
+    }
+    let mut root: OwnedCompound<BE> = OwnedCompound::default();
+    root.insert("values", list);
+    root.insert("odd", 1i32);
+    let mut root = OwnedValue::Compound(root);
+
+    // removing adjacent elements must not skip any
+    root.rewrite(|value| match value.as_int() {
+        Some(n) if n % 2 == 1 || n == 4 => RewriteAction::Remove,
+        _ => RewriteAction::Keep,
+    });
+
+    let values: Vec<i32> = root
+        .get("values")
+        .unwrap()
+        .as_list()
+        .unwrap()
+        .iter()
+        .map(|v| v.as_int().unwrap())
+        .collect();
+    assert_eq!(values, [2, 6, 8]);
+    assert!(root.get("odd").is_none());
+}
+
+#[test]
+fn test_edit_in_place_and_root_actions() {
+    let mut root = OwnedValue::Compound(block("minecraft:stone"));
+    root.rewrite(|value| {
+        if let Some(n) = value.as_int() {
+            value.set_int(n * 10);
+        }
+        RewriteAction::Keep
+    });
+    assert_eq!(root.get("count").unwrap().as_int(), Some(10));
+
+    root.rewrite(|_| RewriteAction::Replace(OwnedValue::from(5i8)));
+    assert_eq!(root.as_byte(), Some(5));
+
+    root.rewrite(|_| RewriteAction::Remove);
+    assert!(root.is_end());
+}
+
+#[test]
+fn test_replace_keeps_key_order() {
+    let mut root: OwnedCompound<BE> = OwnedCompound::default();
+    root.insert("a", 1i32);
+    root.insert("b", "two");
+    root.insert("c", 3i32);
+    let mut root = OwnedValue::Compound(root);
+
+    root.rewrite(|value| match value.as_string() {
+        Some(_) => RewriteAction::Replace(OwnedValue::from(2i64)),
+        None => RewriteAction::Keep,
+    });
+
+    let compound = root.as_compound().unwrap();
+    let keys: Vec<String> = compound
+        .iter()
+        .map(|(key, _)| key.decode().into_owned())
+        .collect();
+    assert_eq!(keys, ["a", "b", "c"]);
+    assert_eq!(compound.get("b").unwrap().as_long(), Some(2));
+}
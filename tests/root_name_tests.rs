@@ -0,0 +1,81 @@
+//! Tests for `root_name` and `read_owned_named`.
+
+use na_nbt::{read_borrowed, read_owned, read_owned_named};
+use zerocopy::byteorder::{BigEndian as BE, LittleEndian as LE};
+
+fn named(name: &[u8], le: bool) -> Vec<u8> {
+    let len = name.len() as u16;
+    let mut data = vec![10];
+    data.extend_from_slice(&if le {
+        len.to_le_bytes()
+    } else {
+        len.to_be_bytes()
+    });
+    data.extend_from_slice(name);
+    data.extend_from_slice(&[1]);
+    data.extend_from_slice(&if le {
+        1u16.to_le_bytes()
+    } else {
+        1u16.to_be_bytes()
+    });
+    data.extend_from_slice(b"x");
+    data.extend_from_slice(&[5, 0]);
+    data
+}
+
+#[test]
+fn test_borrowed_root_name() {
+    let data = named(b"Level", false);
+    let doc = read_borrowed::<BE>(&data).unwrap();
+    let name = doc.root_name().unwrap();
+    assert_eq!(name.raw_bytes(), b"Level");
+    assert_eq!(name.decode(), "Level");
+    assert_eq!(doc.root().get("x").unwrap().as_byte(), Some(5));
+
+    let data = named(b"", true);
+    let doc = read_borrowed::<LE>(&data).unwrap();
+    assert_eq!(doc.root_name().unwrap().decode(), "");
+}
+
+#[test]
+fn test_borrowed_end_root_has_no_name() {
+    let doc = read_borrowed::<BE>(&[0]).unwrap();
+    assert!(doc.root_name().is_none());
+}
+
+#[test]
+fn test_mutf8_root_name() {
+    // U+0000 is encoded as C0 80 in Modified UTF-8
+    let data = named(&[b'a', 0xc0, 0x80, b'b'], false);
+    let doc = read_borrowed::<BE>(&data).unwrap();
+    assert_eq!(doc.root_name().unwrap().decode(), "a\0b");
+
+    let (name, _) = read_owned_named::<BE, BE>(&data).unwrap();
+    assert_eq!(name, "a\0b");
+}
+
+#[test]
+fn test_read_owned_named() {
+    let data = named(b"Data", true);
+    let (name, value) = read_owned_named::<LE, BE>(&data).unwrap();
+    assert_eq!(name, "Data");
+    assert_eq!(value.get("x").unwrap().as_byte(), Some(5));
+
+    // the value is the same as read_owned's
+    let plain = read_owned::<LE, BE>(&data).unwrap();
+    assert_eq!(
+        value.write_to_vec::<BE>().unwrap(),
+        plain.write_to_vec::<BE>().unwrap()
+    );
+
+    let (name, _) = read_owned_named::<BE, BE>(&[0]).unwrap();
+    assert_eq!(name, "");
+}
+
+#[test]
+fn test_read_owned_named_errors() {
+    let data = named(b"Data", false);
+    assert!(read_owned_named::<BE, BE>(&data[..data.len() - 1]).is_err());
+    assert!(read_owned_named::<BE, BE>(&[]).is_err());
+    assert!(read_owned_named::<BE, BE>(&[10, 0, 9, b'a']).is_err());
+}
@@ -0,0 +1,140 @@
+//! Tests for `schema::validate`.
+
+use std::collections::HashMap;
+
+use bytes::Bytes;
+use na_nbt::{
+    OwnedCompound, OwnedList, OwnedValue, Tag, read_borrowed, read_shared,
+    schema::{Schema, SchemaError, validate},
+};
+use zerocopy::byteorder::{BigEndian as BE, LittleEndian as LE};
+
+fn compound(entries: impl IntoIterator<Item = (&'static str, Schema)>) -> Schema {
+    Schema::Compound(
+        entries
+            .into_iter()
+            .map(|(key, schema)| (key.to_string(), schema))
+            .collect::<HashMap<_, _>>(),
+    )
+}
+
+fn list(element: Schema) -> Schema {
+    Schema::List(Box::new(element))
+}
+
+fn optional(schema: Schema) -> Schema {
+    Schema::Optional(Box::new(schema))
+}
+
+fn player_schema() -> Schema {
+    compound([
+        ("name", Schema::String),
+        ("level", Schema::Int),
+        ("nick", optional(Schema::String)),
+        ("extra", Schema::Any),
+        (
+            "items",
+            list(compound([("id", Schema::String), ("count", Schema::Int)])),
+        ),
+    ])
+}
+
+fn item<O: na_nbt::ByteOrder>(id: &str, count: impl Into<OwnedValue<O>>) -> OwnedCompound<O> {
+    let mut item = OwnedCompound::default();
+    item.insert("id", id);
+    item.insert("count", count.into());
+    item
+}
+
+fn error(path: &str, expected: Tag, found: Option<Tag>) -> SchemaError {
+    SchemaError {
+        path: path.to_string(),
+        expected,
+        found,
+    }
+}
+
+#[test]
+fn test_valid_value_passes() {
+    let mut items: OwnedList<BE> = OwnedList::default();
+    items.push(item::<BE>("minecraft:stone", 64));
+    items.push(item::<BE>("minecraft:dirt", 1));
+    let mut player: OwnedCompound<BE> = OwnedCompound::default();
+    player.insert("name", "Steve");
+    player.insert("level", 30);
+    player.insert("extra", vec![1i8, 2]);
+    player.insert("unknown", 1.5f64);
+    player.insert("items", items);
+    let value = OwnedValue::Compound(player);
+    assert_eq!(validate(&value, &player_schema()), Ok(()));
+
+    // the same check through the readonly and shared readers
+    let bytes = value.write_to_vec::<BE>().unwrap();
+    let doc = read_borrowed::<BE>(&bytes).unwrap();
+    assert_eq!(validate(&doc.root(), &player_schema()), Ok(()));
+    let shared = read_shared::<BE>(Bytes::from(bytes)).unwrap();
+    assert_eq!(validate(&shared, &player_schema()), Ok(()));
+}
+
+#[test]
+fn test_all_mismatches_are_collected_with_paths() {
+    let mut items: OwnedList<LE> = OwnedList::default();
+    items.push(item::<LE>("minecraft:stone", 64));
+    items.push(item::<LE>("minecraft:dirt", 1i64));
+    let mut broken: OwnedCompound<LE> = OwnedCompound::default();
+    broken.insert("count", 1);
+    items.push(broken);
+    let mut player: OwnedCompound<LE> = OwnedCompound::default();
+    player.insert("level", "thirty");
+    player.insert("nick", 7i8);
+    player.insert("items", items);
+    let value = OwnedValue::Compound(player);
+
+    let errors = validate(&value, &player_schema()).unwrap_err();
+    assert_eq!(
+        errors,
+        [
+            error("items[1].count", Tag::Int, Some(Tag::Long)),
+            error("items[2].id", Tag::String, None),
+            error("level", Tag::Int, Some(Tag::String)),
+            error("name", Tag::String, None),
+            error("nick", Tag::String, Some(Tag::Byte)),
+        ]
+    );
+    assert_eq!(
+        errors[0].to_string(),
+        "items[1].count: expected TAG_Int, found TAG_Long"
+    );
+    assert_eq!(errors[1].to_string(), "items[2].id: missing TAG_String");
+}
+
+#[test]
+fn test_root_and_nested_lists() {
+    let value: OwnedValue<BE> = OwnedValue::from(5);
+    assert_eq!(
+        validate(&value, &Schema::String).unwrap_err(),
+        [error("", Tag::String, Some(Tag::Int))]
+    );
+    assert_eq!(
+        validate(&value, &Schema::String).unwrap_err()[0].to_string(),
+        "root: expected TAG_String, found TAG_Int"
+    );
+    assert_eq!(validate(&value, &optional(Schema::Int)), Ok(()));
+    assert_eq!(validate(&value, &Schema::Any), Ok(()));
+
+    let mut inner: OwnedList<BE> = OwnedList::default();
+    inner.push(1);
+    inner.push(2);
+    let mut odd: OwnedList<BE> = OwnedList::default();
+    odd.push("x");
+    let mut outer: OwnedList<BE> = OwnedList::default();
+    outer.push(inner);
+    outer.push(odd);
+    outer.push(OwnedList::<BE>::default());
+    let value = OwnedValue::List(outer);
+    assert_eq!(
+        validate(&value, &list(list(Schema::Int))).unwrap_err(),
+        [error("[1][0]", Tag::Int, Some(Tag::String))]
+    );
+    assert_eq!(validate(&value, &list(Schema::Any)), Ok(()));
+}
@@ -100,3 +100,52 @@ fn test_shared_value_access() {
     assert_eq!(sum, 3);
 }
 
+
+fn nested_shared_nbt() -> Bytes {
+    use na_nbt::{OwnedCompound, OwnedValue};
+
+    let mut pos = OwnedCompound::<BigEndian>::default();
+    pos.insert("x", 3i32);
+    let mut player = OwnedCompound::<BigEndian>::default();
+    player.insert("name", "Steve");
+    player.insert("pos", pos);
+    let mut root = OwnedCompound::<BigEndian>::default();
+    root.insert("version", 1i32);
+    root.insert("player", player);
+    root.insert("tail", "end");
+    Bytes::from(OwnedValue::Compound(root).write_to_vec::<BigEndian>().unwrap())
+}
+
+#[test]
+fn test_into_shared_child_is_zero_copy() {
+    let bytes = nested_shared_nbt();
+    let range = bytes.as_ptr_range();
+    let root = read_shared::<BigEndian>(bytes.clone()).unwrap();
+
+    let player = root.into_shared_child(&["player"]).unwrap();
+    let name = player.get("name").unwrap();
+    let name = name.as_string().unwrap();
+    assert_eq!(name.decode(), "Steve");
+    assert!(range.contains(&name.raw_bytes().as_ptr()));
+
+    // the child outlives the parent value and the caller's handle
+    drop(bytes);
+    let handle = thread::spawn(move || player.get("pos").and_then(|p| p.get("x")).and_then(|x| x.as_int()));
+    assert_eq!(handle.join().unwrap(), Some(3));
+}
+
+#[test]
+fn test_into_shared_child_paths() {
+    let root = read_shared::<BigEndian>(nested_shared_nbt()).unwrap();
+
+    let pos = root.clone().into_shared_child(&["player", "pos"]).unwrap();
+    assert_eq!(pos.get("x").and_then(|x| x.as_int()), Some(3));
+
+    let version = root.clone().into_shared_child(&["version"]).unwrap();
+    assert_eq!(version.as_int(), Some(1));
+
+    assert!(root.clone().into_shared_child(&[]).unwrap().is_compound());
+    assert!(root.clone().into_shared_child(&["missing"]).is_none());
+    assert!(root.clone().into_shared_child(&["version", "x"]).is_none());
+    assert!(root.into_shared_child(&["player", "name", "x"]).is_none());
+}
@@ -0,0 +1,75 @@
+//! Tests for `shrink_to_fit` and `compact`.
+
+use na_nbt::{OwnedCompound, OwnedList, OwnedValue, TypedListBuilder};
+use zerocopy::byteorder::BigEndian as BE;
+
+#[test]
+fn test_list_shrink_to_fit() {
+    let mut list: OwnedList<BE> = OwnedList::default();
+    for i in 0..1000 {
+        list.push(i as i64);
+    }
+    for _ in 0..990 {
+        list.remove(0);
+    }
+    let before = list.memory_size();
+    list.shrink_to_fit();
+    assert!(list.memory_size() < before);
+    assert_eq!(list.len(), 10);
+    assert_eq!(list.get(0).unwrap().as_long(), Some(990));
+    assert_eq!(list.get(9).unwrap().as_long(), Some(999));
+}
+
+#[test]
+fn test_compound_shrink_to_fit() {
+    let mut compound: OwnedCompound<BE> = OwnedCompound::default();
+    for i in 0..1000 {
+        compound.insert(&format!("key{i}"), i);
+    }
+    for i in 10..1000 {
+        compound.remove(&format!("key{i}"));
+    }
+    let before = compound.memory_size();
+    compound.shrink_to_fit();
+    assert!(compound.memory_size() < before);
+    assert_eq!(compound.get("key9").unwrap().as_int(), Some(9));
+    assert!(compound.get("key10").is_none());
+}
+
+#[test]
+fn test_typed_list_shrink_to_fit() {
+    let mut builder = TypedListBuilder::<BE, i32>::with_capacity(1000);
+    builder.extend([1, 2, 3]);
+    let before = builder.build().memory_size();
+
+    let mut builder = TypedListBuilder::<BE, i32>::with_capacity(1000);
+    builder.extend([1, 2, 3]);
+    builder.shrink_to_fit();
+    let list = builder.build();
+    assert!(list.memory_size() < before);
+    assert_eq!(list.get(2).unwrap().as_int(), Some(3));
+}
+
+#[test]
+fn test_compact_is_recursive() {
+    let mut inner: OwnedList<BE> = OwnedList::default();
+    for i in 0..1000 {
+        inner.push(i);
+    }
+    for _ in 0..999 {
+        inner.remove(0);
+    }
+    let mut compound: OwnedCompound<BE> = OwnedCompound::default();
+    compound.insert("inner", inner);
+    compound.insert("name", "value");
+    // shrinking the outer buffer alone leaves the nested list's capacity
+    compound.shrink_to_fit();
+    let mut value = OwnedValue::Compound(compound);
+    let bytes = value.write_to_vec::<BE>().unwrap();
+    let shallow = value.memory_size();
+    assert!(shallow > 1000 * 4);
+
+    value.compact();
+    assert!(value.memory_size() < shallow);
+    assert_eq!(value.write_to_vec::<BE>().unwrap(), bytes);
+}
@@ -0,0 +1,238 @@
+//! Tests for SNBT text and the serde entry points built on it.
+
+use std::collections::BTreeMap;
+
+use na_nbt::{
+    Error, OwnedCompound, OwnedList, OwnedValue, Tag, from_snbt, from_snbt_str, to_snbt,
+    to_snbt_string, values_equal,
+};
+use serde::{Deserialize, Serialize};
+use zerocopy::byteorder::BigEndian as BE;
+
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+struct TestCompound {
+    byte: i8,
+    short: i16,
+    int: i32,
+    long: i64,
+    float: f32,
+    double: f64,
+    flag: bool,
+    name: String,
+    tags: Vec<String>,
+    scores: Vec<i32>,
+    #[serde(with = "na_nbt::int_array")]
+    pos: Vec<i32>,
+    #[serde(with = "na_nbt::long_array")]
+    uuid: Vec<i64>,
+    nested: Nested,
+    map: BTreeMap<String, f64>,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+struct Nested {
+    id: String,
+    count: i8,
+}
+
+fn fixture() -> TestCompound {
+    TestCompound {
+        byte: -128,
+        short: 1234,
+        int: i32::MIN,
+        long: i64::MAX,
+        float: 0.1,
+        double: -2.5e-300,
+        flag: true,
+        name: "say \"hi\"\\\n\ttab 日本".to_string(),
+        tags: vec!["a".into(), "".into(), "with space".into()],
+        scores: vec![1, -2, 3],
+        pos: vec![10, 64, -10],
+        uuid: vec![i64::MIN, 42],
+        nested: Nested {
+            id: "minecraft:stone".into(),
+            count: 64,
+        },
+        map: BTreeMap::from([("x.y".into(), 1.0), ("needs quotes".into(), f64::MAX)]),
+    }
+}
+
+#[test]
+fn test_serde_round_trip() {
+    let original = fixture();
+    let snbt = to_snbt_string(&original).unwrap();
+    let read: TestCompound = from_snbt_str(&snbt).unwrap();
+    assert_eq!(read, original);
+}
+
+#[test]
+fn test_serde_output() {
+    let snbt = to_snbt_string(&fixture()).unwrap();
+    assert!(snbt.starts_with("{byte:-128b,short:1234s,int:-2147483648,long:9223372036854775807L,"));
+    assert!(snbt.contains(r#"name:"say \"hi\"\\\n\ttab 日本""#));
+    assert!(snbt.contains("pos:[I;10,64,-10]"));
+    assert!(snbt.contains("uuid:[L;-9223372036854775808L,42L]"));
+    assert!(snbt.contains(r#"nested:{id:"minecraft:stone",count:64b}"#));
+    assert!(snbt.contains(r#"map:{"needs quotes":"#));
+    assert!(snbt.contains("x.y:1.0d"));
+}
+
+#[test]
+fn test_parse_every_tag() {
+    let value = from_snbt::<BE>(
+        r#"{
+            b: 1b, s: -2S, i: 3, l: 4l, f: 1.5F, d: 2.5D, d2: 3.5, e: 1e3,
+            yes: true, no: FALSE,
+            q: "double \"quoted\"", sq: 'single \'', word: stone,
+            list: [1s, 2s], empty: [], nested: [[1], []],
+            ba: [B; 1b, -2b], ia: [I;], la: [L; 1L, 2],
+            "key with spaces": {}
+        }"#,
+    )
+    .unwrap();
+    let get = |key: &str| value.get(key).unwrap();
+    assert_eq!(get("b").as_byte(), Some(1));
+    assert_eq!(get("s").as_short(), Some(-2));
+    assert_eq!(get("i").as_int(), Some(3));
+    assert_eq!(get("l").as_long(), Some(4));
+    assert_eq!(get("f").as_float(), Some(1.5));
+    assert_eq!(get("d").as_double(), Some(2.5));
+    assert_eq!(get("d2").as_double(), Some(3.5));
+    assert_eq!(get("e").as_double(), Some(1000.0));
+    assert_eq!(get("yes").as_byte(), Some(1));
+    assert_eq!(get("no").as_byte(), Some(0));
+    assert_eq!(get("q").as_string().unwrap().decode(), r#"double "quoted""#);
+    assert_eq!(get("sq").as_string().unwrap().decode(), "single '");
+    assert_eq!(get("word").as_string().unwrap().decode(), "stone");
+    assert_eq!(get("list").as_list().unwrap().tag_id(), Tag::Short);
+    assert!(get("empty").as_list().unwrap().is_empty());
+    assert_eq!(get("nested").as_list().unwrap().len(), 2);
+    assert_eq!(**get("ba").as_byte_array().unwrap(), [1, -2]);
+    assert_eq!(get("ia").tag_id(), Tag::IntArray);
+    assert_eq!(get("la").as_long_array().unwrap().len(), 2);
+    assert_eq!(get("key with spaces").tag_id(), Tag::Compound);
+}
+
+#[test]
+fn test_value_round_trip() {
+    let mut inner: OwnedList<BE> = OwnedList::default();
+    inner.push(1.5f32);
+    inner.push(f32::INFINITY);
+    inner.push(-0.0f32);
+    let mut compound: OwnedCompound<BE> = OwnedCompound::default();
+    compound.insert("floats", inner);
+    compound.insert("", "empty key");
+    compound.insert("nul", "a\0b");
+    let value = OwnedValue::Compound(compound);
+
+    let snbt = to_snbt(&value).unwrap();
+    let read = from_snbt::<BE>(&snbt).unwrap();
+    assert_eq!(to_snbt(&read).unwrap(), snbt);
+    assert!(values_equal(&read, &value));
+    let floats = read.get("floats").unwrap();
+    let floats = floats.as_list().unwrap();
+    assert_eq!(floats.get(1).unwrap().as_float(), Some(f32::INFINITY));
+    assert_eq!(
+        read.get("").unwrap().as_string().unwrap().decode(),
+        "empty key"
+    );
+    assert_eq!(
+        read.get("nul").unwrap().as_string().unwrap().decode(),
+        "a\0b"
+    );
+}
+
+#[test]
+fn test_non_finite_output() {
+    let mut list: OwnedList<BE> = OwnedList::default();
+    list.push(f64::INFINITY);
+    list.push(f64::NEG_INFINITY);
+    let doubles = OwnedValue::List(list);
+    assert_eq!(to_snbt(&doubles).unwrap(), "[1e309d,-1e309d]");
+
+    let mut list: OwnedList<BE> = OwnedList::default();
+    list.push(f32::INFINITY);
+    list.push(f32::NEG_INFINITY);
+    let floats = OwnedValue::List(list);
+    assert_eq!(to_snbt(&floats).unwrap(), "[1e39f,-1e39f]");
+
+    let read = from_snbt::<BE>("[1e309d,-1e309d]").unwrap();
+    let read = read.as_list().unwrap();
+    assert_eq!(read.get(0).unwrap().as_double(), Some(f64::INFINITY));
+    assert_eq!(read.get(1).unwrap().as_double(), Some(f64::NEG_INFINITY));
+    let read = from_snbt::<BE>("-1e39f").unwrap();
+    assert_eq!(read.as_float(), Some(f32::NEG_INFINITY));
+}
+
+#[test]
+fn test_nan_is_an_error() {
+    let mut compound: OwnedCompound<BE> = OwnedCompound::default();
+    compound.insert("ok", 1.0f32);
+    compound.insert("nan", f64::NAN);
+    let value = OwnedValue::Compound(compound);
+    let err = to_snbt(&value).err().unwrap();
+    assert!(matches!(err, Error::Message(_)));
+
+    let mut list: OwnedList<BE> = OwnedList::default();
+    list.push(f32::NAN);
+    assert!(to_snbt(&OwnedValue::List(list)).is_err());
+
+    assert!(to_snbt_string(&BTreeMap::from([("x", f64::NAN)])).is_err());
+    assert_eq!(
+        to_snbt_string(&BTreeMap::from([("x", 1.5f64)])).unwrap(),
+        "{x:1.5d}"
+    );
+}
+
+#[test]
+fn test_non_finite_words_are_strings() {
+    for word in [
+        "nan",
+        "NaN",
+        "inf",
+        "infinity",
+        "nanf",
+        "NaNd",
+        "inff",
+        "Infinityd",
+        "-inf",
+        "+nan",
+    ] {
+        let read = from_snbt::<BE>(word).unwrap();
+        assert_eq!(read.as_string().unwrap().decode(), word, "{word}");
+    }
+    // a list of strings, not of doubles
+    let read = from_snbt::<BE>("[nan, inf]").unwrap();
+    assert_eq!(read.as_list().unwrap().tag_id(), Tag::String);
+    let read = from_snbt::<BE>("{a: nan, b: 1e3, c: 2.5f}").unwrap();
+    assert_eq!(read.get("a").unwrap().as_string().unwrap().decode(), "nan");
+    assert_eq!(read.get("b").unwrap().as_double(), Some(1000.0));
+    assert_eq!(read.get("c").unwrap().as_float(), Some(2.5));
+}
+
+#[test]
+fn test_parse_errors() {
+    let err = from_snbt::<BE>("[1, 2b]").err().unwrap();
+    assert_eq!(err.offset(), Some(4));
+    assert!(matches!(err.kind(), Error::TagMismatch(3, 1)));
+
+    let err = from_snbt::<BE>("{a: 1").err().unwrap();
+    assert!(matches!(err.kind(), Error::EndOfFile));
+
+    let err = from_snbt::<BE>("[B; 128]").err().unwrap();
+    assert_eq!(err.offset(), Some(4));
+
+    let err = from_snbt::<BE>("{a: minecraft:stone}").err().unwrap();
+    assert!(matches!(err.kind(), Error::Message(_)));
+
+    assert!(matches!(
+        from_snbt::<BE>("1 2").err().unwrap(),
+        Error::TrailingData(1)
+    ));
+    assert!(from_snbt::<BE>(r#""bad \q escape""#).is_err());
+
+    let deep = "[".repeat(600) + &"]".repeat(600);
+    assert!(from_snbt::<BE>(&deep).is_err());
+    let ok = "[".repeat(500) + &"]".repeat(500);
+    assert!(from_snbt::<BE>(&ok).is_ok());
+}
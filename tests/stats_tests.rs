@@ -0,0 +1,117 @@
+use na_nbt::{
+    NbtStats, OwnedCompound, OwnedList, OwnedValue, ScopedReadableValue, Tag, read_borrowed,
+    read_owned,
+};
+use zerocopy::byteorder::{self, BigEndian as BE, LittleEndian as LE};
+
+fn create_nested_owned() -> OwnedValue<BE> {
+    // { name: "a", pos: [ {x: 1}, {y: "b"} ], arr: IntArray[3], empty: {} }
+    let mut first = OwnedCompound::<BE>::default();
+    first.insert("x", 1i32);
+    let mut second = OwnedCompound::<BE>::default();
+    second.insert("y", "b");
+    let mut list = OwnedList::<BE>::default();
+    list.push(first);
+    list.push(second);
+
+    let mut root = OwnedCompound::<BE>::default();
+    root.insert("name", "a");
+    root.insert("pos", list);
+    root.insert("arr", [1i32, 2, 3].map(byteorder::I32::<BE>::new));
+    root.insert("empty", OwnedCompound::<BE>::default());
+    OwnedValue::Compound(root)
+}
+
+#[test]
+fn owned_stats() {
+    let value = create_nested_owned();
+    let stats = value.stats();
+    assert_eq!(
+        stats,
+        NbtStats {
+            compounds: 4,
+            lists: 1,
+            strings: 2,
+            total_bytes: value.write_to_vec::<BE>().unwrap().len() - 3,
+            max_depth: 3,
+            nodes: 9,
+            tag_counts: [0, 0, 0, 1, 0, 0, 0, 0, 2, 1, 4, 1, 0],
+            // "a", "b" and three ints
+            payload_bytes: 1 + 1 + 12,
+        }
+    );
+    // root, name, pos, 2 list compounds, x, y, arr, empty
+    assert_eq!(value.node_count(), 9);
+}
+
+#[test]
+fn borrowed_stats_match_owned() {
+    let owned = create_nested_owned();
+    let bytes = owned.write_to_vec::<LE>().unwrap();
+    let doc = read_borrowed::<LE>(&bytes).unwrap();
+    let root = doc.root();
+    assert_eq!(root.stats(), owned.stats());
+    assert_eq!(root.node_count(), owned.node_count());
+
+    let reread = read_owned::<LE, BE>(&bytes).unwrap();
+    assert_eq!(reread.stats(), owned.stats());
+}
+
+#[test]
+fn primitive_stats() {
+    let value = OwnedValue::<BE>::from(5i64);
+    assert_eq!(value.node_count(), 1);
+    assert_eq!(
+        value.stats(),
+        NbtStats {
+            total_bytes: 8,
+            nodes: 1,
+            tag_counts: [0, 0, 0, 0, 1, 0, 0, 0, 0, 0, 0, 0, 0],
+            ..NbtStats::default()
+        }
+    );
+}
+
+#[test]
+fn stats_count_every_tag() {
+    let mut bytes = OwnedList::<BE>::default();
+    bytes.push(1i8);
+    bytes.push(2i8);
+
+    let mut root = OwnedCompound::<BE>::default();
+    root.insert("b", 1i8);
+    root.insert("s", 2i16);
+    root.insert("i", 3i32);
+    root.insert("l", 4i64);
+    root.insert("f", 5.0f32);
+    root.insert("d", 6.0f64);
+    root.insert("ba", vec![1i8, 2, 3, 4]);
+    root.insert("str", "hello");
+    root.insert("list", bytes);
+    root.insert("ia", [1i32, 2].map(byteorder::I32::<BE>::new));
+    root.insert("la", [1i64].map(byteorder::I64::<BE>::new));
+    let value = OwnedValue::Compound(root);
+
+    let stats = value.stats();
+    assert_eq!(stats.nodes, value.node_count());
+    assert_eq!(stats.nodes, 14);
+    assert_eq!(stats.count(Tag::End), 0);
+    assert_eq!(stats.count(Tag::Byte), 3);
+    for tag in [
+        Tag::Short,
+        Tag::Int,
+        Tag::Long,
+        Tag::Float,
+        Tag::Double,
+        Tag::ByteArray,
+        Tag::String,
+        Tag::List,
+        Tag::Compound,
+        Tag::IntArray,
+        Tag::LongArray,
+    ] {
+        assert_eq!(stats.count(tag), 1, "{tag:?}");
+    }
+    assert_eq!(stats.payload_bytes, 4 + 5 + 8 + 8);
+    assert_eq!(stats.max_depth, 2);
+}
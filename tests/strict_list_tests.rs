@@ -0,0 +1,85 @@
+use na_nbt::{
+    Error, OwnedList, OwnedValue, ScopedReadableList, Tag, read_borrowed, read_owned,
+    read_owned_strict,
+};
+use zerocopy::byteorder::{BigEndian as BE, LittleEndian as LE};
+
+fn nested_list(element_tag: u8, len: u32, payload: &[u8]) -> Vec<u8> {
+    // compound { "l": list }
+    let mut data = vec![0x0A, 0x00, 0x00, 0x09, 0x00, 0x01, b'l', element_tag];
+    data.extend_from_slice(&len.to_be_bytes());
+    data.extend_from_slice(payload);
+    data.push(0x00);
+    data
+}
+
+#[test]
+fn strict_accepts_valid_lists() {
+    let mut inner = OwnedList::<BE>::default();
+    inner.push("a");
+    inner.push("bc");
+    let mut outer = OwnedList::<BE>::default();
+    outer.push(inner);
+    outer.push(OwnedList::<BE>::default());
+    let data = OwnedValue::List(outer).write_to_vec::<BE>().unwrap();
+
+    let value = read_owned_strict::<BE, LE>(&data).unwrap();
+    assert_eq!(value.write_to_vec::<BE>().unwrap(), data);
+
+    let data = nested_list(0x03, 2, &[0, 0, 0, 1, 0, 0, 0, 2]);
+    assert!(read_owned_strict::<BE, BE>(&data).is_ok());
+}
+
+#[test]
+fn strict_rejects_unknown_element_tag() {
+    let data = nested_list(0x0D, 0, &[]);
+    let Err(err) = read_owned_strict::<BE, BE>(&data) else {
+        panic!("expected an error");
+    };
+    assert!(matches!(err.kind(), Error::InvalidTagType(0x0D)));
+    assert_eq!(err.offset(), Some(7));
+}
+
+#[test]
+fn strict_rejects_count_beyond_input() {
+    // a list of 1000 compounds needs at least 1000 bytes
+    let data = nested_list(0x0A, 1000, &[0x00, 0x00]);
+    match read_owned_strict::<BE, BE>(&data) {
+        Err(Error::ListTooLong(1000)) => {}
+        other => panic!("expected ListTooLong, got {:?}", other.err()),
+    }
+    // the lenient reader only notices when it runs out of input
+    assert!(matches!(read_owned::<BE, BE>(&data), Err(Error::EndOfFile)));
+
+    let data = nested_list(0x04, 2, &[0; 8]);
+    assert!(matches!(
+        read_owned_strict::<BE, BE>(&data),
+        Err(Error::ListTooLong(2))
+    ));
+}
+
+#[test]
+fn strict_truncated_input() {
+    let data = nested_list(0x03, 2, &[0, 0, 0, 1, 0, 0, 0, 2]);
+    for len in 0..data.len() - 1 {
+        assert!(read_owned_strict::<BE, BE>(&data[..len]).is_err());
+    }
+}
+
+#[test]
+fn element_tag_matches_tag_id() {
+    let data = nested_list(0x03, 1, &[0, 0, 0, 7]);
+
+    let owned = read_owned::<BE, BE>(&data).unwrap();
+    let list = owned.get("l").unwrap();
+    let list = list.as_list().unwrap();
+    assert_eq!(list.element_tag(), Tag::Int);
+    assert_eq!(list.element_tag(), list.tag_id());
+
+    let doc = read_borrowed::<BE>(&data).unwrap();
+    let root = doc.root();
+    let list = root.get("l").unwrap();
+    let list = list.as_list().unwrap();
+    assert_eq!(list.element_tag(), Tag::Int);
+    assert_eq!(ScopedReadableList::element_tag(list), Tag::Int);
+}
@@ -0,0 +1,63 @@
+//! Tests for the `strict` feature.
+//!
+//! Without it, or in release builds, a setter given a value of the wrong type
+//! returns `false` (or `None`) and leaves the value untouched; with it, debug
+//! builds panic instead.
+
+use na_nbt::{OwnedCompound, OwnedList, OwnedValue};
+use zerocopy::byteorder::BigEndian as BE;
+
+const STRICT: bool = cfg!(all(feature = "strict", debug_assertions));
+
+fn panics(f: impl FnOnce() + std::panic::UnwindSafe) -> bool {
+    std::panic::catch_unwind(f).is_err()
+}
+
+#[test]
+fn test_owned_setter_mismatch() {
+    let mismatch = panics(|| {
+        let mut value = OwnedValue::<BE>::from("text");
+        assert!(!value.set_int(1));
+        assert!(!value.update_long(|v| v + 1));
+        assert!(!value.try_update_double(Some));
+        assert_eq!(value.as_string().unwrap().decode(), "text");
+    });
+    assert_eq!(mismatch, STRICT);
+}
+
+#[test]
+fn test_mutable_setter_mismatch() {
+    let mismatch = panics(|| {
+        let mut compound: OwnedCompound<BE> = OwnedCompound::default();
+        compound.insert("x", 1i8);
+        assert!(!compound.get_mut("x").unwrap().set_float(1.0));
+        assert_eq!(compound.get("x").unwrap().as_byte(), Some(1));
+    });
+    assert_eq!(mismatch, STRICT);
+}
+
+#[test]
+fn test_list_set_mismatch() {
+    let mismatch = panics(|| {
+        let mut list: OwnedList<BE> = OwnedList::default();
+        list.push(1i32);
+        assert!(list.set(0, 1i64).is_none());
+        assert_eq!(list.get(0).unwrap().as_int(), Some(1));
+    });
+    assert_eq!(mismatch, STRICT);
+}
+
+#[test]
+fn test_matching_writes_never_panic() {
+    let mut value = OwnedValue::<BE>::from(1i32);
+    assert!(value.set_int(2));
+    assert!(value.update_int(|v| v * 10));
+    assert!(!value.try_update_int(|_| None));
+    assert_eq!(value.as_int(), Some(20));
+
+    let mut list: OwnedList<BE> = OwnedList::default();
+    list.push(1i32);
+    // out of bounds is not a type mismatch
+    assert!(list.set(5, 2i32).is_none());
+    assert_eq!(list.set(0, 2i32).unwrap().as_int(), Some(1));
+}
@@ -0,0 +1,84 @@
+//! Tests that `decode` borrows whenever the stored bytes are valid UTF-8.
+
+use std::borrow::Cow;
+
+use na_nbt::{OwnedCompound, OwnedValue, ScopedReadableValue, read_borrowed, read_owned};
+use zerocopy::byteorder::BigEndian as BE;
+
+const STRINGS: [(&str, bool); 5] = [
+    ("", true),
+    ("minecraft:stone", true),
+    ("Grüße, 世界", true),
+    ("nul\0inside", false),
+    ("emoji 😀", false),
+];
+
+fn document() -> Vec<u8> {
+    let mut root: OwnedCompound<BE> = OwnedCompound::default();
+    for (i, (string, _)) in STRINGS.iter().enumerate() {
+        root.insert(&i.to_string(), *string);
+    }
+    OwnedValue::Compound(root).write_to_vec::<BE>().unwrap()
+}
+
+fn check(decoded: Cow<'_, str>, expected: &str, borrowed: bool) {
+    assert_eq!(decoded, expected);
+    assert_eq!(
+        matches!(decoded, Cow::Borrowed(_)),
+        borrowed,
+        "{expected:?}"
+    );
+}
+
+#[test]
+fn test_decode_borrows_valid_utf8() {
+    let data = document();
+
+    let doc = read_borrowed::<BE>(&data).unwrap();
+    let root = doc.root();
+    let owned = read_owned::<BE, BE>(&data).unwrap();
+    for (i, (expected, borrowed)) in STRINGS.iter().enumerate() {
+        let key = i.to_string();
+        check(
+            root.get(key.as_str())
+                .unwrap()
+                .as_string()
+                .unwrap()
+                .decode(),
+            expected,
+            *borrowed,
+        );
+        let value = owned.get(key.as_str()).unwrap();
+        check(
+            value.as_string_scoped().unwrap().decode(),
+            expected,
+            *borrowed,
+        );
+    }
+}
+
+#[test]
+fn test_decode_view_borrows_valid_utf8() {
+    for (expected, borrowed) in STRINGS {
+        let value = OwnedValue::<BE>::from(expected);
+        let OwnedValue::String(string) = &value else {
+            panic!("expected a string");
+        };
+        check(string.decode(), expected, borrowed);
+    }
+}
+
+#[test]
+fn test_decode_keys_and_invalid_bytes() {
+    let data = document();
+    let owned = read_owned::<BE, BE>(&data).unwrap();
+    let root = owned.as_compound().unwrap();
+    for (key, _) in root.iter() {
+        assert!(matches!(key.decode(), Cow::Borrowed(_)));
+    }
+
+    // a string "" whose single byte is not valid MUTF-8
+    let data = [0x08, 0x00, 0x00, 0x00, 0x01, 0xff];
+    let doc = read_borrowed::<BE>(&data).unwrap();
+    check(doc.root().as_string().unwrap().decode(), "\u{fffd}", false);
+}
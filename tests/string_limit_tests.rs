@@ -0,0 +1,72 @@
+//! Tests for `ReadLimits::max_string_bytes`.
+
+use na_nbt::{
+    Error, OwnedCompound, OwnedList, OwnedValue, Parser, ReadLimits,
+    read_owned_from_reader_with_limits, read_owned_with_limits,
+};
+use zerocopy::byteorder::{BigEndian as BE, LittleEndian as LE};
+
+/// A compound of `n` strings of `len` bytes each.
+fn strings(n: usize, len: usize) -> Vec<u8> {
+    let mut list: OwnedList<BE> = OwnedList::default();
+    let mut compound: OwnedCompound<BE> = OwnedCompound::default();
+    for i in 0..n {
+        let value = "x".repeat(len);
+        if i % 2 == 0 {
+            list.push(value.as_str());
+        } else {
+            compound.insert(&format!("a_rather_long_key_{i}"), value.as_str());
+        }
+    }
+    compound.insert("list", list);
+    OwnedValue::Compound(compound).write_to_vec::<BE>().unwrap()
+}
+
+fn string_limits(max_string_bytes: usize) -> ReadLimits {
+    ReadLimits {
+        max_string_bytes,
+        ..ReadLimits::default()
+    }
+}
+
+#[test]
+fn test_string_bytes_over_the_limit() {
+    let data = strings(10, 1000);
+    assert_eq!(ReadLimits::default().max_string_bytes, usize::MAX);
+    assert!(read_owned_with_limits::<BE, BE>(&data, string_limits(10_000)).is_ok());
+    assert!(read_owned_from_reader_with_limits::<BE, LE>(&data[..], string_limits(10_000)).is_ok());
+
+    assert!(matches!(
+        read_owned_with_limits::<BE, BE>(&data, string_limits(9_999)),
+        Err(Error::StringBytesExceeded(9_999))
+    ));
+    assert!(matches!(
+        read_owned_from_reader_with_limits::<BE, LE>(&data[..], string_limits(9_999)),
+        Err(Error::StringBytesExceeded(9_999))
+    ));
+    assert_eq!(
+        Error::StringBytesExceeded(9_999).to_string(),
+        "document strings take more than 9999 bytes"
+    );
+    let mut parser = Parser::with_limits(string_limits(5_000));
+    assert!(matches!(
+        parser.read_owned_from_reader::<BE, BE>(&data[..]),
+        Err(Error::StringBytesExceeded(5_000))
+    ));
+}
+
+#[test]
+fn test_root_string_counts() {
+    let value: OwnedValue<LE> = OwnedValue::from("hello");
+    let data = value.write_to_vec::<LE>().unwrap();
+    assert!(read_owned_with_limits::<LE, LE>(&data, string_limits(5)).is_ok());
+    assert!(read_owned_from_reader_with_limits::<LE, LE>(&data[..], string_limits(5)).is_ok());
+    assert!(matches!(
+        read_owned_with_limits::<LE, LE>(&data, string_limits(4)),
+        Err(Error::StringBytesExceeded(4))
+    ));
+    assert!(matches!(
+        read_owned_from_reader_with_limits::<LE, LE>(&data[..], string_limits(4)),
+        Err(Error::StringBytesExceeded(4))
+    ));
+}
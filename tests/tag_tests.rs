@@ -124,3 +124,73 @@ fn test_tag_hash() {
     assert!(set.contains(&Tag::Int));
     assert!(!set.contains(&Tag::Long));
 }
+
+const ALL_TAGS: [Tag; 13] = [
+    Tag::End,
+    Tag::Byte,
+    Tag::Short,
+    Tag::Int,
+    Tag::Long,
+    Tag::Float,
+    Tag::Double,
+    Tag::ByteArray,
+    Tag::String,
+    Tag::List,
+    Tag::Compound,
+    Tag::IntArray,
+    Tag::LongArray,
+];
+
+#[test]
+fn test_tag_sort_follows_ids() {
+    let mut tags = ALL_TAGS;
+    tags.reverse();
+    tags.sort();
+    assert_eq!(tags, ALL_TAGS);
+    for pair in ALL_TAGS.windows(2) {
+        assert!(pair[0] < pair[1]);
+        assert_eq!(pair[0].cmp(&pair[1]), (pair[0] as u8).cmp(&(pair[1] as u8)));
+    }
+}
+
+#[test]
+fn test_tag_names() {
+    let names: Vec<&str> = ALL_TAGS.iter().map(|tag| tag.name()).collect();
+    assert_eq!(
+        names,
+        [
+            "TAG_End",
+            "TAG_Byte",
+            "TAG_Short",
+            "TAG_Int",
+            "TAG_Long",
+            "TAG_Float",
+            "TAG_Double",
+            "TAG_Byte_Array",
+            "TAG_String",
+            "TAG_List",
+            "TAG_Compound",
+            "TAG_Int_Array",
+            "TAG_Long_Array",
+        ]
+    );
+}
+
+#[test]
+fn test_tag_display_and_sort_values() {
+    use na_nbt::OwnedValue;
+    use zerocopy::byteorder::BigEndian as BE;
+
+    assert_eq!(Tag::Compound.to_string(), "TAG_Compound");
+    assert_eq!(format!("{}", Tag::LongArray), "TAG_Long_Array");
+
+    let mut values: Vec<OwnedValue<BE>> = vec![
+        OwnedValue::from("a"),
+        OwnedValue::from(1i64),
+        OwnedValue::from(1i8),
+        OwnedValue::from(1.0f32),
+    ];
+    values.sort_by_key(|value| value.tag_id());
+    let names: Vec<String> = values.iter().map(|value| value.tag_id().to_string()).collect();
+    assert_eq!(names, ["TAG_Byte", "TAG_Long", "TAG_Float", "TAG_String"]);
+}
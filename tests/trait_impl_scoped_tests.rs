@@ -264,6 +264,10 @@ fn scoped_writable_mut_references() {
 }
 
 #[test]
+#[cfg_attr(
+    all(feature = "strict", debug_assertions),
+    should_panic(expected = "type mismatch")
+)]
 fn scoped_writable_type_mismatch_returns_false() {
     let data = create_all_primitives_be();
     let owned = read_owned::<BE, BE>(&data).unwrap();
@@ -2,6 +2,10 @@ use na_nbt::{OwnedValue, Tag, ScopedReadableValue, ScopedWritableValue};
 use zerocopy::byteorder::BigEndian as BE;
 
 #[test]
+#[cfg_attr(
+    all(feature = "strict", debug_assertions),
+    should_panic(expected = "type mismatch")
+)]
 fn test_owned_value_trait_wrappers() {
     // 1. Byte
     let mut val = OwnedValue::<BE>::from(10i8);
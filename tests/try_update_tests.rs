@@ -0,0 +1,59 @@
+//! Tests for the `try_update_*` helpers.
+
+use na_nbt::{OwnedCompound, OwnedValue, ScopedWritableValue};
+use zerocopy::byteorder::BigEndian as BE;
+
+#[test]
+fn test_try_update_commits_only_on_some() {
+    let mut value = OwnedValue::<BE>::from(i32::MAX - 1);
+    assert!(value.try_update_int(|n| n.checked_add(1)));
+    assert_eq!(value.as_int(), Some(i32::MAX));
+    assert!(!value.try_update_int(|n| n.checked_add(1)));
+    assert_eq!(value.as_int(), Some(i32::MAX));
+
+    let mut value = OwnedValue::<BE>::from(i8::MIN);
+    assert!(!value.try_update_byte(|n| n.checked_sub(1)));
+    assert_eq!(value.as_byte(), Some(i8::MIN));
+    assert!(value.try_update_byte(|n| n.checked_neg().or(Some(0))));
+    assert_eq!(value.as_byte(), Some(0));
+
+    let mut value = OwnedValue::<BE>::from(2.0f64);
+    assert!(!value.try_update_double(|n| Some(n.sqrt()).filter(|n| n.fract() == 0.0)));
+    assert_eq!(value.as_double(), Some(2.0));
+}
+
+#[test]
+#[cfg_attr(
+    all(feature = "strict", debug_assertions),
+    should_panic(expected = "type mismatch")
+)]
+fn test_try_update_wrong_type() {
+    let mut value = OwnedValue::<BE>::from(1i64);
+    let mut called = false;
+    assert!(!value.try_update_int(|n| {
+        called = true;
+        Some(n)
+    }));
+    assert!(!called);
+    assert!(value.try_update_long(|n| n.checked_mul(3)));
+    assert_eq!(value.as_long(), Some(3));
+}
+
+#[test]
+fn test_try_update_mutable_and_trait() {
+    let mut compound: OwnedCompound<BE> = OwnedCompound::default();
+    compound.insert("health", 20i16);
+    compound.insert("speed", 0.5f32);
+
+    let mut health = compound.get_mut("health").unwrap();
+    assert!(!health.try_update_short(|n| n.checked_sub(30).filter(|n| *n >= 0)));
+    assert!(health.try_update_short(|n| n.checked_sub(5).filter(|n| *n >= 0)));
+    assert_eq!(compound.get("health").unwrap().as_short(), Some(15));
+
+    let mut speed = compound.get_mut("speed").unwrap();
+    assert!(ScopedWritableValue::try_update_float(&mut speed, |n| Some(
+        n * 2.0
+    )));
+    assert!(!ScopedWritableValue::try_update_int(&mut speed, Some));
+    assert_eq!(compound.get("speed").unwrap().as_float(), Some(1.0));
+}
@@ -0,0 +1,57 @@
+//! Tests for `TypedListBuilder`.
+
+use na_nbt::{OwnedList, OwnedValue, Tag, TypedListBuilder, read_owned};
+use zerocopy::byteorder::{BigEndian as BE, LittleEndian as LE};
+
+#[test]
+fn test_int_list() {
+    let mut builder = TypedListBuilder::<BE, i32>::with_capacity(3);
+    assert!(builder.is_empty());
+    builder.push(1);
+    builder.push(-2);
+    builder.push(i32::MAX);
+    assert_eq!(builder.len(), 3);
+
+    let list: OwnedList<BE> = builder.into();
+    assert_eq!(list.element_tag(), Tag::Int);
+    let ints: Vec<i32> = list.iter().filter_map(|value| value.as_int()).collect();
+    assert_eq!(ints, [1, -2, i32::MAX]);
+}
+
+#[test]
+fn test_every_primitive_type() {
+    fn tag_of<T: na_nbt::Primitive>(values: Vec<T>) -> Tag {
+        values
+            .into_iter()
+            .collect::<TypedListBuilder<LE, T>>()
+            .build()
+            .element_tag()
+    }
+
+    assert_eq!(tag_of(vec![1i8]), Tag::Byte);
+    assert_eq!(tag_of(vec![1i16]), Tag::Short);
+    assert_eq!(tag_of(vec![1i32]), Tag::Int);
+    assert_eq!(tag_of(vec![1i64]), Tag::Long);
+    assert_eq!(tag_of(vec![1f32]), Tag::Float);
+    assert_eq!(tag_of(vec![1f64]), Tag::Double);
+}
+
+#[test]
+fn test_empty_list_keeps_element_type() {
+    let value: OwnedValue<BE> = TypedListBuilder::<BE, i64>::default().into();
+    assert_eq!(value.as_list().unwrap().element_tag(), Tag::Long);
+
+    // empty lists are written as End, whatever their element type
+    let bytes = value.write_to_vec::<BE>().unwrap();
+    assert_eq!(bytes, [9, 0, 0, 0, 0, 0, 0, 0]);
+}
+
+#[test]
+fn test_round_trip() {
+    let value: OwnedValue<BE> = (0..100i16).collect::<TypedListBuilder<BE, i16>>().into();
+    let bytes = value.write_to_vec::<BE>().unwrap();
+    let read: OwnedValue<LE> = read_owned::<BE, LE>(&bytes).unwrap();
+    let list = read.as_list().unwrap();
+    assert_eq!(list.len(), 100);
+    assert_eq!(list.get(99).unwrap().as_short(), Some(99));
+}
@@ -0,0 +1,81 @@
+//! Tests for `as_uuid` and the UUID constructors.
+
+use na_nbt::{OwnedCompound, OwnedValue, ScopedReadableValue, read_borrowed};
+use zerocopy::byteorder::{BigEndian as BE, I32, I64, LittleEndian as LE};
+
+// Notch's UUID, 069a79f4-44e9-4726-a5be-fca90e38aaf5
+const NOTCH: u128 = 0x069a79f4_44e9_4726_a5be_fca90e38aaf5;
+
+#[test]
+fn test_both_encodings_decode_alike() {
+    let ints = OwnedValue::<BE>::from_uuid_int_array(NOTCH);
+    let longs = OwnedValue::<BE>::from_uuid_long_array(NOTCH);
+    assert_eq!(ints.as_uuid(), Some(NOTCH));
+    assert_eq!(longs.as_uuid(), Some(NOTCH));
+}
+
+#[test]
+fn test_int_array_layout() {
+    let value = OwnedValue::<BE>::from_uuid_int_array(NOTCH);
+    let ints: Vec<i32> = value
+        .as_int_array()
+        .unwrap()
+        .iter()
+        .map(|v| v.get())
+        .collect();
+    assert_eq!(
+        ints,
+        [0x069a79f4, 0x44e94726, 0xa5befca9_u32 as i32, 0x0e38aaf5]
+    );
+
+    // the same array as the game writes it, read back from bytes
+    let mut compound: OwnedCompound<LE> = OwnedCompound::default();
+    compound.insert(
+        "UUID",
+        vec![
+            I32::<LE>::new(0x069a79f4),
+            I32::new(0x44e94726),
+            I32::new(0xa5befca9_u32 as i32),
+            I32::new(0x0e38aaf5),
+        ],
+    );
+    let bytes = OwnedValue::Compound(compound).write_to_vec::<BE>().unwrap();
+    let doc = read_borrowed::<BE>(&bytes).unwrap();
+    assert_eq!(doc.root().get("UUID").unwrap().as_uuid(), Some(NOTCH));
+}
+
+#[test]
+fn test_long_array_layout() {
+    let value = OwnedValue::<LE>::from_uuid_long_array(NOTCH);
+    let longs: Vec<i64> = value
+        .as_long_array()
+        .unwrap()
+        .iter()
+        .map(|v| v.get())
+        .collect();
+    assert_eq!(longs, [0x069a79f4_44e94726, 0xa5befca9_0e38aaf5_u64 as i64]);
+}
+
+#[test]
+fn test_extremes_round_trip() {
+    for uuid in [0, u128::MAX, 1, 1 << 127, NOTCH.reverse_bits()] {
+        assert_eq!(
+            OwnedValue::<BE>::from_uuid_int_array(uuid).as_uuid(),
+            Some(uuid)
+        );
+        assert_eq!(
+            OwnedValue::<LE>::from_uuid_long_array(uuid).as_uuid(),
+            Some(uuid)
+        );
+    }
+}
+
+#[test]
+fn test_other_values_are_not_uuids() {
+    let three_ints = OwnedValue::<BE>::from(vec![I32::<BE>::new(1), I32::new(2), I32::new(3)]);
+    assert_eq!(three_ints.as_uuid(), None);
+    let one_long = OwnedValue::<BE>::from(vec![I64::<BE>::new(1)]);
+    assert_eq!(one_long.as_uuid(), None);
+    assert_eq!(OwnedValue::<BE>::from(4i32).as_uuid(), None);
+    assert_eq!(OwnedValue::<BE>::from("069a79f4").as_uuid(), None);
+}
@@ -0,0 +1,157 @@
+use na_nbt::{
+    OwnedCompound, OwnedList, OwnedValue, read_borrowed, read_owned, semantically_eq, values_equal,
+};
+use zerocopy::byteorder::{self, BigEndian as BE, LittleEndian as LE};
+
+fn build<O: na_nbt::ByteOrder>(reversed: bool) -> OwnedValue<O> {
+    let mut pos = OwnedList::<O>::default();
+    pos.push(1.5f64);
+    pos.push(-2.0f64);
+
+    let mut inner = OwnedCompound::<O>::default();
+    inner.insert("id", "minecraft:stone");
+    inner.insert("count", 64i8);
+
+    let entries: Vec<(&str, OwnedValue<O>)> = vec![
+        ("name", "Steve".into()),
+        ("level", 42i32.into()),
+        ("seed", (-7i64).into()),
+        ("pos", pos.into()),
+        ("item", inner.into()),
+        (
+            "ids",
+            vec![byteorder::I32::<O>::new(1), byteorder::I32::<O>::new(-1)].into(),
+        ),
+    ];
+
+    let mut root = OwnedCompound::<O>::default();
+    if reversed {
+        for (key, value) in entries.into_iter().rev() {
+            root.insert(key, value);
+        }
+    } else {
+        for (key, value) in entries {
+            root.insert(key, value);
+        }
+    }
+    OwnedValue::Compound(root)
+}
+
+#[test]
+fn big_endian_borrowed_equals_little_endian_owned() {
+    let bytes = build::<BE>(false).write_to_vec::<BE>().unwrap();
+    let doc = read_borrowed::<BE>(&bytes).unwrap();
+    let owned = build::<LE>(true);
+    assert!(values_equal(&doc.root(), &owned));
+    assert!(values_equal(&owned, &doc.root()));
+
+    let reread = read_owned::<BE, LE>(&bytes).unwrap();
+    assert!(values_equal(&reread, &owned));
+}
+
+#[test]
+fn differences_are_detected() {
+    let base = build::<BE>(false);
+
+    let mut changed = build::<LE>(false);
+    changed.get_mut("level").unwrap().set_int(43);
+    assert!(!values_equal(&base, &changed));
+
+    let mut extra = build::<LE>(false);
+    if let OwnedValue::Compound(compound) = &mut extra {
+        compound.insert("extra", 0i8);
+    }
+    assert!(!values_equal(&base, &extra));
+    assert!(!values_equal(&extra, &base));
+
+    // same number, different tag
+    assert!(!values_equal(
+        &OwnedValue::<BE>::from(1i32),
+        &OwnedValue::<BE>::from(1i64)
+    ));
+}
+
+#[test]
+fn lists_are_ordered() {
+    fn list<O: na_nbt::ByteOrder>(values: &[i32]) -> OwnedValue<O> {
+        let mut list = OwnedList::<O>::default();
+        for &value in values {
+            list.push(value);
+        }
+        OwnedValue::List(list)
+    }
+
+    assert!(values_equal(&list::<BE>(&[1, 2]), &list::<LE>(&[1, 2])));
+    assert!(!values_equal(&list::<BE>(&[1, 2]), &list::<LE>(&[2, 1])));
+    assert!(!values_equal(&list::<BE>(&[1, 2]), &list::<LE>(&[1])));
+    // an empty list equals any other empty list
+    assert!(values_equal(
+        &list::<BE>(&[]),
+        &OwnedValue::<LE>::List(OwnedList::default())
+    ));
+}
+
+#[test]
+fn semantically_eq_widens_numbers() {
+    let byte = OwnedValue::<BE>::Byte(1);
+    let short = OwnedValue::<LE>::from(1i16);
+    let int = OwnedValue::<BE>::from(1i32);
+    let long = OwnedValue::<LE>::from(1i64);
+    for a in [&byte, &int] {
+        assert!(semantically_eq(a, &short));
+        assert!(semantically_eq(a, &long));
+        assert!(semantically_eq(&short, a));
+    }
+    assert!(!semantically_eq(&byte, &OwnedValue::<BE>::from(2i64)));
+
+    let float = OwnedValue::<BE>::from(0.5f32);
+    let double = OwnedValue::<LE>::from(0.5f64);
+    assert!(semantically_eq(&float, &double));
+    assert!(!values_equal(&float, &double));
+    assert!(!semantically_eq(
+        &OwnedValue::<BE>::from(0.1f32),
+        &OwnedValue::<BE>::from(0.1f64)
+    ));
+    assert!(semantically_eq(
+        &OwnedValue::<BE>::from(0.0f64),
+        &OwnedValue::<BE>::from(-0.0f32)
+    ));
+    assert!(!semantically_eq(
+        &OwnedValue::<BE>::from(f64::NAN),
+        &OwnedValue::<BE>::from(f64::NAN)
+    ));
+
+    // integers and floats stay distinct
+    assert!(!semantically_eq(&int, &OwnedValue::<BE>::from(1.0f64)));
+}
+
+#[test]
+fn semantically_eq_keeps_structure() {
+    let mut bytes = OwnedList::<BE>::default();
+    bytes.push(1i8);
+    bytes.push(2i8);
+    let mut ints = OwnedList::<LE>::default();
+    ints.push(1i32);
+    ints.push(2i32);
+
+    let mut a = OwnedCompound::<BE>::default();
+    a.insert("values", bytes);
+    a.insert("count", 2i8);
+    let mut b = OwnedCompound::<LE>::default();
+    b.insert("count", 2i64);
+    b.insert("values", ints);
+    let a = OwnedValue::Compound(a);
+    let b = OwnedValue::Compound(b);
+    assert!(semantically_eq(&a, &b));
+    assert!(!values_equal(&a, &b));
+
+    // a list never equals an array, and arrays of different tags differ
+    let mut list = OwnedList::<BE>::default();
+    list.push(1i32);
+    let list = OwnedValue::List(list);
+    let int_array = OwnedValue::<BE>::IntArray(vec![1i32.into()].into());
+    let long_array = OwnedValue::<BE>::LongArray(vec![1i64.into()].into());
+    assert!(!semantically_eq(&list, &int_array));
+    assert!(!semantically_eq(&int_array, &long_array));
+    assert!(semantically_eq(&int_array, &int_array));
+}
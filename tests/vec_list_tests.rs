@@ -0,0 +1,92 @@
+//! Tests for the position and rotation list helpers and `to_vec_`.
+
+use na_nbt::{
+    OwnedCompound, OwnedList, OwnedValue, ScopedReadableList, ScopedReadableValue, read_borrowed,
+};
+use zerocopy::byteorder::{BigEndian as BE, LittleEndian as LE};
+
+#[test]
+fn test_from_vec3_round_trip() {
+    let pos = OwnedValue::<LE>::from_vec3(0.5, -64.0, 1e9);
+    assert_eq!(pos.as_vec3_f64(), Some([0.5, -64.0, 1e9]));
+
+    let bytes = pos.write_to_vec::<BE>().unwrap();
+    let doc = read_borrowed::<BE>(&bytes).unwrap();
+    assert_eq!(doc.root().as_vec3_f64(), Some([0.5, -64.0, 1e9]));
+}
+
+#[test]
+fn test_from_vec2_round_trip() {
+    let rotation = OwnedValue::<BE>::from_vec2(90.0, -12.5);
+    assert_eq!(rotation.as_vec2_f32(), Some([90.0, -12.5]));
+    assert_eq!(rotation.as_vec3_f64(), None);
+}
+
+#[test]
+fn test_entity_compound() {
+    let mut entity: OwnedCompound<BE> = OwnedCompound::default();
+    entity.insert("Pos", OwnedValue::from_vec3(1.0, 2.0, 3.0));
+    entity.insert("Rotation", OwnedValue::from_vec2(4.0, 5.0));
+    let entity = OwnedValue::Compound(entity);
+
+    let bytes = entity.write_to_vec::<BE>().unwrap();
+    let doc = read_borrowed::<BE>(&bytes).unwrap();
+    let root = doc.root();
+    assert_eq!(
+        root.get_scoped("Pos").unwrap().as_vec3_f64(),
+        Some([1.0, 2.0, 3.0])
+    );
+    assert_eq!(
+        root.get_scoped("Rotation").unwrap().as_vec2_f32(),
+        Some([4.0, 5.0])
+    );
+}
+
+#[test]
+fn test_wrong_shape_is_none() {
+    // wrong length
+    let mut list: OwnedList<BE> = OwnedList::default();
+    list.push(1.0f64);
+    list.push(2.0f64);
+    assert_eq!(OwnedValue::List(list).as_vec3_f64(), None);
+
+    // wrong element type, no widening
+    let mut list: OwnedList<BE> = OwnedList::default();
+    for value in [1.0f32, 2.0, 3.0] {
+        list.push(value);
+    }
+    assert_eq!(OwnedValue::List(list).as_vec3_f64(), None);
+
+    // not a list
+    assert_eq!(OwnedValue::<BE>::from(1.0f64).as_vec3_f64(), None);
+    assert_eq!(OwnedValue::<BE>::from(1.0f32).as_vec2_f32(), None);
+    assert_eq!(
+        OwnedValue::List(OwnedList::<BE>::default()).as_vec2_f32(),
+        None
+    );
+}
+
+#[test]
+fn test_to_vec_typed() {
+    let mut ints: OwnedList<BE> = OwnedList::default();
+    for n in [3i32, -1, 7] {
+        ints.push(n);
+    }
+    assert_eq!(ints.to_vec_::<i32>(), Some(vec![3, -1, 7]));
+    assert_eq!(ints.to_vec_::<i64>(), None);
+    assert_eq!(ints.to_vec_::<f32>(), None);
+
+    let mut strings: OwnedList<BE> = OwnedList::default();
+    strings.push("a");
+    assert_eq!(strings.to_vec_::<i8>(), None);
+
+    let empty: OwnedList<BE> = OwnedList::default();
+    assert_eq!(empty.to_vec_::<f64>(), Some(vec![]));
+
+    let value = OwnedValue::List(ints);
+    let bytes = value.write_to_vec::<LE>().unwrap();
+    let doc = read_borrowed::<LE>(&bytes).unwrap();
+    let root = doc.root();
+    let list = root.as_list().unwrap();
+    assert_eq!(list.to_vec_::<i32>(), Some(vec![3, -1, 7]));
+}
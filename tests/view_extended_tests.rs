@@ -1,4 +1,4 @@
-use na_nbt::OwnedValue;
+use na_nbt::{OwnedValue, StringViewOwn};
 use zerocopy::byteorder::BigEndian as BE;
 
 #[test]
@@ -234,3 +234,33 @@ fn test_string_view_mut_extended() {
     assert_eq!(v.to_string(), "heo word");
 }
 
+
+#[test]
+fn test_string_view_own_mutf8_helpers() {
+    let original = String::from("a\0b😀");
+    let mut s = StringViewOwn::from_str_mutf8(&original);
+
+    // NUL is two bytes, the emoji a surrogate pair of two 3-byte sequences
+    assert_eq!(
+        s.as_mutf8_bytes(),
+        [b'a', 0xC0, 0x80, b'b', 0xED, 0xA0, 0xBD, 0xED, 0xB8, 0x80]
+    );
+    assert_eq!(s.len(), 10);
+    assert_eq!(s.char_len(), 4);
+    assert!(!s.is_empty());
+
+    // back to the original through the raw bytes
+    let round_trip = StringViewOwn::from(s.as_mutf8_bytes());
+    assert_eq!(round_trip.decode(), original);
+    assert_eq!(round_trip, original);
+
+    s.push('!');
+    s += "?";
+    let s = s + "ok";
+    assert_eq!(s.decode(), "a\0b😀!?ok");
+    assert_eq!(s.char_len(), 8);
+
+    let empty = StringViewOwn::from_str_mutf8("");
+    assert!(empty.is_empty());
+    assert_eq!(empty.char_len(), 0);
+}
@@ -0,0 +1,90 @@
+//! Tests for `Visitor` and `walk`.
+
+use na_nbt::{
+    OwnedCompound, OwnedList, OwnedValue, ReadableString, ScopedReadableValue, Visitor,
+    read_borrowed, walk,
+};
+use zerocopy::byteorder::{BigEndian as BE, LittleEndian as LE};
+
+#[derive(Default)]
+struct Recorder {
+    events: Vec<String>,
+}
+
+impl Visitor for Recorder {
+    fn visit_compound_entry<'doc>(&mut self, key: &str, _value: &impl ScopedReadableValue<'doc>) {
+        self.events.push(format!("entry {key}"));
+    }
+
+    fn visit_list_element<'doc>(&mut self, index: usize, _value: &impl ScopedReadableValue<'doc>) {
+        self.events.push(format!("element {index}"));
+    }
+
+    fn visit_scalar<'doc>(&mut self, value: &impl ScopedReadableValue<'doc>) {
+        self.events.push(format!("scalar {:?}", value.tag_id()));
+    }
+}
+
+fn tree() -> OwnedValue<BE> {
+    let mut list: OwnedList<BE> = OwnedList::default();
+    list.push("a");
+    list.push("b");
+    let mut root: OwnedCompound<BE> = OwnedCompound::default();
+    root.insert("n", 1i64);
+    root.insert("list", list);
+    root.insert("empty", OwnedCompound::<BE>::default());
+    OwnedValue::Compound(root)
+}
+
+const EVENTS: [&str; 8] = [
+    "entry n",
+    "scalar Long",
+    "entry list",
+    "element 0",
+    "scalar String",
+    "element 1",
+    "scalar String",
+    "entry empty",
+];
+
+#[test]
+fn test_walk_order() {
+    let value = tree();
+    let mut recorder = Recorder::default();
+    walk(&value, &mut recorder);
+    assert_eq!(recorder.events, EVENTS);
+
+    let bytes = value.write_to_vec::<LE>().unwrap();
+    let doc = read_borrowed::<LE>(&bytes).unwrap();
+    let mut recorder = Recorder::default();
+    walk(&doc.root(), &mut recorder);
+    assert_eq!(recorder.events, EVENTS);
+}
+
+#[test]
+fn test_walk_scalar_root() {
+    let mut recorder = Recorder::default();
+    walk(&OwnedValue::<BE>::from(1i32), &mut recorder);
+    assert_eq!(recorder.events, ["scalar Int"]);
+}
+
+#[test]
+fn test_default_methods_are_no_ops() {
+    struct Strings(Vec<String>);
+
+    impl Visitor for Strings {
+        fn visit_scalar<'doc>(&mut self, value: &impl ScopedReadableValue<'doc>) {
+            if let Some(string) = value.as_string_scoped() {
+                self.0.push(string.decode().to_uppercase());
+            }
+        }
+    }
+
+    let mut strings = Strings(Vec::new());
+    walk(&tree(), &mut strings);
+    assert_eq!(strings.0, ["A", "B"]);
+
+    struct Nothing;
+    impl Visitor for Nothing {}
+    walk(&tree(), &mut Nothing);
+}
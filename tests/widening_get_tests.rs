@@ -0,0 +1,95 @@
+//! Tests for the widening value readers and compound getters.
+
+use na_nbt::{
+    OwnedCompound, OwnedValue, ScopedReadableCompound, ScopedReadableValue, read_borrowed,
+    read_owned,
+};
+use zerocopy::byteorder::{BigEndian as BE, LittleEndian as LE};
+
+fn numbers() -> OwnedCompound<BE> {
+    let mut compound = OwnedCompound::default();
+    compound.insert("byte", -3i8);
+    compound.insert("short", 300i16);
+    compound.insert("int", -70_000i32);
+    compound.insert("long", 1i64 << 40);
+    compound.insert("float", 0.25f32);
+    compound.insert("double", -1.5f64);
+    compound.insert("string", "7");
+    compound.insert("bytes", vec![1i8]);
+    compound
+}
+
+#[test]
+fn test_get_i64_widens_every_integer() {
+    let compound = numbers();
+    assert_eq!(compound.get_i64("byte"), Some(-3));
+    assert_eq!(compound.get_i64("short"), Some(300));
+    assert_eq!(compound.get_i64("int"), Some(-70_000));
+    assert_eq!(compound.get_i64("long"), Some(1 << 40));
+}
+
+#[test]
+fn test_get_i64_rejects_other_tags() {
+    let compound = numbers();
+    assert_eq!(compound.get_i64("float"), None);
+    assert_eq!(compound.get_i64("double"), None);
+    assert_eq!(compound.get_i64("string"), None);
+    assert_eq!(compound.get_i64("bytes"), None);
+    assert_eq!(compound.get_i64("missing"), None);
+}
+
+#[test]
+fn test_get_f64() {
+    let compound = numbers();
+    assert_eq!(compound.get_f64("float"), Some(0.25));
+    assert_eq!(compound.get_f64("double"), Some(-1.5));
+    assert_eq!(compound.get_f64("int"), None);
+    assert_eq!(compound.get_f64("string"), None);
+    assert_eq!(compound.get_f64("missing"), None);
+}
+
+#[test]
+fn test_borrowed_and_owned_agree() {
+    let bytes = OwnedValue::Compound(numbers())
+        .write_to_vec::<BE>()
+        .unwrap();
+    let doc = read_borrowed::<BE>(&bytes).unwrap();
+    let root = doc.root();
+    let borrowed = root.as_compound().unwrap();
+
+    let owned = read_owned::<BE, LE>(&bytes).unwrap();
+    let owned = owned.as_compound().unwrap();
+
+    for key in ["byte", "short", "int", "long", "float", "string"] {
+        assert_eq!(borrowed.get_i64(key), owned.get_i64(key), "{key}");
+        assert_eq!(borrowed.get_f64(key), owned.get_f64(key), "{key}");
+    }
+}
+
+#[test]
+fn test_version_tolerant_field() {
+    // the same field, written as an Int by one version and a Long by another
+    for value in [OwnedValue::<BE>::from(1234i32), OwnedValue::from(1234i64)] {
+        let mut compound = OwnedCompound::<BE>::default();
+        compound.insert("Time", value);
+        assert_eq!(compound.get_i64("Time"), Some(1234));
+    }
+}
+
+#[test]
+fn test_value_widening_matches_getters() {
+    let bytes = OwnedValue::Compound(numbers())
+        .write_to_vec::<BE>()
+        .unwrap();
+    let doc = read_borrowed::<BE>(&bytes).unwrap();
+    let root = doc.root();
+    let compound = root.as_compound().unwrap();
+    for (key, value) in compound.iter() {
+        let key = key.decode();
+        assert_eq!(value.as_i64_widened(), compound.get_i64(&key), "{key}");
+        assert_eq!(value.as_f64_widened(), compound.get_f64(&key), "{key}");
+    }
+    assert_eq!(OwnedValue::<LE>::from(-2i16).as_i64_widened(), Some(-2));
+    assert_eq!(OwnedValue::<LE>::from(0.5f32).as_f64_widened(), Some(0.5));
+    assert_eq!(OwnedValue::<LE>::from(1i32).as_f64_widened(), None);
+}
@@ -0,0 +1,94 @@
+//! Tests for writing a single value or compound entry out of a document.
+
+use na_nbt::{OwnedCompound, OwnedValue, ScopedReadableCompound, read_borrowed, read_owned};
+use zerocopy::byteorder::{BigEndian as BE, LittleEndian as LE};
+
+fn sample() -> Vec<u8> {
+    let mut pos: OwnedCompound<BE> = OwnedCompound::default();
+    pos.insert("x", 1i32);
+    pos.insert("y", 64i32);
+    let mut root: OwnedCompound<BE> = OwnedCompound::default();
+    root.insert("name", "Steve");
+    root.insert("pos", pos);
+    OwnedValue::Compound(root).write_to_vec::<BE>().unwrap()
+}
+
+#[test]
+fn looked_up_value_writes_as_document() {
+    let data = sample();
+    let doc = read_borrowed::<BE>(&data).unwrap();
+    let pos = doc.root().get("pos").unwrap();
+
+    let bytes = pos.write_to_vec::<BE>().unwrap();
+    assert_eq!(&bytes[..3], [0x0a, 0x00, 0x00]);
+    let value = read_owned::<BE, BE>(&bytes).unwrap();
+    assert_eq!(value.get("y").and_then(|v| v.as_int()), Some(64));
+}
+
+#[test]
+fn entry_carries_its_key_as_name() {
+    let data = sample();
+    let doc = read_borrowed::<BE>(&data).unwrap();
+    let root = doc.root();
+    let compound = root.as_compound().unwrap();
+
+    let entry = compound.write_entry_to_vec::<BE>("name").unwrap().unwrap();
+    assert_eq!(
+        entry,
+        [
+            0x08, 0x00, 0x04, b'n', b'a', b'm', b'e', 0x00, 0x05, b'S', b't', b'e', b'v', b'e'
+        ]
+    );
+
+    let unnamed = compound.get("name").unwrap().write_to_vec::<BE>().unwrap();
+    assert_eq!(unnamed[..3], [0x08, 0x00, 0x00]);
+    assert_eq!(unnamed[3..], entry[7..]);
+
+    assert!(
+        compound
+            .write_entry_to_vec::<BE>("missing")
+            .unwrap()
+            .is_none()
+    );
+}
+
+#[test]
+fn entries_splice_into_a_compound() {
+    let data = sample();
+    let doc = read_borrowed::<BE>(&data).unwrap();
+    let root = doc.root();
+    let compound = root.as_compound().unwrap();
+
+    // a compound holding just the "pos" entry, assembled by hand
+    let mut spliced = vec![0x0a, 0x00, 0x00];
+    spliced.extend(compound.write_entry_to_vec::<LE>("pos").unwrap().unwrap());
+    spliced.push(0x00);
+
+    let value = read_owned::<LE, LE>(&spliced).unwrap();
+    assert_eq!(
+        value
+            .get("pos")
+            .and_then(|p| p.get("x"))
+            .and_then(|v| v.as_int()),
+        Some(1)
+    );
+    assert!(value.get("name").is_none());
+}
+
+#[test]
+fn owned_compound_entry() {
+    let value = read_owned::<BE, BE>(&sample()).unwrap();
+    let root = value.as_compound().unwrap();
+    let entry = root.write_entry_to_vec::<BE>("pos").unwrap().unwrap();
+    assert_eq!(&entry[..6], [0x0a, 0x00, 0x03, b'p', b'o', b's']);
+
+    let whole = read_borrowed::<BE>(&sample())
+        .unwrap()
+        .root()
+        .write_to_vec::<BE>()
+        .unwrap();
+    let offset = whole
+        .windows(entry.len())
+        .position(|w| w == entry.as_slice());
+    assert!(offset.is_some());
+}
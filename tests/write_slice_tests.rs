@@ -0,0 +1,90 @@
+//! Tests for `write_to_slice`.
+
+use na_nbt::{Error, OwnedCompound, OwnedList, OwnedValue, ScopedReadableValue, read_borrowed};
+use zerocopy::byteorder::{BigEndian as BE, I32, I64, LittleEndian as LE};
+
+fn arrays() -> OwnedCompound<BE> {
+    let mut inner: OwnedCompound<BE> = OwnedCompound::default();
+    inner.insert("ints", vec![I32::<BE>::new(1), I32::new(-2), I32::new(3)]);
+    inner.insert("longs", vec![I64::<BE>::new(i64::MIN), I64::new(7)]);
+    inner.insert("bytes", vec![1i8, 2, 3]);
+    inner
+}
+
+fn sample() -> OwnedValue<BE> {
+    let mut list: OwnedList<BE> = OwnedList::default();
+    list.push(arrays());
+    list.push(OwnedCompound::<BE>::default());
+
+    let mut root: OwnedCompound<BE> = OwnedCompound::default();
+    root.insert("byte", 1i8);
+    root.insert("short", 2i16);
+    root.insert("int", 3i32);
+    root.insert("long", 4i64);
+    root.insert("float", 5.5f32);
+    root.insert("double", 6.25f64);
+    root.insert("name", "héllo wörld");
+    root.insert("list", list);
+    root.insert("empty", OwnedList::<BE>::default());
+    root.insert("inner", arrays());
+    OwnedValue::Compound(root)
+}
+
+fn assert_matches_vec<'doc>(value: &impl ScopedReadableValue<'doc>) {
+    let expected = value.write_to_vec::<BE>().unwrap();
+    let mut buf = vec![0; expected.len() + 16];
+    let len = value.write_to_slice::<BE>(&mut buf).unwrap();
+    assert_eq!(buf[..len], expected);
+
+    let expected = value.write_to_vec::<LE>().unwrap();
+    let len = value.write_to_slice::<LE>(&mut buf).unwrap();
+    assert_eq!(buf[..len], expected);
+}
+
+#[test]
+fn test_owned_matches_write_to_vec() {
+    assert_matches_vec(&sample());
+    assert_matches_vec(&OwnedValue::<BE>::from(42i32));
+    assert_matches_vec(&OwnedValue::<BE>::from("root"));
+}
+
+#[test]
+fn test_borrowed_matches_write_to_vec() {
+    let bytes = sample().write_to_vec::<LE>().unwrap();
+    let doc = read_borrowed::<LE>(&bytes).unwrap();
+    assert_matches_vec(&doc.root());
+}
+
+#[test]
+fn test_end_root() {
+    let value = OwnedValue::<BE>::End;
+    let mut buf = [0xff; 4];
+    assert_eq!(value.write_to_slice::<BE>(&mut buf).unwrap(), 1);
+    assert_eq!(buf[0], 0);
+    assert!(matches!(
+        value.write_to_slice::<BE>(&mut []),
+        Err(Error::BufferTooSmall(1))
+    ));
+}
+
+#[test]
+fn test_exact_size_buffer() {
+    let value = sample();
+    let mut buf = vec![0; value.serialized_len()];
+    let len = value.write_to_slice::<BE>(&mut buf).unwrap();
+    assert_eq!(len, buf.len());
+    assert_eq!(buf, value.write_to_vec::<BE>().unwrap());
+}
+
+#[test]
+fn test_buffer_too_small() {
+    let value = sample();
+    let needed = value.serialized_len();
+    for size in [0, 1, 3, needed / 2, needed - 1] {
+        let mut buf = vec![0; size];
+        match value.write_to_slice::<BE>(&mut buf) {
+            Err(Error::BufferTooSmall(n)) => assert_eq!(n, needed),
+            other => panic!("expected BufferTooSmall, got {:?}", other.err()),
+        }
+    }
+}